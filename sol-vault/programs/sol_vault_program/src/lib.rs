@@ -7,26 +7,84 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, SyncNative, Token, TokenAccount},
+};
 
 declare_id!("9UmM8nNR6Lxa8NFyTbG2gVfohQVwq5cNQoChVora19gf");
 
 // Maximum order_id length (constrained by PDA seed limits).
-// Solana's PDA derivation enforces this limit implicitly.
-// AUDIT NOTE (L-08): Order ID length is not explicitly validated at runtime because
-// PDA seed derivation enforces an implicit length limit. Exceeding it causes a clear
-// PDA derivation failure. Backend validates order IDs before submission as defense-in-depth.
+// AUDIT NOTE (L-08): deposit validates the length and charset explicitly and rejects
+// bad IDs with OrderIdInvalid. An ID longer than a single seed still fails PDA
+// derivation of the deposit record, which runs before the handler.
+// Backend validates order IDs before submission as defense-in-depth.
 pub const MAX_ORDER_ID_LEN: usize = 32;
 
+// Returns true if `order_id` fits in a PDA seed and is printable ASCII.
+pub fn is_valid_order_id(order_id: &str) -> bool {
+    order_id.len() <= MAX_ORDER_ID_LEN && order_id.bytes().all(|b| (0x20..=0x7e).contains(&b))
+}
+
+// Maximum number of destinations in the payout table used by withdraw_split.
+pub const MAX_PAYOUT_ENTRIES: usize = 5;
+
+// Basis point denominator (10000 = 100%).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Maximum number of members in the multisig signer set.
+pub const MAX_SIGNERS: usize = 10;
+
+// Maximum deposit fee (10%).
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+
+// Maximum number of entries kept in a user's deposit index.
+pub const MAX_DEPOSIT_INDEX_ENTRIES: usize = 64;
+
+// Default age after which a depositor may close an unsettled deposit record (30 days).
+pub const DEPOSIT_RECORD_TTL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+// Current VaultState layout version; older accounts are upgraded with migrate_vault_state.
+pub const VAULT_STATE_VERSION: u8 = 1;
+
+// Bytes kept free at the end of VaultState for future fields.
+pub const VAULT_STATE_RESERVED_LEN: usize = 48;
+
 #[program]
 pub mod sol_vault_program {
     use super::*;
 
     /// Initialize vault PDA. One-time initialization only.
+    /// The authority funds the vault PDA with its rent-exempt minimum so the
+    /// first deposit can be any size. After close_vault only the authority
+    /// recorded in the tombstone can initialize again.
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         let clock = Clock::get()?;
         
-        // Derive vault PDA for event
-        let (vault_pda_key, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
+        let tombstone = ctx.accounts.tombstone.to_account_info();
+        if tombstone.owner == ctx.program_id {
+            let tombstone = VaultTombstone::try_deserialize(&mut &tombstone.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                tombstone.authority,
+                ctx.accounts.authority.key(),
+                VaultError::Unauthorized
+            );
+        }
+        
+        let vault_pda_key = ctx.accounts.vault_pda.key();
+        
+        // Fund vault PDA rent (it may already hold lamports sent before initialization)
+        let vault_pda_balance = ctx.accounts.vault_pda.lamports();
+        let min_rent_exempt = Rent::get()?.minimum_balance(ctx.accounts.vault_pda.data_len());
+        let rent_top_up = min_rent_exempt.saturating_sub(vault_pda_balance);
+        if rent_top_up > 0 {
+            let transfer_ix = Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.vault_pda.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_ix);
+            transfer(cpi_ctx, rent_top_up)?;
+        }
         
         // Save values before mutable borrow
         let vault_state_key = ctx.accounts.vault_state.key();
@@ -35,6 +93,28 @@ pub mod sol_vault_program {
         let vault = &mut ctx.accounts.vault_state;
         vault.wallet_account = Pubkey::default();
         vault.authority = authority_key;
+        vault.paused = false;
+        vault.min_deposit_lamports = 0;
+        vault.fee_bps = 0;
+        vault.fee_destination = Pubkey::default();
+        vault.total_deposited = 0;
+        vault.deposit_count = 0;
+        vault.total_withdrawn = 0;
+        vault.withdraw_reserve_lamports = 0;
+        vault.guardian = Pubkey::default();
+        vault.frozen = false;
+        vault.wallet_change_delay_seconds = 0;
+        vault.pending_wallet = Pubkey::default();
+        vault.pending_wallet_effective_at = 0;
+        vault.multisig_enabled = false;
+        vault.pending_delay_seconds = 0;
+        vault.pending_delay_effective_at = 0;
+        vault.pending_deposit_count = 0;
+        vault.deposit_record_ttl_seconds = DEPOSIT_RECORD_TTL_SECONDS;
+        vault.version = VAULT_STATE_VERSION;
+        vault.total_refunded = 0;
+        vault.refund_count = 0;
+        vault.reserved = [0; VAULT_STATE_RESERVED_LEN];
         
         emit!(VaultInitializedEvent {
             vault_state: vault_state_key,
@@ -47,112 +127,166 @@ pub mod sol_vault_program {
         Ok(())
     }
 
-    /// Deposit SOL into the vault PDA.
-    pub fn deposit(
-        ctx: Context<Deposit>,
-        order_id: String,
-        amount: u64,
-    ) -> Result<()> {
-        let depositor = &ctx.accounts.depositor;
-        let vault_pda = &ctx.accounts.vault_pda;
-
-        require!(amount > 0, VaultError::InvalidAmount);
-        require!(!order_id.is_empty(), VaultError::OrderIdEmpty);
+    /// Upgrade a vault state created by an older program version to the current
+    /// layout. Fields added since then start zeroed, which is their disabled/default
+    /// value; the deposit record TTL gets its default. The lifetime totals count
+    /// deposits from the migration on. The authority pays any extra rent.
+    pub fn migrate_vault_state(ctx: Context<MigrateVaultState>) -> Result<()> {
+        let info = ctx.accounts.vault_state.to_account_info();
 
-        // Transfer SOL → PDA
-        let transfer_ix = Transfer {
-            from: depositor.to_account_info(),
-            to: vault_pda.to_account_info(),
+        // The account may be too short to deserialize, so check the fixed
+        // prefix (discriminator, wallet_account, authority) by hand
+        let authority = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 + 32 && data[..8] == *VaultState::DISCRIMINATOR,
+                VaultError::InvalidVaultState
+            );
+            Pubkey::try_from(&data[40..72]).map_err(|_| VaultError::InvalidVaultState)?
         };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_ix);
-        transfer(cpi_ctx, amount)?;
+        require_keys_eq!(authority, ctx.accounts.authority.key(), VaultError::Unauthorized);
 
-        msg!("Deposited {} lamports to vault", amount);
+        let old_len = info.data_len();
+        if old_len < VaultState::SPACE {
+            let rent_due = Rent::get()?
+                .minimum_balance(VaultState::SPACE)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                let cpi_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: info.clone(),
+                    },
+                );
+                transfer(cpi_ctx, rent_due)?;
+            }
+            info.resize(VaultState::SPACE)?;
+            info.try_borrow_mut_data()?[old_len..].fill(0);
+        }
+
+        let mut state = VaultState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            state.version < VAULT_STATE_VERSION,
+            VaultError::VaultAlreadyMigrated
+        );
+        let from_version = state.version;
+        if state.deposit_record_ttl_seconds == 0 {
+            state.deposit_record_ttl_seconds = DEPOSIT_RECORD_TTL_SECONDS;
+        }
+        state.version = VAULT_STATE_VERSION;
+        state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
 
-        // Save keys before mutable borrow
-        let deposit_record_key = ctx.accounts.deposit_record.key();
-        let depositor_key = depositor.key();
-        
-        // Record deposit
-        let record = &mut ctx.accounts.deposit_record;
         let clock = Clock::get()?;
-        record.order_id = order_id.clone();
-        record.timestamp = clock.unix_timestamp;
-        record.user = depositor_key;
-        record.sol_amount = amount;
-        
-        emit!(DepositEvent {
-            depositor: depositor_key,
-            order_id: order_id.clone(),
-            amount,
-            deposit_record: deposit_record_key,
-            timestamp: record.timestamp,
+
+        emit!(VaultMigratedEvent {
+            vault_state: info.key(),
+            from_version,
+            to_version: VAULT_STATE_VERSION,
+            authority,
+            timestamp: clock.unix_timestamp,
         });
 
-        msg!(
-            "Deposit recorded: order_id={}, user={}, sol={}",
-            order_id,
-            depositor_key,
-            amount
+        msg!("Vault state migrated from version {} to {}", from_version, VAULT_STATE_VERSION);
+        Ok(())
+    }
+
+    /// Deposit SOL into the vault PDA.
+    /// The new DepositRecord is appended to the depositor's UserDepositIndex.
+    /// `sequence` must be greater than the depositor's last order sequence, so an
+    /// order cannot be deposited again once its record has been closed.
+    pub fn deposit(
+        ctx: Context<Deposit>,
+        order_id: String,
+        amount: u64,
+        sequence: u64,
+    ) -> Result<()> {
+        let depositor_key = ctx.accounts.depositor.key();
+        DepositFlow {
+            payer: &ctx.accounts.depositor,
+            vault_pda: &ctx.accounts.vault_pda,
+            vault_state: &mut ctx.accounts.vault_state,
+            deposit_record: &mut ctx.accounts.deposit_record,
+            deposit_index: Some((&mut ctx.accounts.deposit_index, ctx.bumps.deposit_index)),
+            order_sequence: &mut ctx.accounts.order_sequence,
+            order_sequence_bump: ctx.bumps.order_sequence,
+            fee_destination: ctx.accounts.fee_destination.as_ref(),
+            system_program: &ctx.accounts.system_program,
+        }
+        .process(depositor_key, order_id, amount, sequence)
+    }
+
+    /// Deposit SOL on behalf of a beneficiary.
+    /// The signer pays the lamports, but the DepositRecord is keyed to and
+    /// credited to `beneficiary`, enabling sponsored deposits from an omnibus wallet.
+    /// The beneficiary must co-sign, so a sponsor cannot open records or advance the
+    /// order sequence in someone else's name. Sponsored records are not indexed.
+    pub fn deposit_for(
+        ctx: Context<DepositFor>,
+        beneficiary: Pubkey,
+        order_id: String,
+        amount: u64,
+        sequence: u64,
+    ) -> Result<()> {
+        require!(
+            beneficiary != Pubkey::default(),
+            VaultError::InvalidBeneficiary
         );
 
-        Ok(())
+        DepositFlow {
+            payer: &ctx.accounts.payer,
+            vault_pda: &ctx.accounts.vault_pda,
+            vault_state: &mut ctx.accounts.vault_state,
+            deposit_record: &mut ctx.accounts.deposit_record,
+            deposit_index: None,
+            order_sequence: &mut ctx.accounts.order_sequence,
+            order_sequence_bump: ctx.bumps.order_sequence,
+            fee_destination: ctx.accounts.fee_destination.as_ref(),
+            system_program: &ctx.accounts.system_program,
+        }
+        .process(beneficiary, order_id, amount, sequence)
     }
 
     /// Withdraw all funds (admin only).
-    /// BEST PRACTICE: This instruction does NOT take wallet_account as a named parameter.
-    /// Instead, it must be provided via remainingAccounts and is validated to match
-    /// the preconfigured wallet stored in vault_state.wallet_account.
-    /// 
-    /// The caller must provide the wallet account in remainingAccounts[0] (for Solana transaction handling),
-    /// but the program enforces it can ONLY be the preconfigured wallet, not any arbitrary address.
+    /// BEST PRACTICE: The destination is never chosen by the caller. The `wallet`
+    /// account is constrained to the preconfigured wallet stored in
+    /// vault_state.wallet_account, so it can ONLY be that wallet, not any arbitrary address.
     /// 
     /// Reasons:
     /// - Security: Eliminates attack surface by preventing any possibility of sending to an unintended address
     /// - Auditability: Single source of truth for withdrawal destination makes auditing simpler
     /// - Admin UX: Configure once via set_withdrawal_account, then all withdrawals enforce that address
     /// - Intent clarity: The validation makes it explicit that withdrawals always use the configured wallet
-    pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>) -> Result<()> {
+    ///
+    /// Requires signer set approval when multisig is enabled.
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         let vault_state = &ctx.accounts.vault_state;
         let vault_pda = &ctx.accounts.vault_pda;
 
+        vault_state.verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
         require!(
             vault_state.wallet_account != Pubkey::default(),
             VaultError::WalletNotSet
         );
 
-        // Get wallet account from remaining accounts
-        let remaining_accounts = &ctx.remaining_accounts;
-        require!(
-            !remaining_accounts.is_empty(),
-            VaultError::WalletAccountMissing
-        );
-        
-        let wallet_account_info = remaining_accounts.get(0)
-            .ok_or(VaultError::WalletAccountMissing)?;
-        
-        // Verify that the provided wallet account matches the configured one
-        require!(
-            wallet_account_info.key() == vault_state.wallet_account,
-            VaultError::WalletAccountMismatch
-        );
-
         // PDA signer seeds
         let (_pda, bump) = Pubkey::find_program_address(&[b"vault_pda".as_ref()], ctx.program_id);
         let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[bump]]];
 
-        // Transfer SOL → wallet (keep rent-exempt minimum)
+        // Transfer SOL → wallet (keep rent-exempt minimum plus the configured reserve)
         let vault_balance = **vault_pda.to_account_info().lamports.borrow();
         let rent = Rent::get()?;
         let min_rent_exempt = rent.minimum_balance(vault_pda.to_account_info().data_len());
+        let retained = min_rent_exempt.saturating_add(vault_state.withdraw_reserve_lamports);
         
-        // Calculate withdrawable amount (total - rent exempt)
-        let withdrawable = vault_balance.saturating_sub(min_rent_exempt);
+        // Calculate withdrawable amount (total - rent exempt - reserve)
+        let withdrawable = vault_balance.saturating_sub(retained);
         require!(withdrawable > 0, VaultError::NoFunds);
 
         let transfer_ix = Transfer {
             from: vault_pda.to_account_info(),
-            to: wallet_account_info.clone(),
+            to: ctx.accounts.wallet.to_account_info(),
         };
         let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.system_program.to_account_info(),
@@ -161,6 +295,13 @@ pub mod sol_vault_program {
         );
         transfer(cpi_ctx, withdrawable)?;
 
+        // Update running statistics
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.total_withdrawn = vault_state
+            .total_withdrawn
+            .checked_add(withdrawable)
+            .ok_or(VaultError::MathOverflow)?;
+
         let clock = Clock::get()?;
         
         emit!(WithdrawEvent {
@@ -172,179 +313,1907 @@ pub mod sol_vault_program {
         });
 
         msg!(
-            "Withdrawn {} lamports to {} (kept {} for rent)",
+            "Withdrawn {} lamports to {} (kept {} for rent and reserve)",
             withdrawable,
             vault_state.wallet_account,
-            min_rent_exempt
+            retained
         );
 
         Ok(())
     }
 
-    /// View deposit record.
-    pub fn check_deposit(ctx: Context<CheckDeposit>, _order_id: String) -> Result<DepositRecord> {
-        let record = &ctx.accounts.deposit_record;
-        let depositor = &ctx.accounts.depositor;
+    /// Withdraw `amount` lamports to the withdrawal wallet as wrapped SOL (admin only).
+    /// The lamports land in the wallet's wSOL associated token account, created on
+    /// first use, and are synced into its token balance. The same rent and reserve
+    /// floor as withdraw applies.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn withdraw_wrapped(ctx: Context<WithdrawWrapped>, amount: u64) -> Result<()> {
+        let vault_state = &ctx.accounts.vault_state;
+        let vault_pda = &ctx.accounts.vault_pda;
 
-        // Validate that the provided depositor matches the user in the record
-        require_keys_eq!(
-            record.user,
-            depositor.key(),
-            VaultError::DepositNotFound
+        vault_state.verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        require!(
+            vault_state.wallet_account != Pubkey::default(),
+            VaultError::WalletNotSet
         );
+        require!(amount > 0, VaultError::InvalidAmount);
 
-        Ok(DepositRecord {
-            order_id: record.order_id.clone(),
-            timestamp: record.timestamp,
-            user: record.user,
-            sol_amount: record.sol_amount,
-        })
+        let vault_balance = **vault_pda.to_account_info().lamports.borrow();
+        let rent = Rent::get()?;
+        let min_rent_exempt = rent.minimum_balance(vault_pda.to_account_info().data_len());
+        let retained = min_rent_exempt.saturating_add(vault_state.withdraw_reserve_lamports);
+        let withdrawable = vault_balance.saturating_sub(retained);
+        require!(amount <= withdrawable, VaultError::InsufficientVaultBalance);
+
+        // PDA signer seeds
+        let (_pda, bump) = Pubkey::find_program_address(&[b"vault_pda".as_ref()], ctx.program_id);
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[bump]]];
+
+        // Move the lamports into the wSOL account, then sync its token amount
+        let transfer_ix = Transfer {
+            from: vault_pda.to_account_info(),
+            to: ctx.accounts.wallet_token_account.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        transfer(cpi_ctx, amount)?;
+
+        token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.wallet_token_account.to_account_info(),
+            },
+        ))?;
+
+        // Update running statistics
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.total_withdrawn = vault_state
+            .total_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+
+        let clock = Clock::get()?;
+
+        emit!(WithdrawWrappedEvent {
+            vault_state: vault_state.key(),
+            wallet_account: vault_state.wallet_account,
+            token_account: ctx.accounts.wallet_token_account.key(),
+            amount,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Withdrawn {} lamports as wSOL to {}",
+            amount,
+            ctx.accounts.wallet_token_account.key()
+        );
+
+        Ok(())
     }
 
-    /// View vault status.
-    pub fn check(ctx: Context<Check>) -> Result<()> {
+    /// Decommission the vault (admin only).
+    /// Sends every lamport in the vault PDA, including its rent reserve, and the
+    /// vault_state rent to the configured withdrawal wallet, then closes vault_state
+    /// together with the signer set and payout table PDAs.
+    /// Refused while any deposit is still pending, since it could no longer be refunded.
+    /// A tombstone keeps the closing authority so nobody else can re-initialize.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
         let vault_state = &ctx.accounts.vault_state;
         let vault_pda = &ctx.accounts.vault_pda;
-        
-        // Read actual balance from vault account
-        let vault_balance = **vault_pda.to_account_info().lamports.borrow();
-        
-        msg!("Vault status:");
-        msg!("SOL balance: {}", vault_balance);
-        msg!("Withdrawal wallet: {}", vault_state.wallet_account);
-        Ok(())
-    }
 
-    /// Set withdrawal destination wallet.
-    pub fn set_withdrawal_account(
-        ctx: Context<SetWithdrawalAccount>,
-    ) -> Result<()> {
-        let vault = &mut ctx.accounts.vault_state;
-        let new_wallet = ctx.accounts.new_wallet.key();
-        
-        // Derive vault PDAs for validation
-        let (vault_state_pda, _) = Pubkey::find_program_address(&[b"vault_state"], ctx.program_id);
-        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
-        
-        // Validation: Disallow setting withdrawal wallet to:
-        // 1. Default public key (Pubkey::default())
-        // 2. Program account (program ID)
-        // 3. System program account
-        // 4. Vault state PDA
-        // 5. Vault PDA
+        let signer_set_info = ctx.accounts.signer_set.to_account_info();
+        let signer_set = if signer_set_info.owner == ctx.program_id {
+            Some(SignerSet::try_deserialize(&mut &signer_set_info.try_borrow_data()?[..])?)
+        } else {
+            None
+        };
+        vault_state.verify_approvals(signer_set.as_ref(), ctx.remaining_accounts)?;
+
         require!(
-            new_wallet != Pubkey::default() &&
-            new_wallet != crate::ID &&
-            new_wallet != anchor_lang::system_program::ID &&
-            new_wallet != vault_state_pda &&
-            new_wallet != vault_pda,
-            VaultError::InvalidWithdrawalWallet
+            vault_state.wallet_account != Pubkey::default(),
+            VaultError::WalletNotSet
         );
-        
-        vault.wallet_account = new_wallet;
-        
+        require!(
+            vault_state.pending_deposit_count == 0,
+            VaultError::PendingDepositsOutstanding
+        );
+
+        // PDA signer seeds
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[ctx.bumps.vault_pda]]];
+
+        // Transfer everything, rent reserve included
+        let vault_balance = vault_pda.lamports();
+        if vault_balance > 0 {
+            let transfer_ix = Transfer {
+                from: vault_pda.to_account_info(),
+                to: ctx.accounts.wallet.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_ix,
+                signer_seeds,
+            );
+            transfer(cpi_ctx, vault_balance)?;
+        }
+
+        // Close the auxiliary PDAs so they do not outlive the vault
+        let wallet = ctx.accounts.wallet.to_account_info();
+        close_if_initialized(&signer_set_info, &wallet, ctx.program_id)?;
+        close_if_initialized(&ctx.accounts.payout_table.to_account_info(), &wallet, ctx.program_id)?;
+
+        let tombstone = &mut ctx.accounts.tombstone;
+        tombstone.authority = vault_state.authority;
+        tombstone.bump = ctx.bumps.tombstone;
+
         let clock = Clock::get()?;
-        
-        emit!(WithdrawalWalletUpdatedEvent {
-            vault_state: vault.key(),
-            new_wallet,
+
+        emit!(VaultClosedEvent {
+            vault_state: vault_state.key(),
+            wallet_account: vault_state.wallet_account,
+            amount: vault_balance,
             authority: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
         });
-        
-        msg!("Withdrawal wallet set to {}", new_wallet);
+
+        msg!(
+            "Vault closed: {} lamports sent to {}",
+            vault_balance,
+            vault_state.wallet_account
+        );
+
         Ok(())
     }
 
-    /// Update vault authority (transfer admin rights).
-    pub fn update_authority(
-        ctx: Context<UpdateAuthority>,
-        new_authority: Pubkey,
+    /// Configure the payout table used by withdraw_split (admin only).
+    /// Entries are (wallet, bps) pairs; shares must be non-zero, wallets unique,
+    /// and the shares must add up to exactly 10000 bps.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_payout_table(
+        ctx: Context<SetPayoutTable>,
+        entries: Vec<PayoutEntry>,
     ) -> Result<()> {
-        // Validate new authority is not the default/system key
-        require!(
-            new_authority != Pubkey::default(),
-            VaultError::InvalidNewAuthority
-        );
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
 
-        // Validate new authority is not the vault PDA (to prevent locking)
-        let (vault_pda_key, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
         require!(
-            new_authority != vault_pda_key,
-            VaultError::AuthorityCannotBeVaultAccount
+            !entries.is_empty() && entries.len() <= MAX_PAYOUT_ENTRIES,
+            VaultError::InvalidPayoutTable
         );
 
-        // Validate new authority is not the vault state PDA
         let vault_state_key = ctx.accounts.vault_state.key();
-        require!(
-            new_authority != vault_state_key,
-            VaultError::AuthorityCannotBeVaultAccount
-        );
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
+
+        let mut total_bps: u64 = 0;
+        for (i, entry) in entries.iter().enumerate() {
+            require!(entry.bps > 0, VaultError::InvalidPayoutTable);
+            // Same rules as set_withdrawal_account
+            require!(
+                entry.wallet != Pubkey::default() &&
+                entry.wallet != crate::ID &&
+                entry.wallet != anchor_lang::system_program::ID &&
+                entry.wallet != vault_state_key &&
+                entry.wallet != vault_pda,
+                VaultError::InvalidWithdrawalWallet
+            );
+            require!(
+                !entries[..i].iter().any(|e| e.wallet == entry.wallet),
+                VaultError::InvalidPayoutTable
+            );
+            total_bps = total_bps
+                .checked_add(entry.bps as u64)
+                .ok_or(VaultError::MathOverflow)?;
+        }
+        require!(total_bps == BPS_DENOMINATOR, VaultError::InvalidPayoutTable);
+
+        let table = &mut ctx.accounts.payout_table;
+        table.entries = entries.clone();
+        table.bump = ctx.bumps.payout_table;
 
-        // Save previous authority before update
-        let previous_authority = ctx.accounts.vault_state.authority;
-        
-        // Update the authority
-        let vault = &mut ctx.accounts.vault_state;
-        vault.authority = new_authority;
-        
         let clock = Clock::get()?;
-        
-        emit!(AuthorityUpdatedEvent {
+
+        emit!(PayoutTableUpdatedEvent {
             vault_state: vault_state_key,
-            previous_authority,
-            new_authority,
+            entries,
+            authority: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
         });
-        
-        msg!(
-            "Authority updated from {} to {}",
-            previous_authority,
-            new_authority
-        );
-        
+
+        msg!("Payout table updated with {} entries", table.entries.len());
         Ok(())
     }
-}
 
-#[account]
-pub struct DepositRecord {
-    pub order_id: String,
-    pub timestamp: i64,
-    pub user: Pubkey,
-    pub sol_amount: u64,
-}
+    /// Withdraw all funds split across the payout table (admin only).
+    /// The destination wallets are passed as remaining accounts in table order
+    /// and must match the configured entries exactly. Each wallet receives its
+    /// bps share of the withdrawable lamports; rounding dust goes to the last one.
+    /// As with withdraw, the rent-exempt minimum stays in the vault PDA.
+    /// Requires signer set approval when multisig is enabled; co-signers follow
+    /// the destination wallets in the remaining accounts.
+    pub fn withdraw_split<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawSplit<'info>>) -> Result<()> {
+        let table = &ctx.accounts.payout_table;
+        let vault_pda = &ctx.accounts.vault_pda;
 
-#[derive(Accounts)]
-#[instruction(order_id: String)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub depositor: Signer<'info>,
+        require!(
+            ctx.remaining_accounts.len() >= table.entries.len(),
+            VaultError::PayoutTableMismatch
+        );
+        let (destinations, co_signers) = ctx.remaining_accounts.split_at(table.entries.len());
 
-    // AUDIT NOTE (I-03): The vault PDA is not explicitly initialized. The first deposit must
-    // include enough SOL to cover the rent-exempt minimum (~890,880 lamports for 0 bytes).
-    // Deployment scripts should bootstrap this with an initial deposit.
-    /// CHECK: PDA to hold SOL
-    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
-    pub vault_pda: AccountInfo<'info>,
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), co_signers)?;
 
+        for (entry, account) in table.entries.iter().zip(destinations.iter()) {
+            require!(
+                account.key() == entry.wallet && account.is_writable,
+                VaultError::PayoutTableMismatch
+            );
+        }
+
+        // PDA signer seeds
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[ctx.bumps.vault_pda]]];
+
+        // Split everything above the rent-exempt minimum and the configured reserve
+        let vault_balance = vault_pda.lamports();
+        let min_rent_exempt = Rent::get()?.minimum_balance(vault_pda.data_len());
+        let retained = min_rent_exempt
+            .saturating_add(ctx.accounts.vault_state.withdraw_reserve_lamports);
+        let withdrawable = vault_balance.saturating_sub(retained);
+        require!(withdrawable > 0, VaultError::NoFunds);
+
+        let mut amounts = Vec::with_capacity(destinations.len());
+        let mut distributed: u64 = 0;
+        for (i, (entry, account)) in table.entries.iter().zip(destinations.iter()).enumerate() {
+            let share = if i == table.entries.len() - 1 {
+                withdrawable
+                    .checked_sub(distributed)
+                    .ok_or(VaultError::MathOverflow)?
+            } else {
+                let share = (withdrawable as u128)
+                    .checked_mul(entry.bps as u128)
+                    .ok_or(VaultError::MathOverflow)?
+                    / BPS_DENOMINATOR as u128;
+                share as u64
+            };
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(VaultError::MathOverflow)?;
+            amounts.push(share);
+
+            if share == 0 {
+                continue;
+            }
+
+            let transfer_ix = Transfer {
+                from: vault_pda.to_account_info(),
+                to: account.clone(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_ix,
+                signer_seeds,
+            );
+            transfer(cpi_ctx, share)?;
+        }
+
+        // Update running statistics
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.total_withdrawn = vault_state
+            .total_withdrawn
+            .checked_add(withdrawable)
+            .ok_or(VaultError::MathOverflow)?;
+
+        let clock = Clock::get()?;
+
+        emit!(WithdrawSplitEvent {
+            vault_state: ctx.accounts.vault_state.key(),
+            wallets: table.entries.iter().map(|e| e.wallet).collect(),
+            amounts,
+            amount: withdrawable,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Withdrawn {} lamports across {} wallets (kept {} for rent and reserve)",
+            withdrawable,
+            table.entries.len(),
+            retained
+        );
+
+        Ok(())
+    }
+
+    /// Refund a deposit to its original depositor (admin only).
+    /// Returns the recorded lamports from the vault PDA and marks the record refunded,
+    /// so the same order cannot be refunded twice. Used for failed orders.
+    pub fn refund_deposit(ctx: Context<RefundDeposit>, order_id: String) -> Result<()> {
+        let vault_pda = &ctx.accounts.vault_pda;
+        let record = &ctx.accounts.deposit_record;
+
+        require!(
+            record.status != DepositStatus::Refunded,
+            VaultError::DepositAlreadyRefunded
+        );
+        require!(
+            record.status != DepositStatus::Processed,
+            VaultError::DepositAlreadyProcessed
+        );
+
+        let amount = record.sol_amount;
+
+        // The vault PDA must stay rent-exempt after the refund
+        let vault_balance = **vault_pda.to_account_info().lamports.borrow();
+        let min_rent_exempt = Rent::get()?.minimum_balance(vault_pda.to_account_info().data_len());
+        require!(
+            vault_balance.saturating_sub(min_rent_exempt) >= amount,
+            VaultError::InsufficientVaultBalance
+        );
+
+        // PDA signer seeds
+        let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[ctx.bumps.vault_pda]]];
+
+        // Transfer SOL → depositor
+        let transfer_ix = Transfer {
+            from: vault_pda.to_account_info(),
+            to: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        transfer(cpi_ctx, amount)?;
+
+        // Save keys before mutable borrow
+        let deposit_record_key = ctx.accounts.deposit_record.key();
+        let depositor_key = ctx.accounts.depositor.key();
+
+        let record = &mut ctx.accounts.deposit_record;
+        record.status = DepositStatus::Refunded;
+
+        // Lifetime deposit totals stay as they were; refunds are tracked separately
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.record_refund(amount)?;
+        vault_state.pending_deposit_count = vault_state.pending_deposit_count.saturating_sub(1);
+
+        let clock = Clock::get()?;
+
+        emit!(DepositRefundedEvent {
+            depositor: depositor_key,
+            order_id: order_id.clone(),
+            amount,
+            deposit_record: deposit_record_key,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Deposit refunded: order_id={}, user={}, sol={}",
+            order_id,
+            depositor_key,
+            amount
+        );
+
+        Ok(())
+    }
+
+    /// Mark a deposit as consumed by the backend (admin only).
+    /// Processed deposits can no longer be refunded.
+    pub fn mark_processed(ctx: Context<MarkProcessed>, order_id: String) -> Result<()> {
+        let record = &mut ctx.accounts.deposit_record;
+
+        require!(
+            record.status != DepositStatus::Refunded,
+            VaultError::DepositAlreadyRefunded
+        );
+        require!(
+            record.status != DepositStatus::Processed,
+            VaultError::DepositAlreadyProcessed
+        );
+
+        record.status = DepositStatus::Processed;
+
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.pending_deposit_count = vault_state.pending_deposit_count.saturating_sub(1);
+
+        let clock = Clock::get()?;
+
+        emit!(DepositProcessedEvent {
+            depositor: record.user,
+            order_id: order_id.clone(),
+            deposit_record: record.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Deposit processed: order_id={}", order_id);
+        Ok(())
+    }
+
+    /// Close a deposit record and return its rent to whoever paid it.
+    /// Allowed once the deposit has been processed or refunded, or after the vault's
+    /// `deposit_record_ttl_seconds`. Keeps account bloat under control for high-volume
+    /// deposit flows. An expired deposit that was never processed is refunded to the
+    /// depositor before its record is closed. The order cannot be deposited again
+    /// because order sequences only increase.
+    pub fn close_deposit_record(ctx: Context<CloseDepositRecord>, order_id: String) -> Result<()> {
+        let record = &ctx.accounts.deposit_record;
+        let clock = Clock::get()?;
+
+        let expired = clock.unix_timestamp
+            >= record.timestamp.saturating_add(ctx.accounts.vault_state.deposit_record_ttl_seconds);
+        require!(
+            record.status != DepositStatus::Pending || expired,
+            VaultError::DepositRecordNotClosable
+        );
+
+        // An expired pending deposit goes back to the depositor with its record
+        let mut refunded = 0;
+        if record.status == DepositStatus::Pending {
+            require!(!ctx.accounts.vault_state.frozen, VaultError::VaultFrozen);
+
+            refunded = record.sol_amount;
+
+            // The vault PDA must stay rent-exempt after the refund
+            let vault_pda = &ctx.accounts.vault_pda;
+            let vault_balance = vault_pda.lamports();
+            let min_rent_exempt = Rent::get()?.minimum_balance(vault_pda.data_len());
+            require!(
+                vault_balance.saturating_sub(min_rent_exempt) >= refunded,
+                VaultError::InsufficientVaultBalance
+            );
+
+            // PDA signer seeds
+            let signer_seeds: &[&[&[u8]]] = &[&[b"vault_pda".as_ref(), &[ctx.bumps.vault_pda]]];
+
+            // Transfer SOL → depositor
+            let transfer_ix = Transfer {
+                from: vault_pda.to_account_info(),
+                to: ctx.accounts.depositor.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_ix,
+                signer_seeds,
+            );
+            transfer(cpi_ctx, refunded)?;
+
+            let vault_state = &mut ctx.accounts.vault_state;
+            vault_state.record_refund(refunded)?;
+            vault_state.pending_deposit_count = vault_state.pending_deposit_count.saturating_sub(1);
+        }
+
+        emit!(DepositRecordClosedEvent {
+            depositor: ctx.accounts.depositor.key(),
+            order_id: order_id.clone(),
+            deposit_record: ctx.accounts.deposit_record.key(),
+            rent_recipient: ctx.accounts.rent_recipient.key(),
+            refunded,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("Deposit record closed: order_id={}", order_id);
+        Ok(())
+    }
+
+    /// View deposit record.
+    pub fn check_deposit(ctx: Context<CheckDeposit>, _order_id: String) -> Result<DepositRecord> {
+        let record = &ctx.accounts.deposit_record;
+        let depositor = &ctx.accounts.depositor;
+
+        // Validate that the provided depositor matches the user in the record
+        require_keys_eq!(
+            record.user,
+            depositor.key(),
+            VaultError::DepositNotFound
+        );
+
+        Ok(DepositRecord {
+            order_id: record.order_id.clone(),
+            timestamp: record.timestamp,
+            user: record.user,
+            sol_amount: record.sol_amount,
+            status: record.status,
+            rent_payer: record.rent_payer,
+            sequence: record.sequence,
+        })
+    }
+
+    /// View vault status.
+    /// The status is also returned via return data for monitors and CPI callers.
+    pub fn check(ctx: Context<Check>) -> Result<VaultStatus> {
+        let vault_state = &ctx.accounts.vault_state;
+        let vault_pda = &ctx.accounts.vault_pda;
+        
+        // Read actual balance from vault account
+        let vault_balance = **vault_pda.to_account_info().lamports.borrow();
+        
+        // Same calculation as withdraw
+        let min_rent_exempt = Rent::get()?.minimum_balance(vault_pda.to_account_info().data_len());
+        let withdrawable = vault_balance
+            .saturating_sub(min_rent_exempt.saturating_add(vault_state.withdraw_reserve_lamports));
+        
+        msg!("Vault status:");
+        msg!("SOL balance: {}", vault_balance);
+        msg!("Withdrawable: {}", withdrawable);
+        msg!("Withdrawal wallet: {}", vault_state.wallet_account);
+        msg!("Deposits paused: {}", vault_state.paused);
+        msg!("Minimum deposit: {}", vault_state.min_deposit_lamports);
+        msg!("Deposit fee: {} bps to {}", vault_state.fee_bps, vault_state.fee_destination);
+        msg!("Total deposited: {}", vault_state.total_deposited);
+        msg!("Deposit count: {}", vault_state.deposit_count);
+        msg!("Total withdrawn: {}", vault_state.total_withdrawn);
+        msg!("Total refunded: {} ({} refunds)", vault_state.total_refunded, vault_state.refund_count);
+        msg!("Withdraw reserve: {}", vault_state.withdraw_reserve_lamports);
+        msg!("Guardian: {}", vault_state.guardian);
+        msg!("Frozen: {}", vault_state.frozen);
+        msg!("Wallet change delay: {}s", vault_state.wallet_change_delay_seconds);
+        msg!("Pending wallet: {}", vault_state.pending_wallet);
+        
+        Ok(VaultStatus {
+            lamports: vault_balance,
+            withdrawable,
+            wallet_account: vault_state.wallet_account,
+            authority: vault_state.authority,
+            paused: vault_state.paused,
+            frozen: vault_state.frozen,
+            guardian: vault_state.guardian,
+            multisig_enabled: vault_state.multisig_enabled,
+            pending_wallet: vault_state.pending_wallet,
+            pending_wallet_effective_at: vault_state.pending_wallet_effective_at,
+            min_deposit_lamports: vault_state.min_deposit_lamports,
+            fee_bps: vault_state.fee_bps,
+            withdraw_reserve_lamports: vault_state.withdraw_reserve_lamports,
+            total_deposited: vault_state.total_deposited,
+            deposit_count: vault_state.deposit_count,
+            total_withdrawn: vault_state.total_withdrawn,
+            total_refunded: vault_state.total_refunded,
+            refund_count: vault_state.refund_count,
+        })
+    }
+
+    /// Pause or resume deposits (admin only).
+    /// Withdrawals are unaffected so funds can still be moved out during an incident.
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        vault.paused = paused;
+        
+        let clock = Clock::get()?;
+        
+        emit!(VaultPausedEvent {
+            vault_state: vault.key(),
+            paused,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Deposits paused: {}", paused);
+        Ok(())
+    }
+
+    /// Set the minimum deposit amount in lamports (admin only).
+    /// Keeps out dust deposits whose DepositRecord rent exceeds their value. 0 disables the check.
+    pub fn set_min_deposit(ctx: Context<SetMinDeposit>, min_deposit_lamports: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        let previous_min_deposit = vault.min_deposit_lamports;
+        vault.min_deposit_lamports = min_deposit_lamports;
+        
+        let clock = Clock::get()?;
+        
+        emit!(MinDepositUpdatedEvent {
+            vault_state: vault.key(),
+            previous_min_deposit,
+            new_min_deposit: min_deposit_lamports,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Minimum deposit set to {} lamports", min_deposit_lamports);
+        Ok(())
+    }
+
+    /// Set or clear the guardian, a security key that can only freeze and
+    /// unfreeze the vault. Cannot be changed while frozen (admin only).
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        let vault = &mut ctx.accounts.vault_state;
+        require!(!vault.frozen, VaultError::VaultFrozen);
+
+        let previous_guardian = vault.guardian;
+        vault.guardian = guardian.unwrap_or_default();
+        
+        let clock = Clock::get()?;
+        
+        emit!(GuardianUpdatedEvent {
+            vault_state: vault.key(),
+            previous_guardian,
+            new_guardian: vault.guardian,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Guardian set to {}", vault.guardian);
+        Ok(())
+    }
+
+    /// Freeze or unfreeze the vault (guardian only).
+    /// While frozen, withdrawals, refunds, closing the vault and wallet changes are
+    /// rejected; deposits still go through.
+    pub fn set_frozen(ctx: Context<SetFrozen>, frozen: bool) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        vault.frozen = frozen;
+        
+        let clock = Clock::get()?;
+        
+        emit!(VaultFrozenEvent {
+            vault_state: vault.key(),
+            frozen,
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Vault frozen: {}", frozen);
+        Ok(())
+    }
+
+    /// Set the lamports kept in the vault PDA on top of rent when withdrawing (admin only).
+    /// The reserve stays behind as an operational float; 0 sweeps everything above rent.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_withdraw_reserve(
+        ctx: Context<SetWithdrawReserve>,
+        withdraw_reserve_lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        let vault = &mut ctx.accounts.vault_state;
+        let previous_reserve = vault.withdraw_reserve_lamports;
+        vault.withdraw_reserve_lamports = withdraw_reserve_lamports;
+        
+        let clock = Clock::get()?;
+        
+        emit!(WithdrawReserveUpdatedEvent {
+            vault_state: vault.key(),
+            previous_reserve,
+            new_reserve: withdraw_reserve_lamports,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Withdraw reserve set to {} lamports", withdraw_reserve_lamports);
+        Ok(())
+    }
+
+    /// Set how long a pending deposit record must age before its depositor can
+    /// close it (admin only). Requires signer set approval when multisig is enabled.
+    pub fn set_deposit_record_ttl(
+        ctx: Context<SetDepositRecordTtl>,
+        ttl_seconds: i64,
+    ) -> Result<()> {
+        require!(ttl_seconds > 0, VaultError::InvalidDepositRecordTtl);
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        let vault = &mut ctx.accounts.vault_state;
+        let previous_ttl_seconds = vault.deposit_record_ttl_seconds;
+        vault.deposit_record_ttl_seconds = ttl_seconds;
+        
+        let clock = Clock::get()?;
+        
+        emit!(DepositRecordTtlUpdatedEvent {
+            vault_state: vault.key(),
+            previous_ttl_seconds,
+            new_ttl_seconds: ttl_seconds,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Deposit record TTL set to {} seconds", ttl_seconds);
+        Ok(())
+    }
+
+    /// Set the deposit fee and the wallet that receives it (admin only).
+    /// Fees are capped at `MAX_DEPOSIT_FEE_BPS`; 0 disables the fee. The fee is
+    /// taken from the deposit at deposit time, so the vault only receives the net amount.
+    /// A non-zero fee needs a funded, rent-exempt destination, otherwise a fee below the
+    /// rent-exempt minimum would make every deposit fail.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_deposit_fee(ctx: Context<SetDepositFee>, fee_bps: u16) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        require!(fee_bps <= MAX_DEPOSIT_FEE_BPS, VaultError::InvalidFeeBps);
+
+        let fee_destination = ctx.accounts.fee_destination.key();
+
+        // Same rules as the withdrawal wallet
+        let (vault_state_pda, _) = Pubkey::find_program_address(&[b"vault_state"], ctx.program_id);
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
+        require!(
+            fee_destination != Pubkey::default() &&
+            fee_destination != crate::ID &&
+            fee_destination != anchor_lang::system_program::ID &&
+            fee_destination != vault_state_pda &&
+            fee_destination != vault_pda,
+            VaultError::InvalidFeeDestination
+        );
+
+        if fee_bps > 0 {
+            let destination_info = ctx.accounts.fee_destination.to_account_info();
+            let min_rent_exempt = Rent::get()?.minimum_balance(destination_info.data_len());
+            require!(
+                destination_info.lamports() > 0 && destination_info.lamports() >= min_rent_exempt,
+                VaultError::FeeDestinationNotRentExempt
+            );
+        }
+
+        let vault = &mut ctx.accounts.vault_state;
+        let previous_fee_bps = vault.fee_bps;
+        vault.fee_bps = fee_bps;
+        vault.fee_destination = fee_destination;
+        
+        let clock = Clock::get()?;
+        
+        emit!(DepositFeeUpdatedEvent {
+            vault_state: vault.key(),
+            previous_fee_bps,
+            new_fee_bps: fee_bps,
+            fee_destination,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Deposit fee set to {} bps, destination {}", fee_bps, fee_destination);
+        Ok(())
+    }
+
+    /// Set withdrawal destination wallet.
+    /// When a wallet change delay is configured the new wallet is only queued and
+    /// must be applied with apply_withdrawal_account once the delay has elapsed.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_withdrawal_account(
+        ctx: Context<SetWithdrawalAccount>,
+    ) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        let vault = &mut ctx.accounts.vault_state;
+        let new_wallet = ctx.accounts.new_wallet.key();
+        
+        // Derive vault PDAs for validation
+        let (vault_state_pda, _) = Pubkey::find_program_address(&[b"vault_state"], ctx.program_id);
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
+        
+        // Validation: Disallow setting withdrawal wallet to:
+        // 1. Default public key (Pubkey::default())
+        // 2. Program account (program ID)
+        // 3. System program account
+        // 4. Vault state PDA
+        // 5. Vault PDA
+        require!(
+            new_wallet != Pubkey::default() &&
+            new_wallet != crate::ID &&
+            new_wallet != anchor_lang::system_program::ID &&
+            new_wallet != vault_state_pda &&
+            new_wallet != vault_pda,
+            VaultError::InvalidWithdrawalWallet
+        );
+        
+        let clock = Clock::get()?;
+        
+        // Without a delay the change applies immediately
+        if vault.wallet_change_delay_seconds == 0 {
+            vault.wallet_account = new_wallet;
+            vault.pending_wallet = Pubkey::default();
+            vault.pending_wallet_effective_at = 0;
+            
+            emit!(WithdrawalWalletUpdatedEvent {
+                vault_state: vault.key(),
+                new_wallet,
+                authority: ctx.accounts.authority.key(),
+                timestamp: clock.unix_timestamp,
+            });
+            
+            msg!("Withdrawal wallet set to {}", new_wallet);
+            return Ok(());
+        }
+        
+        // Otherwise queue it; a newer request replaces any pending one
+        let effective_at = clock
+            .unix_timestamp
+            .checked_add(vault.wallet_change_delay_seconds as i64)
+            .ok_or(VaultError::MathOverflow)?;
+        vault.pending_wallet = new_wallet;
+        vault.pending_wallet_effective_at = effective_at;
+        
+        emit!(WithdrawalWalletQueuedEvent {
+            vault_state: vault.key(),
+            pending_wallet: new_wallet,
+            effective_at,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Withdrawal wallet {} queued, effective at {}", new_wallet, effective_at);
+        Ok(())
+    }
+
+    /// Apply a queued withdrawal wallet change once its delay has elapsed (admin only).
+    pub fn apply_withdrawal_account(ctx: Context<ApplyWithdrawalAccount>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        let clock = Clock::get()?;
+        
+        require!(
+            vault.pending_wallet != Pubkey::default(),
+            VaultError::NoPendingWalletChange
+        );
+        require!(
+            clock.unix_timestamp >= vault.pending_wallet_effective_at,
+            VaultError::WalletChangeTimelocked
+        );
+        
+        let new_wallet = vault.pending_wallet;
+        vault.wallet_account = new_wallet;
+        vault.pending_wallet = Pubkey::default();
+        vault.pending_wallet_effective_at = 0;
+        
+        emit!(WithdrawalWalletUpdatedEvent {
+            vault_state: vault.key(),
+            new_wallet,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Withdrawal wallet set to {}", new_wallet);
+        Ok(())
+    }
+
+    /// Cancel a queued withdrawal wallet change.
+    /// Callable by the authority or the guardian, including while frozen.
+    pub fn cancel_withdrawal_account(ctx: Context<CancelWithdrawalAccount>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        
+        require!(
+            vault.pending_wallet != Pubkey::default(),
+            VaultError::NoPendingWalletChange
+        );
+        
+        let cancelled_wallet = vault.pending_wallet;
+        vault.pending_wallet = Pubkey::default();
+        vault.pending_wallet_effective_at = 0;
+        
+        let clock = Clock::get()?;
+        
+        emit!(WithdrawalWalletChangeCancelledEvent {
+            vault_state: vault.key(),
+            cancelled_wallet,
+            cancelled_by: ctx.accounts.signer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Withdrawal wallet change to {} cancelled", cancelled_wallet);
+        Ok(())
+    }
+
+    /// Set the delay applied to withdrawal wallet changes (admin only).
+    /// 0 disables the timelock and makes set_withdrawal_account take effect immediately.
+    /// Increases apply at once; a reduction is itself timelocked by the current delay
+    /// and must be applied with apply_wallet_change_delay.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn set_wallet_change_delay(
+        ctx: Context<SetWalletChangeDelay>,
+        wallet_change_delay_seconds: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        require!(
+            wallet_change_delay_seconds <= i64::MAX as u64,
+            VaultError::InvalidWalletChangeDelay
+        );
+        
+        let vault = &mut ctx.accounts.vault_state;
+        let clock = Clock::get()?;
+        
+        // A shorter delay only takes effect once the current one has run
+        if wallet_change_delay_seconds < vault.wallet_change_delay_seconds {
+            let effective_at = clock
+                .unix_timestamp
+                .checked_add(vault.wallet_change_delay_seconds as i64)
+                .ok_or(VaultError::MathOverflow)?;
+            vault.pending_delay_seconds = wallet_change_delay_seconds;
+            vault.pending_delay_effective_at = effective_at;
+            
+            emit!(WalletChangeDelayQueuedEvent {
+                vault_state: vault.key(),
+                pending_delay_seconds: wallet_change_delay_seconds,
+                effective_at,
+                authority: ctx.accounts.authority.key(),
+                timestamp: clock.unix_timestamp,
+            });
+            
+            msg!(
+                "Wallet change delay of {} seconds queued, effective at {}",
+                wallet_change_delay_seconds,
+                effective_at
+            );
+            return Ok(());
+        }
+        
+        let previous_delay_seconds = vault.wallet_change_delay_seconds;
+        vault.wallet_change_delay_seconds = wallet_change_delay_seconds;
+        vault.pending_delay_seconds = 0;
+        vault.pending_delay_effective_at = 0;
+        
+        emit!(WalletChangeDelayUpdatedEvent {
+            vault_state: vault.key(),
+            previous_delay_seconds,
+            new_delay_seconds: wallet_change_delay_seconds,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Wallet change delay set to {} seconds", wallet_change_delay_seconds);
+        Ok(())
+    }
+
+    /// Apply a queued wallet change delay reduction once it is due (admin only).
+    pub fn apply_wallet_change_delay(ctx: Context<ApplyWalletChangeDelay>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault_state;
+        let clock = Clock::get()?;
+        
+        require!(
+            vault.pending_delay_effective_at != 0,
+            VaultError::NoPendingDelayChange
+        );
+        require!(
+            clock.unix_timestamp >= vault.pending_delay_effective_at,
+            VaultError::WalletChangeTimelocked
+        );
+        
+        let previous_delay_seconds = vault.wallet_change_delay_seconds;
+        let new_delay_seconds = vault.pending_delay_seconds;
+        vault.wallet_change_delay_seconds = new_delay_seconds;
+        vault.pending_delay_seconds = 0;
+        vault.pending_delay_effective_at = 0;
+        
+        emit!(WalletChangeDelayUpdatedEvent {
+            vault_state: vault.key(),
+            previous_delay_seconds,
+            new_delay_seconds,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Wallet change delay set to {} seconds", new_delay_seconds);
+        Ok(())
+    }
+
+    /// Add a member to the multisig signer set (admin only).
+    /// Changing an active signer set needs its own approval, with co-signers
+    /// passed as remaining accounts.
+    pub fn add_signer(ctx: Context<ManageSignerSet>, signer: Pubkey) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(Some(&ctx.accounts.signer_set), ctx.remaining_accounts)?;
+
+        let signer_set = &mut ctx.accounts.signer_set;
+        require!(
+            signer != Pubkey::default()
+                && signer_set.signers.len() < MAX_SIGNERS
+                && !signer_set.signers.contains(&signer),
+            VaultError::InvalidSignerSet
+        );
+        signer_set.signers.push(signer);
+        signer_set.bump = ctx.bumps.signer_set;
+
+        emit_signer_set_updated(&ctx.accounts.vault_state, signer_set, &ctx.accounts.authority)?;
+
+        msg!("Signer {} added", signer);
+        Ok(())
+    }
+
+    /// Remove a member from the multisig signer set (admin only).
+    /// The threshold must still be reachable afterwards.
+    pub fn remove_signer(ctx: Context<ManageSignerSet>, signer: Pubkey) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(Some(&ctx.accounts.signer_set), ctx.remaining_accounts)?;
+
+        let signer_set = &mut ctx.accounts.signer_set;
+        let index = signer_set
+            .signers
+            .iter()
+            .position(|s| *s == signer)
+            .ok_or(VaultError::InvalidSignerSet)?;
+        signer_set.signers.remove(index);
+        require!(
+            signer_set.threshold as usize <= signer_set.signers.len(),
+            VaultError::InvalidSignerSet
+        );
+
+        emit_signer_set_updated(&ctx.accounts.vault_state, signer_set, &ctx.accounts.authority)?;
+
+        msg!("Signer {} removed", signer);
+        Ok(())
+    }
+
+    /// Set how many signer set members must co-sign withdraw, close_vault,
+    /// set_withdrawal_account, set_payout_table and update_authority (admin only).
+    /// 0 disables multisig.
+    pub fn set_signer_threshold(ctx: Context<ManageSignerSet>, threshold: u8) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(Some(&ctx.accounts.signer_set), ctx.remaining_accounts)?;
+
+        let signer_set = &mut ctx.accounts.signer_set;
+        require!(
+            threshold as usize <= signer_set.signers.len(),
+            VaultError::InvalidSignerSet
+        );
+        signer_set.threshold = threshold;
+        signer_set.bump = ctx.bumps.signer_set;
+
+        let vault = &mut ctx.accounts.vault_state;
+        vault.multisig_enabled = threshold > 0;
+
+        emit_signer_set_updated(vault, signer_set, &ctx.accounts.authority)?;
+
+        msg!("Signer threshold set to {}", threshold);
+        Ok(())
+    }
+
+    /// Update vault authority (transfer admin rights).
+    /// Requires signer set approval when multisig is enabled.
+    pub fn update_authority(
+        ctx: Context<UpdateAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+
+        // Validate new authority is not the default/system key
+        require!(
+            new_authority != Pubkey::default(),
+            VaultError::InvalidNewAuthority
+        );
+
+        // Validate new authority is not the vault PDA (to prevent locking)
+        let (vault_pda_key, _) = Pubkey::find_program_address(&[b"vault_pda"], ctx.program_id);
+        require!(
+            new_authority != vault_pda_key,
+            VaultError::AuthorityCannotBeVaultAccount
+        );
+
+        // Validate new authority is not the vault state PDA
+        let vault_state_key = ctx.accounts.vault_state.key();
+        require!(
+            new_authority != vault_state_key,
+            VaultError::AuthorityCannotBeVaultAccount
+        );
+
+        // Save previous authority before update
+        let previous_authority = ctx.accounts.vault_state.authority;
+        
+        // Update the authority
+        let vault = &mut ctx.accounts.vault_state;
+        vault.authority = new_authority;
+        
+        let clock = Clock::get()?;
+        
+        emit!(AuthorityUpdatedEvent {
+            vault_state: vault_state_key,
+            previous_authority,
+            new_authority,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!(
+            "Authority updated from {} to {}",
+            previous_authority,
+            new_authority
+        );
+        
+        Ok(())
+    }
+}
+
+/// Accounts shared by `deposit` and `deposit_for`.
+struct DepositFlow<'a, 'info> {
+    payer: &'a Signer<'info>,
+    vault_pda: &'a AccountInfo<'info>,
+    vault_state: &'a mut Account<'info, VaultState>,
+    deposit_record: &'a mut Account<'info, DepositRecord>,
+    /// The depositor's own index and its bump; `None` for sponsored deposits
+    deposit_index: Option<(&'a mut Account<'info, UserDepositIndex>, u8)>,
+    order_sequence: &'a mut Account<'info, OrderSequence>,
+    order_sequence_bump: u8,
+    fee_destination: Option<&'a UncheckedAccount<'info>>,
+    system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> DepositFlow<'a, 'info> {
+    /// Move `amount` lamports (less the fee) from the payer into the vault PDA
+    /// and record the deposit against `beneficiary`.
+    fn process(self, beneficiary: Pubkey, order_id: String, amount: u64, sequence: u64) -> Result<()> {
+        let payer = self.payer;
+        let vault_pda = self.vault_pda;
+
+        require!(!self.vault_state.paused, VaultError::DepositsPaused);
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(
+            amount >= self.vault_state.min_deposit_lamports,
+            VaultError::DepositBelowMinimum
+        );
+        require!(!order_id.is_empty(), VaultError::OrderIdEmpty);
+        require!(is_valid_order_id(&order_id), VaultError::OrderIdInvalid);
+
+        // Order sequences only move forward, which keeps closed orders from being replayed
+        let order_sequence = &mut *self.order_sequence;
+        require!(
+            sequence > order_sequence.last_sequence,
+            VaultError::OrderSequenceNotIncreasing
+        );
+        order_sequence.user = beneficiary;
+        order_sequence.last_sequence = sequence;
+        order_sequence.bump = self.order_sequence_bump;
+
+        // Split off the deposit fee, if any
+        let fee = (amount as u128)
+            .checked_mul(self.vault_state.fee_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let fee = fee as u64;
+        let net_amount = amount.checked_sub(fee).ok_or(VaultError::MathOverflow)?;
+
+        if fee > 0 {
+            let fee_destination = self
+                .fee_destination
+                .ok_or(VaultError::FeeDestinationRequired)?;
+
+            // Transfer fee → fee destination
+            let transfer_ix = Transfer {
+                from: payer.to_account_info(),
+                to: fee_destination.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_ix);
+            transfer(cpi_ctx, fee)?;
+        }
+
+        // Transfer SOL → PDA
+        let transfer_ix = Transfer {
+            from: payer.to_account_info(),
+            to: vault_pda.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_ix);
+        transfer(cpi_ctx, net_amount)?;
+
+        msg!("Deposited {} lamports to vault (fee {})", net_amount, fee);
+
+        // Update running statistics
+        let vault_state = &mut *self.vault_state;
+        vault_state.total_deposited = vault_state
+            .total_deposited
+            .checked_add(net_amount)
+            .ok_or(VaultError::MathOverflow)?;
+        vault_state.deposit_count = vault_state
+            .deposit_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        vault_state.pending_deposit_count = vault_state
+            .pending_deposit_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+
+        // Save keys before mutable borrow
+        let deposit_record_key = self.deposit_record.key();
+        let payer_key = payer.key();
+    
+        // Record deposit
+        let record = &mut *self.deposit_record;
+        let clock = Clock::get()?;
+        record.order_id = order_id.clone();
+        record.timestamp = clock.unix_timestamp;
+        record.user = beneficiary;
+        record.sol_amount = net_amount;
+        record.status = DepositStatus::Pending;
+        record.rent_payer = payer_key;
+        record.sequence = sequence;
+
+        // Only the beneficiary's own deposits are indexed, so third parties cannot
+        // grow someone else's index. It is grown by one entry up to the cap; past
+        // that the oldest entry makes room.
+        if let Some((index, bump)) = self.deposit_index {
+            index.user = beneficiary;
+            index.bump = bump;
+
+            if index.deposits.len() >= MAX_DEPOSIT_INDEX_ENTRIES {
+                index.deposits.remove(0);
+            } else {
+                let index_info = index.to_account_info();
+                let new_len = UserDepositIndex::space(index.deposits.len() + 1);
+                let rent_due = Rent::get()?
+                    .minimum_balance(new_len)
+                    .saturating_sub(index_info.lamports());
+                if rent_due > 0 {
+                    let transfer_ix = Transfer {
+                        from: payer.to_account_info(),
+                        to: index_info.clone(),
+                    };
+                    let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_ix);
+                    transfer(cpi_ctx, rent_due)?;
+                }
+                index_info.resize(new_len)?;
+            }
+            index.deposits.push(deposit_record_key);
+        }
+    
+        emit!(DepositEvent {
+            depositor: beneficiary,
+            order_id: order_id.clone(),
+            amount,
+            fee,
+            net_amount,
+            deposit_record: deposit_record_key,
+            timestamp: record.timestamp,
+            payer: payer_key,
+            sequence,
+        });
+
+        msg!(
+            "Deposit recorded: order_id={}, user={}, sol={}",
+            order_id,
+            beneficiary,
+            net_amount
+        );
+
+        Ok(())
+    }
+}
+
+/// Move all lamports of a program-owned account to `destination` and hand it
+/// back to the system program. No-op if the account was never created.
+fn close_if_initialized<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if account.owner != program_id {
+        return Ok(());
+    }
+
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(VaultError::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+fn emit_signer_set_updated(
+    vault_state: &Account<VaultState>,
+    signer_set: &SignerSet,
+    authority: &Signer,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    emit!(SignerSetUpdatedEvent {
+        vault_state: vault_state.key(),
+        signers: signer_set.signers.clone(),
+        threshold: signer_set.threshold,
+        authority: authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[account]
+pub struct DepositRecord {
+    pub order_id: String,
+    pub timestamp: i64,
+    pub user: Pubkey,
+    pub sol_amount: u64,
+    pub status: DepositStatus,
+    /// Account that paid the record rent and gets it back on close
+    pub rent_payer: Pubkey,
+    /// Order sequence the deposit was made with
+    pub sequence: u64,
+}
+
+/// Highest order sequence deposited for a user. Deposits must use a larger
+/// sequence, so closing a record never allows its order to be replayed.
+#[account]
+pub struct OrderSequence {
+    pub user: Pubkey,
+    pub last_sequence: u64,
+    pub bump: u8,
+}
+
+/// The most recent MAX_DEPOSIT_INDEX_ENTRIES DepositRecords a user paid for
+/// themselves, in deposit order. Entries are kept after the record itself is
+/// closed; sponsored deposit_for records are not indexed.
+#[account]
+pub struct UserDepositIndex {
+    pub user: Pubkey,
+    pub deposits: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl UserDepositIndex {
+    // discriminator + user + deposits + bump
+    pub fn space(entries: usize) -> usize {
+        8 + 32 + 4 + entries * 32 + 1
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositStatus {
+    Pending,
+    Processed,
+    Refunded,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // AUDIT NOTE (I-03): The vault PDA is funded with its rent-exempt minimum in initialize,
+    // so deposits of any size can land in it.
+    /// CHECK: PDA to hold SOL
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"vault_state".as_ref()], bump)]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + 4 + MAX_ORDER_ID_LEN + 8 + 32 + 8 + 1 + 32 + 8,
+        seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = UserDepositIndex::space(0),
+        seeds = [b"user_deposit_index", depositor.key().as_ref()],
+        bump
+    )]
+    pub deposit_index: Account<'info, UserDepositIndex>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = 8 + 32 + 8 + 1, // discriminator + user + last_sequence + bump
+        seeds = [b"order_sequence", depositor.key().as_ref()],
+        bump
+    )]
+    pub order_sequence: Account<'info, OrderSequence>,
+
+    /// CHECK: Receives the deposit fee; required when the vault charges one
+    #[account(
+        mut,
+        address = vault_state.fee_destination @ VaultError::InvalidFeeDestination
+    )]
+    pub fee_destination: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey, order_id: String)]
+pub struct DepositFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: PDA to hold SOL
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut, seeds = [b"vault_state".as_ref()], bump)]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 4 + MAX_ORDER_ID_LEN + 8 + 32 + 8 + 1 + 32 + 8,
+        seeds = [b"deposit_record", beneficiary.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Beneficiary credited with the deposit; co-signs to approve the sponsor
+    #[account(address = beneficiary @ VaultError::InvalidBeneficiary)]
+    pub beneficiary_signer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 8 + 1, // discriminator + user + last_sequence + bump
+        seeds = [b"order_sequence", beneficiary.as_ref()],
+        bump
+    )]
+    pub order_sequence: Account<'info, OrderSequence>,
+
+    /// CHECK: Receives the deposit fee; required when the vault charges one
+    #[account(
+        mut,
+        address = vault_state.fee_destination @ VaultError::InvalidFeeDestination
+    )]
+    pub fee_destination: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    /// CHECK: PDA holds SOL
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the SOL; must be the configured withdrawal wallet
+    #[account(
+        mut,
+        constraint = wallet.key() == vault_state.wallet_account @ VaultError::WalletAccountMismatch
+    )]
+    pub wallet: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWrapped<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    /// CHECK: PDA holds SOL
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Owner of the receiving wSOL account; must be the configured withdrawal wallet
+    #[account(
+        constraint = wallet.key() == vault_state.wallet_account @ VaultError::WalletAccountMismatch
+    )]
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = native_mint,
+        associated_token::authority = wallet
+    )]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = token::spl_token::native_mint::ID)]
+    pub native_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen,
+        close = wallet
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    /// CHECK: PDA holds SOL
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Receives the SOL; must be the configured withdrawal wallet
+    #[account(
+        mut,
+        constraint = wallet.key() == vault_state.wallet_account @ VaultError::WalletAccountMismatch
+    )]
+    pub wallet: UncheckedAccount<'info>,
+
+    /// Records the closing authority; checked by initialize
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1, // discriminator + authority + bump
+        seeds = [b"vault_tombstone".as_ref()],
+        bump
+    )]
+    pub tombstone: Account<'info, VaultTombstone>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Signer set PDA; used for approvals and closed with the vault if it exists
+    #[account(mut, seeds = [b"signer_set".as_ref()], bump)]
+    pub signer_set: UncheckedAccount<'info>,
+
+    /// CHECK: Payout table PDA; closed with the vault if it exists
+    #[account(mut, seeds = [b"payout_table".as_ref()], bump)]
+    pub payout_table: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct RefundDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    /// CHECK: PDA holds SOL
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Original depositor; bound to the record through the PDA seeds
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct MarkProcessed<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: The depositor public key used in PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct CloseDepositRecord<'info> {
     #[account(mut, seeds = [b"vault_state".as_ref()], bump)]
     pub vault_state: Account<'info, VaultState>,
 
+    /// CHECK: PDA holds SOL; refunds an expired pending deposit
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    pub vault_pda: AccountInfo<'info>,
+
     #[account(
-        init,
-        payer = depositor,
-        space = 8 + 4 + MAX_ORDER_ID_LEN + 8 + 32 + 8,
+        mut,
         seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()],
+        bump,
+        close = rent_recipient
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// CHECK: Receives the record rent; must be the account that paid it
+    #[account(
+        mut,
+        address = deposit_record.rent_payer @ VaultError::InvalidRentRecipient
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutTable<'info> {
+    #[account(
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 4 + MAX_PAYOUT_ENTRIES * (32 + 2) + 1, // discriminator + entries + bump
+        seeds = [b"payout_table".as_ref()],
         bump
     )]
+    pub payout_table: Account<'info, PayoutTable>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    /// CHECK: PDA holds SOL
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(seeds = [b"payout_table".as_ref()], bump = payout_table.bump)]
+    pub payout_table: Account<'info, PayoutTable>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct Check<'info> {
+    #[account(seeds = [b"vault_state".as_ref()], bump)]
+    pub vault_state: Account<'info, VaultState>,
+    
+    /// CHECK: PDA holds SOL
+    #[account(seeds = [b"vault_pda".as_ref()], bump)]
+    pub vault_pda: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct CheckDeposit<'info> {
+    #[account(seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()], bump)]
     pub deposit_record: Account<'info, DepositRecord>,
+    
+    /// CHECK: The depositor public key used in PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: new wallet to set
+    pub new_wallet: UncheckedAccount<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyWithdrawalAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawalAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        constraint = signer.key() == vault_state.authority
+            || (vault_state.guardian != Pubkey::default()
+                && signer.key() == vault_state.guardian) @ VaultError::Unauthorized
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    /// Vault authority or guardian
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetWalletChangeDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    pub authority: Signer<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyWalletChangeDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+}
+
+#[derive(Accounts)]
+pub struct ManageSignerSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 4 + MAX_SIGNERS * 32 + 1 + 1, // discriminator + signers + threshold + bump
+        seeds = [b"signer_set".as_ref()],
+        bump
+    )]
+    pub signer_set: Account<'info, SignerSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
     #[account(
         mut,
         seeds = [b"vault_state".as_ref()],
@@ -352,72 +2221,222 @@ pub struct Withdraw<'info> {
         has_one = authority
     )]
     pub vault_state: Account<'info, VaultState>,
-
-    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
-    /// CHECK: PDA holds SOL
-    pub vault_pda: AccountInfo<'info>,
-
-    #[account(mut)]
+    
     pub authority: Signer<'info>,
 
-    pub system_program: Program<'info, System>,
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
 }
 
 #[derive(Accounts)]
-pub struct Check<'info> {
-    #[account(seeds = [b"vault_state".as_ref()], bump)]
+pub struct SetFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        constraint = vault_state.guardian != Pubkey::default()
+            && vault_state.guardian == guardian.key() @ VaultError::Unauthorized
+    )]
     pub vault_state: Account<'info, VaultState>,
     
-    /// CHECK: PDA holds SOL
-    #[account(seeds = [b"vault_pda".as_ref()], bump)]
-    pub vault_pda: AccountInfo<'info>,
+    pub guardian: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(order_id: String)]
-pub struct CheckDeposit<'info> {
-    #[account(seeds = [b"deposit_record", depositor.key().as_ref(), order_id.as_bytes()], bump)]
-    pub deposit_record: Account<'info, DepositRecord>,
+pub struct SetWithdrawReserve<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state".as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
     
-    /// CHECK: The depositor public key used in PDA derivation
-    pub depositor: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
 }
 
 #[derive(Accounts)]
-pub struct SetWithdrawalAccount<'info> {
+pub struct SetDepositRecordTtl<'info> {
     #[account(
         mut,
         seeds = [b"vault_state".as_ref()],
         bump,
-        has_one = authority 
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
     )]
     pub vault_state: Account<'info, VaultState>,
     
-    #[account(mut)]
     pub authority: Signer<'info>,
-    
-    /// CHECK: new wallet to set
-    pub new_wallet: UncheckedAccount<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateAuthority<'info> {
+pub struct SetDepositFee<'info> {
     #[account(
         mut,
         seeds = [b"vault_state".as_ref()],
         bump,
-        has_one = authority
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
     )]
     pub vault_state: Account<'info, VaultState>,
     
-    #[account(mut)]
     pub authority: Signer<'info>,
+    
+    /// CHECK: wallet that will receive deposit fees
+    pub fee_destination: UncheckedAccount<'info>,
+
+    /// Required when multisig is enabled
+    #[account(seeds = [b"signer_set".as_ref()], bump = signer_set.bump)]
+    pub signer_set: Option<Account<'info, SignerSet>>,
 }
 
 #[account]
 pub struct VaultState {
     pub wallet_account: Pubkey,
     pub authority: Pubkey,
+    pub paused: bool,
+    pub min_deposit_lamports: u64,
+    pub fee_bps: u16,
+    pub fee_destination: Pubkey,
+    pub total_deposited: u64,
+    pub deposit_count: u64,
+    pub total_withdrawn: u64,
+    pub withdraw_reserve_lamports: u64,
+    pub guardian: Pubkey,
+    pub frozen: bool,
+    pub wallet_change_delay_seconds: u64,
+    pub pending_wallet: Pubkey,
+    pub pending_wallet_effective_at: i64,
+    pub multisig_enabled: bool,
+    pub pending_delay_seconds: u64,
+    pub pending_delay_effective_at: i64,
+    pub pending_deposit_count: u64,
+    pub deposit_record_ttl_seconds: i64,
+    /// Layout version; see `VAULT_STATE_VERSION`
+    pub version: u8,
+    pub total_refunded: u64,
+    pub refund_count: u64,
+    pub reserved: [u8; VAULT_STATE_RESERVED_LEN],
+}
+
+impl VaultState {
+    // discriminator + wallet_account + authority + paused + min_deposit_lamports + fee_bps +
+    // fee_destination + total_deposited + deposit_count + total_withdrawn +
+    // withdraw_reserve_lamports + guardian + frozen + wallet_change_delay_seconds +
+    // pending_wallet + pending_wallet_effective_at + multisig_enabled + pending_delay_seconds +
+    // pending_delay_effective_at + pending_deposit_count + deposit_record_ttl_seconds +
+    // version + total_refunded + refund_count + reserved
+    pub const SPACE: usize = 8 + 32 + 32 + 1 + 8 + 2 + 32 + 8 + 8 + 8 + 8 + 32 + 1 + 8 + 32 + 8 + 1 + 8 + 8 + 8 + 8
+        + 1
+        + 8
+        + 8
+        + VAULT_STATE_RESERVED_LEN;
+
+    /// Add a refunded deposit to the refund totals.
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.total_refunded = self
+            .total_refunded
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        self.refund_count = self
+            .refund_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Require `threshold` distinct signer set members among `co_signers`
+    /// when multisig is enabled. No-op otherwise.
+    pub fn verify_approvals(
+        &self,
+        signer_set: Option<&SignerSet>,
+        co_signers: &[AccountInfo],
+    ) -> Result<()> {
+        if !self.multisig_enabled {
+            return Ok(());
+        }
+        let signer_set = signer_set.ok_or(VaultError::SignerSetRequired)?;
+
+        let mut approvals: Vec<Pubkey> = Vec::with_capacity(signer_set.signers.len());
+        for account in co_signers {
+            if account.is_signer
+                && signer_set.signers.contains(account.key)
+                && !approvals.contains(account.key)
+            {
+                approvals.push(*account.key);
+            }
+        }
+
+        require!(
+            approvals.len() >= signer_set.threshold as usize,
+            VaultError::InsufficientSigners
+        );
+        Ok(())
+    }
+}
+
+/// Snapshot returned by `check`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultStatus {
+    /// SOL balance of the vault PDA
+    pub lamports: u64,
+    /// Lamports a withdraw would move now (balance - rent - reserve)
+    pub withdrawable: u64,
+    pub wallet_account: Pubkey,
+    pub authority: Pubkey,
+    pub paused: bool,
+    pub frozen: bool,
+    pub guardian: Pubkey,
+    pub multisig_enabled: bool,
+    pub pending_wallet: Pubkey,
+    pub pending_wallet_effective_at: i64,
+    pub min_deposit_lamports: u64,
+    pub fee_bps: u16,
+    pub withdraw_reserve_lamports: u64,
+    pub total_deposited: u64,
+    pub deposit_count: u64,
+    pub total_withdrawn: u64,
+    pub total_refunded: u64,
+    pub refund_count: u64,
+}
+
+/// M-of-N co-signers required for sensitive admin actions.
+#[account]
+pub struct SignerSet {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+/// Written by close_vault; only this authority may initialize the vault again.
+#[account]
+pub struct VaultTombstone {
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+/// Destinations and shares used by withdraw_split.
+#[account]
+pub struct PayoutTable {
+    pub entries: Vec<PayoutEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PayoutEntry {
+    /// Wallet receiving this share
+    pub wallet: Pubkey,
+    /// Share of the withdrawal in basis points
+    pub bps: u16,
 }
 
 #[derive(Accounts)]
@@ -427,12 +2446,33 @@ pub struct Initialize<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 32, // discriminator + wallet_account + authority
+        space = VaultState::SPACE,
         seeds = [b"vault_state".as_ref()],
         bump
     )]
     pub vault_state: Account<'info, VaultState>,
 
+    /// CHECK: PDA to hold SOL; funded with its rent-exempt minimum here
+    #[account(mut, seeds = [b"vault_pda".as_ref()], bump)]
+    pub vault_pda: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Left behind by close_vault; absent until the vault is first closed
+    #[account(seeds = [b"vault_tombstone".as_ref()], bump)]
+    pub tombstone: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVaultState<'info> {
+    /// CHECK: May predate the current layout, so the discriminator and authority
+    /// are checked by hand in the handler
+    #[account(mut, owner = crate::ID, seeds = [b"vault_state".as_ref()], bump)]
+    pub vault_state: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -456,6 +2496,21 @@ pub struct VaultInitializedEvent {
     pub timestamp: i64,
 }
 
+/// Emitted when the vault state is upgraded to the current layout
+#[event]
+pub struct VaultMigratedEvent {
+    /// The vault state PDA
+    pub vault_state: Pubkey,
+    /// Layout version before the migration
+    pub from_version: u8,
+    /// Layout version after the migration
+    pub to_version: u8,
+    /// Authority who ran the migration
+    pub authority: Pubkey,
+    /// Timestamp of migration
+    pub timestamp: i64,
+}
+
 /// Emitted when SOL is deposited into the vault
 #[event]
 pub struct DepositEvent {
@@ -465,10 +2520,18 @@ pub struct DepositEvent {
     pub order_id: String,
     /// Amount of SOL deposited (in lamports)
     pub amount: u64,
+    /// Fee sent to the fee destination (in lamports)
+    pub fee: u64,
+    /// Amount credited to the vault after the fee (in lamports)
+    pub net_amount: u64,
     /// The deposit record PDA
     pub deposit_record: Pubkey,
     /// Timestamp of deposit
     pub timestamp: i64,
+    /// Wallet that paid the lamports (differs from depositor for deposit_for)
+    pub payer: Pubkey,
+    /// Order sequence the deposit was made with
+    pub sequence: u64,
 }
 
 /// Emitted when SOL is withdrawn from the vault (admin only)
@@ -486,6 +2549,220 @@ pub struct WithdrawEvent {
     pub timestamp: i64,
 }
 
+/// Emitted when SOL is withdrawn as wrapped SOL (admin only)
+#[event]
+pub struct WithdrawWrappedEvent {
+    /// The vault from which funds were withdrawn
+    pub vault_state: Pubkey,
+    /// The wallet that owns the receiving token account
+    pub wallet_account: Pubkey,
+    /// The wSOL associated token account that received the funds
+    pub token_account: Pubkey,
+    /// Amount withdrawn (in lamports)
+    pub amount: u64,
+    /// Authority who authorized the withdrawal
+    pub authority: Pubkey,
+    /// Timestamp of withdrawal
+    pub timestamp: i64,
+}
+
+/// Emitted when the vault is decommissioned (admin only)
+#[event]
+pub struct VaultClosedEvent {
+    /// The closed vault state PDA
+    pub vault_state: Pubkey,
+    /// The wallet that received the remaining funds
+    pub wallet_account: Pubkey,
+    /// Amount swept from the vault PDA (in lamports)
+    pub amount: u64,
+    /// Authority who closed the vault
+    pub authority: Pubkey,
+    /// Timestamp of closure
+    pub timestamp: i64,
+}
+
+/// Emitted when SOL is withdrawn across the payout table (admin only)
+#[event]
+pub struct WithdrawSplitEvent {
+    /// The vault from which funds were withdrawn
+    pub vault_state: Pubkey,
+    /// The wallets that received a share, in table order
+    pub wallets: Vec<Pubkey>,
+    /// Lamports sent to each wallet, in table order
+    pub amounts: Vec<u64>,
+    /// Total amount withdrawn (in lamports)
+    pub amount: u64,
+    /// Authority who authorized the withdrawal
+    pub authority: Pubkey,
+    /// Timestamp of withdrawal
+    pub timestamp: i64,
+}
+
+/// Emitted when a deposit is refunded to its depositor (admin only)
+#[event]
+pub struct DepositRefundedEvent {
+    /// The user who received the refund
+    pub depositor: Pubkey,
+    /// The order ID of the refunded deposit
+    pub order_id: String,
+    /// Amount refunded (in lamports)
+    pub amount: u64,
+    /// The deposit record PDA
+    pub deposit_record: Pubkey,
+    /// Authority who issued the refund
+    pub authority: Pubkey,
+    /// Timestamp of refund
+    pub timestamp: i64,
+}
+
+/// Emitted when the backend marks a deposit as processed (admin only)
+#[event]
+pub struct DepositProcessedEvent {
+    /// The user who made the deposit
+    pub depositor: Pubkey,
+    /// The order ID of the processed deposit
+    pub order_id: String,
+    /// The deposit record PDA
+    pub deposit_record: Pubkey,
+    /// Authority who marked the deposit
+    pub authority: Pubkey,
+    /// Timestamp of processing
+    pub timestamp: i64,
+}
+
+/// Emitted when a deposit record is closed and its rent reclaimed
+#[event]
+pub struct DepositRecordClosedEvent {
+    /// The depositor who closed the record
+    pub depositor: Pubkey,
+    /// The order ID of the closed record
+    pub order_id: String,
+    /// The closed deposit record PDA
+    pub deposit_record: Pubkey,
+    /// Account the record rent was returned to
+    pub rent_recipient: Pubkey,
+    /// Lamports refunded to the depositor for an expired pending deposit
+    pub refunded: u64,
+    /// Timestamp of closure
+    pub timestamp: i64,
+}
+
+/// Emitted when the payout table is set or updated
+#[event]
+pub struct PayoutTableUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// The new payout entries
+    pub entries: Vec<PayoutEntry>,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when deposits are paused or resumed
+#[event]
+pub struct VaultPausedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Whether deposits are now paused
+    pub paused: bool,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the minimum deposit amount changes
+#[event]
+pub struct MinDepositUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous minimum (in lamports)
+    pub previous_min_deposit: u64,
+    /// New minimum (in lamports)
+    pub new_min_deposit: u64,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the guardian is set or cleared
+#[event]
+pub struct GuardianUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous guardian (default if none)
+    pub previous_guardian: Pubkey,
+    /// New guardian (default if cleared)
+    pub new_guardian: Pubkey,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the guardian freezes or unfreezes the vault
+#[event]
+pub struct VaultFrozenEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Whether outflows are now frozen
+    pub frozen: bool,
+    /// Guardian who made the change
+    pub guardian: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the withdraw reserve changes
+#[event]
+pub struct WithdrawReserveUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous reserve (in lamports)
+    pub previous_reserve: u64,
+    /// New reserve (in lamports)
+    pub new_reserve: u64,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the deposit record TTL changes
+#[event]
+pub struct DepositRecordTtlUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous TTL (in seconds)
+    pub previous_ttl_seconds: i64,
+    /// New TTL (in seconds)
+    pub new_ttl_seconds: i64,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when the deposit fee changes
+#[event]
+pub struct DepositFeeUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous fee (in bps)
+    pub previous_fee_bps: u16,
+    /// New fee (in bps)
+    pub new_fee_bps: u16,
+    /// Wallet receiving deposit fees
+    pub fee_destination: Pubkey,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
 /// Emitted when the withdrawal wallet is set or updated
 #[event]
 pub struct WithdrawalWalletUpdatedEvent {
@@ -499,6 +2776,79 @@ pub struct WithdrawalWalletUpdatedEvent {
     pub timestamp: i64,
 }
 
+/// Emitted when a withdrawal wallet change is queued behind the timelock
+#[event]
+pub struct WithdrawalWalletQueuedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// The queued withdrawal wallet
+    pub pending_wallet: Pubkey,
+    /// Earliest time the change can be applied
+    pub effective_at: i64,
+    /// Authority who queued the change
+    pub authority: Pubkey,
+    /// Timestamp of request
+    pub timestamp: i64,
+}
+
+/// Emitted when a queued withdrawal wallet change is cancelled
+#[event]
+pub struct WithdrawalWalletChangeCancelledEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// The wallet that will no longer be applied
+    pub cancelled_wallet: Pubkey,
+    /// Authority or guardian who cancelled
+    pub cancelled_by: Pubkey,
+    /// Timestamp of cancellation
+    pub timestamp: i64,
+}
+
+/// Emitted when the withdrawal wallet change delay is updated
+#[event]
+pub struct WalletChangeDelayUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Previous delay (in seconds)
+    pub previous_delay_seconds: u64,
+    /// New delay (in seconds)
+    pub new_delay_seconds: u64,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
+/// Emitted when a wallet change delay reduction is queued
+#[event]
+pub struct WalletChangeDelayQueuedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Delay (in seconds) that will apply once effective
+    pub pending_delay_seconds: u64,
+    /// Earliest time the reduction can be applied
+    pub effective_at: i64,
+    /// Authority who queued the change
+    pub authority: Pubkey,
+    /// Timestamp of request
+    pub timestamp: i64,
+}
+
+/// Emitted when the multisig signer set or threshold changes
+#[event]
+pub struct SignerSetUpdatedEvent {
+    /// The vault affected
+    pub vault_state: Pubkey,
+    /// Current signer set members
+    pub signers: Vec<Pubkey>,
+    /// Number of co-signers required (0 = multisig disabled)
+    pub threshold: u8,
+    /// Authority who made the change
+    pub authority: Pubkey,
+    /// Timestamp of change
+    pub timestamp: i64,
+}
+
 /// Emitted when the vault authority is updated
 #[event]
 pub struct AuthorityUpdatedEvent {
@@ -534,4 +2884,64 @@ pub enum VaultError {
     AuthorityCannotBeVaultAccount,
     #[msg("Order ID cannot be empty")]
     OrderIdEmpty,
+    #[msg("Payout table must have 1-5 unique wallets with shares summing to 10000 bps")]
+    InvalidPayoutTable,
+    #[msg("Destination accounts do not match the payout table")]
+    PayoutTableMismatch,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Deposits are paused")]
+    DepositsPaused,
+    #[msg("Deposit amount is below the vault minimum")]
+    DepositBelowMinimum,
+    #[msg("Deposit fee exceeds the maximum")]
+    InvalidFeeBps,
+    #[msg("Fee destination account is required when a deposit fee is set")]
+    FeeDestinationRequired,
+    #[msg("Invalid fee destination")]
+    InvalidFeeDestination,
+    #[msg("Deposit has already been refunded")]
+    DepositAlreadyRefunded,
+    #[msg("Insufficient vault balance")]
+    InsufficientVaultBalance,
+    #[msg("Deposit record cannot be closed yet")]
+    DepositRecordNotClosable,
+    #[msg("Deposit has already been processed")]
+    DepositAlreadyProcessed,
+    #[msg("Order ID must be at most 32 printable ASCII characters")]
+    OrderIdInvalid,
+    #[msg("Vault is frozen")]
+    VaultFrozen,
+    #[msg("Unauthorized access")]
+    Unauthorized,
+    #[msg("No withdrawal wallet change is pending")]
+    NoPendingWalletChange,
+    #[msg("Withdrawal wallet change delay has not elapsed")]
+    WalletChangeTimelocked,
+    #[msg("Invalid wallet change delay")]
+    InvalidWalletChangeDelay,
+    #[msg("Signer set must have unique members and a threshold no larger than its size")]
+    InvalidSignerSet,
+    #[msg("Signer set account is required when multisig is enabled")]
+    SignerSetRequired,
+    #[msg("Not enough signer set members approved this action")]
+    InsufficientSigners,
+    #[msg("Invalid beneficiary")]
+    InvalidBeneficiary,
+    #[msg("No wallet change delay reduction is pending")]
+    NoPendingDelayChange,
+    #[msg("Vault still has pending deposits")]
+    PendingDepositsOutstanding,
+    #[msg("Order sequence must be greater than the user's last deposit")]
+    OrderSequenceNotIncreasing,
+    #[msg("Rent recipient must be the account that paid the record rent")]
+    InvalidRentRecipient,
+    #[msg("Deposit record TTL must be positive")]
+    InvalidDepositRecordTtl,
+    #[msg("Fee destination must exist and be rent-exempt")]
+    FeeDestinationNotRentExempt,
+    #[msg("Account is not the vault state for this program")]
+    InvalidVaultState,
+    #[msg("Vault state is already at the current version")]
+    VaultAlreadyMigrated,
 }
@@ -0,0 +1,210 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🧹 SOL Vault Program - Close Deposit Records", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let depositRecordPda: anchor.web3.PublicKey;
+  const user = anchor.web3.Keypair.generate();
+  const orderId = "close-" + Date.now();
+  const orderSequence = nextOrderSequence();
+
+  const close = (
+    signer: anchor.web3.Keypair,
+    id: string = orderId,
+    rentRecipient: anchor.web3.PublicKey = signer.publicKey
+  ) =>
+    program.methods
+      .closeDepositRecord(id)
+      .accounts({
+        depositRecord: getDepositRecordPda(signer.publicKey, id, program.programId)[0],
+        depositor: signer.publicKey,
+        rentRecipient,
+      })
+      .signers([signer])
+      .rpc();
+
+  const refund = (id: string) =>
+    program.methods
+      .refundDeposit(id)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        depositRecord: getDepositRecordPda(user.publicKey, id, program.programId)[0],
+        depositor: user.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  const setTtl = (seconds: number) =>
+    program.methods
+      .setDepositRecordTtl(new anchor.BN(seconds))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+    [depositRecordPda] = getDepositRecordPda(user.publicKey, orderId, program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    // Keep the vault PDA rent-exempt regardless of earlier withdrawals
+    const bootstrapOrder = "close-bootstrap-" + Date.now();
+    await program.methods
+      .deposit(bootstrapOrder, new anchor.BN(1_000_000), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, bootstrapOrder, program.programId)[0],
+      })
+      .rpc();
+
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), orderSequence)
+      .accounts({
+        depositor: user.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("1. ❌ Open deposits cannot be closed before the TTL", async () => {
+    try {
+      await close(user);
+      throw new Error("Unexpected close of open deposit");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositRecordNotClosable");
+    }
+  });
+
+  it("2. ✅ Depositor closes a refunded record and reclaims rent", async () => {
+    await refund(orderId);
+
+    const rent = await provider.connection.getBalance(depositRecordPda);
+    const balanceBefore = await provider.connection.getBalance(user.publicKey);
+
+    await close(user);
+
+    expect(await provider.connection.getAccountInfo(depositRecordPda)).to.be.null;
+    // The depositor may pay the transaction fee; no account is left behind
+    const balanceAfter = await provider.connection.getBalance(user.publicKey);
+    expect(balanceAfter).to.be.greaterThan(balanceBefore + rent - 10_000);
+    console.log("✅ Rent reclaimed:", rent);
+  });
+
+  it("3. ❌ A closed order cannot be deposited again", async () => {
+    try {
+      await program.methods
+        .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), orderSequence)
+        .accounts({
+          depositor: user.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: depositRecordPda,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected reuse of a closed order");
+    } catch (err: any) {
+      expect(err.toString()).to.include("OrderSequenceNotIncreasing");
+    }
+  });
+
+  it("4. ✅ Rent of a sponsored record goes back to the payer", async () => {
+    const sponsoredOrder = "close-sponsored-" + Date.now();
+    await program.methods
+      .depositFor(user.publicKey, sponsoredOrder, new anchor.BN(1_000_000), nextOrderSequence())
+      .accounts({
+        payer: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(user.publicKey, sponsoredOrder, program.programId)[0],
+        beneficiarySigner: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+    await refund(sponsoredOrder);
+
+    try {
+      await close(user, sponsoredOrder);
+      throw new Error("Unexpected rent refund to the beneficiary");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidRentRecipient");
+    }
+
+    const recordPda = getDepositRecordPda(user.publicKey, sponsoredOrder, program.programId)[0];
+    const rent = await provider.connection.getBalance(recordPda);
+    const payerBefore = await provider.connection.getBalance(authority.publicKey);
+
+    await close(user, sponsoredOrder, authority.publicKey);
+
+    // The payer also pays the transaction fee
+    const payerAfter = await provider.connection.getBalance(authority.publicKey);
+    expect(payerAfter).to.be.greaterThan(payerBefore + rent - 10_000);
+  });
+
+  it("5. ✅ An expired pending deposit is refunded when its record closes", async () => {
+    try {
+      await setTtl(0);
+      throw new Error("Unexpected zero TTL");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidDepositRecordTtl");
+    }
+
+    const pendingOrder = "close-ttl-" + Date.now();
+    await program.methods
+      .deposit(pendingOrder, new anchor.BN(1_000_000), nextOrderSequence())
+      .accounts({
+        depositor: user.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(user.publicKey, pendingOrder, program.programId)[0],
+      })
+      .signers([user])
+      .rpc();
+
+    const pendingRecordPda = getDepositRecordPda(user.publicKey, pendingOrder, program.programId)[0];
+    const record = await program.account.depositRecord.fetch(pendingRecordPda);
+    const rent = await provider.connection.getBalance(pendingRecordPda);
+    const pendingBefore = (await program.account.vaultState.fetch(vaultStatePda)).pendingDepositCount;
+
+    await setTtl(1);
+    try {
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const balanceBefore = await provider.connection.getBalance(user.publicKey);
+      await close(user, pendingOrder);
+
+      const balanceAfter = await provider.connection.getBalance(user.publicKey);
+      expect(balanceAfter).to.be.greaterThan(
+        balanceBefore + record.solAmount.toNumber() + rent - 10_000
+      );
+    } finally {
+      await setTtl(30 * 24 * 60 * 60);
+    }
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.depositRecordTtlSeconds.toNumber()).to.equal(30 * 24 * 60 * 60);
+    expect(vaultState.pendingDepositCount.toNumber()).to.equal(pendingBefore.toNumber() - 1);
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🔒 SOL Vault Program - Close Vault", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+
+  const pdaBalance = async (seed: string) =>
+    provider.connection.getBalance(
+      anchor.web3.PublicKey.findProgramAddressSync([Buffer.from(seed)], program.programId)[0]
+    );
+
+  const closeVault = (signer?: anchor.web3.Keypair) =>
+    program.methods
+      .closeVault()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: signer ? signer.publicKey : authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const orderId = "close-vault-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    // Other suites share the global vault
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+  });
+
+  it("1. ❌ Only the authority can close the vault", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await closeVault(stranger);
+      throw new Error("Unexpected close by non-authority");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintHasOne|has one/i);
+    }
+  });
+
+  it("2. ❌ Cannot close while deposits are pending", async () => {
+    try {
+      await closeVault();
+      throw new Error("Unexpected close with pending deposits");
+    } catch (err: any) {
+      expect(err.toString()).to.include("PendingDepositsOutstanding");
+    }
+  });
+
+  it("3. ✅ Closing sweeps every lamport to the withdrawal wallet", async () => {
+    // Settle every deposit still pending in the shared vault
+    const records = await program.account.depositRecord.all();
+    for (const record of records.filter((r) => "pending" in r.account.status)) {
+      await program.methods
+        .markProcessed(record.account.orderId)
+        .accounts({
+          vaultState: vaultStatePda,
+          depositRecord: record.publicKey,
+          depositor: record.account.user,
+          authority: authority.publicKey,
+        })
+        .rpc();
+    }
+
+    const vaultBalance = await provider.connection.getBalance(vaultPda);
+    const stateRent = await provider.connection.getBalance(vaultStatePda);
+    const auxRent = (await pdaBalance("signer_set")) + (await pdaBalance("payout_table"));
+
+    await closeVault();
+
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(0);
+    expect(await provider.connection.getAccountInfo(vaultStatePda)).to.be.null;
+    expect(await pdaBalance("signer_set")).to.equal(0);
+    expect(await pdaBalance("payout_table")).to.equal(0);
+    expect(await provider.connection.getBalance(withdrawalWallet.publicKey)).to.equal(
+      vaultBalance + stateRent + auxRent
+    );
+    console.log("✅ Vault closed, swept:", vaultBalance + stateRent + auxRent);
+  });
+
+  it("4. ❌ Only the closing authority can initialize again", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      stranger.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .initialize()
+        .accounts({ vaultState: vaultStatePda, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Unexpected re-initialization by a stranger");
+    } catch (err: any) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+});
@@ -0,0 +1,133 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  getEventsFromTransaction,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("💸 SOL Vault Program - Deposit Fee", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const feeDestination = anchor.web3.Keypair.generate();
+
+  const setDepositFee = (feeBps: number, destination = feeDestination.publicKey) =>
+    program.methods
+      .setDepositFee(feeBps)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        feeDestination: destination,
+      })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    // Fund the fee destination so small fees keep it rent-exempt
+    const sig = await provider.connection.requestAirdrop(
+      feeDestination.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+  });
+
+  after(async () => {
+    await setDepositFee(0);
+  });
+
+  it("1. ❌ Fee cannot exceed the maximum", async () => {
+    try {
+      await setDepositFee(1_001);
+      throw new Error("Unexpected fee above maximum");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidFeeBps");
+    }
+  });
+
+  it("2. ❌ Fee destination must exist and be rent-exempt", async () => {
+    const unfunded = anchor.web3.Keypair.generate();
+    try {
+      await setDepositFee(250, unfunded.publicKey);
+      throw new Error("Unexpected fee with an unfunded destination");
+    } catch (err: any) {
+      expect(err.toString()).to.include("FeeDestinationNotRentExempt");
+    }
+  });
+
+  it("3. ✅ Authority sets a 2.5% fee", async () => {
+    await setDepositFee(250);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.feeBps).to.equal(250);
+    expect(vaultState.feeDestination.toBase58()).to.equal(feeDestination.publicKey.toBase58());
+  });
+
+  it("4. ❌ Deposits must pass the fee destination", async () => {
+    const orderId = "nofee-" + Date.now();
+    try {
+      await program.methods
+        .deposit(orderId, new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+        .accounts({
+          depositor: authority.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+        })
+        .rpc();
+      throw new Error("Unexpected deposit without fee destination");
+    } catch (err: any) {
+      expect(err.toString()).to.include("FeeDestinationRequired");
+    }
+  });
+
+  it("5. ✅ Fee is diverted and the net amount recorded", async () => {
+    const orderId = "fee-" + Date.now();
+    const amount = anchor.web3.LAMPORTS_PER_SOL;
+    const fee = (amount * 250) / 10_000;
+    const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
+
+    const destinationBefore = await provider.connection.getBalance(feeDestination.publicKey);
+    const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+    const tx = await program.methods
+      .deposit(orderId, new anchor.BN(amount), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+        feeDestination: feeDestination.publicKey,
+      })
+      .rpc();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    expect(await provider.connection.getBalance(feeDestination.publicKey)).to.equal(
+      destinationBefore + fee
+    );
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(vaultBefore + amount - fee);
+
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.solAmount.toNumber()).to.equal(amount - fee);
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositEvent"
+    );
+    expect(event.data.fee.toNumber()).to.equal(fee);
+    expect(event.data.netAmount.toNumber()).to.equal(amount - fee);
+    console.log("✅ Fee collected:", fee);
+  });
+});
@@ -0,0 +1,109 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  getEventsFromTransaction,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🎁 SOL Vault Program - Sponsored Deposits", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const beneficiary = anchor.web3.Keypair.generate();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+  });
+
+  it("1. ✅ Payer funds a deposit credited to the beneficiary", async () => {
+    const orderId = "sponsored-" + Date.now();
+    const amount = 0.3 * anchor.web3.LAMPORTS_PER_SOL;
+    const [depositRecordPda] = getDepositRecordPda(
+      beneficiary.publicKey,
+      orderId,
+      program.programId
+    );
+    const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+    const tx = await program.methods
+      .depositFor(beneficiary.publicKey, orderId, new anchor.BN(amount), nextOrderSequence())
+      .accounts({
+        payer: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+        beneficiarySigner: beneficiary.publicKey,
+      })
+      .signers([beneficiary])
+      .rpc();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.user.toBase58()).to.equal(beneficiary.publicKey.toBase58());
+    expect(record.solAmount.toNumber()).to.equal(amount);
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(vaultBefore + amount);
+    // The beneficiary only co-signed; the payer covered everything
+    expect(await provider.connection.getBalance(beneficiary.publicKey)).to.equal(0);
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositEvent"
+    );
+    expect(event.data.depositor.toBase58()).to.equal(beneficiary.publicKey.toBase58());
+    expect(event.data.payer.toBase58()).to.equal(authority.publicKey.toBase58());
+  });
+
+  it("2. ❌ Record PDA must be derived from the beneficiary", async () => {
+    const orderId = "sponsored-bad-" + Date.now();
+    try {
+      await program.methods
+        .depositFor(beneficiary.publicKey, orderId, new anchor.BN(1_000_000), nextOrderSequence())
+        .accounts({
+          payer: authority.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+          beneficiarySigner: beneficiary.publicKey,
+        })
+        .signers([beneficiary])
+        .rpc();
+      throw new Error("Unexpected deposit with payer-derived record");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintSeeds|seeds constraint/i);
+    }
+  });
+
+
+  it("3. ❌ The beneficiary must co-sign a sponsored deposit", async () => {
+    const orderId = "sponsored-unsigned-" + Date.now();
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .depositFor(beneficiary.publicKey, orderId, new anchor.BN(1_000_000), nextOrderSequence())
+        .accounts({
+          payer: authority.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: getDepositRecordPda(beneficiary.publicKey, orderId, program.programId)[0],
+          beneficiarySigner: stranger.publicKey,
+        })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Unexpected deposit without the beneficiary's signature");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidBeneficiary");
+    }
+  });
+});
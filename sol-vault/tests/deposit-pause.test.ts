@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("⏸️ SOL Vault Program - Deposit Pause", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+
+  const deposit = (orderId: string) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+
+  const setPaused = (paused: boolean) =>
+    program.methods
+      .setPaused(paused)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    // Always leave deposits open for the other suites
+    await setPaused(false);
+  });
+
+  it("1. ✅ Authority can pause deposits", async () => {
+    await deposit("pre-pause-" + Date.now());
+    await setPaused(true);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.paused).to.be.true;
+  });
+
+  it("2. ❌ Deposits fail while paused", async () => {
+    try {
+      await deposit("paused-" + Date.now());
+      throw new Error("Unexpected deposit while paused");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositsPaused");
+    }
+  });
+
+  it("3. ✅ Withdrawals still work while paused", async () => {
+    await program.methods
+      .withdraw()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const balance = await provider.connection.getBalance(withdrawalWallet.publicKey);
+    expect(balance).to.be.greaterThan(0);
+  });
+
+  it("4. ❌ Only the authority can pause", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setPaused(true)
+        .accounts({ vaultState: vaultStatePda, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Unexpected pause by non-authority");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintHasOne|has one/i);
+    }
+  });
+
+  it("5. ✅ Deposits resume after unpausing", async () => {
+    await setPaused(false);
+    await deposit("resumed-" + Date.now());
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.paused).to.be.false;
+  });
+});
@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("📋 SOL Vault Program - Deposit Status", () => {
+  const { program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const orderId = "status-" + Date.now();
+  let depositRecordPda: anchor.web3.PublicKey;
+
+  const markProcessed = () =>
+    program.methods
+      .markProcessed(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+        depositor: authority.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+    [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+      })
+      .rpc();
+  });
+
+  it("1. ✅ New deposits start as pending", async () => {
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.status).to.deep.equal({ pending: {} });
+  });
+
+  it("2. ✅ Authority marks the deposit processed", async () => {
+    await markProcessed();
+
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.status).to.deep.equal({ processed: {} });
+  });
+
+  it("3. ❌ Processed deposits cannot be processed again or refunded", async () => {
+    try {
+      await markProcessed();
+      throw new Error("Unexpected second mark_processed");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositAlreadyProcessed");
+    }
+
+    try {
+      await program.methods
+        .refundDeposit(orderId)
+        .accounts({
+          vaultState: vaultStatePda,
+          vaultPda: vaultPda,
+          depositRecord: depositRecordPda,
+          depositor: authority.publicKey,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected refund of processed deposit");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositAlreadyProcessed");
+    }
+  });
+
+  it("4. ✅ Processed records can be closed", async () => {
+    await program.methods
+      .closeDepositRecord(orderId)
+      .accounts({
+        depositRecord: depositRecordPda,
+        depositor: authority.publicKey,
+        rentRecipient: authority.publicKey,
+      })
+      .rpc();
+
+    const info = await program.provider.connection.getAccountInfo(depositRecordPda);
+    expect(info).to.be.null;
+  });
+});
@@ -7,6 +7,7 @@ import {
   getDepositRecordPda,
   getEventsFromTransaction,
   ensureVaultInitialized,
+  nextOrderSequence,
 } from "../helpers/utils";
 
 describe("🎉 SOL Vault Program - Withdrawal Events", () => {
@@ -75,7 +76,7 @@ describe("🎉 SOL Vault Program - Withdrawal Events", () => {
     const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
 
     const tx = await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
@@ -110,14 +111,8 @@ describe("🎉 SOL Vault Program - Withdrawal Events", () => {
         vaultState: vaultStatePda,
         vaultPda: vaultPda,
         authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
       })
-      .remainingAccounts([
-        {
-          pubkey: withdrawalWallet.publicKey,
-          isSigner: false,
-          isWritable: true,
-        },
-      ])
       .rpc();
 
     await provider.connection.confirmTransaction(tx, "confirmed");
@@ -148,7 +143,7 @@ describe("🎉 SOL Vault Program - Withdrawal Events", () => {
     const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
 
     const depositTx = await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
@@ -179,14 +174,8 @@ describe("🎉 SOL Vault Program - Withdrawal Events", () => {
         vaultState: vaultStatePda,
         vaultPda: vaultPda,
         authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
       })
-      .remainingAccounts([
-        {
-          pubkey: withdrawalWallet.publicKey,
-          isSigner: false,
-          isWritable: true,
-        },
-      ])
       .rpc();
 
     await provider.connection.confirmTransaction(withdrawTx, "confirmed");
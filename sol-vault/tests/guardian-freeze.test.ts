@@ -0,0 +1,159 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🛡️ SOL Vault Program - Guardian Freeze", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const guardian = anchor.web3.Keypair.generate();
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+
+  const setFrozen = (frozen: boolean, signer: anchor.web3.Keypair = guardian) =>
+    program.methods
+      .setFrozen(frozen)
+      .accounts({ vaultState: vaultStatePda, guardian: signer.publicKey })
+      .signers([signer])
+      .rpc();
+
+  const setGuardian = (key: anchor.web3.PublicKey | null) =>
+    program.methods
+      .setGuardian(key)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const sig = await provider.connection.requestAirdrop(
+      guardian.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    await setGuardian(guardian.publicKey);
+  });
+
+  after(async () => {
+    // Leave the shared vault unfrozen and without a guardian
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    if (vaultState.frozen) {
+      await setFrozen(false);
+    }
+    await setGuardian(null);
+  });
+
+  it("1. ❌ Only the guardian can freeze", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      stranger.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await setFrozen(true, stranger);
+      throw new Error("Unexpected freeze by non-guardian");
+    } catch (err: any) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ✅ Guardian freezes the vault", async () => {
+    await setFrozen(true);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.frozen).to.be.true;
+  });
+
+  it("3. ❌ Withdrawals and wallet changes are blocked while frozen", async () => {
+    try {
+      await program.methods
+        .withdraw()
+        .accounts({
+          vaultState: vaultStatePda,
+          vaultPda: vaultPda,
+          authority: authority.publicKey,
+          wallet: withdrawalWallet.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected withdrawal while frozen");
+    } catch (err: any) {
+      expect(err.toString()).to.include("VaultFrozen");
+    }
+
+    try {
+      await program.methods
+        .setWithdrawalAccount()
+        .accounts({
+          vaultState: vaultStatePda,
+          authority: authority.publicKey,
+          newWallet: anchor.web3.Keypair.generate().publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected wallet change while frozen");
+    } catch (err: any) {
+      expect(err.toString()).to.include("VaultFrozen");
+    }
+
+    try {
+      await setGuardian(null);
+      throw new Error("Unexpected guardian change while frozen");
+    } catch (err: any) {
+      expect(err.toString()).to.include("VaultFrozen");
+    }
+  });
+
+  it("4. ✅ Deposits still go through while frozen", async () => {
+    const orderId = "frozen-" + Date.now();
+    const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+      })
+      .rpc();
+
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.orderId).to.equal(orderId);
+  });
+
+  it("5. ✅ Guardian unfreezes and withdrawals resume", async () => {
+    await setFrozen(false);
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    expect(await provider.connection.getBalance(withdrawalWallet.publicKey)).to.be.greaterThan(0);
+  });
+});
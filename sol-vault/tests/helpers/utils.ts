@@ -67,6 +67,43 @@ export function getDepositRecordPda(
   );
 }
 
+/**
+ * Derives the per-user deposit index PDA
+ */
+export function getUserDepositIndexPda(
+  user: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user_deposit_index"), user.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derives the per-user order sequence PDA
+ */
+export function getOrderSequencePda(
+  user: anchor.web3.PublicKey,
+  programId: anchor.web3.PublicKey
+) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("order_sequence"), user.toBuffer()],
+    programId
+  );
+}
+
+let lastOrderSequence = 0;
+
+/**
+ * Returns an order sequence larger than any returned before, including by
+ * earlier test runs against the same validator
+ */
+export function nextOrderSequence() {
+  lastOrderSequence = Math.max(lastOrderSequence + 1, Date.now());
+  return new anchor.BN(lastOrderSequence);
+}
+
 /**
  * Ensures the vault is initialized. If it already exists, skips initialization.
  * This is useful for test files that run in parallel and share the same vault PDA.
@@ -3,6 +3,7 @@ import { expect } from "chai";
 import {
   getTestEnvironment,
   getVaultStatePda,
+  getVaultPda,
 } from "./helpers/utils";
 
 describe("🎉 SOL Vault Program - Initialization", () => {
@@ -48,4 +49,13 @@ describe("🎉 SOL Vault Program - Initialization", () => {
       console.log("✅ Vault initialized successfully");
     }
   });
+
+  it("✅ Vault PDA is rent-exempt after initialization", async () => {
+    const [vaultPda] = getVaultPda(program.programId);
+    const balance = await provider.connection.getBalance(vaultPda);
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    expect(balance).to.be.at.least(rentExempt);
+    console.log("✅ Vault PDA balance:", balance);
+  });
 });
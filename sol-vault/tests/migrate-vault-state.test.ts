@@ -0,0 +1,59 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  ensureVaultInitialized,
+} from "./helpers/utils";
+
+describe("🧬 SOL Vault Program - Vault State Migration", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+  });
+
+  it("1. ✅ The vault is at the current layout version", async () => {
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.version).to.equal(1);
+    expect(vaultState.reserved.length).to.equal(48);
+  });
+
+  it("2. ❌ A current vault cannot be migrated again", async () => {
+    try {
+      await program.methods
+        .migrateVaultState()
+        .accounts({ authority: authority.publicKey })
+        .rpc();
+      throw new Error("Unexpected migration of current vault");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("already at the current version");
+    }
+  });
+
+  it("3. ❌ Only the vault authority can migrate", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      stranger.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .migrateVaultState()
+        .accounts({ authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Unexpected migration by non-authority");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized access");
+    }
+  });
+});
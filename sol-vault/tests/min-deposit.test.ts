@@ -0,0 +1,86 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🪙 SOL Vault Program - Minimum Deposit", () => {
+  const { program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const minDeposit = 10_000_000;
+
+  const deposit = (orderId: string, amount: number) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(amount), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+
+  const setMinDeposit = (amount: number) =>
+    program.methods
+      .setMinDeposit(new anchor.BN(amount))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+    await setMinDeposit(minDeposit);
+  });
+
+  after(async () => {
+    // Other suites deposit small amounts
+    await setMinDeposit(0);
+  });
+
+  it("1. ✅ Authority can set the minimum", async () => {
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.minDepositLamports.toNumber()).to.equal(minDeposit);
+  });
+
+  it("2. ❌ Deposits below the minimum are rejected", async () => {
+    try {
+      await deposit("dust-" + Date.now(), minDeposit - 1);
+      throw new Error("Unexpected dust deposit");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositBelowMinimum");
+    }
+  });
+
+  it("3. ✅ Deposits at the minimum succeed", async () => {
+    const orderId = "min-" + Date.now();
+    await deposit(orderId, minDeposit);
+
+    const record = await program.account.depositRecord.fetch(
+      getDepositRecordPda(authority.publicKey, orderId, program.programId)[0]
+    );
+    expect(record.solAmount.toNumber()).to.equal(minDeposit);
+  });
+
+  it("4. ❌ Only the authority can change the minimum", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setMinDeposit(new anchor.BN(0))
+        .accounts({ vaultState: vaultStatePda, authority: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      throw new Error("Unexpected update by non-authority");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintHasOne|has one/i);
+    }
+  });
+});
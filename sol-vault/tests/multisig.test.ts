@@ -0,0 +1,181 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("👥 SOL Vault Program - Multisig Authority", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let signerSetPda: anchor.web3.PublicKey;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+
+  const coSigners = (keys: anchor.web3.Keypair[]) =>
+    keys.map((k) => ({ pubkey: k.publicKey, isSigner: true, isWritable: false }));
+
+  const withdraw = (approvers: anchor.web3.Keypair[], withSignerSet = true) =>
+    program.methods
+      .withdraw()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+        signerSet: withSignerSet ? signerSetPda : null,
+      })
+      .remainingAccounts(coSigners(approvers))
+      .signers(approvers)
+      .rpc();
+
+  const manage = (method: any, approvers: anchor.web3.Keypair[] = []) =>
+    method
+      .accounts({
+        vaultState: vaultStatePda,
+        signerSet: signerSetPda,
+        authority: authority.publicKey,
+      })
+      .remainingAccounts(coSigners(approvers))
+      .signers(approvers)
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+    [signerSetPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("signer_set")],
+      program.programId
+    );
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const orderId = "multisig-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.2 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    // Disable multisig for the other suites
+    await manage(program.methods.setSignerThreshold(0), [alice, bob]);
+    await manage(program.methods.removeSigner(alice.publicKey));
+    await manage(program.methods.removeSigner(bob.publicKey));
+  });
+
+  it("1. ✅ Authority builds a 2-of-2 signer set", async () => {
+    await manage(program.methods.addSigner(alice.publicKey));
+    await manage(program.methods.addSigner(bob.publicKey));
+    await manage(program.methods.setSignerThreshold(2));
+
+    const signerSet = await program.account.signerSet.fetch(signerSetPda);
+    expect(signerSet.signers.length).to.equal(2);
+    expect(signerSet.threshold).to.equal(2);
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.multisigEnabled).to.be.true;
+  });
+
+  it("2. ❌ Threshold cannot exceed the signer count", async () => {
+    try {
+      await manage(program.methods.setSignerThreshold(3), [alice, bob]);
+      throw new Error("Unexpected threshold above signer count");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidSignerSet");
+    }
+  });
+
+  it("3. ❌ Withdraw needs the signer set and enough co-signers", async () => {
+    try {
+      await withdraw([alice, bob], false);
+      throw new Error("Unexpected withdrawal without signer set");
+    } catch (err: any) {
+      expect(err.toString()).to.include("SignerSetRequired");
+    }
+
+    try {
+      await withdraw([alice]);
+      throw new Error("Unexpected withdrawal with one co-signer");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InsufficientSigners");
+    }
+  });
+
+  it("4. ❌ Authority changes also need co-signers", async () => {
+    try {
+      await program.methods
+        .updateAuthority(alice.publicKey)
+        .accounts({
+          vaultState: vaultStatePda,
+          authority: authority.publicKey,
+          signerSet: signerSetPda,
+        })
+        .rpc();
+      throw new Error("Unexpected authority change without co-signers");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InsufficientSigners");
+    }
+  });
+
+  it("5. ❌ Guardian, reserve and delay changes also need co-signers", async () => {
+    const setters = [
+      program.methods.setGuardian(alice.publicKey),
+      program.methods.setWithdrawReserve(new anchor.BN(0)),
+      program.methods.setWalletChangeDelay(new anchor.BN(0)),
+    ];
+    for (const method of setters) {
+      try {
+        await manage(method, [alice]);
+        throw new Error("Unexpected admin change with one co-signer");
+      } catch (err: any) {
+        expect(err.toString()).to.include("InsufficientSigners");
+      }
+    }
+  });
+
+  it("6. ❌ Deposit fee changes also need co-signers", async () => {
+    try {
+      await program.methods
+        .setDepositFee(100)
+        .accounts({
+          vaultState: vaultStatePda,
+          authority: authority.publicKey,
+          feeDestination: withdrawalWallet.publicKey,
+          signerSet: signerSetPda,
+        })
+        .remainingAccounts(coSigners([alice]))
+        .signers([alice])
+        .rpc();
+      throw new Error("Unexpected fee change with one co-signer");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InsufficientSigners");
+    }
+  });
+
+  it("7. ✅ Withdraw succeeds with both co-signers", async () => {
+    await withdraw([alice, bob]);
+
+    expect(await provider.connection.getBalance(withdrawalWallet.publicKey)).to.be.greaterThan(0);
+  });
+});
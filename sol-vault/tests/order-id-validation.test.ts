@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { expect } from "chai";
+import { nextOrderSequence } from "./helpers/utils";
 
 describe("sol_vault_program - Order ID Validation", () => {
   const provider = anchor.AnchorProvider.local();
@@ -54,7 +55,7 @@ describe("sol_vault_program - Order ID Validation", () => {
 
     try {
       await program.methods
-        .deposit(orderId, depositAmount)
+        .deposit(orderId, depositAmount, nextOrderSequence())
         .accounts({
           depositor: wallet.publicKey,
           vaultPda: vaultPda,
@@ -87,7 +88,7 @@ describe("sol_vault_program - Order ID Validation", () => {
       );
 
       await program.methods
-        .deposit(orderId, depositAmount)
+        .deposit(orderId, depositAmount, nextOrderSequence())
         .accounts({
           depositor: wallet.publicKey,
           vaultPda: vaultPda,
@@ -104,6 +105,37 @@ describe("sol_vault_program - Order ID Validation", () => {
     }
   });
 
+  it("Fails to deposit with non-printable characters in order_id", async () => {
+    const orderId = "order\n" + (++testCounter);
+    const depositAmount = new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL);
+
+    const [depositRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        wallet.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .deposit(orderId, depositAmount, nextOrderSequence())
+        .accounts({
+          depositor: wallet.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: depositRecordPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      throw new Error("Expected deposit to fail for non-printable order_id");
+    } catch (err: any) {
+      expect(err.toString()).to.include("OrderIdInvalid");
+    }
+  });
+
   it("Successfully deposits with valid order_id (1 byte)", async () => {
     const orderId = "a";
     const depositAmount = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
@@ -118,7 +150,7 @@ describe("sol_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: wallet.publicKey,
         vaultPda: vaultPda,
@@ -148,7 +180,7 @@ describe("sol_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: wallet.publicKey,
         vaultPda: vaultPda,
@@ -178,7 +210,7 @@ describe("sol_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: wallet.publicKey,
         vaultPda: vaultPda,
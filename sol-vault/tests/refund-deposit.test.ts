@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  getEventsFromTransaction,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("↩️ SOL Vault Program - Deposit Refunds", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const user = anchor.web3.Keypair.generate();
+  const orderId = "refund-" + Date.now();
+  const depositAmount = 0.5 * anchor.web3.LAMPORTS_PER_SOL;
+
+  const refund = (signer?: anchor.web3.Keypair) =>
+    program.methods
+      .refundDeposit(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        depositRecord: getDepositRecordPda(user.publicKey, orderId, program.programId)[0],
+        depositor: user.publicKey,
+        authority: signer ? signer.publicKey : authority.publicKey,
+      })
+      .signers(signer ? [signer] : [])
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    // Keep the vault PDA rent-exempt regardless of earlier withdrawals
+    const bootstrapOrder = "refund-bootstrap-" + Date.now();
+    await program.methods
+      .deposit(bootstrapOrder, new anchor.BN(1_000_000), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, bootstrapOrder, program.programId)[0],
+      })
+      .rpc();
+
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(depositAmount), nextOrderSequence())
+      .accounts({
+        depositor: user.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(user.publicKey, orderId, program.programId)[0],
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("1. ❌ Only the authority can refund", async () => {
+    try {
+      await refund(user);
+      throw new Error("Unexpected refund by non-authority");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintHasOne|has one/i);
+    }
+  });
+
+  it("2. ✅ Authority refunds the deposit to the depositor", async () => {
+    const balanceBefore = await provider.connection.getBalance(user.publicKey);
+    const stateBefore = await program.account.vaultState.fetch(vaultStatePda);
+
+    const tx = await refund();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    expect(await provider.connection.getBalance(user.publicKey)).to.equal(
+      balanceBefore + depositAmount
+    );
+
+    const record = await program.account.depositRecord.fetch(
+      getDepositRecordPda(user.publicKey, orderId, program.programId)[0]
+    );
+    expect(record.status).to.deep.equal({ refunded: {} });
+
+    // Lifetime deposit totals are kept; the refund is counted on its own
+    const stateAfter = await program.account.vaultState.fetch(vaultStatePda);
+    expect(stateAfter.totalDeposited.eq(stateBefore.totalDeposited)).to.be.true;
+    expect(stateAfter.depositCount.eq(stateBefore.depositCount)).to.be.true;
+    expect(stateAfter.totalRefunded.sub(stateBefore.totalRefunded).toNumber()).to.equal(
+      depositAmount
+    );
+    expect(stateAfter.refundCount.sub(stateBefore.refundCount).toNumber()).to.equal(1);
+    expect(
+      stateBefore.pendingDepositCount.sub(stateAfter.pendingDepositCount).toNumber()
+    ).to.equal(1);
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositRefundedEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event.data.orderId).to.equal(orderId);
+    expect(event.data.amount.toNumber()).to.equal(depositAmount);
+  });
+
+  it("3. ❌ A deposit cannot be refunded twice", async () => {
+    try {
+      await refund();
+      throw new Error("Unexpected second refund");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositAlreadyRefunded");
+    }
+  });
+});
@@ -1,6 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { expect } from "chai";
+import { nextOrderSequence } from "./helpers/utils";
 
 describe("sol_vault_program", () => {
   // Configure the client to use the local cluster
@@ -65,7 +66,7 @@ describe("sol_vault_program", () => {
     const balanceBefore = vaultPdaBefore?.lamports || 0;
 
     await program.methods
-      .deposit(orderId, new anchor.BN(amountLamports))
+      .deposit(orderId, new anchor.BN(amountLamports), nextOrderSequence())
       .accounts({
         depositor: wallet.publicKey,
         vaultPda,
@@ -123,14 +124,8 @@ describe("sol_vault_program", () => {
         vaultPda,
         authority: wallet.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        wallet: walletAccount,
       })
-      .remainingAccounts([
-        {
-          pubkey: walletAccount,
-          isSigner: false,
-          isWritable: true,
-        },
-      ])
       .rpc();
 
     // Verify withdrawal succeeded - vault PDA should still exist with rent-exempt balance
@@ -181,14 +176,8 @@ await program.methods
         vaultPda,
         authority: attacker.publicKey,
         systemProgram: anchor.web3.SystemProgram.programId,
+        wallet: walletAccount,
       })
-      .remainingAccounts([
-        {
-          pubkey: walletAccount,
-          isSigner: false,
-          isWritable: true,
-        },
-      ])
       .signers([attacker])
       .rpc();
 
@@ -215,14 +204,8 @@ await program.methods
           vaultPda,
           authority: wallet.publicKey,
           systemProgram: anchor.web3.SystemProgram.programId,
+          wallet: wrongWallet,
         })
-        .remainingAccounts([
-          {
-            pubkey: wrongWallet,
-            isSigner: false,
-            isWritable: true,
-          },
-        ])
         .rpc();
 
       // If it succeeds, fail the test
@@ -263,7 +246,7 @@ await program.methods
 
     // Both deposits should succeed
     await program.methods
-      .deposit(sameOrderId, depositAmount)
+      .deposit(sameOrderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: user1.publicKey,
         vaultPda,
@@ -275,7 +258,7 @@ await program.methods
       .rpc();
 
     await program.methods
-      .deposit(sameOrderId, depositAmount)
+      .deposit(sameOrderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: user2.publicKey,
         vaultPda,
@@ -319,7 +302,7 @@ await program.methods
       // Attacker tries to deposit but uses victim's PDA
       // This should fail because the PDA seeds won't match the signer (attacker)
       await program.methods
-        .deposit(orderId, depositAmount)
+        .deposit(orderId, depositAmount, nextOrderSequence())
         .accounts({
           depositor: attacker.publicKey,  // Attacker is the signer
           vaultPda,
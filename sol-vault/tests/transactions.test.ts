@@ -6,6 +6,7 @@ import {
   getVaultPda,
   getDepositRecordPda,
   ensureVaultInitialized,
+  nextOrderSequence,
 } from "./helpers/utils";
 
 describe("🎉 SOL Vault Program - Transactions", () => {
@@ -52,7 +53,7 @@ describe("🎉 SOL Vault Program - Transactions", () => {
     const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
 
     const tx = await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
@@ -82,7 +83,7 @@ describe("🎉 SOL Vault Program - Transactions", () => {
     const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
@@ -102,14 +103,8 @@ describe("🎉 SOL Vault Program - Transactions", () => {
         vaultState: vaultStatePda,
         vaultPda: vaultPda,
         authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
       })
-      .remainingAccounts([
-        {
-          pubkey: withdrawalWallet.publicKey,
-          isSigner: false,
-          isWritable: true,
-        },
-      ])
       .rpc();
 
     await provider.connection.confirmTransaction(tx, "confirmed");
@@ -141,7 +136,7 @@ describe("🎉 SOL Vault Program - Transactions", () => {
 
     // First deposit
     await program.methods
-      .deposit(orderId1, depositAmount)
+      .deposit(orderId1, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
@@ -152,7 +147,7 @@ describe("🎉 SOL Vault Program - Transactions", () => {
 
     // Second deposit
     await program.methods
-      .deposit(orderId2, depositAmount)
+      .deposit(orderId2, depositAmount, nextOrderSequence())
       .accounts({
         depositor: authority.publicKey,
         vaultPda: vaultPda,
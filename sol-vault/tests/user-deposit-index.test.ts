@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  getUserDepositIndexPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("📇 SOL Vault Program - User Deposit Index", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const user = anchor.web3.Keypair.generate();
+  const beneficiary = anchor.web3.Keypair.generate();
+  const orderIds = ["index-a-" + Date.now(), "index-b-" + Date.now()];
+
+  const deposit = (orderId: string) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: user.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(user.publicKey, orderId, program.programId)[0],
+        depositIndex: getUserDepositIndexPda(user.publicKey, program.programId)[0],
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+  });
+
+  it("1. ✅ First deposit creates the index with one entry", async () => {
+    await deposit(orderIds[0]);
+
+    const index = await program.account.userDepositIndex.fetch(
+      getUserDepositIndexPda(user.publicKey, program.programId)[0]
+    );
+    expect(index.user.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(index.deposits.map((d: anchor.web3.PublicKey) => d.toBase58())).to.deep.equal([
+      getDepositRecordPda(user.publicKey, orderIds[0], program.programId)[0].toBase58(),
+    ]);
+  });
+
+  it("2. ✅ Later deposits are appended in order", async () => {
+    await deposit(orderIds[1]);
+
+    const index = await program.account.userDepositIndex.fetch(
+      getUserDepositIndexPda(user.publicKey, program.programId)[0]
+    );
+    expect(index.deposits.map((d: anchor.web3.PublicKey) => d.toBase58())).to.deep.equal(
+      orderIds.map((orderId) =>
+        getDepositRecordPda(user.publicKey, orderId, program.programId)[0].toBase58()
+      )
+    );
+  });
+
+  it("3. ✅ Sponsored deposits stay out of the beneficiary's index", async () => {
+    const orderId = "index-for-" + Date.now();
+    await program.methods
+      .depositFor(
+        beneficiary.publicKey,
+        orderId,
+        new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
+        nextOrderSequence()
+      )
+      .accounts({
+        payer: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(beneficiary.publicKey, orderId, program.programId)[0],
+        beneficiarySigner: beneficiary.publicKey,
+      })
+      .signers([beneficiary])
+      .rpc();
+
+    const index = await program.account.userDepositIndex.fetchNullable(
+      getUserDepositIndexPda(beneficiary.publicKey, program.programId)[0]
+    );
+    expect(index).to.be.null;
+  });
+});
@@ -0,0 +1,97 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("📊 SOL Vault Program - Running Statistics", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+  });
+
+  it("1. ✅ Deposits update total_deposited and deposit_count", async () => {
+    const before = await program.account.vaultState.fetch(vaultStatePda);
+    const amount = 0.2 * anchor.web3.LAMPORTS_PER_SOL;
+
+    for (const suffix of ["a", "b"]) {
+      const orderId = `stats-${suffix}-` + Date.now();
+      await program.methods
+        .deposit(orderId, new anchor.BN(amount), nextOrderSequence())
+        .accounts({
+          depositor: authority.publicKey,
+          vaultPda: vaultPda,
+          vaultState: vaultStatePda,
+          depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+        })
+        .rpc();
+    }
+
+    const after = await program.account.vaultState.fetch(vaultStatePda);
+    expect(after.totalDeposited.sub(before.totalDeposited).toNumber()).to.equal(2 * amount);
+    expect(after.depositCount.sub(before.depositCount).toNumber()).to.equal(2);
+  });
+
+  it("2. ✅ Withdrawals update total_withdrawn", async () => {
+    const before = await program.account.vaultState.fetch(vaultStatePda);
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const withdrawn = await provider.connection.getBalance(withdrawalWallet.publicKey);
+    const after = await program.account.vaultState.fetch(vaultStatePda);
+    expect(after.totalWithdrawn.sub(before.totalWithdrawn).toNumber()).to.equal(withdrawn);
+    console.log("✅ Total withdrawn:", after.totalWithdrawn.toString());
+  });
+
+  it("3. ✅ check returns a typed status", async () => {
+    const status = await program.methods
+      .check()
+      .accounts({ vaultState: vaultStatePda, vaultPda: vaultPda })
+      .view();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    const lamports = await provider.connection.getBalance(vaultPda);
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+
+    expect(status.lamports.toNumber()).to.equal(lamports);
+    expect(status.withdrawable.toNumber()).to.equal(
+      Math.max(0, lamports - rentExempt - vaultState.withdrawReserveLamports.toNumber())
+    );
+    expect(status.walletAccount.toBase58()).to.equal(withdrawalWallet.publicKey.toBase58());
+    expect(status.authority.toBase58()).to.equal(authority.publicKey.toBase58());
+    expect(status.paused).to.equal(vaultState.paused);
+    expect(status.depositCount.toNumber()).to.equal(vaultState.depositCount.toNumber());
+    expect(status.totalDeposited.toNumber()).to.equal(vaultState.totalDeposited.toNumber());
+    expect(status.totalWithdrawn.toNumber()).to.equal(vaultState.totalWithdrawn.toNumber());
+  });
+});
@@ -0,0 +1,95 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🏦 SOL Vault Program - Withdraw Reserve", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+  const reserve = 50_000_000;
+
+  const setWithdrawReserve = (amount: number) =>
+    program.methods
+      .setWithdrawReserve(new anchor.BN(amount))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    await setWithdrawReserve(0);
+  });
+
+  it("1. ✅ Authority sets the reserve", async () => {
+    await setWithdrawReserve(reserve);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.withdrawReserveLamports.toNumber()).to.equal(reserve);
+  });
+
+  it("2. ✅ Withdraw leaves rent plus the reserve in the vault", async () => {
+    const orderId = "reserve-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.5 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+
+    await program.methods
+      .withdraw()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(rentExempt + reserve);
+  });
+
+  it("3. ❌ Nothing to withdraw once only the reserve is left", async () => {
+    try {
+      await program.methods
+        .withdraw()
+        .accounts({
+          vaultState: vaultStatePda,
+          vaultPda: vaultPda,
+          authority: authority.publicKey,
+          wallet: withdrawalWallet.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected withdrawal of the reserve");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NoFunds");
+    }
+  });
+});
@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  getEventsFromTransaction,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+describe("🔀 SOL Vault Program - Split Withdrawals", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let payoutTablePda: anchor.web3.PublicKey;
+  const treasury = anchor.web3.Keypair.generate();
+  const partner = anchor.web3.Keypair.generate();
+
+  const setPayoutTable = (entries: { wallet: anchor.web3.PublicKey; bps: number }[]) =>
+    program.methods
+      .setPayoutTable(entries)
+      .accounts({
+        vaultState: vaultStatePda,
+        payoutTable: payoutTablePda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  const withdrawSplit = (destinations: anchor.web3.PublicKey[]) =>
+    program.methods
+      .withdrawSplit()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        payoutTable: payoutTablePda,
+        authority: authority.publicKey,
+      })
+      .remainingAccounts(
+        destinations.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+      )
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+    [payoutTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_table")],
+      program.programId
+    );
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+  });
+
+  it("1. ❌ Rejects tables that do not add up to 100%", async () => {
+    try {
+      await setPayoutTable([
+        { wallet: treasury.publicKey, bps: 7000 },
+        { wallet: partner.publicKey, bps: 2000 },
+      ]);
+      throw new Error("Unexpected payout table accepted");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidPayoutTable");
+    }
+  });
+
+  it("2. ❌ Rejects duplicate wallets", async () => {
+    try {
+      await setPayoutTable([
+        { wallet: treasury.publicKey, bps: 5000 },
+        { wallet: treasury.publicKey, bps: 5000 },
+      ]);
+      throw new Error("Unexpected payout table accepted");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidPayoutTable");
+    }
+  });
+
+  it("3. ✅ Sets a 70/30 payout table", async () => {
+    await setPayoutTable([
+      { wallet: treasury.publicKey, bps: 7000 },
+      { wallet: partner.publicKey, bps: 3000 },
+    ]);
+
+    const table = await program.account.payoutTable.fetch(payoutTablePda);
+    expect(table.entries.length).to.equal(2);
+    expect(table.entries[0].wallet.toBase58()).to.equal(treasury.publicKey.toBase58());
+    expect(table.entries[1].bps).to.equal(3000);
+  });
+
+  it("4. ❌ Destinations must match the table order", async () => {
+    try {
+      await withdrawSplit([partner.publicKey, treasury.publicKey]);
+      throw new Error("Unexpected split withdrawal with wrong destinations");
+    } catch (err: any) {
+      expect(err.toString()).to.include("PayoutTableMismatch");
+    }
+  });
+
+  it("5. ✅ Splits the withdrawable balance across the table", async () => {
+    const orderId = "split-" + Date.now();
+    const [depositRecordPda] = getDepositRecordPda(authority.publicKey, orderId, program.programId);
+    await program.methods
+      .deposit(orderId, new anchor.BN(anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: depositRecordPda,
+      })
+      .rpc();
+
+    const vaultBalance = await provider.connection.getBalance(vaultPda);
+    const rentExempt = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const withdrawable = vaultBalance - rentExempt;
+
+    const tx = await withdrawSplit([treasury.publicKey, partner.publicKey]);
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    const treasuryShare = Math.floor((withdrawable * 7000) / 10000);
+    expect(await provider.connection.getBalance(treasury.publicKey)).to.equal(treasuryShare);
+    expect(await provider.connection.getBalance(partner.publicKey)).to.equal(
+      withdrawable - treasuryShare
+    );
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(rentExempt);
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "withdrawSplitEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event.data.amount.toNumber()).to.equal(withdrawable);
+    console.log("✅ Split withdrawal:", event.data.amounts.map((a: any) => a.toString()));
+  });
+});
@@ -0,0 +1,104 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getVaultStatePda,
+  getVaultPda,
+  getDepositRecordPda,
+  ensureVaultInitialized,
+  nextOrderSequence,
+} from "./helpers/utils";
+
+const NATIVE_MINT = new anchor.web3.PublicKey(
+  "So11111111111111111111111111111111111111112"
+);
+
+describe("🏦 SOL Vault Program - Withdraw Wrapped SOL", () => {
+  const { provider, program, authority } = getTestEnvironment();
+
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let walletTokenAccount: anchor.web3.PublicKey;
+  const withdrawalWallet = anchor.web3.Keypair.generate();
+  const amount = 0.2 * anchor.web3.LAMPORTS_PER_SOL;
+
+  const withdrawWrapped = (lamports: number) =>
+    program.methods
+      .withdrawWrapped(new anchor.BN(lamports))
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultPda: vaultPda,
+        authority: authority.publicKey,
+        wallet: withdrawalWallet.publicKey,
+        walletTokenAccount: walletTokenAccount,
+        nativeMint: NATIVE_MINT,
+      })
+      .rpc();
+
+  before(async () => {
+    [vaultStatePda] = getVaultStatePda(program.programId);
+    [vaultPda] = getVaultPda(program.programId);
+    walletTokenAccount = anchor.utils.token.associatedAddress({
+      mint: NATIVE_MINT,
+      owner: withdrawalWallet.publicKey,
+    });
+
+    await ensureVaultInitialized(program, vaultStatePda, authority.publicKey);
+
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+      })
+      .rpc();
+
+    const orderId = "wrapped-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(0.5 * anchor.web3.LAMPORTS_PER_SOL), nextOrderSequence())
+      .accounts({
+        depositor: authority.publicKey,
+        vaultPda: vaultPda,
+        vaultState: vaultStatePda,
+        depositRecord: getDepositRecordPda(authority.publicKey, orderId, program.programId)[0],
+      })
+      .rpc();
+  });
+
+  it("1. ✅ Withdraws lamports as wSOL to the wallet's token account", async () => {
+    const vaultBefore = await provider.connection.getBalance(vaultPda);
+
+    await withdrawWrapped(amount);
+
+    const balance = await provider.connection.getTokenAccountBalance(walletTokenAccount);
+    expect(Number(balance.value.amount)).to.equal(amount);
+    expect(await provider.connection.getBalance(vaultPda)).to.equal(vaultBefore - amount);
+  });
+
+  it("2. ✅ Later withdrawals reuse the existing token account", async () => {
+    await withdrawWrapped(amount);
+
+    const balance = await provider.connection.getTokenAccountBalance(walletTokenAccount);
+    expect(Number(balance.value.amount)).to.equal(2 * amount);
+  });
+
+  it("3. ❌ Cannot withdraw more than the withdrawable balance", async () => {
+    const vaultBalance = await provider.connection.getBalance(vaultPda);
+    try {
+      await withdrawWrapped(vaultBalance);
+      throw new Error("Unexpected withdrawal of the rent reserve");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InsufficientVaultBalance");
+    }
+  });
+
+  it("4. ❌ Zero amount is rejected", async () => {
+    try {
+      await withdrawWrapped(0);
+      throw new Error("Unexpected zero withdrawal");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidAmount");
+    }
+  });
+});
@@ -87,7 +87,7 @@ async function deployAndInitialize() {
     console.log("🔐 Calling initialize instruction...");
     
     const tx = await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         vaultTokenAccount: vaultTokenAccount,
@@ -5,36 +5,75 @@
 // (stake_program, sol-vault) to ensure consistent security boundaries.
 // =============================================================================
 
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
 use anchor_spl::{
     associated_token::{self, get_associated_token_address, Create},
     token::{self, Mint, Token, TokenAccount, Transfer},
 };
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+use solana_sdk_ids::ed25519_program;
 
 declare_id!("CX7oWiXadkmto4iwK2kKuDErG4UJVw6EbDHhuQ9EEfSz");
 
 /// Maximum length for order IDs (constrained by PDA seed limits).
-/// Solana's PDA derivation enforces this limit implicitly.
-/// An explicit length check is intentionally omitted because:
-/// - PDA derivation fails first, providing equivalent protection
-/// - Adding a redundant check would increase compute cost unnecessarily
-/// - The seed construction naturally bounds the effective length
-// AUDIT NOTE (L-08): Order ID length is not explicitly validated at runtime because
-// PDA seed derivation enforces an implicit length limit. Exceeding it causes a clear
-// PDA derivation failure. Backend validates order IDs before submission as defense-in-depth.
+/// `deposit` validates the length and charset explicitly via `is_valid_order_id`;
+/// an ID longer than a single seed still fails PDA derivation of the deposit
+/// record, which Anchor performs before the instruction handler runs.
+// AUDIT NOTE (L-08): Order IDs are validated on-chain (length and printable ASCII)
+// and rejected with OrderIdInvalid. Backend validates order IDs before submission
+// as defense-in-depth.
 pub const MAX_ORDER_ID_LEN: usize = 32;
 
+/// Maximum length for the optional deposit memo.
+pub const MAX_MEMO_LEN: usize = 64;
+
+/// Maximum length for the optional settlement batch reference on withdrawals.
+pub const MAX_SETTLEMENT_ID_LEN: usize = 64;
+
+/// Returns true if `order_id` fits in a PDA seed and is printable ASCII.
+pub fn is_valid_order_id(order_id: &str) -> bool {
+    order_id.len() <= MAX_ORDER_ID_LEN && order_id.bytes().all(|b| (0x20..=0x7e).contains(&b))
+}
+
+/// Maximum number of destinations in a vault payout table.
+pub const MAX_PAYOUT_ENTRIES: usize = 5;
+
+/// Basis points denominator; payout table shares must sum to this value.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum deposit fee (10%).
+pub const MAX_DEPOSIT_FEE_BPS: u16 = 1_000;
+
+/// Maximum number of members in a vault signer set.
+pub const MAX_SIGNERS: usize = 10;
+
+/// Maximum number of wallets in a vault's withdrawal whitelist.
+pub const MAX_WHITELIST_WALLETS: usize = 4;
+
+/// Current `VaultState` layout version; older accounts are upgraded with
+/// `migrate_vault_state`.
+pub const VAULT_STATE_VERSION: u8 = 2;
+
+/// Bytes kept free at the end of `VaultState` for future fields.
+pub const VAULT_STATE_RESERVED_LEN: usize = 28;
+
 #[program]
 pub mod spl_token_vault_program {
     use super::*;
 
     /// Initialize a new vault for a specific SPL token mint.
-    /// Creates a vault state PDA and associated token account to hold deposits.
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    /// Creates a vault state PDA and associated token account to hold deposits,
+    /// and appends the vault to the global registry. The optional `cold_wallet`
+    /// is the fixed destination for `emergency_withdraw_to_cold` and can never
+    /// be changed afterwards.
+    pub fn initialize(ctx: Context<Initialize>, cold_wallet: Option<Pubkey>) -> Result<()> {
         let clock = Clock::get()?;
 
         let vault_state_key = ctx.accounts.vault_state.key();
         let token_mint_key = ctx.accounts.token_mint.key();
+        if let Some(cold_wallet) = cold_wallet {
+            validate_withdrawal_wallet(&vault_state_key, &token_mint_key, &cold_wallet)?;
+        }
         let vault_token_account_key = ctx.accounts.vault_token_account.key();
         let authority_key = ctx.accounts.authority.key();
 
@@ -42,22 +81,255 @@ pub mod spl_token_vault_program {
         vault_state.authority = authority_key;
         vault_state.token_mint = token_mint_key;
         vault_state.wallet_account = Pubkey::default();
+        vault_state.refund_after_seconds = 0;
+        vault_state.min_deposit = 0;
+        vault_state.max_deposit = 0;
+        vault_state.fee_bps = 0;
+        vault_state.fee_collector = Pubkey::default();
+        vault_state.multisig_enabled = false;
+        vault_state.sweep_enabled = false;
+        vault_state.sweep_threshold = 0;
+        vault_state.passthrough = false;
+        vault_state.event_seq = 0;
+        vault_state.operator = Pubkey::default();
+        vault_state.guardian = Pubkey::default();
+        vault_state.frozen = false;
+        vault_state.deposit_ttl_seconds = 0;
+        vault_state.version = VAULT_STATE_VERSION;
+        vault_state.require_global_order_id = false;
+        vault_state.verify_tolerance_bps = 0;
+        vault_state.settlement_delay_seconds = 0;
+        vault_state.pending_balance = 0;
+        vault_state.rent_sponsorship = false;
+        vault_state.staked_balance = 0;
+        vault_state.deposit_count = 0;
+        vault_state.cold_wallet = cold_wallet.unwrap_or_default();
+        vault_state.reserved = [0; VAULT_STATE_RESERVED_LEN];
 
+        let event_seq = vault_state.next_event_seq()?;
         emit!(VaultInitializedEvent {
             vault_state: vault_state_key,
             token_mint: token_mint_key,
             vault_token_account: vault_token_account_key,
             authority: authority_key,
             timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        let registry = &mut ctx.accounts.vault_registry;
+        registry.bump = ctx.bumps.vault_registry;
+        let index = VaultRegistry::append(
+            registry,
+            RegisteredVault {
+                token_mint: token_mint_key,
+                vault_state: vault_state_key,
+            },
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+        )?;
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(VaultRegisteredEvent {
+            vault_state: vault_state_key,
+            token_mint: token_mint_key,
+            index,
+            timestamp: clock.unix_timestamp,
+            event_seq,
         });
 
         msg!("Vault initialized for token mint: {}", token_mint_key);
         Ok(())
     }
 
+    /// Add a vault created before the registry existed. Permissionless;
+    /// the payer covers the registry's extra rent.
+    pub fn register_vault(ctx: Context<RegisterVault>) -> Result<()> {
+        let vault_state_key = ctx.accounts.vault_state.key();
+        let token_mint_key = ctx.accounts.vault_state.token_mint;
+
+        let registry = &mut ctx.accounts.vault_registry;
+        registry.bump = ctx.bumps.vault_registry;
+        let index = VaultRegistry::append(
+            registry,
+            RegisteredVault {
+                token_mint: token_mint_key,
+                vault_state: vault_state_key,
+            },
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let clock = Clock::get()?;
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(VaultRegisteredEvent {
+            vault_state: vault_state_key,
+            token_mint: token_mint_key,
+            index,
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Vault {} registered at index {}", vault_state_key, index);
+        Ok(())
+    }
+
+    /// Upgrade a vault state account created by an older program version to
+    /// the current layout. Fields added since then start zeroed, which is
+    /// their disabled/default value. The authority pays any extra rent.
+    pub fn migrate_vault_state(ctx: Context<MigrateVaultState>) -> Result<()> {
+        let info = ctx.accounts.vault_state.to_account_info();
+
+        // The account may be too short to deserialize, so check the fixed
+        // prefix (discriminator, authority, token_mint) by hand
+        let (authority, token_mint) = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 + 32 + 32 && data[..8] == *VaultState::DISCRIMINATOR,
+                VaultError::InvalidVaultState
+            );
+            let authority = Pubkey::try_from(&data[8..40]).map_err(|_| VaultError::InvalidVaultState)?;
+            let token_mint = Pubkey::try_from(&data[40..72]).map_err(|_| VaultError::InvalidVaultState)?;
+            (authority, token_mint)
+        };
+        require_keys_eq!(
+            authority,
+            ctx.accounts.authority.key(),
+            VaultError::Unauthorized
+        );
+        let (expected_vault_state, _) =
+            Pubkey::find_program_address(&[b"vault_state", token_mint.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected_vault_state, VaultError::InvalidVaultState);
+
+        if info.data_len() < VaultState::SPACE {
+            let rent_due = Rent::get()?
+                .minimum_balance(VaultState::SPACE)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.authority.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            info.resize(VaultState::SPACE)?;
+        }
+
+        let mut state = VaultState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            state.version < VAULT_STATE_VERSION,
+            VaultError::VaultAlreadyMigrated
+        );
+        let from_version = state.version;
+        state.version = VAULT_STATE_VERSION;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(VaultMigratedEvent {
+            vault_state: info.key(),
+            token_mint,
+            from_version,
+            to_version: VAULT_STATE_VERSION,
+            authority,
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        msg!("Vault state migrated from version {} to {}", from_version, VAULT_STATE_VERSION);
+        Ok(())
+    }
+
+    /// Upgrade a deposit record created with the original layout
+    /// (`LegacyDepositRecord`) so `check_deposit`, `refund_deposit` and
+    /// `claim_refund` can read it. The account is grown to
+    /// `DepositRecord::SPACE` (the payer covers the extra rent) and the new
+    /// fields get the values such a record implies: active, no memo or expiry,
+    /// unverified, not counted in `pending_balance`, and rent owed to the
+    /// depositor who paid it. Permissionless: it never moves funds.
+    pub fn migrate_deposit_record(
+        ctx: Context<MigrateDepositRecord>,
+        _order_id: String,
+    ) -> Result<()> {
+        let info = ctx.accounts.deposit_record.to_account_info();
+
+        let legacy = {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *DepositRecord::DISCRIMINATOR,
+                VaultError::InvalidDepositRecord
+            );
+            require!(
+                data.len() < DepositRecord::SPACE,
+                VaultError::DepositRecordAlreadyMigrated
+            );
+            LegacyDepositRecord::deserialize(&mut &data[8..])?
+        };
+        require_keys_eq!(
+            legacy.user,
+            ctx.accounts.depositor.key(),
+            VaultError::InvalidDepositRecord
+        );
+
+        let rent_due = Rent::get()?
+            .minimum_balance(DepositRecord::SPACE)
+            .saturating_sub(info.lamports());
+        if rent_due > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        info.resize(DepositRecord::SPACE)?;
+
+        let record = DepositRecord {
+            order_id: legacy.order_id,
+            user: legacy.user,
+            token_mint: legacy.token_mint,
+            amount: legacy.amount,
+            timestamp: legacy.timestamp,
+            status: DepositStatus::Active,
+            memo: String::new(),
+            expires_at: None,
+            verified: false,
+            pending: false,
+            rent_payer: legacy.user,
+        };
+        record.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let clock = Clock::get()?;
+        let vault_state = &mut ctx.accounts.vault_state;
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(DepositRecordMigratedEvent {
+            vault_state: vault_state.key(),
+            deposit_record: info.key(),
+            order_id: record.order_id.clone(),
+            user: record.user,
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Deposit record {} migrated", record.order_id);
+        Ok(())
+    }
+
     /// Query vault status including balance and rent exemption status.
-    /// Read-only operation for monitoring purposes.
-    pub fn check(ctx: Context<Check>) -> Result<()> {
+    /// Read-only operation for monitoring purposes; the status is also returned
+    /// via return data for tooling and CPI callers.
+    pub fn check(ctx: Context<Check>) -> Result<VaultStatus> {
         let vault_state = &ctx.accounts.vault_state;
         let vault_token_account = &ctx.accounts.vault_token_account;
         let rent = &ctx.accounts.rent;
@@ -73,6 +345,12 @@ pub mod spl_token_vault_program {
         msg!("  Token balance: {}", vault_token_account.amount);
         msg!("  Withdrawal wallet: {}", vault_state.wallet_account);
         msg!("  Authority: {}", vault_state.authority);
+        msg!("  Pending balance: {}", vault_state.pending_balance);
+        msg!("  Staked balance: {}", vault_state.staked_balance);
+        msg!(
+            "  Settled balance: {}",
+            vault_state.withdrawable(vault_token_account.amount)
+        );
         msg!("Rent status:");
         msg!("  SOL balance (lamports): {}", lamports);
         msg!("  Minimum rent: {}", min_rent);
@@ -82,7 +360,24 @@ pub mod spl_token_vault_program {
             msg!("WARNING: Account is NOT rent exempt and may be closed");
         }
 
-        Ok(())
+        Ok(VaultStatus {
+            token_mint: vault_state.token_mint,
+            balance: vault_token_account.amount,
+            wallet_account: vault_state.wallet_account,
+            authority: vault_state.authority,
+            lamports,
+            min_rent,
+            is_rent_exempt,
+            multisig_enabled: vault_state.multisig_enabled,
+            passthrough: vault_state.passthrough,
+            operator: vault_state.operator,
+            guardian: vault_state.guardian,
+            frozen: vault_state.frozen,
+            pending_balance: vault_state.pending_balance,
+            settled_balance: vault_state.withdrawable(vault_token_account.amount),
+            staked_balance: vault_state.staked_balance,
+            cold_wallet: vault_state.cold_wallet,
+        })
     }
 
     /// Query a specific deposit record by order ID.
@@ -98,165 +393,495 @@ pub mod spl_token_vault_program {
             token_mint: record.token_mint,
             amount: record.amount,
             timestamp: record.timestamp,
+            status: record.status,
+            memo: record.memo.clone(),
+            expires_at: record.expires_at,
+            verified: record.verified,
+            pending: record.pending,
+            rent_payer: record.rent_payer,
         })
     }
 
+    /// Resolve a bare order ID to its deposit through the mint-wide order
+    /// index, without knowing the depositor. Only finds deposits that claimed
+    /// an index entry.
+    pub fn lookup_order(ctx: Context<LookupOrder>, order_id: String) -> Result<DepositRecord> {
+        let order_index = &ctx.accounts.order_index;
+
+        msg!(
+            "Order {} deposited by {} at {}",
+            order_id,
+            order_index.user,
+            order_index.deposit_record
+        );
+
+        Ok((*ctx.accounts.deposit_record).clone())
+    }
+
     /// Set or update the withdrawal destination wallet.
     /// Validates the wallet address and creates an ATA if needed.
+    /// Requires signer set approval when multisig is enabled.
     pub fn set_withdrawal_account(ctx: Context<SetWithdrawalAccount>) -> Result<()> {
+        ctx.accounts.vault_state.verify_approvals(
+            ctx.accounts.signer_set.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+
         let vault = &mut ctx.accounts.vault_state;
         let new_wallet = ctx.accounts.new_wallet.key();
         let token_mint = vault.token_mint;
 
-        // Derive vault token account address for validation
-        let vault_token_account = anchor_spl::associated_token::get_associated_token_address(
-            &vault.key(),
-            &token_mint,
-        );
-
-        // Prevent setting withdrawal wallet to invalid addresses that could cause fund loss
-        require!(
-            new_wallet != Pubkey::default()
-                && new_wallet != crate::ID
-                && new_wallet != anchor_lang::system_program::ID
-                && new_wallet != vault.key()
-                && new_wallet != token_mint
-                && new_wallet != vault_token_account,
-            VaultError::InvalidWithdrawalWallet
-        );
+        validate_withdrawal_wallet(&vault.key(), &token_mint, &new_wallet)?;
 
         vault.wallet_account = new_wallet;
         msg!("Setting withdrawal wallet to {}", new_wallet);
 
-        let ata = get_associated_token_address(&new_wallet, &token_mint);
+        let ata = create_or_validate_wallet_ata(
+            &ctx.accounts.new_wallet,
+            &ctx.accounts.associated_token,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.authority,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
 
-        // Verify the provided ATA matches the canonical derivation
-        let ata_account_info = ctx.accounts.associated_token.to_account_info();
-        require_keys_eq!(
-            ata_account_info.key(),
-            ata,
-            VaultError::InvalidWithdrawalWallet
-        );
+        let clock = Clock::get()?;
+
+        let event_seq = vault.next_event_seq()?;
+        emit!(WithdrawalWalletUpdatedEvent {
+            vault_state: vault.key(),
+            token_mint,
+            new_wallet,
+            wallet_ata: ata,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
 
-        msg!("Checking ATA for wallet {}", new_wallet);
-        msg!("Token mint: {}", token_mint);
-        msg!("Expected ATA: {}", ata);
+        Ok(())
+    }
 
-        // If ATA exists, validate its configuration
-        if ata_account_info.owner == &token::ID {
-            let ata_data =
-                TokenAccount::try_deserialize(&mut &ata_account_info.data.borrow()[..])?;
+    /// Add a wallet to the withdrawal whitelist, creating its ATA if needed.
+    /// `withdraw` can then target it by index without further setup.
+    /// Requires signer set approval when multisig is enabled.
+    pub fn add_whitelisted_wallet(ctx: Context<AddWhitelistedWallet>) -> Result<()> {
+        ctx.accounts.vault_state.verify_approvals(
+            ctx.accounts.signer_set.as_deref(),
+            ctx.remaining_accounts,
+        )?;
 
-            // Verify mint matches
-            require_keys_eq!(ata_data.mint, token_mint, VaultError::MintMismatch);
+        let vault_key = ctx.accounts.vault_state.key();
+        let token_mint = ctx.accounts.vault_state.token_mint;
+        let wallet = ctx.accounts.wallet.key();
 
-            // Verify ownership
-            require_keys_eq!(
-                ata_data.owner,
-                new_wallet,
-                VaultError::InvalidWithdrawalWallet
-            );
+        validate_withdrawal_wallet(&vault_key, &token_mint, &wallet)?;
 
-            msg!("ATA already exists and validated: {}", ata);
-        } else {
-            // Create ATA if it doesn't exist
-            msg!("Creating new ATA at {}", ata);
-
-            let create_ctx = CpiContext::new(
-                ctx.accounts.associated_token_program.to_account_info(),
-                Create {
-                    payer: ctx.accounts.authority.to_account_info(),
-                    associated_token: ctx.accounts.associated_token.to_account_info(),
-                    authority: ctx.accounts.new_wallet.to_account_info(),
-                    mint: ctx.accounts.token_mint.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                    token_program: ctx.accounts.token_program.to_account_info(),
-                },
-            );
+        let whitelist = &mut ctx.accounts.wallet_whitelist;
+        require!(
+            !whitelist.wallets.contains(&wallet),
+            VaultError::WalletAlreadyWhitelisted
+        );
+        require!(
+            whitelist.wallets.len() < MAX_WHITELIST_WALLETS,
+            VaultError::WhitelistFull
+        );
+        whitelist.vault_state = vault_key;
+        whitelist.bump = ctx.bumps.wallet_whitelist;
+        whitelist.wallets.push(wallet);
+        let wallets = whitelist.wallets.clone();
 
-            associated_token::create(create_ctx)?;
-            msg!("ATA created successfully for wallet {}", new_wallet);
-        }
+        create_or_validate_wallet_ata(
+            &ctx.accounts.wallet,
+            &ctx.accounts.associated_token,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.authority,
+            &ctx.accounts.associated_token_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.token_program,
+        )?;
 
         let clock = Clock::get()?;
 
-        emit!(WithdrawalWalletUpdatedEvent {
-            vault_state: vault.key(),
+        let state = &mut ctx.accounts.vault_state;
+        let event_seq = state.next_event_seq()?;
+        emit!(WalletWhitelistUpdatedEvent {
+            vault_state: vault_key,
             token_mint,
-            new_wallet,
-            wallet_ata: ata,
+            wallet,
+            added: true,
+            wallets,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Wallet {} whitelisted", wallet);
+        Ok(())
+    }
+
+    /// Remove a wallet from the withdrawal whitelist. Later entries shift down
+    /// by one index. Requires signer set approval when multisig is enabled.
+    pub fn remove_whitelisted_wallet(
+        ctx: Context<RemoveWhitelistedWallet>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.vault_state.verify_approvals(
+            ctx.accounts.signer_set.as_deref(),
+            ctx.remaining_accounts,
+        )?;
+
+        let whitelist = &mut ctx.accounts.wallet_whitelist;
+        let position = whitelist
+            .wallets
+            .iter()
+            .position(|w| *w == wallet)
+            .ok_or(VaultError::WalletNotWhitelisted)?;
+        whitelist.wallets.remove(position);
+        let wallets = whitelist.wallets.clone();
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        let event_seq = state.next_event_seq()?;
+        emit!(WalletWhitelistUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            wallet,
+            added: false,
+            wallets,
             authority: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
+            event_seq,
         });
 
+        msg!("Wallet {} removed from whitelist", wallet);
         Ok(())
     }
 
     /// Deposit tokens into the vault.
     /// Records the actual received amount to support fee-on-transfer tokens.
-    pub fn deposit(ctx: Context<Deposit>, order_id: String, amount: u64) -> Result<()> {
-        let user = &ctx.accounts.user;
-        let vault_state = &mut ctx.accounts.vault_state;
-        let user_token_account = &ctx.accounts.user_token_account;
-        let vault_token_account = &ctx.accounts.vault_token_account;
+    /// When a deposit fee is configured, the fee is routed to the fee collector
+    /// and only the net amount is recorded. An optional memo (invoice id,
+    /// campaign code) is stored on the record and echoed in the event.
+    /// Passthrough vaults forward the tokens to the withdrawal wallet's ATA
+    /// instead of custodying them.
+    pub fn deposit(
+        ctx: Context<Deposit>,
+        order_id: String,
+        amount: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let depositor = ctx.accounts.user.key();
+        DepositFlow {
+            vault_state: &mut ctx.accounts.vault_state,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            passthrough_destination: ctx.accounts.passthrough_destination.as_mut(),
+            fee_collector: ctx.accounts.fee_collector.as_ref(),
+            source: DepositSource::Token {
+                account: &ctx.accounts.user_token_account,
+                authority: ctx.accounts.user.to_account_info(),
+            },
+            token_program: &ctx.accounts.token_program,
+            order_index: ctx.accounts.order_index.as_mut().zip(ctx.bumps.order_index),
+            rent_escrow: ctx.accounts.rent_escrow.as_mut(),
+            rent_payer: ctx.accounts.user.to_account_info(),
+        }
+        .execute(
+            &mut ctx.accounts.deposit_record,
+            depositor,
+            order_id,
+            amount,
+            memo,
+            &[],
+        )
+    }
 
-        require!(amount > 0, VaultError::InvalidAmount);
-        require!(!order_id.is_empty(), VaultError::OrderIdEmpty);
+    /// Deposit into the vault and stake `stake_amount` of the same token in a
+    /// stake_program pool in one transaction. The deposit is recorded exactly
+    /// as in `deposit`; the stake is then made by CPI into `deposit_stake`
+    /// with the depositor as staker.
+    pub fn deposit_and_stake(
+        ctx: Context<DepositAndStake>,
+        order_id: String,
+        amount: u64,
+        memo: Option<String>,
+        pool_id: u64,
+        stake_amount: u64,
+        partner_tag: Option<[u8; 16]>,
+    ) -> Result<()> {
+        let depositor = ctx.accounts.user.key();
+        DepositFlow {
+            vault_state: &mut ctx.accounts.vault_state,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            passthrough_destination: ctx.accounts.passthrough_destination.as_mut(),
+            fee_collector: ctx.accounts.fee_collector.as_ref(),
+            source: DepositSource::Token {
+                account: &ctx.accounts.user_token_account,
+                authority: ctx.accounts.user.to_account_info(),
+            },
+            token_program: &ctx.accounts.token_program,
+            order_index: ctx.accounts.order_index.as_mut().zip(ctx.bumps.order_index),
+            rent_escrow: ctx.accounts.rent_escrow.as_mut(),
+            rent_payer: ctx.accounts.user.to_account_info(),
+        }
+        .execute(
+            &mut ctx.accounts.deposit_record,
+            depositor,
+            order_id,
+            amount,
+            memo,
+            &[],
+        )?;
 
-        // Capture balance before transfer for fee-on-transfer token support
-        let balance_before = vault_token_account.amount;
+        let stake_accounts = stake_program::cpi::accounts::DepositStake {
+            pool: ctx.accounts.pool.to_account_info(),
+            user_stake: ctx.accounts.user_stake.to_account_info(),
+            user: ctx.accounts.user.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            user_token_account: ctx.accounts.user_token_account.to_account_info(),
+            pool_vault: ctx.accounts.pool_vault.to_account_info(),
+            leaderboard: ctx.accounts.leaderboard.as_ref().map(|a| a.to_account_info()),
+            attestation: ctx.accounts.attestation.as_ref().map(|a| a.to_account_info()),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+            event_authority: ctx.accounts.stake_event_authority.to_account_info(),
+            program: ctx.accounts.stake_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.stake_program.to_account_info(), stake_accounts);
+        stake_program::cpi::deposit_stake(cpi_ctx, pool_id, stake_amount, partner_tag)?;
 
-        let transfer_ix = token::Transfer {
-            from: user_token_account.to_account_info(),
-            to: vault_token_account.to_account_info(),
-            authority: user.to_account_info(),
+        msg!("Deposit recorded and {} tokens staked in pool {}", stake_amount, pool_id);
+        Ok(())
+    }
+
+    /// Deposit on behalf of a token owner who approved the vault PDA as SPL
+    /// delegate for at least `amount`. A relayer submits and pays for the
+    /// record; the owner is credited as the depositor and must co-sign, so the
+    /// allowance cannot be pulled under an order ID the owner did not choose.
+    /// The delegate approval alone does not authorize a deposit: relayers must
+    /// collect the owner's signature on the transaction before submitting it.
+    pub fn deposit_delegated(
+        ctx: Context<DepositDelegated>,
+        order_id: String,
+        amount: u64,
+    ) -> Result<()> {
+        let owner_token_account = &ctx.accounts.owner_token_account;
+        let vault_key = ctx.accounts.vault_state.key();
+        require!(
+            owner_token_account.delegate == COption::Some(vault_key)
+                && owner_token_account.delegated_amount >= amount,
+            VaultError::InsufficientDelegation
+        );
+
+        let token_mint = ctx.accounts.vault_state.token_mint;
+        let seeds = &[
+            b"vault_state",
+            token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let depositor = ctx.accounts.owner.key();
+        let source = DepositSource::Token {
+            account: &ctx.accounts.owner_token_account,
+            authority: ctx.accounts.vault_state.to_account_info(),
         };
-        let cpi_ctx =
-            CpiContext::new(ctx.accounts.token_program.to_account_info(), transfer_ix);
-        token::transfer(cpi_ctx, amount)?;
+        DepositFlow {
+            vault_state: &mut ctx.accounts.vault_state,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            passthrough_destination: ctx.accounts.passthrough_destination.as_mut(),
+            fee_collector: ctx.accounts.fee_collector.as_ref(),
+            source,
+            token_program: &ctx.accounts.token_program,
+            order_index: ctx.accounts.order_index.as_mut().zip(ctx.bumps.order_index),
+            rent_escrow: ctx.accounts.rent_escrow.as_mut(),
+            rent_payer: ctx.accounts.relayer.to_account_info(),
+        }
+        .execute(
+            &mut ctx.accounts.deposit_record,
+            depositor,
+            order_id,
+            amount,
+            None,
+            signer_seeds,
+        )
+    }
 
-        // Reload to get actual balance after transfer
-        ctx.accounts.vault_token_account.reload()?;
-        let balance_after = ctx.accounts.vault_token_account.amount;
+    /// Gasless deposit: a relayer submits and pays for the transaction while
+    /// the user only signs `(vault_state, order_id, amount, expiry, nonce)` off-chain.
+    /// `nonce` is the user's current `RelayNonce` value and is bumped on use, so
+    /// an authorization cannot be replayed after its record is closed.
+    /// The signature is checked through an ed25519 program instruction placed
+    /// right before this one. Tokens are pulled with the vault PDA's delegate
+    /// allowance, as in `deposit_delegated`, and the record and refund rights
+    /// belong to the user.
+    pub fn deposit_relayed(
+        ctx: Context<DepositRelayed>,
+        order_id: String,
+        amount: u64,
+        expiry: i64,
+    ) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= expiry,
+            VaultError::AuthorizationExpired
+        );
 
-        // Calculate actual received amount (handles fee-on-transfer tokens)
-        let actual_amount_received = balance_after
-            .checked_sub(balance_before)
+        let vault_key = ctx.accounts.vault_state.key();
+        let relay_nonce = &mut ctx.accounts.relay_nonce;
+        let message =
+            relayed_deposit_message(&vault_key, &order_id, amount, expiry, relay_nonce.nonce);
+        verify_ed25519_authorization(
+            &ctx.accounts.instructions,
+            &ctx.accounts.owner.key(),
+            &message,
+        )?;
+
+        // Consume the nonce so the same signature cannot be submitted again
+        relay_nonce.owner = ctx.accounts.owner.key();
+        relay_nonce.bump = ctx.bumps.relay_nonce;
+        relay_nonce.nonce = relay_nonce
+            .nonce
+            .checked_add(1)
             .ok_or(VaultError::MathOverflow)?;
 
-        // Store deposit record with actual received amount
-        let record = &mut ctx.accounts.deposit_record;
-        record.order_id = order_id.clone();
-        record.user = user.key();
-        record.amount = actual_amount_received;
-        record.timestamp = Clock::get()?.unix_timestamp;
-        record.token_mint = vault_state.token_mint;
+        let owner_token_account = &ctx.accounts.owner_token_account;
+        require!(
+            owner_token_account.delegate == COption::Some(vault_key)
+                && owner_token_account.delegated_amount >= amount,
+            VaultError::InsufficientDelegation
+        );
 
-        emit!(DepositEvent {
-            user: record.user,
-            order_id: record.order_id.clone(),
-            amount: record.amount,
-            token_mint: record.token_mint,
-            timestamp: record.timestamp,
-        });
+        let token_mint = ctx.accounts.vault_state.token_mint;
+        let seeds = &[
+            b"vault_state",
+            token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
 
-        Ok(())
+        let depositor = ctx.accounts.owner.key();
+        let source = DepositSource::Token {
+            account: &ctx.accounts.owner_token_account,
+            authority: ctx.accounts.vault_state.to_account_info(),
+        };
+        DepositFlow {
+            vault_state: &mut ctx.accounts.vault_state,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            passthrough_destination: ctx.accounts.passthrough_destination.as_mut(),
+            fee_collector: ctx.accounts.fee_collector.as_ref(),
+            source,
+            token_program: &ctx.accounts.token_program,
+            order_index: ctx.accounts.order_index.as_mut().zip(ctx.bumps.order_index),
+            rent_escrow: ctx.accounts.rent_escrow.as_mut(),
+            rent_payer: ctx.accounts.relayer.to_account_info(),
+        }
+        .execute(
+            &mut ctx.accounts.deposit_record,
+            depositor,
+            order_id,
+            amount,
+            None,
+            signer_seeds,
+        )
     }
 
-    /// Withdraw all tokens from the vault to the configured withdrawal wallet.
-    /// Authority only.
-    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
-        let vault_state = &ctx.accounts.vault_state;
+    /// Deposit native SOL into a wSOL vault. The lamports are sent straight
+    /// to the receiving wSOL accounts (vault, fee collector, passthrough
+    /// wallet) and synced, so the depositor needs no wSOL account of their
+    /// own. Recorded exactly as a `deposit` of `lamports`.
+    pub fn deposit_sol_wrapped(
+        ctx: Context<DepositSolWrapped>,
+        order_id: String,
+        lamports: u64,
+    ) -> Result<()> {
+        let depositor = ctx.accounts.user.key();
+        DepositFlow {
+            vault_state: &mut ctx.accounts.vault_state,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            passthrough_destination: ctx.accounts.passthrough_destination.as_mut(),
+            fee_collector: ctx.accounts.fee_collector.as_ref(),
+            source: DepositSource::Lamports {
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            token_program: &ctx.accounts.token_program,
+            order_index: ctx.accounts.order_index.as_mut().zip(ctx.bumps.order_index),
+            rent_escrow: ctx.accounts.rent_escrow.as_mut(),
+            rent_payer: ctx.accounts.user.to_account_info(),
+        }
+        .execute(
+            &mut ctx.accounts.deposit_record,
+            depositor,
+            order_id,
+            lamports,
+            None,
+            &[],
+        )
+    }
+
+    /// Withdraw tokens from the vault to the configured withdrawal wallet, or
+    /// to the whitelisted wallet at `wallet_index` when one is given.
+    /// An `amount` of 0 withdraws everything `withdrawable()` allows, i.e. the
+    /// balance less pending deposits. Authority or operator, plus
+    /// signer set approval when multisig is enabled. The operator may only
+    /// withdraw to the configured wallet. An optional `settlement_id` ties the
+    /// withdrawal to a backend settlement batch and is echoed in the event.
+    pub fn withdraw(
+        ctx: Context<Withdraw>,
+        amount: u64,
+        wallet_index: Option<u8>,
+        settlement_id: Option<String>,
+    ) -> Result<()> {
+        let settlement_id = settlement_id.unwrap_or_default();
+        require!(
+            settlement_id.len() <= MAX_SETTLEMENT_ID_LEN,
+            VaultError::SettlementIdTooLong
+        );
+
+        let vault_state = &mut ctx.accounts.vault_state;
+        vault_state.verify_approvals(ctx.accounts.signer_set.as_deref(), ctx.remaining_accounts)?;
+        require!(
+            wallet_index.is_none() || ctx.accounts.authority.key() == vault_state.authority,
+            VaultError::Unauthorized
+        );
         let vault_token_account = &ctx.accounts.vault_token_account;
         let destination_token_account = &ctx.accounts.destination_token_account;
 
+        let destination_wallet = match wallet_index {
+            Some(index) => *ctx
+                .accounts
+                .wallet_whitelist
+                .as_ref()
+                .and_then(|whitelist| whitelist.wallets.get(index as usize))
+                .ok_or(VaultError::WalletNotWhitelisted)?,
+            None => vault_state.wallet_account,
+        };
         require!(
-            vault_state.wallet_account != Pubkey::default(),
+            destination_wallet != Pubkey::default(),
             VaultError::WalletNotSet
         );
+        require_keys_eq!(
+            destination_token_account.key(),
+            get_associated_token_address(&destination_wallet, &vault_state.token_mint),
+            VaultError::InvalidWithdrawalWallet
+        );
+
+        let balance = vault_token_account.amount;
+        let available = vault_state.withdrawable(balance);
+        require!(available > 0, VaultError::NoFunds);
 
-        let amount = vault_token_account.amount;
-        require!(amount > 0, VaultError::NoFunds);
+        let requested_amount = amount;
+        let amount = if requested_amount == 0 {
+            available
+        } else {
+            requested_amount
+        };
+        require!(amount <= available, VaultError::InsufficientVaultBalance);
+        let remaining_balance = balance
+            .checked_sub(amount)
+            .ok_or(VaultError::MathOverflow)?;
 
         let seeds = &[
             b"vault_state",
@@ -279,13 +904,18 @@ pub mod spl_token_vault_program {
 
         let clock = Clock::get()?;
 
+        let event_seq = vault_state.next_event_seq()?;
         emit!(WithdrawEvent {
             vault_state: vault_state.key(),
             token_mint: vault_state.token_mint,
+            requested_amount,
             amount,
-            destination_wallet: vault_state.wallet_account,
+            remaining_balance,
+            destination_wallet,
             authority: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
+            settlement_id,
+            event_seq,
         });
 
         msg!(
@@ -296,162 +926,2977 @@ pub mod spl_token_vault_program {
         Ok(())
     }
 
-    // close_vault function removed
-    // Rationale: Closing vaults introduces risks of:
-    // - Accidental fund loss if tokens remain
-    // - State inconsistency with dependent systems
-    // - Potential for griefing attacks
-    // Vaults are designed to be permanent for the lifetime of the protocol.
-
-    // create_wallet_ata_if_needed function removed
-    // Rationale: This function exposes unnecessary attack surface:
-    // - Allows arbitrary ATA creation which could be abused
-    // - Client-side ATA creation is preferred using standard SPL Token tooling
-    // - Reduces program complexity and potential for bugs
-    //
-    // Users should create ATAs client-side using:
-    // - @solana/spl-token: createAssociatedTokenAccount()
-    // - Anchor: anchor.utils.token.associatedAddress()
+    /// Transfer the vault balance above the configured float threshold to the
+    /// withdrawal wallet. Permissionless, so a keeper can keep hot-vault
+    /// exposure low without holding the authority key.
+    pub fn sweep(ctx: Context<Sweep>) -> Result<()> {
+        let vault_state = &mut ctx.accounts.vault_state;
+        let vault_token_account = &ctx.accounts.vault_token_account;
 
-    /// Transfer vault authority to a new address.
-    /// Validates the new authority is not a reserved address.
-    pub fn update_authority(ctx: Context<UpdateAuthority>, new_authority: Pubkey) -> Result<()> {
+        require!(vault_state.sweep_enabled, VaultError::SweepDisabled);
         require!(
-            new_authority != Pubkey::default(),
-            VaultError::InvalidAuthority
-        );
-        require_keys_neq!(
-            new_authority,
-            ctx.accounts.vault_state.key(),
-            VaultError::InvalidAuthority
-        );
-        require_keys_neq!(
-            new_authority,
-            ctx.accounts.vault_state.token_mint,
-            VaultError::InvalidAuthority
+            vault_state.wallet_account != Pubkey::default(),
+            VaultError::WalletNotSet
         );
 
-        let state = &mut ctx.accounts.vault_state;
-
-        let old_authority = state.authority;
-        state.authority = new_authority;
+        let balance = vault_token_account.amount;
+        let amount = vault_state
+            .withdrawable(balance)
+            .saturating_sub(vault_state.sweep_threshold);
+        require!(amount > 0, VaultError::NothingToSweep);
 
-        let clock = Clock::get()?;
+        let seeds = &[
+            b"vault_state",
+            vault_state.token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
 
-        emit!(AuthorityUpdatedEvent {
+        let transfer_ix = Transfer {
+            from: vault_token_account.to_account_info(),
+            to: ctx.accounts.destination_token_account.to_account_info(),
+            authority: vault_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let clock = Clock::get()?;
+
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(SweepEvent {
+            vault_state: vault_state.key(),
+            token_mint: vault_state.token_mint,
+            amount,
+            remaining_balance: balance - amount,
+            destination_wallet: vault_state.wallet_account,
+            caller: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Swept {} tokens above threshold", amount);
+        Ok(())
+    }
+
+    /// Configure permissionless sweeping. `None` disables it; otherwise the
+    /// value is the float left in the vault after a sweep. Authority only.
+    pub fn set_sweep_threshold(
+        ctx: Context<UpdateVaultConfig>,
+        sweep_threshold: Option<u64>,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        state.sweep_enabled = sweep_threshold.is_some();
+        state.sweep_threshold = sweep_threshold.unwrap_or(0);
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(SweepThresholdUpdatedEvent {
             vault_state: state.key(),
             token_mint: state.token_mint,
-            old_authority,
-            new_authority,
+            sweep_enabled: state.sweep_enabled,
+            sweep_threshold: state.sweep_threshold,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!(
+            "Sweep {} with threshold {}",
+            if state.sweep_enabled { "enabled" } else { "disabled" },
+            state.sweep_threshold
+        );
+        Ok(())
+    }
+
+    /// Designate the stake_program pool used for idle balances and the largest
+    /// share of the vault's total (held plus staked) balance that may sit in
+    /// it, in basis points. The pool cannot change while funds are staked.
+    /// Authority only.
+    pub fn set_yield_pool(ctx: Context<SetYieldPool>, max_stake_bps: u16) -> Result<()> {
+        require!(
+            max_stake_bps as u64 <= BPS_DENOMINATOR,
+            VaultError::InvalidStakeBps
+        );
+
+        let pool = ctx.accounts.pool.key();
+        let yield_config = &mut ctx.accounts.yield_config;
+        require!(
+            ctx.accounts.vault_state.staked_balance == 0 || yield_config.pool == pool,
+            VaultError::YieldPoolInUse
+        );
+        yield_config.vault_state = ctx.accounts.vault_state.key();
+        yield_config.pool = pool;
+        yield_config.max_stake_bps = max_stake_bps;
+        yield_config.bump = ctx.bumps.yield_config;
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        let event_seq = state.next_event_seq()?;
+        emit!(YieldPoolUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            pool,
+            max_stake_bps,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Yield pool set to {} (max {} bps)", pool, max_stake_bps);
+        Ok(())
+    }
+
+    /// Stake part of the vault's idle balance in the yield pool, with the vault
+    /// PDA as staker. Only settled funds are eligible, and the staked total is
+    /// capped at `max_stake_bps` of the vault's total balance. The vault's
+    /// position in the pool must already exist (`init_user_stake` with the
+    /// vault PDA as user). Authority only.
+    pub fn stake_idle(ctx: Context<StakeIdle>, pool_id: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let vault_state = &ctx.accounts.vault_state;
+        let balance = ctx.accounts.vault_token_account.amount;
+        require!(
+            amount <= vault_state.withdrawable(balance),
+            VaultError::InsufficientVaultBalance
+        );
+
+        let staked_after = vault_state
+            .staked_balance
+            .checked_add(amount)
+            .ok_or(VaultError::MathOverflow)?;
+        let total = (balance as u128)
+            .checked_add(vault_state.staked_balance as u128)
+            .ok_or(VaultError::MathOverflow)?;
+        let stake_cap = total
+            .checked_mul(ctx.accounts.yield_config.max_stake_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        require!(
+            staked_after as u128 <= stake_cap,
+            VaultError::StakeCapExceeded
+        );
+
+        let token_mint = vault_state.token_mint;
+        let seeds = &[
+            b"vault_state",
+            token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let stake_accounts = stake_program::cpi::accounts::DepositStake {
+            pool: ctx.accounts.pool.to_account_info(),
+            user_stake: ctx.accounts.vault_stake.to_account_info(),
+            user: ctx.accounts.vault_state.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            user_token_account: ctx.accounts.vault_token_account.to_account_info(),
+            pool_vault: ctx.accounts.pool_vault.to_account_info(),
+            leaderboard: ctx.accounts.leaderboard.as_ref().map(|a| a.to_account_info()),
+            attestation: None,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+            event_authority: ctx.accounts.stake_event_authority.to_account_info(),
+            program: ctx.accounts.stake_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.stake_program.to_account_info(),
+            stake_accounts,
+            signer_seeds,
+        );
+        stake_program::cpi::deposit_stake(cpi_ctx, pool_id, amount, None)?;
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        state.staked_balance = staked_after;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(IdleStakedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            pool: ctx.accounts.pool.key(),
+            amount,
+            staked_balance: state.staked_balance,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Staked {} idle tokens in pool {}", amount, pool_id);
+        Ok(())
+    }
+
+    /// Pull `amount` of staked principal back into the vault from the yield
+    /// pool. Rewards are paid by stake_program into the vault PDA's reward
+    /// token account, which must already exist. Allowed while frozen, since
+    /// funds only move back into the vault. Authority only.
+    pub fn unstake_idle(ctx: Context<UnstakeIdle>, pool_id: u64, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let staked_after = ctx
+            .accounts
+            .vault_state
+            .staked_balance
+            .checked_sub(amount)
+            .ok_or(VaultError::InsufficientStakedBalance)?;
+
+        let token_mint = ctx.accounts.vault_state.token_mint;
+        let seeds = &[
+            b"vault_state",
+            token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let stake_accounts = stake_program::cpi::accounts::WithdrawStake {
+            pool: ctx.accounts.pool.to_account_info(),
+            user_stake: ctx.accounts.vault_stake.to_account_info(),
+            user: ctx.accounts.vault_state.to_account_info(),
+            token_mint: ctx.accounts.token_mint.to_account_info(),
+            user_token_account: ctx.accounts.vault_token_account.to_account_info(),
+            user_reward_account: ctx.accounts.vault_reward_account.to_account_info(),
+            reward_mint: ctx.accounts.reward_mint.to_account_info(),
+            pool_vault: ctx.accounts.pool_vault.to_account_info(),
+            reward_vault: ctx.accounts.reward_vault.to_account_info(),
+            leaderboard: ctx.accounts.leaderboard.as_ref().map(|a| a.to_account_info()),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.stake_event_authority.to_account_info(),
+            program: ctx.accounts.stake_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.stake_program.to_account_info(),
+            stake_accounts,
+            signer_seeds,
+        );
+        stake_program::cpi::withdraw_stake(cpi_ctx, pool_id, amount)?;
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        state.staked_balance = staked_after;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(IdleUnstakedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            pool: ctx.accounts.pool.key(),
+            amount,
+            staked_balance: state.staked_balance,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Unstaked {} tokens from pool {}", amount, pool_id);
+        Ok(())
+    }
+
+    /// Refund a deposit back to the original depositor's token account.
+    /// Transfers the recorded amount and marks the record refunded. Only active
+    /// deposits can be refunded; processed ones have already been fulfilled.
+    /// Authority only.
+    pub fn refund_deposit(ctx: Context<RefundDeposit>, order_id: String) -> Result<()> {
+        let vault_state = &mut ctx.accounts.vault_state;
+        let vault_token_account = &ctx.accounts.vault_token_account;
+        let record = &mut ctx.accounts.deposit_record;
+
+        record.ensure_active()?;
+
+        let amount = record.amount;
+        require!(
+            amount <= vault_state.refundable(vault_token_account.amount, record),
+            VaultError::InsufficientVaultBalance
+        );
+
+        let seeds = &[
+            b"vault_state",
+            vault_state.token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ix = Transfer {
+            from: vault_token_account.to_account_info(),
+            to: ctx.accounts.depositor_token_account.to_account_info(),
+            authority: vault_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        vault_state.release_pending(record);
+        record.status = DepositStatus::Refunded;
+
+        let clock = Clock::get()?;
+
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(RefundEvent {
+            vault_state: vault_state.key(),
+            token_mint: vault_state.token_mint,
+            order_id,
+            user: record.user,
+            amount,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Refunded {} tokens to {}", amount, record.user);
+        Ok(())
+    }
+
+    /// Mark a deposit as fulfilled by the backend.
+    /// Processed deposits can no longer be self-refunded and no longer wait
+    /// out the settlement delay. Authority only.
+    pub fn mark_deposit_processed(
+        ctx: Context<MarkDepositProcessed>,
+        order_id: String,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let record = &mut ctx.accounts.deposit_record;
+        record.ensure_active()?;
+        require!(
+            !record.is_expired(clock.unix_timestamp),
+            VaultError::DepositExpired
+        );
+        record.status = DepositStatus::Processed;
+        // A confirmed order no longer needs to wait out the settlement delay
+        ctx.accounts.vault_state.release_pending(record);
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(DepositProcessedEvent {
+            vault_state: ctx.accounts.vault_state.key(),
+            token_mint: record.token_mint,
+            order_id,
+            user: record.user,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        Ok(())
+    }
+
+    /// Check a deposit against its invoice amount. A recorded amount within
+    /// the vault's `verify_tolerance_bps` of `expected_amount` marks the record
+    /// verified; otherwise a mismatch event is emitted and the record is left
+    /// unverified. Authority only.
+    pub fn verify_deposit(
+        ctx: Context<VerifyDeposit>,
+        order_id: String,
+        expected_amount: u64,
+    ) -> Result<()> {
+        let record = &mut ctx.accounts.deposit_record;
+        require!(
+            record.status != DepositStatus::Refunded,
+            VaultError::DepositNotRefundable
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        let tolerance = (expected_amount as u128)
+            .checked_mul(state.verify_tolerance_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let matched = (record.amount.abs_diff(expected_amount) as u128) <= tolerance;
+
+        let clock = Clock::get()?;
+        let event_seq = state.next_event_seq()?;
+
+        if matched {
+            record.verified = true;
+            emit!(DepositVerifiedEvent {
+                vault_state: state.key(),
+                token_mint: record.token_mint,
+                order_id,
+                user: record.user,
+                amount: record.amount,
+                expected_amount,
+                authority: ctx.accounts.authority.key(),
+                timestamp: clock.unix_timestamp,
+                event_seq,
+            });
+        } else {
+            emit!(DepositAmountMismatchEvent {
+                vault_state: state.key(),
+                token_mint: record.token_mint,
+                order_id,
+                user: record.user,
+                amount: record.amount,
+                expected_amount,
+                authority: ctx.accounts.authority.key(),
+                timestamp: clock.unix_timestamp,
+                event_seq,
+            });
+            msg!(
+                "Deposit amount {} does not match expected {}",
+                record.amount,
+                expected_amount
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Set the tolerance `verify_deposit` allows between the recorded and the
+    /// expected amount, in basis points of the expected amount. Authority only.
+    pub fn set_verify_tolerance(ctx: Context<UpdateVaultConfig>, tolerance_bps: u16) -> Result<()> {
+        require!(
+            tolerance_bps as u64 <= BPS_DENOMINATOR,
+            VaultError::InvalidVerifyTolerance
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        let old_tolerance_bps = state.verify_tolerance_bps;
+        state.verify_tolerance_bps = tolerance_bps;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(VerifyToleranceUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_tolerance_bps,
+            new_tolerance_bps: tolerance_bps,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Verify tolerance set to {} bps", tolerance_bps);
+        Ok(())
+    }
+
+    /// Set how long new deposits are held back from `withdraw`, `withdraw_split`
+    /// and `sweep`. Deposits already pending are released against the new
+    /// delay. 0 disables the hold for new deposits. Authority only.
+    pub fn set_settlement_delay(
+        ctx: Context<UpdateVaultConfig>,
+        settlement_delay_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            settlement_delay_seconds <= i64::MAX as u64,
+            VaultError::InvalidSettlementDelay
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        let old_settlement_delay_seconds = state.settlement_delay_seconds;
+        state.settlement_delay_seconds = settlement_delay_seconds;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(SettlementDelayUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_settlement_delay_seconds,
+            new_settlement_delay_seconds: settlement_delay_seconds,
+            authority: ctx.accounts.authority.key(),
             timestamp: clock.unix_timestamp,
+            event_seq,
         });
 
-        msg!("Authority updated to {}", new_authority);
+        msg!("Settlement delay set to {} seconds", settlement_delay_seconds);
+        Ok(())
+    }
+
+    /// Release a pending deposit once the settlement delay has passed, making
+    /// its amount withdrawable. Permissionless crank.
+    pub fn release_deposit(ctx: Context<ReleaseDeposit>, order_id: String) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        let record = &mut ctx.accounts.deposit_record;
+        require!(record.pending, VaultError::DepositNotPending);
+
+        let releasable_at = record
+            .timestamp
+            .checked_add(state.settlement_delay_seconds as i64)
+            .ok_or(VaultError::MathOverflow)?;
+        require!(
+            clock.unix_timestamp >= releasable_at,
+            VaultError::SettlementDelayNotElapsed
+        );
+
+        state.release_pending(record);
+
+        let event_seq = state.next_event_seq()?;
+        emit!(DepositReleasedEvent {
+            vault_state: state.key(),
+            token_mint: record.token_mint,
+            order_id,
+            user: record.user,
+            amount: record.amount,
+            pending_balance: state.pending_balance,
+            caller: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        Ok(())
+    }
+
+    /// Set how long an unprocessed deposit must wait before its depositor
+    /// can reclaim it with `claim_refund`. 0 disables self-refunds. Authority only.
+    pub fn set_refund_after_seconds(
+        ctx: Context<UpdateVaultConfig>,
+        refund_after_seconds: u64,
+    ) -> Result<()> {
+        require!(
+            refund_after_seconds <= i64::MAX as u64,
+            VaultError::InvalidRefundWindow
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        let old_refund_after_seconds = state.refund_after_seconds;
+        state.refund_after_seconds = refund_after_seconds;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(RefundWindowUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_refund_after_seconds,
+            new_refund_after_seconds: refund_after_seconds,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Refund window set to {} seconds", refund_after_seconds);
+        Ok(())
+    }
+
+    /// Require every deposit to also create a mint-wide order index entry,
+    /// making order IDs unique across depositors. Authority only.
+    pub fn set_require_global_order_id(
+        ctx: Context<UpdateVaultConfig>,
+        required: bool,
+    ) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        state.require_global_order_id = required;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(GlobalOrderIdUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            required,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Global order ID uniqueness set to {}", required);
+        Ok(())
+    }
+
+    /// Set how long new deposits stay valid before they expire and become
+    /// reclaimable by the depositor. 0 disables expiry. Existing deposits keep
+    /// their `expires_at`. Authority only.
+    pub fn set_deposit_ttl(ctx: Context<UpdateVaultConfig>, deposit_ttl_seconds: u64) -> Result<()> {
+        require!(
+            deposit_ttl_seconds <= i64::MAX as u64,
+            VaultError::InvalidDepositTtl
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        let old_deposit_ttl_seconds = state.deposit_ttl_seconds;
+        state.deposit_ttl_seconds = deposit_ttl_seconds;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(DepositTtlUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_deposit_ttl_seconds,
+            new_deposit_ttl_seconds: deposit_ttl_seconds,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Deposit TTL set to {} seconds", deposit_ttl_seconds);
+        Ok(())
+    }
+
+    /// Flag an unprocessed deposit whose `expires_at` has passed as expired.
+    /// Permissionless crank; the depositor can then reclaim it with `claim_refund`.
+    pub fn expire_deposit(ctx: Context<ExpireDeposit>, order_id: String) -> Result<()> {
+        let clock = Clock::get()?;
+
+        let record = &mut ctx.accounts.deposit_record;
+        require!(
+            record.status == DepositStatus::Active && record.is_expired(clock.unix_timestamp),
+            VaultError::DepositNotExpired
+        );
+        record.status = DepositStatus::Expired;
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(DepositExpiredEvent {
+            vault_state: ctx.accounts.vault_state.key(),
+            token_mint: record.token_mint,
+            order_id,
+            user: record.user,
+            amount: record.amount,
+            expires_at: record.expires_at.unwrap_or_default(),
+            caller: ctx.accounts.caller.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        Ok(())
+    }
+
+    /// Set the per-deposit amount limits enforced by `deposit`.
+    /// A `max_deposit` of 0 means no upper limit. Authority only.
+    pub fn set_deposit_limits(
+        ctx: Context<UpdateVaultConfig>,
+        min_deposit: u64,
+        max_deposit: u64,
+    ) -> Result<()> {
+        require!(
+            max_deposit == 0 || min_deposit <= max_deposit,
+            VaultError::InvalidDepositLimits
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+        state.min_deposit = min_deposit;
+        state.max_deposit = max_deposit;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(DepositLimitsUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            min_deposit,
+            max_deposit,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Deposit limits set to [{}, {}]", min_deposit, max_deposit);
+        Ok(())
+    }
+
+    /// Set the deposit fee and the token account that collects it.
+    /// Fees are capped at `MAX_DEPOSIT_FEE_BPS`; 0 disables the fee. Authority only.
+    pub fn set_deposit_fee(ctx: Context<SetDepositFee>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_DEPOSIT_FEE_BPS, VaultError::InvalidFeeBps);
+
+        let fee_collector = ctx.accounts.fee_collector.key();
+        let state = &mut ctx.accounts.vault_state;
+        let old_fee_bps = state.fee_bps;
+        state.fee_bps = fee_bps;
+        state.fee_collector = fee_collector;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(DepositFeeUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_fee_bps,
+            new_fee_bps: fee_bps,
+            fee_collector,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!(
+            "Deposit fee set to {} bps, collector {}",
+            fee_bps,
+            fee_collector
+        );
+        Ok(())
+    }
+
+    /// Toggle passthrough mode, where deposits are forwarded to the withdrawal
+    /// wallet's ATA and never held by the vault. Requires a withdrawal wallet
+    /// to enable. Authority only.
+    pub fn set_passthrough(ctx: Context<UpdateVaultConfig>, enabled: bool) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        require!(
+            !enabled || state.wallet_account != Pubkey::default(),
+            VaultError::WalletNotSet
+        );
+        state.passthrough = enabled;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(PassthroughUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            enabled,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Passthrough mode set to {}", enabled);
+        Ok(())
+    }
+
+    /// Enable or disable rent sponsorship. While enabled, deposits that pass
+    /// the rent escrow are paid back the deposit record rent out of the
+    /// escrow, so depositors end up paying only transaction fees. Authority only.
+    pub fn set_rent_sponsorship(ctx: Context<UpdateVaultConfig>, enabled: bool) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        state.rent_sponsorship = enabled;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(RentSponsorshipUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            enabled,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Rent sponsorship set to {}", enabled);
+        Ok(())
+    }
+
+    /// Top up the vault's rent escrow with `lamports`, creating the escrow on
+    /// first use. Only lamports above the escrow's own rent-exempt minimum
+    /// are spent on deposit records. Authority only.
+    pub fn fund_rent_escrow(ctx: Context<FundRentEscrow>, lamports: u64) -> Result<()> {
+        require!(lamports > 0, VaultError::InvalidAmount);
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.rent_escrow.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+
+        let rent_escrow = &mut ctx.accounts.rent_escrow;
+        rent_escrow.vault_state = ctx.accounts.vault_state.key();
+        rent_escrow.bump = ctx.bumps.rent_escrow;
+        let balance = rent_escrow.get_lamports();
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        let event_seq = state.next_event_seq()?;
+        emit!(RentEscrowFundedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            amount: lamports,
+            balance,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Rent escrow funded with {} lamports", lamports);
+        Ok(())
+    }
+
+    /// Close a processed or refunded deposit record. Its rent goes back to
+    /// whoever paid it: the rent escrow for sponsored records, otherwise the
+    /// depositor or relayer that created it. The authority funds a small
+    /// `ConsumedOrder` marker so the depositor cannot reuse the order ID.
+    /// Authority only.
+    pub fn close_deposit_record(
+        ctx: Context<CloseDepositRecord>,
+        order_id: String,
+    ) -> Result<()> {
+        let record = &ctx.accounts.deposit_record;
+        require!(
+            matches!(
+                record.status,
+                DepositStatus::Processed | DepositStatus::Refunded
+            ),
+            VaultError::DepositRecordNotClosable
+        );
+        ctx.accounts.consumed_order.bump = ctx.bumps.consumed_order;
+
+        let clock = Clock::get()?;
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(DepositRecordClosedEvent {
+            vault_state: ctx.accounts.vault_state.key(),
+            token_mint: record.token_mint,
+            order_id,
+            user: record.user,
+            rent_recipient: record.rent_payer,
+            lamports: record.get_lamports(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        Ok(())
+    }
+
+    /// Set or clear the operator allowed to run `withdraw` to the configured
+    /// wallet. Configuration stays with the authority. Authority only.
+    pub fn set_operator(ctx: Context<UpdateVaultConfig>, operator: Option<Pubkey>) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        let new_operator = operator.unwrap_or_default();
+        require_keys_neq!(new_operator, state.key(), VaultError::Unauthorized);
+
+        let old_operator = state.operator;
+        state.operator = new_operator;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(OperatorUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_operator,
+            new_operator,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Operator set to {}", new_operator);
+        Ok(())
+    }
+
+    /// Set or clear the guardian, a key that can only freeze and unfreeze the
+    /// vault. Cannot be changed while frozen. Authority only.
+    pub fn set_guardian(ctx: Context<UpdateVaultConfig>, guardian: Option<Pubkey>) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        require!(!state.frozen, VaultError::VaultFrozen);
+
+        let old_guardian = state.guardian;
+        state.guardian = guardian.unwrap_or_default();
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(GuardianUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_guardian,
+            new_guardian: state.guardian,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Guardian set to {}", state.guardian);
+        Ok(())
+    }
+
+    /// Freeze or unfreeze the vault. While frozen, withdrawals, sweeps and
+    /// withdrawal wallet changes are rejected; deposits still go through.
+    /// Guardian only.
+    pub fn set_frozen(ctx: Context<SetFrozen>, frozen: bool) -> Result<()> {
+        let state = &mut ctx.accounts.vault_state;
+        state.frozen = frozen;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(VaultFrozenEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            frozen,
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Vault frozen: {}", frozen);
+        Ok(())
+    }
+
+    /// Move `amount` (0 = the full token balance) to the cold wallet fixed at
+    /// initialize. Works while the vault is frozen and skips multisig, so the
+    /// guardian can always evacuate funds to a known-safe address. Guardian only.
+    pub fn emergency_withdraw_to_cold(
+        ctx: Context<EmergencyWithdrawToCold>,
+        amount: u64,
+    ) -> Result<()> {
+        let balance = ctx.accounts.vault_token_account.amount;
+        require!(balance > 0, VaultError::NoFunds);
+        let amount = if amount == 0 { balance } else { amount };
+        require!(amount <= balance, VaultError::InsufficientVaultBalance);
+
+        let token_mint = ctx.accounts.vault_state.token_mint;
+        let seeds = &[
+            b"vault_state",
+            token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ix = token::Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.cold_wallet_token_account.to_account_info(),
+            authority: ctx.accounts.vault_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let clock = Clock::get()?;
+
+        let state = &mut ctx.accounts.vault_state;
+        let event_seq = state.next_event_seq()?;
+        emit!(EmergencyWithdrawalEvent {
+            vault_state: state.key(),
+            token_mint,
+            amount,
+            remaining_balance: balance - amount,
+            cold_wallet: state.cold_wallet,
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Emergency withdrawal of {} tokens to cold wallet", amount);
+        Ok(())
+    }
+
+    /// Reclaim an unprocessed deposit once the vault's refund window has elapsed.
+    /// Permissionless for the original depositor.
+    pub fn claim_refund(ctx: Context<ClaimRefund>, order_id: String) -> Result<()> {
+        let vault_state = &mut ctx.accounts.vault_state;
+        let vault_token_account = &ctx.accounts.vault_token_account;
+        let record = &mut ctx.accounts.deposit_record;
+
+        let clock = Clock::get()?;
+
+        // Expired deposits are reclaimable regardless of the refund window
+        if !record.is_expired(clock.unix_timestamp) {
+            require!(
+                vault_state.refund_after_seconds > 0,
+                VaultError::RefundsDisabled
+            );
+            record.ensure_active()?;
+
+            let refundable_at = record
+                .timestamp
+                .checked_add(vault_state.refund_after_seconds as i64)
+                .ok_or(VaultError::MathOverflow)?;
+            require!(
+                clock.unix_timestamp >= refundable_at,
+                VaultError::RefundWindowNotElapsed
+            );
+        }
+
+        let amount = record.amount;
+        require!(
+            amount <= vault_state.refundable(vault_token_account.amount, record),
+            VaultError::InsufficientVaultBalance
+        );
+
+        let seeds = &[
+            b"vault_state",
+            vault_state.token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let transfer_ix = Transfer {
+            from: vault_token_account.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: vault_state.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_ix,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        vault_state.release_pending(record);
+        record.status = DepositStatus::Refunded;
+
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(RefundEvent {
+            vault_state: vault_state.key(),
+            token_mint: vault_state.token_mint,
+            order_id,
+            user: record.user,
+            amount,
+            authority: ctx.accounts.user.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Depositor reclaimed {} tokens", amount);
+        Ok(())
+    }
+
+    /// Configure the payout table used by `withdraw_split`.
+    /// Shares are in basis points and must sum to 10,000. Authority only.
+    pub fn set_payout_table(
+        ctx: Context<SetPayoutTable>,
+        entries: Vec<PayoutEntry>,
+    ) -> Result<()> {
+        let vault_key = ctx.accounts.vault_state.key();
+
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_PAYOUT_ENTRIES,
+            VaultError::InvalidPayoutTable
+        );
+
+        let mut total_bps: u64 = 0;
+        for (i, entry) in entries.iter().enumerate() {
+            require!(entry.bps > 0, VaultError::InvalidPayoutTable);
+            require!(
+                entry.wallet != Pubkey::default()
+                    && entry.wallet != crate::ID
+                    && entry.wallet != anchor_lang::system_program::ID
+                    && entry.wallet != vault_key,
+                VaultError::InvalidWithdrawalWallet
+            );
+            require!(
+                !entries[..i].iter().any(|e| e.wallet == entry.wallet),
+                VaultError::InvalidPayoutTable
+            );
+            total_bps = total_bps
+                .checked_add(entry.bps as u64)
+                .ok_or(VaultError::MathOverflow)?;
+        }
+        require!(total_bps == BPS_DENOMINATOR, VaultError::InvalidPayoutTable);
+
+        let table = &mut ctx.accounts.payout_table;
+        table.vault_state = vault_key;
+        table.entries = entries.clone();
+        table.bump = ctx.bumps.payout_table;
+
+        let clock = Clock::get()?;
+
+        let event_seq = ctx.accounts.vault_state.next_event_seq()?;
+        emit!(PayoutTableUpdatedEvent {
+            vault_state: vault_key,
+            token_mint: ctx.accounts.vault_state.token_mint,
+            entries,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Payout table updated with {} entries", table.entries.len());
+        Ok(())
+    }
+
+    /// Withdraw tokens split across the destinations in the payout table.
+    /// `splits` must match the configured table entry for entry, and the
+    /// destination ATAs are passed as remaining accounts in the same order,
+    /// followed by any signer set co-signers. Rounding dust goes to the last
+    /// destination. An `amount` of 0 splits the full balance. Authority only.
+    pub fn withdraw_split<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawSplit<'info>>,
+        amount: u64,
+        splits: Vec<PayoutSplit>,
+        settlement_id: Option<String>,
+    ) -> Result<()> {
+        let settlement_id = settlement_id.unwrap_or_default();
+        require!(
+            settlement_id.len() <= MAX_SETTLEMENT_ID_LEN,
+            VaultError::SettlementIdTooLong
+        );
+
+        let vault_state = &mut ctx.accounts.vault_state;
+        let vault_token_account = &ctx.accounts.vault_token_account;
+        let table = &ctx.accounts.payout_table;
+
+        require!(
+            splits.len() == table.entries.len(),
+            VaultError::PayoutTableMismatch
+        );
+        require!(
+            ctx.remaining_accounts.len() >= splits.len(),
+            VaultError::PayoutTableMismatch
+        );
+        let (destinations, co_signers) = ctx.remaining_accounts.split_at(splits.len());
+        vault_state.verify_approvals(ctx.accounts.signer_set.as_deref(), co_signers)?;
+        for ((split, entry), account) in splits
+            .iter()
+            .zip(table.entries.iter())
+            .zip(destinations.iter())
+        {
+            let expected_ata =
+                get_associated_token_address(&entry.wallet, &vault_state.token_mint);
+            require!(
+                split.bps == entry.bps
+                    && split.destination == expected_ata
+                    && account.key() == expected_ata,
+                VaultError::PayoutTableMismatch
+            );
+        }
+
+        let balance = vault_state.withdrawable(vault_token_account.amount);
+        require!(balance > 0, VaultError::NoFunds);
+
+        let amount = if amount == 0 { balance } else { amount };
+        require!(amount <= balance, VaultError::InsufficientVaultBalance);
+
+        let seeds = &[
+            b"vault_state",
+            vault_state.token_mint.as_ref(),
+            &[ctx.bumps.vault_state],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let mut amounts = Vec::with_capacity(splits.len());
+        let mut distributed: u64 = 0;
+        for (i, (split, account)) in splits
+            .iter()
+            .zip(destinations.iter())
+            .enumerate()
+        {
+            let share = if i == splits.len() - 1 {
+                amount
+                    .checked_sub(distributed)
+                    .ok_or(VaultError::MathOverflow)?
+            } else {
+                let share = (amount as u128)
+                    .checked_mul(split.bps as u128)
+                    .ok_or(VaultError::MathOverflow)?
+                    / BPS_DENOMINATOR as u128;
+                share as u64
+            };
+            distributed = distributed
+                .checked_add(share)
+                .ok_or(VaultError::MathOverflow)?;
+            amounts.push(share);
+
+            if share == 0 {
+                continue;
+            }
+
+            let transfer_ix = Transfer {
+                from: vault_token_account.to_account_info(),
+                to: account.clone(),
+                authority: vault_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_ix,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, share)?;
+        }
+
+        let clock = Clock::get()?;
+
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(WithdrawSplitEvent {
+            vault_state: vault_state.key(),
+            token_mint: vault_state.token_mint,
+            amount,
+            destinations: splits.iter().map(|s| s.destination).collect(),
+            amounts,
+            remaining_balance: vault_token_account
+                .amount
+                .checked_sub(amount)
+                .ok_or(VaultError::MathOverflow)?,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            settlement_id,
+            event_seq,
+        });
+
+        msg!(
+            "Withdrawn {} tokens across {} destinations",
+            amount,
+            splits.len()
+        );
+        Ok(())
+    }
+
+    /// Replace the vault signer set. Once a set is active, withdrawals and
+    /// withdrawal wallet changes need `threshold` of its members as co-signers,
+    /// passed as remaining accounts. Changing an active set also needs its
+    /// approval. A threshold of 0 with no signers disables multisig. Authority only.
+    pub fn set_signer_set(
+        ctx: Context<ManageSignerSet>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .vault_state
+            .verify_approvals(Some(&ctx.accounts.signer_set), ctx.remaining_accounts)?;
+
+        if threshold == 0 {
+            require!(signers.is_empty(), VaultError::InvalidSignerSet);
+        } else {
+            require!(
+                signers.len() <= MAX_SIGNERS && (threshold as usize) <= signers.len(),
+                VaultError::InvalidSignerSet
+            );
+            for (i, signer) in signers.iter().enumerate() {
+                require!(
+                    *signer != Pubkey::default() && !signers[..i].contains(signer),
+                    VaultError::InvalidSignerSet
+                );
+            }
+        }
+
+        let vault_key = ctx.accounts.vault_state.key();
+        let signer_set = &mut ctx.accounts.signer_set;
+        signer_set.vault_state = vault_key;
+        signer_set.signers = signers.clone();
+        signer_set.threshold = threshold;
+        signer_set.bump = ctx.bumps.signer_set;
+
+        let state = &mut ctx.accounts.vault_state;
+        state.multisig_enabled = threshold > 0;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(SignerSetUpdatedEvent {
+            vault_state: vault_key,
+            token_mint: state.token_mint,
+            signers,
+            threshold,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Signer set updated, threshold {}", threshold);
+        Ok(())
+    }
+
+    // close_vault function removed
+    // Rationale: Closing vaults introduces risks of:
+    // - Accidental fund loss if tokens remain
+    // - State inconsistency with dependent systems
+    // - Potential for griefing attacks
+    // Vaults are designed to be permanent for the lifetime of the protocol.
+    // close_vault_with_dust is declined for the same reason and not implemented.
+    // Residual balances (fee-on-transfer residue, airdropped dust) are cleared
+    // with withdraw(0), which sends withdrawable() - the balance less deposits
+    // still pending - to the withdrawal wallet.
+
+    // create_wallet_ata_if_needed function removed
+    // Rationale: This function exposes unnecessary attack surface:
+    // - Allows arbitrary ATA creation which could be abused
+    // - Client-side ATA creation is preferred using standard SPL Token tooling
+    // - Reduces program complexity and potential for bugs
+    //
+    // Users should create ATAs client-side using:
+    // - @solana/spl-token: createAssociatedTokenAccount()
+    // - Anchor: anchor.utils.token.associatedAddress()
+
+    /// Transfer vault authority to a new address.
+    /// Validates the new authority is not a reserved address.
+    pub fn update_authority(ctx: Context<UpdateAuthority>, new_authority: Pubkey) -> Result<()> {
+        require!(
+            new_authority != Pubkey::default(),
+            VaultError::InvalidAuthority
+        );
+        require_keys_neq!(
+            new_authority,
+            ctx.accounts.vault_state.key(),
+            VaultError::InvalidAuthority
+        );
+        require_keys_neq!(
+            new_authority,
+            ctx.accounts.vault_state.token_mint,
+            VaultError::InvalidAuthority
+        );
+
+        let state = &mut ctx.accounts.vault_state;
+
+        let old_authority = state.authority;
+        state.authority = new_authority;
+
+        let clock = Clock::get()?;
+
+        let event_seq = state.next_event_seq()?;
+        emit!(AuthorityUpdatedEvent {
+            vault_state: state.key(),
+            token_mint: state.token_mint,
+            old_authority,
+            new_authority,
+            timestamp: clock.unix_timestamp,
+            event_seq,
+        });
+
+        msg!("Authority updated to {}", new_authority);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Deposit Helpers
+// ============================================================================
+
+/// Where a deposit's funds come from.
+enum DepositSource<'a, 'info> {
+    /// SPL transfer out of `account`. `authority` is the depositor signing
+    /// directly, or the vault PDA acting as SPL delegate.
+    Token {
+        account: &'a Account<'info, TokenAccount>,
+        authority: AccountInfo<'info>,
+    },
+    /// Native SOL sent by `payer` and wrapped in place; wSOL vaults only
+    Lamports {
+        payer: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+    },
+}
+
+impl<'a, 'info> DepositSource<'a, 'info> {
+    /// Move `amount` into the token account `to`.
+    fn pay(
+        &self,
+        to: AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        match self {
+            DepositSource::Token { account, authority } => {
+                let transfer_ix = token::Transfer {
+                    from: account.to_account_info(),
+                    to,
+                    authority: authority.clone(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    transfer_ix,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, amount)
+            }
+            DepositSource::Lamports {
+                payer,
+                system_program,
+            } => {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        anchor_lang::system_program::Transfer {
+                            from: payer.clone(),
+                            to: to.clone(),
+                        },
+                    ),
+                    amount,
+                )?;
+                token::sync_native(CpiContext::new(
+                    token_program.to_account_info(),
+                    token::SyncNative { account: to },
+                ))
+            }
+        }
+    }
+}
+
+/// Accounts shared by every deposit entry point.
+struct DepositFlow<'a, 'info> {
+    vault_state: &'a mut Account<'info, VaultState>,
+    vault_token_account: &'a mut Account<'info, TokenAccount>,
+    passthrough_destination: Option<&'a mut Account<'info, TokenAccount>>,
+    fee_collector: Option<&'a Account<'info, TokenAccount>>,
+    source: DepositSource<'a, 'info>,
+    token_program: &'a Program<'info, Token>,
+    /// Mint-wide order index entry and its bump, when the caller created one
+    order_index: Option<(&'a mut Account<'info, OrderIndex>, u8)>,
+    rent_escrow: Option<&'a mut Account<'info, RentEscrow>>,
+    /// Account that paid the deposit record rent on `init`
+    rent_payer: AccountInfo<'info>,
+}
+
+impl<'a, 'info> DepositFlow<'a, 'info> {
+    /// Validate the deposit against the vault limits, route the fee and the
+    /// net amount, fill in `record` with the amount actually received, and
+    /// emit the deposit event.
+    fn execute(
+        self,
+        record: &mut Account<'info, DepositRecord>,
+        depositor: Pubkey,
+        order_id: String,
+        amount: u64,
+        memo: Option<String>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let vault_state = self.vault_state;
+
+        require!(amount > 0, VaultError::InvalidAmount);
+        require!(!order_id.is_empty(), VaultError::OrderIdEmpty);
+        require!(is_valid_order_id(&order_id), VaultError::OrderIdInvalid);
+        let memo = memo.unwrap_or_default();
+        require!(memo.len() <= MAX_MEMO_LEN, VaultError::MemoTooLong);
+        require!(
+            amount >= vault_state.min_deposit,
+            VaultError::DepositBelowMinimum
+        );
+        require!(
+            vault_state.max_deposit == 0 || amount <= vault_state.max_deposit,
+            VaultError::DepositAboveMaximum
+        );
+        require!(
+            !vault_state.require_global_order_id || self.order_index.is_some(),
+            VaultError::OrderIndexRequired
+        );
+
+        let fee = (amount as u128)
+            .checked_mul(vault_state.fee_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let fee = fee as u64;
+        let net_amount = amount.checked_sub(fee).ok_or(VaultError::MathOverflow)?;
+
+        if fee > 0 {
+            let fee_collector = self.fee_collector.ok_or(VaultError::FeeCollectorRequired)?;
+            self.source.pay(
+                fee_collector.to_account_info(),
+                self.token_program,
+                fee,
+                signer_seeds,
+            )?;
+        }
+
+        // Passthrough deposits leave the vault's own balance untouched
+        let vault_balance_before = self.vault_token_account.amount;
+
+        // Passthrough vaults forward deposits straight to the withdrawal wallet
+        let destination = if vault_state.passthrough {
+            self.passthrough_destination
+                .ok_or(VaultError::PassthroughDestinationRequired)?
+        } else {
+            self.vault_token_account
+        };
+
+        // Capture balance before transfer for fee-on-transfer token support
+        let balance_before = destination.amount;
+
+        self.source.pay(
+            destination.to_account_info(),
+            self.token_program,
+            net_amount,
+            signer_seeds,
+        )?;
+
+        // Reload to get actual balance after transfer
+        destination.reload()?;
+        let balance_after = destination.amount;
+
+        // Calculate actual received amount (handles fee-on-transfer tokens)
+        let actual_amount_received = balance_after
+            .checked_sub(balance_before)
+            .ok_or(VaultError::MathOverflow)?;
+        let vault_balance_after = if vault_state.passthrough {
+            vault_balance_before
+        } else {
+            balance_after
+        };
+        vault_state.deposit_count = vault_state
+            .deposit_count
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+
+        // Store deposit record with actual received amount
+        record.order_id = order_id;
+        record.user = depositor;
+        record.amount = actual_amount_received;
+        record.timestamp = Clock::get()?.unix_timestamp;
+        record.token_mint = vault_state.token_mint;
+        record.status = DepositStatus::Active;
+        record.memo = memo;
+        record.verified = false;
+        // Held deposits stay out of withdrawals until the settlement delay passes
+        record.pending = !vault_state.passthrough && vault_state.settlement_delay_seconds > 0;
+        if record.pending {
+            vault_state.pending_balance = vault_state
+                .pending_balance
+                .checked_add(actual_amount_received)
+                .ok_or(VaultError::MathOverflow)?;
+        }
+        record.expires_at = if vault_state.deposit_ttl_seconds > 0 {
+            Some(
+                record
+                    .timestamp
+                    .checked_add(vault_state.deposit_ttl_seconds as i64)
+                    .ok_or(VaultError::MathOverflow)?,
+            )
+        } else {
+            None
+        };
+
+        // Sponsored vaults pay the record rent back out of the escrow surplus;
+        // when the escrow runs dry the payer simply keeps the cost
+        record.rent_payer = self.rent_payer.key();
+        if vault_state.rent_sponsorship {
+            if let Some(rent_escrow) = self.rent_escrow {
+                let record_rent = record.get_lamports();
+                let escrow_info = rent_escrow.to_account_info();
+                let escrow_floor = Rent::get()?.minimum_balance(escrow_info.data_len());
+                if escrow_info.lamports().saturating_sub(escrow_floor) >= record_rent {
+                    rent_escrow.sub_lamports(record_rent)?;
+                    self.rent_payer.add_lamports(record_rent)?;
+                    record.rent_payer = rent_escrow.key();
+                }
+            }
+        }
+
+        // The index account is created by `init`, so a second deposit under
+        // the same order ID for this mint fails before reaching here
+        if let Some((order_index, bump)) = self.order_index {
+            order_index.deposit_record = record.key();
+            order_index.user = depositor;
+            order_index.bump = bump;
+        }
+
+        let event_seq = vault_state.next_event_seq()?;
+        emit!(DepositEvent {
+            user: record.user,
+            order_id: record.order_id.clone(),
+            amount: record.amount,
+            fee,
+            token_mint: record.token_mint,
+            timestamp: record.timestamp,
+            memo: record.memo.clone(),
+            vault_balance_after,
+            deposit_count: vault_state.deposit_count,
+            event_seq,
+        });
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Authorization Helpers
+// ============================================================================
+
+/// Bytes a user signs to authorize `deposit_relayed`: the vault key, the raw
+/// order ID, then `amount`, `expiry` and `nonce` little-endian.
+fn relayed_deposit_message(
+    vault: &Pubkey,
+    order_id: &str,
+    amount: u64,
+    expiry: i64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + order_id.len() + 24);
+    message.extend_from_slice(vault.as_ref());
+    message.extend_from_slice(order_id.as_bytes());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Require the instruction right before the current one to be an ed25519
+/// program check of `message` signed by `signer`. The runtime verifies the
+/// signature itself; this binds that check to the expected key and message.
+fn verify_ed25519_authorization(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    require!(current_index > 0, VaultError::InvalidUserAuthorization);
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        VaultError::InvalidUserAuthorization
+    );
+
+    // Layout: signature count, padding, then one 14-byte offsets record
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        VaultError::InvalidUserAuthorization
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    // Signature, key and message must all live in the ed25519 instruction itself
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        VaultError::InvalidUserAuthorization
+    );
+
+    let pubkey_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_len = read_u16(12) as usize;
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + 32)
+        .ok_or(VaultError::InvalidUserAuthorization)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(VaultError::InvalidUserAuthorization)?;
+    require!(
+        signed_pubkey == signer.as_ref() && signed_message == message,
+        VaultError::InvalidUserAuthorization
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Wallet Helpers
+// ============================================================================
+
+/// Reject withdrawal wallets that would send funds to an unrecoverable or
+/// program-owned address.
+fn validate_withdrawal_wallet(vault: &Pubkey, token_mint: &Pubkey, wallet: &Pubkey) -> Result<()> {
+    // Derive vault token account address for validation
+    let vault_token_account = get_associated_token_address(vault, token_mint);
+
+    // Prevent setting withdrawal wallet to invalid addresses that could cause fund loss
+    require!(
+        *wallet != Pubkey::default()
+            && *wallet != crate::ID
+            && *wallet != anchor_lang::system_program::ID
+            && wallet != vault
+            && wallet != token_mint
+            && *wallet != vault_token_account,
+        VaultError::InvalidWithdrawalWallet
+    );
+    Ok(())
+}
+
+/// Validate the wallet's canonical ATA for `token_mint`, creating it when it
+/// does not exist yet. Returns the ATA address.
+fn create_or_validate_wallet_ata<'info>(
+    wallet: &UncheckedAccount<'info>,
+    associated_token: &UncheckedAccount<'info>,
+    token_mint: &Account<'info, Mint>,
+    payer: &Signer<'info>,
+    associated_token_program: &Program<'info, associated_token::AssociatedToken>,
+    system_program: &Program<'info, System>,
+    token_program: &Program<'info, Token>,
+) -> Result<Pubkey> {
+    let wallet_key = wallet.key();
+    let mint_key = token_mint.key();
+    let ata = get_associated_token_address(&wallet_key, &mint_key);
+
+    // Verify the provided ATA matches the canonical derivation
+    let ata_account_info = associated_token.to_account_info();
+    require_keys_eq!(
+        ata_account_info.key(),
+        ata,
+        VaultError::InvalidWithdrawalWallet
+    );
+
+    msg!("Checking ATA for wallet {}", wallet_key);
+    msg!("Token mint: {}", mint_key);
+    msg!("Expected ATA: {}", ata);
+
+    // If ATA exists, validate its configuration
+    if ata_account_info.owner == &token::ID {
+        let ata_data = TokenAccount::try_deserialize(&mut &ata_account_info.data.borrow()[..])?;
+
+        // Verify mint matches
+        require_keys_eq!(ata_data.mint, mint_key, VaultError::MintMismatch);
+
+        // Verify ownership
+        require_keys_eq!(
+            ata_data.owner,
+            wallet_key,
+            VaultError::InvalidWithdrawalWallet
+        );
+
+        msg!("ATA already exists and validated: {}", ata);
+    } else {
+        // Create ATA if it doesn't exist
+        msg!("Creating new ATA at {}", ata);
+
+        let create_ctx = CpiContext::new(
+            associated_token_program.to_account_info(),
+            Create {
+                payer: payer.to_account_info(),
+                associated_token: ata_account_info,
+                authority: wallet.to_account_info(),
+                mint: token_mint.to_account_info(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        );
+
+        associated_token::create(create_ctx)?;
+        msg!("ATA created successfully for wallet {}", wallet_key);
+    }
+
+    Ok(ata)
+}
+
+// ============================================================================
+// Account Structs
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = VaultState::SPACE,
+        seeds = [b"vault_state", token_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    /// Use init_if_needed to allow reinitialization of vault ATA
+    /// This is safe because:
+    /// 1. The ATA is deterministically derived from vault_state and token_mint
+    /// 2. The authority is set to vault_state PDA (not a user-controlled address)
+    /// 3. Reinitializing an existing ATA has no negative security impact
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Created by the first vault; grown by one entry per vault in the handler
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = VaultRegistry::space(0),
+        seeds = [b"vault_registry"],
+        bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateVaultState<'info> {
+    /// CHECK: May predate the current layout, so the discriminator, PDA and
+    /// authority are checked by hand in the handler
+    #[account(mut, owner = crate::ID)]
+    pub vault_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct MigrateDepositRecord<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    /// CHECK: May predate the current layout, so it is deserialized by hand in
+    /// the handler; the PDA ties it to the vault, depositor and order ID
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: UncheckedAccount<'info>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+
+    /// Pays the rent for the grown record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = VaultRegistry::space(0),
+        seeds = [b"vault_registry"],
+        bump
+    )]
+    pub vault_registry: Account<'info, VaultRegistry>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Check<'info> {
+    #[account(
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawalAccount<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority,
+        has_one = token_mint,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Validated in instruction logic
+    pub new_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: May or may not exist; validated/created in instruction
+    #[account(mut)]
+    pub associated_token: UncheckedAccount<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// Required when multisig is enabled
+    #[account(
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = DepositRecord::SPACE,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Mint-wide claim on `order_id`; required when the vault enforces
+    /// globally unique order IDs
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 1,
+        seeds = [b"order_index", vault_state.token_mint.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub order_index: Option<Account<'info, OrderIndex>>,
+
+    /// CHECK: Marker written when a record for this order ID was closed; must not exist
+    #[account(
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump,
+        constraint = consumed_order.data_is_empty() @ VaultError::OrderIdConsumed
+    )]
+    pub consumed_order: UncheckedAccount<'info>,
+
+    /// Required when the vault charges a deposit fee
+    #[account(
+        mut,
+        address = vault_state.fee_collector @ VaultError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<Account<'info, TokenAccount>>,
+
+    /// Withdrawal wallet ATA; required when the vault is in passthrough mode
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub passthrough_destination: Option<Account<'info, TokenAccount>>,
+
+    /// Pays the record rent back when the vault sponsors rent
+    #[account(
+        mut,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump = rent_escrow.bump
+    )]
+    pub rent_escrow: Option<Account<'info, RentEscrow>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct DepositAndStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = token_mint
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = DepositRecord::SPACE,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Mint-wide claim on `order_id`; required when the vault enforces
+    /// globally unique order IDs
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 1,
+        seeds = [b"order_index", vault_state.token_mint.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub order_index: Option<Account<'info, OrderIndex>>,
+
+    /// CHECK: Marker written when a record for this order ID was closed; must not exist
+    #[account(
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump,
+        constraint = consumed_order.data_is_empty() @ VaultError::OrderIdConsumed
+    )]
+    pub consumed_order: UncheckedAccount<'info>,
+
+    /// Required when the vault charges a deposit fee
+    #[account(
+        mut,
+        address = vault_state.fee_collector @ VaultError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<Account<'info, TokenAccount>>,
+
+    /// Withdrawal wallet ATA; required when the vault is in passthrough mode
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub passthrough_destination: Option<Account<'info, TokenAccount>>,
+
+    /// Pays the record rent back when the vault sponsors rent
+    #[account(
+        mut,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump = rent_escrow.bump
+    )]
+    pub rent_escrow: Option<Account<'info, RentEscrow>>,
+
+    /// Vault mint, also the staking pool's mint
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Staking pool; validated by stake_program
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Depositor's stake position; validated by stake_program
+    #[account(mut)]
+    pub user_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Pool token vault; validated by stake_program
+    #[account(mut)]
+    pub pool_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool leaderboard, if one exists; validated by stake_program
+    #[account(mut)]
+    pub leaderboard: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Staker credential, when the pool requires one; validated by stake_program
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: stake_program event authority PDA; validated by stake_program
+    pub stake_event_authority: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, stake_program::program::StakeProgram>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct DepositDelegated<'info> {
+    /// Submits the transaction and pays for the deposit record
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Token owner credited with the deposit; co-signs the order ID and amount
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = DepositRecord::SPACE,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), owner.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Mint-wide claim on `order_id`; required when the vault enforces
+    /// globally unique order IDs
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + 32 + 32 + 1,
+        seeds = [b"order_index", vault_state.token_mint.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub order_index: Option<Account<'info, OrderIndex>>,
+
+    /// CHECK: Marker written when a record for this order ID was closed; must not exist
+    #[account(
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), owner.key().as_ref(), order_id.as_bytes()],
+        bump,
+        constraint = consumed_order.data_is_empty() @ VaultError::OrderIdConsumed
+    )]
+    pub consumed_order: UncheckedAccount<'info>,
+
+    /// Required when the vault charges a deposit fee
+    #[account(
+        mut,
+        address = vault_state.fee_collector @ VaultError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<Account<'info, TokenAccount>>,
+
+    /// Withdrawal wallet ATA; required when the vault is in passthrough mode
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub passthrough_destination: Option<Account<'info, TokenAccount>>,
+
+    /// Pays the record rent back when the vault sponsors rent
+    #[account(
+        mut,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump = rent_escrow.bump
+    )]
+    pub rent_escrow: Option<Account<'info, RentEscrow>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct DepositSolWrapped<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        constraint = vault_state.token_mint == token::spl_token::native_mint::ID @ VaultError::NotWrappedSolVault
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = DepositRecord::SPACE,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Mint-wide claim on `order_id`; required when the vault enforces
+    /// globally unique order IDs
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 1,
+        seeds = [b"order_index", vault_state.token_mint.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub order_index: Option<Account<'info, OrderIndex>>,
+
+    /// CHECK: Marker written when a record for this order ID was closed; must not exist
+    #[account(
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump,
+        constraint = consumed_order.data_is_empty() @ VaultError::OrderIdConsumed
+    )]
+    pub consumed_order: UncheckedAccount<'info>,
+
+    /// Required when the vault charges a deposit fee
+    #[account(
+        mut,
+        address = vault_state.fee_collector @ VaultError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<Account<'info, TokenAccount>>,
+
+    /// Withdrawal wallet ATA; required when the vault is in passthrough mode
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub passthrough_destination: Option<Account<'info, TokenAccount>>,
+
+    /// Pays the record rent back when the vault sponsors rent
+    #[account(
+        mut,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump = rent_escrow.bump
+    )]
+    pub rent_escrow: Option<Account<'info, RentEscrow>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct DepositRelayed<'info> {
+    /// Submits the transaction and pays for the deposit record
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Token owner who signed the authorization; checked against the ed25519 instruction
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = DepositRecord::SPACE,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), owner.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// Mint-wide claim on `order_id`; required when the vault enforces
+    /// globally unique order IDs
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + 32 + 32 + 1,
+        seeds = [b"order_index", vault_state.token_mint.as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub order_index: Option<Account<'info, OrderIndex>>,
+
+    /// CHECK: Marker written when a record for this order ID was closed; must not exist
+    #[account(
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), owner.key().as_ref(), order_id.as_bytes()],
+        bump,
+        constraint = consumed_order.data_is_empty() @ VaultError::OrderIdConsumed
+    )]
+    pub consumed_order: UncheckedAccount<'info>,
+
+    /// Required when the vault charges a deposit fee
+    #[account(
+        mut,
+        address = vault_state.fee_collector @ VaultError::InvalidFeeCollector
+    )]
+    pub fee_collector: Option<Account<'info, TokenAccount>>,
+
+    /// Withdrawal wallet ATA; required when the vault is in passthrough mode
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub passthrough_destination: Option<Account<'info, TokenAccount>>,
+
+    /// Pays the record rent back when the vault sponsors rent
+    #[account(
+        mut,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump = rent_escrow.bump
+    )]
+    pub rent_escrow: Option<Account<'info, RentEscrow>>,
+
+    /// Per-owner counter bound into each relayed authorization
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + 32 + 8 + 1,
+        seeds = [b"relay_nonce", vault_state.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub relay_nonce: Account<'info, RelayNonce>,
+
+    /// CHECK: Instructions sysvar, read to find the ed25519 check
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddWhitelistedWallet<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority,
+        has_one = token_mint,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 4 + MAX_WHITELIST_WALLETS * 32 + 1,
+        seeds = [b"wallet_whitelist", vault_state.key().as_ref()],
+        bump
+    )]
+    pub wallet_whitelist: Account<'info, WalletWhitelist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Validated in instruction logic
+    pub wallet: UncheckedAccount<'info>,
+
+    /// CHECK: May or may not exist; validated/created in instruction
+    #[account(mut)]
+    pub associated_token: UncheckedAccount<'info>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// Required when multisig is enabled
+    #[account(
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveWhitelistedWallet<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"wallet_whitelist", vault_state.key().as_ref()],
+        bump = wallet_whitelist.bump
+    )]
+    pub wallet_whitelist: Account<'info, WalletWhitelist>,
+
+    /// Required when multisig is enabled
+    #[account(
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct CheckDeposit<'info> {
+    #[account(
+        seeds = [b"deposit_record", token_mint.key().as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct LookupOrder<'info> {
+    #[account(
+        seeds = [b"order_index", token_mint.key().as_ref(), order_id.as_bytes()],
+        bump = order_index.bump
+    )]
+    pub order_index: Account<'info, OrderIndex>,
+
+    #[account(address = order_index.deposit_record)]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        constraint = authority.key() == vault_state.authority
+            || (vault_state.operator != Pubkey::default()
+                && authority.key() == vault_state.operator) @ VaultError::Unauthorized,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// ATA of the configured wallet or of the selected whitelisted wallet;
+    /// checked against the canonical derivation in the handler
+    #[account(
+        mut,
+        token::mint = vault_state.token_mint
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// Required when multisig is enabled
+    #[account(
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
+
+    /// Required when withdrawing to a whitelisted wallet
+    #[account(
+        seeds = [b"wallet_whitelist", vault_state.key().as_ref()],
+        bump = wallet_whitelist.bump
+    )]
+    pub wallet_whitelist: Option<Account<'info, WalletWhitelist>>,
+
+    /// Vault authority or operator
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.wallet_account
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetYieldPool<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + 2 + 1,
+        seeds = [b"yield_config", vault_state.key().as_ref()],
+        bump
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    /// stake_program pool for the vault's mint
+    #[account(
+        constraint = pool.token_mint == vault_state.token_mint @ VaultError::InvalidYieldPool
+    )]
+    pub pool: Account<'info, stake_program::Pool>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct StakeIdle<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority,
+        has_one = token_mint,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"yield_config", vault_state.key().as_ref()],
+        bump = yield_config.bump,
+        has_one = pool @ VaultError::InvalidYieldPool
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Yield pool; bound to `yield_config` and validated by stake_program
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA's stake position; validated by stake_program
+    #[account(mut)]
+    pub vault_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Pool token vault; validated by stake_program
+    #[account(mut)]
+    pub pool_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool leaderboard, if one exists; validated by stake_program
+    #[account(mut)]
+    pub leaderboard: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: stake_program event authority PDA; validated by stake_program
+    pub stake_event_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+    pub stake_program: Program<'info, stake_program::program::StakeProgram>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeIdle<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority,
+        has_one = token_mint
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        seeds = [b"yield_config", vault_state.key().as_ref()],
+        bump = yield_config.bump,
+        has_one = pool @ VaultError::InvalidYieldPool
+    )]
+    pub yield_config: Account<'info, YieldConfig>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Yield pool; bound to `yield_config` and validated by stake_program
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA's stake position; validated by stake_program
+    #[account(mut)]
+    pub vault_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Vault PDA's token account for the pool's reward mint; validated by stake_program
+    #[account(mut)]
+    pub vault_reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: Pool reward mint; validated by stake_program
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Pool token vault; validated by stake_program
+    #[account(mut)]
+    pub pool_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool reward vault; validated by stake_program
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pool leaderboard, if one exists; validated by stake_program
+    #[account(mut)]
+    pub leaderboard: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: stake_program event authority PDA; validated by stake_program
+    pub stake_event_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+    pub stake_program: Program<'info, stake_program::program::StakeProgram>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct RefundDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Public key used for PDA derivation and refund destination
+    pub depositor: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = depositor
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct MarkDepositProcessed<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundRentEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1,
+        seeds = [b"rent_escrow", vault_state.key().as_ref()],
+        bump
+    )]
+    pub rent_escrow: Account<'info, RentEscrow>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct CloseDepositRecord<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump,
+        close = rent_recipient
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the record rent; must be the account that paid it
+    #[account(
+        mut,
+        address = deposit_record.rent_payer @ VaultError::InvalidRentRecipient
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    /// Keeps the order ID from being deposited again once the record is gone
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 1,
+        seeds = [b"consumed_order", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub consumed_order: Account<'info, ConsumedOrder>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct VerifyDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: String)]
+pub struct ReleaseDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
 
-        Ok(())
-    }
+    pub caller: Signer<'info>,
 }
 
-// ============================================================================
-// Account Structs
-// ============================================================================
-
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+#[instruction(order_id: String)]
+pub struct ExpireDeposit<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + 32 + 32 + 32,
-        seeds = [b"vault_state", token_mint.key().as_ref()],
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
         bump
     )]
     pub vault_state: Account<'info, VaultState>,
 
-    /// Use init_if_needed to allow reinitialization of vault ATA
-    /// This is safe because:
-    /// 1. The ATA is deterministically derived from vault_state and token_mint
-    /// 2. The authority is set to vault_state PDA (not a user-controlled address)
-    /// 3. Reinitializing an existing ATA has no negative security impact
     #[account(
-        init_if_needed,
-        payer = authority,
-        associated_token::mint = token_mint,
-        associated_token::authority = vault_state
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        bump
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub deposit_record: Account<'info, DepositRecord>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+    /// CHECK: Public key used for PDA derivation
+    pub depositor: UncheckedAccount<'info>,
 
-    pub token_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Check<'info> {
+#[instruction(order_id: String)]
+pub struct ClaimRefund<'info> {
     #[account(
+        mut,
         seeds = [b"vault_state", vault_state.token_mint.as_ref()],
         bump
     )]
     pub vault_state: Account<'info, VaultState>,
 
     #[account(
+        mut,
         associated_token::mint = vault_state.token_mint,
         associated_token::authority = vault_state
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
 
-    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        mut,
+        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
+        bump
+    )]
+    pub deposit_record: Account<'info, DepositRecord>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = user
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct SetWithdrawalAccount<'info> {
+pub struct SetDepositFee<'info> {
     #[account(
         mut,
         seeds = [b"vault_state", vault_state.token_mint.as_ref()],
         bump,
-        has_one = authority,
-        has_one = token_mint
+        has_one = authority
     )]
     pub vault_state: Account<'info, VaultState>,
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    /// CHECK: Validated in instruction logic
-    pub new_wallet: UncheckedAccount<'info>,
+    #[account(
+        constraint = fee_collector.mint == vault_state.token_mint @ VaultError::MintMismatch
+    )]
+    pub fee_collector: Account<'info, TokenAccount>,
 
-    /// CHECK: May or may not exist; validated/created in instruction
-    #[account(mut)]
-    pub associated_token: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
 
-    pub token_mint: Account<'info, Mint>,
+#[derive(Accounts)]
+pub struct UpdateVaultConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, associated_token::AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(order_id: String)]
-pub struct Deposit<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
+pub struct SetFrozen<'info> {
     #[account(
         mut,
-        associated_token::mint = vault_state.token_mint,
-        associated_token::authority = user
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        constraint = vault_state.guardian != Pubkey::default()
+            && vault_state.guardian == guardian.key() @ VaultError::Unauthorized
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub vault_state: Account<'info, VaultState>,
+
+    pub guardian: Signer<'info>,
+}
 
+#[derive(Accounts)]
+pub struct EmergencyWithdrawToCold<'info> {
     #[account(
         mut,
         seeds = [b"vault_state", vault_state.token_mint.as_ref()],
-        bump
+        bump,
+        constraint = vault_state.guardian != Pubkey::default()
+            && vault_state.guardian == guardian.key() @ VaultError::Unauthorized,
+        constraint = vault_state.cold_wallet != Pubkey::default() @ VaultError::ColdWalletNotSet
     )]
     pub vault_state: Account<'info, VaultState>,
 
@@ -463,54 +3908,49 @@ pub struct Deposit<'info> {
     pub vault_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        init,
-        payer = user,
-        space = 8 + 4 + MAX_ORDER_ID_LEN + 32 + 32 + 8 + 8,
-        seeds = [b"deposit_record", vault_state.token_mint.as_ref(), user.key().as_ref(), order_id.as_bytes()],
-        bump
+        mut,
+        associated_token::mint = vault_state.token_mint,
+        associated_token::authority = vault_state.cold_wallet
     )]
-    pub deposit_record: Account<'info, DepositRecord>,
+    pub cold_wallet_token_account: Account<'info, TokenAccount>,
 
+    pub guardian: Signer<'info>,
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateAuthority<'info> {
+pub struct SetPayoutTable<'info> {
     #[account(
         mut,
         seeds = [b"vault_state", vault_state.token_mint.as_ref()],
         bump,
-        has_one = authority
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
     )]
     pub vault_state: Account<'info, VaultState>,
 
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(order_id: String)]
-pub struct CheckDeposit<'info> {
     #[account(
-        seeds = [b"deposit_record", token_mint.key().as_ref(), depositor.key().as_ref(), order_id.as_bytes()],
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 4 + MAX_PAYOUT_ENTRIES * (32 + 2) + 1,
+        seeds = [b"payout_table", vault_state.key().as_ref()],
         bump
     )]
-    pub deposit_record: Account<'info, DepositRecord>,
-
-    pub token_mint: Account<'info, Mint>,
+    pub payout_table: Account<'info, PayoutTable>,
 
-    /// CHECK: Public key used for PDA derivation
-    pub depositor: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
+pub struct WithdrawSplit<'info> {
     #[account(
         mut,
         seeds = [b"vault_state", vault_state.token_mint.as_ref()],
         bump,
-        has_one = authority
+        has_one = authority,
+        constraint = !vault_state.frozen @ VaultError::VaultFrozen
     )]
     pub vault_state: Account<'info, VaultState>,
 
@@ -522,16 +3962,46 @@ pub struct Withdraw<'info> {
     pub vault_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        mut,
-        associated_token::mint = vault_state.token_mint,
-        associated_token::authority = vault_state.wallet_account
+        seeds = [b"payout_table", vault_state.key().as_ref()],
+        bump = payout_table.bump
     )]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub payout_table: Account<'info, PayoutTable>,
+
+    /// Required when multisig is enabled
+    #[account(
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump = signer_set.bump
+    )]
+    pub signer_set: Option<Account<'info, SignerSet>>,
 
     pub authority: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct ManageSignerSet<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault_state", vault_state.token_mint.as_ref()],
+        bump,
+        has_one = authority
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 4 + MAX_SIGNERS * 32 + 1 + 1,
+        seeds = [b"signer_set", vault_state.key().as_ref()],
+        bump
+    )]
+    pub signer_set: Account<'info, SignerSet>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // CloseVault struct removed
 // See comment in program module for rationale.
 
@@ -547,6 +4017,275 @@ pub struct VaultState {
     pub authority: Pubkey,
     pub token_mint: Pubkey,
     pub wallet_account: Pubkey,
+    /// Seconds after which an unprocessed deposit can be self-refunded (0 = disabled)
+    pub refund_after_seconds: u64,
+    /// Smallest accepted deposit amount
+    pub min_deposit: u64,
+    /// Largest accepted deposit amount (0 = no limit)
+    pub max_deposit: u64,
+    /// Deposit fee in basis points (0 = no fee)
+    pub fee_bps: u16,
+    /// Token account receiving deposit fees
+    pub fee_collector: Pubkey,
+    /// Whether vault operations require signer set approval
+    pub multisig_enabled: bool,
+    /// Whether anyone may sweep the balance above `sweep_threshold`
+    pub sweep_enabled: bool,
+    /// Float left in the vault after a sweep
+    pub sweep_threshold: u64,
+    /// Whether deposits are forwarded to the withdrawal wallet instead of held
+    pub passthrough: bool,
+    /// Sequence number of the last event emitted for this vault
+    pub event_seq: u64,
+    /// Key allowed to withdraw to the configured wallet only (default = none)
+    pub operator: Pubkey,
+    /// Key allowed to freeze and unfreeze the vault (default = none)
+    pub guardian: Pubkey,
+    /// Whether withdrawals and wallet changes are blocked
+    pub frozen: bool,
+    /// Seconds after which an unprocessed deposit expires (0 = never)
+    pub deposit_ttl_seconds: u64,
+    /// Layout version; see `VAULT_STATE_VERSION`
+    pub version: u8,
+    /// Whether every deposit must claim its order ID in the mint-wide order index
+    pub require_global_order_id: bool,
+    /// Allowed deviation from the invoice amount in `verify_deposit`, in basis points
+    pub verify_tolerance_bps: u16,
+    /// Seconds a deposit stays pending before it can be withdrawn (0 = immediately)
+    pub settlement_delay_seconds: u64,
+    /// Deposited amount still inside the settlement delay; excluded from withdrawals
+    pub pending_balance: u64,
+    /// Whether the rent escrow pays deposit record rent
+    pub rent_sponsorship: bool,
+    /// Principal currently staked in the yield pool; not part of the token balance
+    pub staked_balance: u64,
+    /// Deposits recorded over the vault's lifetime
+    pub deposit_count: u64,
+    /// Fixed emergency destination, set at initialize (default = none)
+    pub cold_wallet: Pubkey,
+    pub reserved: [u8; VAULT_STATE_RESERVED_LEN],
+}
+
+impl VaultState {
+    /// Part of `balance` not held back by the settlement delay.
+    pub fn withdrawable(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.pending_balance)
+    }
+
+    /// Part of `balance` that may fund a refund of `record`: the settled
+    /// balance, plus the record's own amount while it is still held as pending.
+    /// Other depositors' pending funds are never touched.
+    pub fn refundable(&self, balance: u64, record: &DepositRecord) -> u64 {
+        let own_pending = if record.pending { record.amount } else { 0 };
+        self.withdrawable(balance).saturating_add(own_pending).min(balance)
+    }
+
+    /// Drop a pending deposit's amount from `pending_balance`, e.g. once it
+    /// settles or is refunded. No-op for deposits that are not pending.
+    pub fn release_pending(&mut self, record: &mut DepositRecord) {
+        if record.pending {
+            self.pending_balance = self.pending_balance.saturating_sub(record.amount);
+            record.pending = false;
+        }
+    }
+
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 32 + 1 + 1 + 8 + 1 + 8 + 32 + 32 + 1
+        + 8
+        + 1
+        + 1
+        + 2
+        + 8
+        + 8
+        + 1
+        + 8
+        + 8
+        + 32
+        + VAULT_STATE_RESERVED_LEN;
+
+    /// Advance and return the vault's event sequence number. Every event carries
+    /// it so off-chain consumers can detect gaps in delivery.
+    pub fn next_event_seq(&mut self) -> Result<u64> {
+        self.event_seq = self
+            .event_seq
+            .checked_add(1)
+            .ok_or(VaultError::MathOverflow)?;
+        Ok(self.event_seq)
+    }
+
+    /// Require `threshold` distinct signer set members among `co_signers`
+    /// when multisig is enabled. No-op otherwise.
+    pub fn verify_approvals(
+        &self,
+        signer_set: Option<&SignerSet>,
+        co_signers: &[AccountInfo],
+    ) -> Result<()> {
+        if !self.multisig_enabled {
+            return Ok(());
+        }
+        let signer_set = signer_set.ok_or(VaultError::SignerSetRequired)?;
+
+        let mut approvals: Vec<Pubkey> = Vec::with_capacity(signer_set.signers.len());
+        for account in co_signers {
+            if account.is_signer
+                && signer_set.signers.contains(account.key)
+                && !approvals.contains(account.key)
+            {
+                approvals.push(*account.key);
+            }
+        }
+
+        require!(
+            approvals.len() >= signer_set.threshold as usize,
+            VaultError::InsufficientSigners
+        );
+        Ok(())
+    }
+}
+
+/// Snapshot returned by `check`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VaultStatus {
+    pub token_mint: Pubkey,
+    /// Token balance of the vault ATA
+    pub balance: u64,
+    pub wallet_account: Pubkey,
+    pub authority: Pubkey,
+    /// SOL balance of the vault ATA
+    pub lamports: u64,
+    pub min_rent: u64,
+    pub is_rent_exempt: bool,
+    pub multisig_enabled: bool,
+    pub passthrough: bool,
+    pub operator: Pubkey,
+    pub guardian: Pubkey,
+    pub frozen: bool,
+    /// Deposits still inside the settlement delay
+    pub pending_balance: u64,
+    /// Part of `balance` available to withdraw
+    pub settled_balance: u64,
+    /// Principal staked in the yield pool, on top of `balance`
+    pub staked_balance: u64,
+    pub cold_wallet: Pubkey,
+}
+
+/// Every vault initialized by this deployment, in creation order.
+#[account]
+pub struct VaultRegistry {
+    pub bump: u8,
+    pub vaults: Vec<RegisteredVault>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RegisteredVault {
+    pub token_mint: Pubkey,
+    pub vault_state: Pubkey,
+}
+
+impl VaultRegistry {
+    pub const fn space(len: usize) -> usize {
+        8 + 1 + 4 + len * (32 + 32)
+    }
+
+    /// Append a vault, growing the account by one entry with the payer covering
+    /// the extra rent. Returns the entry's index.
+    pub fn append<'info>(
+        registry: &mut Account<'info, VaultRegistry>,
+        entry: RegisteredVault,
+        payer: &Signer<'info>,
+        system_program: &Program<'info, System>,
+    ) -> Result<u32> {
+        require!(
+            !registry
+                .vaults
+                .iter()
+                .any(|v| v.token_mint == entry.token_mint),
+            VaultError::VaultAlreadyRegistered
+        );
+
+        let info = registry.to_account_info();
+        let new_len = VaultRegistry::space(registry.vaults.len() + 1);
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(info.lamports());
+        if rent_due > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        info.resize(new_len)?;
+
+        let index = registry.vaults.len() as u32;
+        registry.vaults.push(entry);
+        Ok(index)
+    }
+}
+
+/// M-of-N co-signers required for sensitive vault operations.
+#[account]
+pub struct SignerSet {
+    pub vault_state: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+/// Additional withdrawal destinations selectable by index in `withdraw`.
+#[account]
+pub struct WalletWhitelist {
+    pub vault_state: Pubkey,
+    pub wallets: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// stake_program pool that takes the vault's idle balance (see `stake_idle`).
+#[account]
+pub struct YieldConfig {
+    pub vault_state: Pubkey,
+    pub pool: Pubkey,
+    /// Cap on the staked share of the vault's total balance, in basis points
+    pub max_stake_bps: u16,
+    pub bump: u8,
+}
+
+/// Lamport float that pays deposit record rent for sponsored vaults, topped
+/// up by the authority with `fund_rent_escrow`.
+#[account]
+pub struct RentEscrow {
+    pub vault_state: Pubkey,
+    pub bump: u8,
+}
+
+/// Next nonce a user must sign for `deposit_relayed`; incremented on every
+/// accepted authorization.
+#[account]
+pub struct RelayNonce {
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+/// Left behind by `close_deposit_record` so a closed order ID cannot be
+/// deposited again by the same depositor.
+#[account]
+pub struct ConsumedOrder {
+    pub bump: u8,
+}
+
+/// Claims an order ID across all depositors of a mint (see
+/// `VaultState::require_global_order_id`) and resolves it back to the deposit
+/// via `lookup_order`.
+#[account]
+pub struct OrderIndex {
+    pub deposit_record: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
 }
 
 #[account]
@@ -556,6 +4295,82 @@ pub struct DepositRecord {
     pub token_mint: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
+    pub status: DepositStatus,
+    /// Free-form merchant reference; empty when not provided
+    pub memo: String,
+    /// Time after which an unprocessed deposit can be reclaimed (None = never)
+    pub expires_at: Option<i64>,
+    /// Set by `verify_deposit` once the amount matched the invoice
+    pub verified: bool,
+    /// Whether the amount still counts towards the vault's `pending_balance`
+    pub pending: bool,
+    /// Account that bore the record rent and gets it back on close
+    pub rent_payer: Pubkey,
+}
+
+/// `DepositRecord` layout before status, memo, expiry, verification, pending
+/// tracking and rent payer were added; read by `migrate_deposit_record`.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyDepositRecord {
+    pub order_id: String,
+    pub user: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositStatus {
+    Active,
+    Refunded,
+    Processed,
+    Expired,
+}
+
+impl DepositRecord {
+    pub const SPACE: usize =
+        8 + 4 + MAX_ORDER_ID_LEN + 32 + 32 + 8 + 8 + 1 + 4 + MAX_MEMO_LEN + 1 + 8 + 1 + 1 + 32;
+
+    pub fn ensure_active(&self) -> Result<()> {
+        match self.status {
+            DepositStatus::Active => Ok(()),
+            DepositStatus::Refunded => err!(VaultError::DepositNotRefundable),
+            DepositStatus::Processed => err!(VaultError::DepositAlreadyProcessed),
+            DepositStatus::Expired => err!(VaultError::DepositExpired),
+        }
+    }
+
+    /// True once an unprocessed deposit has passed its `expires_at`, whether
+    /// or not `expire_deposit` has flagged it yet.
+    pub fn is_expired(&self, now: i64) -> bool {
+        match self.status {
+            DepositStatus::Expired => true,
+            DepositStatus::Active => self.expires_at.is_some_and(|expires_at| now >= expires_at),
+            _ => false,
+        }
+    }
+}
+
+/// Destination shares used by `withdraw_split`.
+#[account]
+pub struct PayoutTable {
+    pub vault_state: Pubkey,
+    pub entries: Vec<PayoutEntry>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PayoutEntry {
+    /// Wallet whose canonical ATA receives this share
+    pub wallet: Pubkey,
+    pub bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct PayoutSplit {
+    /// Destination ATA; must be the canonical ATA of the table entry's wallet
+    pub destination: Pubkey,
+    pub bps: u16,
 }
 
 // ============================================================================
@@ -589,6 +4404,112 @@ pub enum VaultError {
     InvalidDataLength,
     #[msg("Token account state is corrupted or invalid")]
     CorruptedTokenAccount,
+    #[msg("Withdrawal amount exceeds vault balance")]
+    InsufficientVaultBalance,
+    #[msg("Payout table must have 1-5 unique entries whose shares sum to 10000 bps")]
+    InvalidPayoutTable,
+    #[msg("Split destinations do not match the payout table")]
+    PayoutTableMismatch,
+    #[msg("Deposit has already been refunded")]
+    DepositNotRefundable,
+    #[msg("Deposit has already been processed")]
+    DepositAlreadyProcessed,
+    #[msg("Self-refunds are not enabled for this vault")]
+    RefundsDisabled,
+    #[msg("Refund window has not elapsed yet")]
+    RefundWindowNotElapsed,
+    #[msg("Invalid refund window")]
+    InvalidRefundWindow,
+    #[msg("Deposit amount is below the vault minimum")]
+    DepositBelowMinimum,
+    #[msg("Deposit amount is above the vault maximum")]
+    DepositAboveMaximum,
+    #[msg("Minimum deposit cannot exceed maximum deposit")]
+    InvalidDepositLimits,
+    #[msg("Deposit fee exceeds the maximum")]
+    InvalidFeeBps,
+    #[msg("Fee collector account is required when a deposit fee is set")]
+    FeeCollectorRequired,
+    #[msg("Fee collector does not match the vault configuration")]
+    InvalidFeeCollector,
+    #[msg("Signer set must have unique members and a threshold no larger than its size")]
+    InvalidSignerSet,
+    #[msg("Signer set account is required when multisig is enabled")]
+    SignerSetRequired,
+    #[msg("Not enough signer set members approved this operation")]
+    InsufficientSigners,
+    #[msg("Vault is already in the registry")]
+    VaultAlreadyRegistered,
+    #[msg("Order ID must be at most 32 printable ASCII characters")]
+    OrderIdInvalid,
+    #[msg("Memo exceeds the maximum length")]
+    MemoTooLong,
+    #[msg("Sweeping is not enabled for this vault")]
+    SweepDisabled,
+    #[msg("Vault balance does not exceed the sweep threshold")]
+    NothingToSweep,
+    #[msg("Withdrawal wallet token account is required in passthrough mode")]
+    PassthroughDestinationRequired,
+    #[msg("Wallet is already in the withdrawal whitelist")]
+    WalletAlreadyWhitelisted,
+    #[msg("Withdrawal whitelist is full")]
+    WhitelistFull,
+    #[msg("Wallet is not in the withdrawal whitelist")]
+    WalletNotWhitelisted,
+    #[msg("Vault is not approved as delegate for the deposit amount")]
+    InsufficientDelegation,
+    #[msg("Vault is frozen")]
+    VaultFrozen,
+    #[msg("Deposit has expired")]
+    DepositExpired,
+    #[msg("Deposit has not expired")]
+    DepositNotExpired,
+    #[msg("Deposit TTL is out of range")]
+    InvalidDepositTtl,
+    #[msg("Account is not a vault state for this program")]
+    InvalidVaultState,
+    #[msg("Vault state is already at the current version")]
+    VaultAlreadyMigrated,
+    #[msg("Order index account is required when order IDs must be globally unique")]
+    OrderIndexRequired,
+    #[msg("Verify tolerance cannot exceed 100%")]
+    InvalidVerifyTolerance,
+    #[msg("Settlement delay is out of range")]
+    InvalidSettlementDelay,
+    #[msg("Deposit is not pending settlement")]
+    DepositNotPending,
+    #[msg("Settlement delay has not elapsed yet")]
+    SettlementDelayNotElapsed,
+    #[msg("Settlement ID exceeds the maximum length")]
+    SettlementIdTooLong,
+    #[msg("Only processed or refunded deposit records can be closed")]
+    DepositRecordNotClosable,
+    #[msg("Rent recipient must be the account that paid the record rent")]
+    InvalidRentRecipient,
+    #[msg("Missing or invalid ed25519 authorization from the depositor")]
+    InvalidUserAuthorization,
+    #[msg("Deposit authorization has expired")]
+    AuthorizationExpired,
+    #[msg("Vault token is not wrapped SOL")]
+    NotWrappedSolVault,
+    #[msg("Stake share must be at most 10000 basis points")]
+    InvalidStakeBps,
+    #[msg("Yield pool does not match the vault's configuration or mint")]
+    InvalidYieldPool,
+    #[msg("Yield pool cannot change while funds are staked")]
+    YieldPoolInUse,
+    #[msg("Stake would exceed the configured share of the vault balance")]
+    StakeCapExceeded,
+    #[msg("Amount exceeds the vault's staked balance")]
+    InsufficientStakedBalance,
+    #[msg("Vault has no cold wallet configured")]
+    ColdWalletNotSet,
+    #[msg("Account is not a deposit record for this vault")]
+    InvalidDepositRecord,
+    #[msg("Deposit record is already at the current layout")]
+    DepositRecordAlreadyMigrated,
+    #[msg("Order ID was already used by a closed deposit record")]
+    OrderIdConsumed,
 }
 
 // ============================================================================
@@ -602,6 +4523,37 @@ pub struct VaultInitializedEvent {
     pub vault_token_account: Pubkey,
     pub authority: Pubkey,
     pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VaultMigratedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositRecordMigratedEvent {
+    pub vault_state: Pubkey,
+    pub deposit_record: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VaultRegisteredEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub index: u32,
+    pub timestamp: i64,
+    pub event_seq: u64,
 }
 
 #[event]
@@ -609,18 +4561,369 @@ pub struct DepositEvent {
     pub user: Pubkey,
     pub order_id: String,
     pub amount: u64,
+    pub fee: u64,
     pub token_mint: Pubkey,
     pub timestamp: i64,
+    pub memo: String,
+    /// Vault token balance right after this deposit
+    pub vault_balance_after: u64,
+    /// Number of deposits the vault has recorded, this one included
+    pub deposit_count: u64,
+    pub event_seq: u64,
 }
 
 #[event]
 pub struct WithdrawEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    /// Amount passed to `withdraw`; 0 means everything withdrawable was requested
+    pub requested_amount: u64,
+    pub amount: u64,
+    pub remaining_balance: u64,
+    pub destination_wallet: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    /// Backend settlement batch reference; empty when not provided
+    pub settlement_id: String,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct WithdrawSplitEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub destinations: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+    pub remaining_balance: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    /// Backend settlement batch reference; empty when not provided
+    pub settlement_id: String,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PayoutTableUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub entries: Vec<PayoutEntry>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SweepEvent {
     pub vault_state: Pubkey,
     pub token_mint: Pubkey,
     pub amount: u64,
+    pub remaining_balance: u64,
     pub destination_wallet: Pubkey,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SweepThresholdUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub sweep_enabled: bool,
+    pub sweep_threshold: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct PassthroughUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct YieldPoolUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub pool: Pubkey,
+    pub max_stake_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct IdleStakedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    /// Total principal staked after this stake
+    pub staked_balance: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct IdleUnstakedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    /// Principal still staked after this unstake
+    pub staked_balance: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RentSponsorshipUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub enabled: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RentEscrowFundedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    /// Escrow lamports after the top-up
+    pub balance: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositRecordClosedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub lamports: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct OperatorUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct GuardianUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VaultFrozenEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub frozen: bool,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct EmergencyWithdrawalEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub remaining_balance: u64,
+    pub cold_wallet: Pubkey,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositVerifiedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub expected_amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositAmountMismatchEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub expected_amount: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct VerifyToleranceUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_tolerance_bps: u16,
+    pub new_tolerance_bps: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositReleasedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub amount: u64,
+    /// Vault pending balance after the release
+    pub pending_balance: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SettlementDelayUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_settlement_delay_seconds: u64,
+    pub new_settlement_delay_seconds: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositExpiredEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct GlobalOrderIdUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub required: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositTtlUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_deposit_ttl_seconds: u64,
+    pub new_deposit_ttl_seconds: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositProcessedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub order_id: String,
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct RefundWindowUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_refund_after_seconds: u64,
+    pub new_refund_after_seconds: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositLimitsUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub min_deposit: u64,
+    pub max_deposit: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct DepositFeeUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+    pub fee_collector: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct SignerSetUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
     pub authority: Pubkey,
     pub timestamp: i64,
+    pub event_seq: u64,
 }
 
 #[event]
@@ -631,6 +4934,20 @@ pub struct WithdrawalWalletUpdatedEvent {
     pub wallet_ata: Pubkey,
     pub authority: Pubkey,
     pub timestamp: i64,
+    pub event_seq: u64,
+}
+
+#[event]
+pub struct WalletWhitelistUpdatedEvent {
+    pub vault_state: Pubkey,
+    pub token_mint: Pubkey,
+    pub wallet: Pubkey,
+    /// True when `wallet` was added, false when removed
+    pub added: bool,
+    pub wallets: Vec<Pubkey>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub event_seq: u64,
 }
 
 #[event]
@@ -640,6 +4957,7 @@ pub struct AuthorityUpdatedEvent {
     pub old_authority: Pubkey,
     pub new_authority: Pubkey,
     pub timestamp: i64,
+    pub event_seq: u64,
 }
 
 // VaultClosedEvent removed
@@ -58,7 +58,7 @@ describe("🔒 SPL Token Vault Program - Atomic Deployment Security", () => {
     if (!isInitialized) {
       // Initialize vault
       const tx = await program.methods
-        .initialize()
+        .initialize(null)
         .accounts({
           vaultState: vaultStatePda,
           vaultTokenAccount: vaultTokenAccount,
@@ -86,7 +86,7 @@ describe("🔒 SPL Token Vault Program - Atomic Deployment Security", () => {
     // CRITICAL TEST: Attempt to reinitialize should fail
     try {
       const tx = await program.methods
-        .initialize()
+        .initialize(null)
         .accounts({
           vaultState: vaultStatePda,
           vaultTokenAccount: vaultTokenAccount,
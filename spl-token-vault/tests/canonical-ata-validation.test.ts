@@ -62,7 +62,7 @@ describe("SPL Token Vault - Strict Canonical ATA Validation", () => {
       await provider.sendAndConfirm(tx, [mintKeypair]);
 
       await program.methods
-        .initialize()
+        .initialize(null)
         .accounts({
           vaultState: vaultStatePda,
           vaultTokenAccount: vaultTokenAccount,
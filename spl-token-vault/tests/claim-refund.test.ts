@@ -0,0 +1,230 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("⏳ SPL Token Vault Program - Depositor Self-Refunds", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const DEPOSIT_AMOUNT = 100_000_000;
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        user.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const deposit = (orderId: string) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(DEPOSIT_AMOUNT), null)
+      .accounts({
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const claimRefund = (orderId: string) =>
+    program.methods
+      .claimRefund(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        userTokenAccount: userTokenAccount,
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const setRefundWindow = (seconds: number) =>
+    program.methods
+      .setRefundAfterSeconds(new anchor.BN(seconds))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      DEPOSIT_AMOUNT * 3
+    );
+
+    await deposit("stuck-1");
+    await deposit("stuck-2");
+  });
+
+  it("1. ❌ Self-refunds are disabled by default", async () => {
+    try {
+      await claimRefund("stuck-1");
+      throw new Error("Unexpected refund while disabled");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected disabled error:", errMsg);
+      expect(errMsg).to.include("not enabled");
+    }
+  });
+
+  it("2. ❌ Deposits cannot be reclaimed before the window elapses", async () => {
+    await setRefundWindow(86_400);
+    try {
+      await claimRefund("stuck-1");
+      throw new Error("Unexpected refund inside the window");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected window error:", errMsg);
+      expect(errMsg).to.include("Refund window has not elapsed");
+    }
+  });
+
+  it("3. ✅ Depositor reclaims an unprocessed deposit after the window", async () => {
+    await setRefundWindow(1);
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const before = await getAccount(provider.connection, userTokenAccount);
+    await claimRefund("stuck-1");
+    const after = await getAccount(provider.connection, userTokenAccount);
+    expect(Number(after.amount) - Number(before.amount)).to.equal(DEPOSIT_AMOUNT);
+
+    const record = await program.account.depositRecord.fetch(recordPda("stuck-1"));
+    expect(record.status).to.deep.equal({ refunded: {} });
+    console.log("✅ Reclaimed:", DEPOSIT_AMOUNT);
+  });
+
+  it("4. ❌ Processed deposits cannot be reclaimed", async () => {
+    await program.methods
+      .markDepositProcessed("stuck-2")
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda("stuck-2"),
+        depositor: user.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    try {
+      await claimRefund("stuck-2");
+      throw new Error("Unexpected refund of processed deposit");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("already been processed");
+    }
+  });
+
+  it("5. ❌ Refunds cannot spend other deposits' pending funds", async () => {
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      DEPOSIT_AMOUNT * 2
+    );
+    await deposit("settled-1");
+
+    // Later deposits are held as pending
+    await program.methods
+      .setSettlementDelay(new anchor.BN(86_400))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+    await deposit("held-1");
+
+    // Sweep the settled balance so only held-1's pending funds remain
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    const withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+    await program.methods
+      .withdraw(new anchor.BN(0), null, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    try {
+      await claimRefund("settled-1");
+      throw new Error("Unexpected refund paid from pending funds");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("exceeds vault balance");
+    }
+
+    // The pending deposit can still reclaim its own funds
+    await claimRefund("held-1");
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+  });
+});
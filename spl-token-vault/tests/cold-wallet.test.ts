@@ -0,0 +1,105 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🧊 SPL Token Vault Program - Emergency Cold Wallet", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let coldWalletAta: anchor.web3.PublicKey;
+  const coldWallet = anchor.web3.Keypair.generate();
+  const guardian = anchor.web3.Keypair.generate();
+
+  const emergencyWithdraw = (signer: anchor.web3.Keypair) =>
+    program.methods
+      .emergencyWithdrawToCold(new anchor.BN(0))
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        coldWalletTokenAccount: coldWalletAta,
+        guardian: signer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(coldWallet.publicKey)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+    coldWalletAta = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        coldWallet.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      vaultTokenAccount,
+      authority.payer,
+      50_000_000
+    );
+
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .setFrozen(true)
+      .accounts({ vaultState: vaultStatePda, guardian: guardian.publicKey })
+      .signers([guardian])
+      .rpc();
+  });
+
+  it("1. ✅ Cold wallet is fixed at initialize", async () => {
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.coldWallet.toBase58()).to.equal(coldWallet.publicKey.toBase58());
+  });
+
+  it("2. ❌ Only the guardian can evacuate", async () => {
+    try {
+      await emergencyWithdraw(authority.payer);
+      throw new Error("Unexpected emergency withdrawal by non-guardian");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized access");
+    }
+  });
+
+  it("3. ✅ Guardian evacuates a frozen vault to the cold wallet", async () => {
+    await emergencyWithdraw(guardian);
+
+    const cold = await getAccount(provider.connection, coldWalletAta);
+    expect(Number(cold.amount)).to.equal(50_000_000);
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+    console.log("✅ Evacuated to cold wallet:", cold.amount.toString());
+  });
+});
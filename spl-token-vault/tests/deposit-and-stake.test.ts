@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+const STAKE_PROGRAM_ID = new anchor.web3.PublicKey(
+  "GtgbhnDFLdbh1kBu4htmBbZrB3c5C8MP8px8Yq5jbstX"
+);
+
+describe("🥩 SPL Token Vault Program - Deposit and Stake", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      10_000_000
+    );
+  });
+
+  it("1. ❌ A failed stake rolls back the deposit", async () => {
+    const orderId = "stake-" + Date.now();
+    const [depositRecord] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+    const [stakeEventAuthority] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      STAKE_PROGRAM_ID
+    );
+    const bogus = anchor.web3.Keypair.generate().publicKey;
+
+    try {
+      await program.methods
+        .depositAndStake(
+          orderId,
+          new anchor.BN(1_000_000),
+          null,
+          new anchor.BN(0),
+          new anchor.BN(1_000_000),
+          null
+        )
+        .accounts({
+          user: authority.publicKey,
+          userTokenAccount: userTokenAccount,
+          vaultState: vaultStatePda,
+          vaultTokenAccount: vaultTokenAccount,
+          depositRecord,
+          tokenMint: tokenMint,
+          pool: bogus,
+          userStake: bogus,
+          poolVault: bogus,
+          stakeEventAuthority,
+          stakeProgram: STAKE_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      throw new Error("Unexpected deposit_and_stake into a missing pool");
+    } catch (err: any) {
+      console.log("❌ Expected stake failure:", err.error?.errorMessage || err.message);
+      expect(err.message).to.not.include("Unexpected");
+    }
+
+    const record = await provider.connection.getAccountInfo(depositRecord);
+    expect(record).to.be.null;
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+  });
+});
@@ -0,0 +1,134 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  approve,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🤝 SPL Token Vault Program - Delegated Deposits", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let ownerTokenAccount: anchor.web3.PublicKey;
+  const owner = anchor.web3.Keypair.generate();
+  const relayer = anchor.web3.Keypair.generate();
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        owner.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const depositDelegated = (orderId: string, amount: number) =>
+    program.methods
+      .depositDelegated(orderId, new anchor.BN(amount))
+      .accounts({
+        relayer: relayer.publicKey,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([relayer, owner])
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const sig = await provider.connection.requestAirdrop(
+      relayer.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    ownerTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        owner.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      ownerTokenAccount,
+      authority.payer,
+      100_000_000
+    );
+  });
+
+  it("1. ❌ Deposits without an approval are rejected", async () => {
+    try {
+      await depositDelegated("noapprove-" + Date.now(), 10_000_000);
+      throw new Error("Unexpected delegated deposit without approval");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("not approved as delegate");
+    }
+  });
+
+  it("2. ✅ Relayer pulls an approved deposit and credits the owner", async () => {
+    await approve(
+      provider.connection,
+      authority.payer,
+      ownerTokenAccount,
+      vaultStatePda,
+      owner,
+      30_000_000
+    );
+
+    const orderId = "delegated-" + Date.now();
+    await depositDelegated(orderId, 30_000_000);
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(30_000_000);
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.user.toBase58()).to.equal(owner.publicKey.toBase58());
+    expect(record.amount.toNumber()).to.equal(30_000_000);
+    console.log("✅ Delegated deposit credited to:", record.user.toBase58());
+  });
+
+  it("3. ❌ Deposits beyond the remaining allowance are rejected", async () => {
+    try {
+      await depositDelegated("over-" + Date.now(), 1);
+      throw new Error("Unexpected delegated deposit beyond allowance");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected delegation error:", errMsg);
+      expect(errMsg).to.include("not approved as delegate");
+    }
+  });
+});
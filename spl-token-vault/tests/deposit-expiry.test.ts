@@ -0,0 +1,150 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("⌛ SPL Token Vault Program - Deposit Expiry", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const orderId = "ttl-" + Date.now();
+
+  const recordPda = (id: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(id),
+      ],
+      program.programId
+    )[0];
+
+  const expireDeposit = () =>
+    program.methods
+      .expireDeposit(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(orderId),
+        depositor: authority.publicKey,
+        caller: authority.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      10_000_000
+    );
+
+    await program.methods
+      .setDepositTtl(new anchor.BN(2))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(4_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ✅ Deposit records carry an expiry from the vault TTL", async () => {
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.expiresAt.toNumber()).to.equal(record.timestamp.toNumber() + 2);
+  });
+
+  it("2. ❌ Deposits cannot be expired early", async () => {
+    try {
+      await expireDeposit();
+      throw new Error("Unexpected early expiry");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Deposit has not expired");
+    }
+  });
+
+  it("3. ✅ Crank flags the expired deposit", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const tx = await expireDeposit();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositExpiredEvent"
+    );
+    expect(event.data.orderId).to.equal(orderId);
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.status).to.deep.equal({ expired: {} });
+  });
+
+  it("4. ✅ Depositor reclaims the expired deposit without a refund window", async () => {
+    await program.methods
+      .claimRefund(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        userTokenAccount: userTokenAccount,
+        user: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const user = await getAccount(provider.connection, userTokenAccount);
+    expect(Number(user.amount)).to.equal(10_000_000);
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.status).to.deep.equal({ refunded: {} });
+    console.log("✅ Expired deposit reclaimed:", record.orderId);
+  });
+});
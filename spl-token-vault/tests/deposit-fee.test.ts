@@ -0,0 +1,159 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("💸 SPL Token Vault Program - Deposit Fees", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let feeCollector: anchor.web3.PublicKey;
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const deposit = (orderId: string, amount: number, collector: anchor.web3.PublicKey | null) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(amount), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        feeCollector: collector,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      1_000_000_000
+    );
+
+    feeCollector = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        anchor.web3.Keypair.generate().publicKey
+      )
+    ).address;
+  });
+
+  it("1. ❌ Fee cannot exceed the maximum", async () => {
+    try {
+      await program.methods
+        .setDepositFee(1_001)
+        .accounts({
+          vaultState: vaultStatePda,
+          feeCollector: feeCollector,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected success with excessive fee");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Deposit fee exceeds the maximum");
+    }
+  });
+
+  it("2. ✅ Authority configures a 2.5% fee", async () => {
+    await program.methods
+      .setDepositFee(250)
+      .accounts({
+        vaultState: vaultStatePda,
+        feeCollector: feeCollector,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.feeBps).to.equal(250);
+    expect(vaultState.feeCollector.toString()).to.equal(feeCollector.toString());
+  });
+
+  it("3. ❌ Deposits must include the fee collector", async () => {
+    try {
+      await deposit("nofee-" + Date.now(), 100_000_000, null);
+      throw new Error("Unexpected deposit without collector");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected collector error:", errMsg);
+      expect(errMsg).to.include("Fee collector account is required");
+    }
+  });
+
+  it("4. ✅ Fee is routed to the collector and the net amount recorded", async () => {
+    const orderId = "fee-" + Date.now();
+    const tx = await deposit(orderId, 100_000_000, feeCollector);
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositEvent"
+    );
+    expect(event.data.fee.toNumber()).to.equal(2_500_000);
+    expect(event.data.amount.toNumber()).to.equal(97_500_000);
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.amount.toNumber()).to.equal(97_500_000);
+
+    const collector = await getAccount(provider.connection, feeCollector);
+    expect(Number(collector.amount)).to.equal(2_500_000);
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(97_500_000);
+    console.log("✅ Fee collected:", collector.amount.toString());
+  });
+});
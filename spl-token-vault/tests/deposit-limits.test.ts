@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("📏 SPL Token Vault Program - Deposit Limits", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+
+  const deposit = (orderId: string, amount: number) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(amount), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      1_000_000_000
+    );
+  });
+
+  it("1. ❌ Minimum cannot exceed maximum", async () => {
+    try {
+      await program.methods
+        .setDepositLimits(new anchor.BN(500), new anchor.BN(100))
+        .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+        .rpc();
+      throw new Error("Unexpected success with inverted limits");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("cannot exceed maximum");
+    }
+  });
+
+  it("2. ✅ Authority sets deposit limits", async () => {
+    await program.methods
+      .setDepositLimits(new anchor.BN(1_000), new anchor.BN(1_000_000))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.minDeposit.toNumber()).to.equal(1_000);
+    expect(vaultState.maxDeposit.toNumber()).to.equal(1_000_000);
+  });
+
+  it("3. ❌ Dust deposits are rejected", async () => {
+    try {
+      await deposit("dust-" + Date.now(), 999);
+      throw new Error("Unexpected dust deposit");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected minimum error:", errMsg);
+      expect(errMsg).to.include("below the vault minimum");
+    }
+  });
+
+  it("4. ❌ Oversized deposits are rejected", async () => {
+    try {
+      await deposit("fat-" + Date.now(), 1_000_001);
+      throw new Error("Unexpected oversized deposit");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected maximum error:", errMsg);
+      expect(errMsg).to.include("above the vault maximum");
+    }
+  });
+
+  it("5. ✅ Deposits within the limits succeed", async () => {
+    await deposit("ok-" + Date.now(), 1_000_000);
+    console.log("✅ Deposit at the maximum accepted");
+  });
+});
@@ -0,0 +1,109 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("📝 SPL Token Vault Program - Deposit Memo", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const deposit = (orderId: string, memo: string | null) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(1_000_000), memo)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      10_000_000
+    );
+  });
+
+  it("1. ✅ Memo is stored on the record and echoed in the event", async () => {
+    const orderId = "memo-" + Date.now();
+    const tx = await deposit(orderId, "INV-2024-0042");
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "depositEvent"
+    );
+    expect(event.data.memo).to.equal("INV-2024-0042");
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.memo).to.equal("INV-2024-0042");
+    console.log("✅ Memo recorded:", record.memo);
+  });
+
+  it("2. ❌ Memos over the limit are rejected", async () => {
+    try {
+      await deposit("longmemo-" + Date.now(), "x".repeat(65));
+      throw new Error("Unexpected deposit with oversized memo");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected memo error:", errMsg);
+      expect(errMsg).to.include("Memo exceeds the maximum length");
+    }
+  });
+});
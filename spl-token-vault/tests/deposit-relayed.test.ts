@@ -0,0 +1,202 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  approve,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("✍️ SPL Token Vault Program - Relayed Deposits", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let ownerTokenAccount: anchor.web3.PublicKey;
+  const owner = anchor.web3.Keypair.generate();
+  const relayer = anchor.web3.Keypair.generate();
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        owner.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const relayNoncePda = () =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("relay_nonce"), vaultStatePda.toBuffer(), owner.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const currentNonce = async () => {
+    const account = await program.account.relayNonce.fetchNullable(relayNoncePda());
+    return account ? account.nonce : new anchor.BN(0);
+  };
+
+  const authorization = (
+    signer: anchor.web3.Keypair,
+    orderId: string,
+    amount: anchor.BN,
+    expiry: anchor.BN,
+    nonce: anchor.BN
+  ) =>
+    anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message: Buffer.concat([
+        vaultStatePda.toBuffer(),
+        Buffer.from(orderId),
+        amount.toArrayLike(Buffer, "le", 8),
+        expiry.toArrayLike(Buffer, "le", 8),
+        nonce.toArrayLike(Buffer, "le", 8),
+      ]),
+    });
+
+  const depositRelayed = async (
+    orderId: string,
+    amount: anchor.BN,
+    expiry: anchor.BN,
+    signer: anchor.web3.Keypair = owner,
+    nonce?: anchor.BN
+  ) =>
+    program.methods
+      .depositRelayed(orderId, amount, expiry)
+      .accounts({
+        relayer: relayer.publicKey,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        relayNonce: relayNoncePda(),
+        instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([
+        authorization(signer, orderId, amount, expiry, nonce ?? (await currentNonce())),
+      ])
+      .signers([relayer])
+      .rpc();
+
+  const inOneHour = () => new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const sig = await provider.connection.requestAirdrop(
+      relayer.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    ownerTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        owner.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      ownerTokenAccount,
+      authority.payer,
+      100_000_000
+    );
+    await approve(
+      provider.connection,
+      authority.payer,
+      ownerTokenAccount,
+      vaultStatePda,
+      owner,
+      100_000_000
+    );
+  });
+
+  it("1. ❌ Authorizations signed by someone else are rejected", async () => {
+    try {
+      await depositRelayed(
+        "forged-" + Date.now(),
+        new anchor.BN(10_000_000),
+        inOneHour(),
+        anchor.web3.Keypair.generate()
+      );
+      throw new Error("Unexpected relayed deposit with forged authorization");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("invalid ed25519 authorization");
+    }
+  });
+
+  it("2. ❌ Expired authorizations are rejected", async () => {
+    try {
+      await depositRelayed(
+        "stale-" + Date.now(),
+        new anchor.BN(10_000_000),
+        new anchor.BN(Math.floor(Date.now() / 1000) - 60)
+      );
+      throw new Error("Unexpected relayed deposit with expired authorization");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("authorization has expired");
+    }
+  });
+
+  it("3. ✅ Relayer submits a signed deposit on the owner's behalf", async () => {
+    const orderId = "relayed-" + Date.now();
+    await depositRelayed(orderId, new anchor.BN(25_000_000), inOneHour());
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(25_000_000);
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.user.toBase58()).to.equal(owner.publicKey.toBase58());
+    expect(record.rentPayer.toBase58()).to.equal(relayer.publicKey.toBase58());
+    console.log("✅ Relayed deposit credited to:", record.user.toBase58());
+  });
+
+  it("4. ❌ Authorizations signed for a used nonce are rejected", async () => {
+    const used = (await currentNonce()).subn(1);
+    expect(used.toNumber()).to.equal(0);
+
+    try {
+      await depositRelayed(
+        "replay-" + Date.now(),
+        new anchor.BN(10_000_000),
+        inOneHour(),
+        owner,
+        used
+      );
+      throw new Error("Unexpected relayed deposit with a used nonce");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("invalid ed25519 authorization");
+    }
+  });
+});
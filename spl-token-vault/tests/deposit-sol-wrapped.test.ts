@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { NATIVE_MINT, TOKEN_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("💧 SPL Token Vault Program - Wrapped SOL Deposits", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  const depositSolWrapped = (
+    vaultStatePda: anchor.web3.PublicKey,
+    vaultTokenAccount: anchor.web3.PublicKey,
+    mint: anchor.web3.PublicKey,
+    orderId: string,
+    lamports: number
+  ) =>
+    program.methods
+      .depositSolWrapped(orderId, new anchor.BN(lamports))
+      .accounts({
+        user: authority.publicKey,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            mint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  it("1. ✅ SOL is wrapped into the vault's wSOL account", async () => {
+    const [vaultStatePda] = deriveVaultStatePda(NATIVE_MINT, program.programId);
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: NATIVE_MINT,
+      })
+      .rpc();
+    const vaultTokenAccount = await getVaultTokenAccount(NATIVE_MINT, vaultStatePda);
+
+    const orderId = "sol-" + Date.now();
+    await depositSolWrapped(
+      vaultStatePda,
+      vaultTokenAccount,
+      NATIVE_MINT,
+      orderId,
+      anchor.web3.LAMPORTS_PER_SOL / 4
+    );
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(anchor.web3.LAMPORTS_PER_SOL / 4);
+    console.log("✅ Wrapped SOL held by vault:", vault.amount.toString());
+  });
+
+  it("2. ❌ Non-wSOL vaults reject SOL deposits", async () => {
+    const tokenMint = await createTestTokenMint(provider, authority);
+    const [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+    const vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    try {
+      await depositSolWrapped(
+        vaultStatePda,
+        vaultTokenAccount,
+        tokenMint,
+        "sol-" + Date.now(),
+        1_000_000
+      );
+      throw new Error("Unexpected SOL deposit into a non-wSOL vault");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("not wrapped SOL");
+    }
+  });
+});
@@ -0,0 +1,121 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("🔢 SPL Token Vault Program - Event Sequence", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+
+  const eventsOf = async (tx: string) => {
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return getEventsFromTransaction(program, txDetails);
+  };
+
+  const deposit = (orderId: string) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(1_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      10_000_000
+    );
+  });
+
+  it("1. ✅ Initialization events start the sequence at 1", async () => {
+    const tx = await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const seqs = (await eventsOf(tx)).map((e) => e.data.eventSeq.toNumber());
+    expect(seqs).to.deep.equal([1, 2]);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.eventSeq.toNumber()).to.equal(2);
+  });
+
+  it("2. ✅ Each subsequent event advances the sequence by one", async () => {
+    let expected = 3;
+    for (const orderId of ["seq-a-" + Date.now(), "seq-b-" + Date.now()]) {
+      const events = await eventsOf(await deposit(orderId));
+      const event = events.find((e) => e.name === "depositEvent");
+      expect(event.data.eventSeq.toNumber()).to.equal(expected++);
+    }
+
+    const tx = await program.methods
+      .setDepositLimits(new anchor.BN(0), new anchor.BN(0))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+    const [limitsEvent] = await eventsOf(tx);
+    expect(limitsEvent.data.eventSeq.toNumber()).to.equal(expected);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.eventSeq.toNumber()).to.equal(expected);
+    console.log("✅ Vault event sequence at:", vaultState.eventSeq.toString());
+  });
+
+  it("3. ✅ Deposit events carry the running balance and deposit count", async () => {
+    const events = await eventsOf(await deposit("seq-c-" + Date.now()));
+    const event = events.find((e) => e.name === "depositEvent");
+    expect(event.data.depositCount.toNumber()).to.equal(3);
+    expect(event.data.vaultBalanceAfter.toNumber()).to.equal(3_000_000);
+    console.log("✅ Vault balance after deposit:", event.data.vaultBalanceAfter.toString());
+  });
+});
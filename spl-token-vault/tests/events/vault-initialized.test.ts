@@ -31,7 +31,7 @@ describe("🎉 SPL Token Vault Program - VaultInitializedEvent", () => {
 
   it("✅ VaultInitializedEvent emitted on vault initialization", async () => {
     const tx = await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
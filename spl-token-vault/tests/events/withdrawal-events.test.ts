@@ -30,7 +30,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Events", () => {
 
     // Initialize vault
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
@@ -123,7 +123,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Events", () => {
 
     // Deposit tokens
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccountInfo.address,
@@ -141,7 +141,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Events", () => {
     });
 
     const tx = await program.methods
-      .withdraw()
+      .withdraw(new anchor.BN(0), null, "batch-2024-01")
       .accounts({
         vaultState: vaultStatePda,
         vaultTokenAccount: vaultTokenAccount,
@@ -164,6 +164,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Events", () => {
     expect(event).to.not.be.undefined;
     expect(event.data.vaultState.toString()).to.equal(vaultStatePda.toString());
     expect(event.data.tokenMint.toString()).to.equal(tokenMint.toString());
+    expect(event.data.settlementId).to.equal("batch-2024-01");
     console.log("✅ WithdrawEvent emitted with correct data");
   });
 
@@ -31,7 +31,7 @@ describe("🔄 SPL Token Vault - Fee-on-Transfer Token Support", () => {
 
     // Initialize vault
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
@@ -85,7 +85,7 @@ describe("🔄 SPL Token Vault - Fee-on-Transfer Token Support", () => {
 
     // Make deposit
     const tx = await program.methods
-      .deposit(orderId, requestedAmount)
+      .deposit(orderId, requestedAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -149,7 +149,7 @@ describe("🔄 SPL Token Vault - Fee-on-Transfer Token Support", () => {
 
     // Make deposit
     const tx = await program.methods
-      .deposit(orderId, requestedAmount)
+      .deposit(orderId, requestedAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -212,7 +212,7 @@ describe("🔄 SPL Token Vault - Fee-on-Transfer Token Support", () => {
 
     // Make deposit
     await program.methods
-      .deposit(orderId, requestedAmount)
+      .deposit(orderId, requestedAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -288,7 +288,7 @@ describe("🔄 SPL Token Vault - Fee-on-Transfer Token Support", () => {
       );
 
       await program.methods
-        .deposit(orderId, requestedAmount)
+        .deposit(orderId, requestedAmount, null)
         .accounts({
           user: authority.publicKey,
           userTokenAccount: userTokenAccount,
@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🌐 SPL Token Vault Program - Global Order IDs", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  const alice = anchor.web3.Keypair.generate();
+  const bob = anchor.web3.Keypair.generate();
+  const tokenAccounts = new Map<string, anchor.web3.PublicKey>();
+
+  const orderIndexPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("order_index"), tokenMint.toBuffer(), Buffer.from(orderId)],
+      program.programId
+    )[0];
+
+  const deposit = (user: anchor.web3.Keypair, orderId: string, withIndex: boolean) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(1_000_000), null)
+      .accounts({
+        user: user.publicKey,
+        userTokenAccount: tokenAccounts.get(user.publicKey.toBase58()),
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            user.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        orderIndex: withIndex ? orderIndexPda(orderId) : null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    for (const user of [alice, bob]) {
+      const sig = await provider.connection.requestAirdrop(
+        user.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const account = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        user.publicKey
+      );
+      await mintTo(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        account.address,
+        authority.payer,
+        10_000_000
+      );
+      tokenAccounts.set(user.publicKey.toBase58(), account.address);
+    }
+
+    await program.methods
+      .setRequireGlobalOrderId(true)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("1. ❌ Deposits must claim the order index when required", async () => {
+    try {
+      await deposit(alice, "noindex-" + Date.now(), false);
+      throw new Error("Unexpected deposit without order index");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Order index account is required");
+    }
+  });
+
+  it("2. ✅ First depositor claims the order ID", async () => {
+    const orderId = "shared-order";
+    await deposit(alice, orderId, true);
+
+    const index = await program.account.orderIndex.fetch(orderIndexPda(orderId));
+    expect(index.user.toBase58()).to.equal(alice.publicKey.toBase58());
+  });
+
+  it("3. ❌ Another depositor cannot reuse the order ID", async () => {
+    try {
+      await deposit(bob, "shared-order", true);
+      throw new Error("Unexpected duplicate order ID across users");
+    } catch (err: any) {
+      console.log("❌ Expected duplicate order error:", err.message);
+      expect(err.message).to.match(/already in use|custom program error: 0x0/);
+    }
+  });
+
+  it("4. ✅ Bare order ID resolves to its deposit", async () => {
+    const record = await program.methods
+      .lookupOrder("shared-order")
+      .accounts({
+        orderIndex: orderIndexPda("shared-order"),
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            alice.publicKey.toBuffer(),
+            Buffer.from("shared-order"),
+          ],
+          program.programId
+        )[0],
+        tokenMint: tokenMint,
+      })
+      .view();
+    expect(record.user.toBase58()).to.equal(alice.publicKey.toBase58());
+    expect(record.amount.toNumber()).to.equal(1_000_000);
+    console.log("✅ Order resolved to depositor:", record.user.toBase58());
+  });
+});
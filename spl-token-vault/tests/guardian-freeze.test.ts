@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🧊 SPL Token Vault Program - Guardian Freeze", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+  const guardian = anchor.web3.Keypair.generate();
+
+  const setFrozen = (frozen: boolean, signer: anchor.web3.Keypair) =>
+    program.methods
+      .setFrozen(frozen)
+      .accounts({ vaultState: vaultStatePda, guardian: signer.publicKey })
+      .signers([signer])
+      .rpc();
+
+  const withdraw = () =>
+    program.methods
+      .withdraw(new anchor.BN(0), null, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      100_000_000
+    );
+
+    const sig = await provider.connection.requestAirdrop(
+      guardian.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+  });
+
+  it("1. ❌ Only the guardian can freeze", async () => {
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    try {
+      await setFrozen(true, authority.payer);
+      throw new Error("Unexpected freeze by authority");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized access");
+    }
+  });
+
+  it("2. ✅ Frozen vault still accepts deposits", async () => {
+    await setFrozen(true, guardian);
+
+    const orderId = "frozen-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(50_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.frozen).to.be.true;
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(50_000_000);
+  });
+
+  it("3. ❌ Frozen vault blocks withdrawals", async () => {
+    try {
+      await withdraw();
+      throw new Error("Unexpected withdrawal while frozen");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected freeze error:", errMsg);
+      expect(errMsg).to.include("Vault is frozen");
+    }
+  });
+
+  it("4. ❌ Frozen vault blocks wallet changes and guardian rotation", async () => {
+    const newWallet = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setWithdrawalAccount()
+        .accounts({
+          vaultState: vaultStatePda,
+          authority: authority.publicKey,
+          newWallet: newWallet.publicKey,
+          associatedToken: await anchor.utils.token.associatedAddress({
+            mint: tokenMint,
+            owner: newWallet.publicKey,
+          }),
+          tokenMint: tokenMint,
+        })
+        .rpc();
+      throw new Error("Unexpected wallet change while frozen");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Vault is frozen");
+    }
+
+    try {
+      await program.methods
+        .setGuardian(null)
+        .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+        .rpc();
+      throw new Error("Unexpected guardian change while frozen");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Vault is frozen");
+    }
+  });
+
+  it("5. ✅ Guardian unfreezes and withdrawals resume", async () => {
+    await setFrozen(false, guardian);
+    await withdraw();
+
+    const destination = await getAccount(provider.connection, withdrawalWalletAta);
+    expect(Number(destination.amount)).to.equal(50_000_000);
+    console.log("✅ Withdrawal after unfreeze:", destination.amount.toString());
+  });
+});
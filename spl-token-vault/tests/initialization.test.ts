@@ -26,7 +26,7 @@ describe("🎉 SPL Token Vault Program - Initialization", () => {
 
   it("✅ Initializes the vault successfully", async () => {
     const tx = await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
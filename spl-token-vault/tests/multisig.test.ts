@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🔐 SPL Token Vault Program - Multisig Authority", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let signerSetPda: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+  const members = [
+    anchor.web3.Keypair.generate(),
+    anchor.web3.Keypair.generate(),
+    anchor.web3.Keypair.generate(),
+  ];
+
+  const asCoSigners = (signers: anchor.web3.Keypair[]) =>
+    signers.map((s) => ({ pubkey: s.publicKey, isSigner: true, isWritable: false }));
+
+  const withdraw = (coSigners: anchor.web3.Keypair[]) =>
+    program.methods
+      .withdraw(new anchor.BN(100_000_000), null, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        signerSet: signerSetPda,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(asCoSigners(coSigners))
+      .signers(coSigners)
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+    [signerSetPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("signer_set"), vaultStatePda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      1_000_000_000
+    );
+
+    const orderId = "multisig-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(1_000_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ❌ Threshold cannot exceed the number of signers", async () => {
+    try {
+      await program.methods
+        .setSignerSet([members[0].publicKey], 2)
+        .accounts({
+          vaultState: vaultStatePda,
+          signerSet: signerSetPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected success with oversized threshold");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("threshold no larger than its size");
+    }
+  });
+
+  it("2. ✅ Authority installs a 2-of-3 signer set", async () => {
+    await program.methods
+      .setSignerSet(
+        members.map((m) => m.publicKey),
+        2
+      )
+      .accounts({
+        vaultState: vaultStatePda,
+        signerSet: signerSetPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.multisigEnabled).to.equal(true);
+    const signerSet = await program.account.signerSet.fetch(signerSetPda);
+    expect(signerSet.threshold).to.equal(2);
+  });
+
+  it("3. ❌ Withdrawals need the threshold of co-signers", async () => {
+    try {
+      await withdraw([members[0]]);
+      throw new Error("Unexpected withdrawal with one co-signer");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected signer error:", errMsg);
+      expect(errMsg).to.include("Not enough signer set members");
+    }
+  });
+
+  it("4. ✅ Withdrawal succeeds with 2 of 3 co-signers", async () => {
+    await withdraw([members[0], members[2]]);
+
+    const destination = await getAccount(provider.connection, withdrawalWalletAta);
+    expect(Number(destination.amount)).to.equal(100_000_000);
+    console.log("✅ Withdrawn with 2-of-3 approval");
+  });
+
+  it("5. ❌ Changing an active signer set needs its approval", async () => {
+    try {
+      await program.methods
+        .setSignerSet([], 0)
+        .accounts({
+          vaultState: vaultStatePda,
+          signerSet: signerSetPda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected signer set change without approval");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Not enough signer set members");
+    }
+  });
+});
@@ -0,0 +1,158 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🛠️ SPL Token Vault Program - Operator Role", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+  const operator = anchor.web3.Keypair.generate();
+
+  const operatorWithdraw = (walletIndex: number | null) =>
+    program.methods
+      .withdraw(new anchor.BN(100_000_000), walletIndex, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        authority: operator.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([operator])
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    const sig = await provider.connection.requestAirdrop(
+      operator.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      500_000_000
+    );
+
+    const orderId = "op-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(500_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ❌ Withdrawals need the authority before an operator is set", async () => {
+    try {
+      await operatorWithdraw(null);
+      throw new Error("Unexpected withdrawal by non-operator");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized access");
+    }
+  });
+
+  it("2. ✅ Operator withdraws to the configured wallet", async () => {
+    await program.methods
+      .setOperator(operator.publicKey)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    await operatorWithdraw(null);
+
+    const destination = await getAccount(provider.connection, withdrawalWalletAta);
+    expect(Number(destination.amount)).to.equal(100_000_000);
+    console.log("✅ Operator withdrawal:", destination.amount.toString());
+  });
+
+  it("3. ❌ Operator cannot pick a whitelisted destination", async () => {
+    try {
+      await operatorWithdraw(0);
+      throw new Error("Unexpected operator withdrawal to whitelist");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized access");
+    }
+  });
+
+  it("4. ❌ Operator cannot change vault configuration", async () => {
+    try {
+      await program.methods
+        .setPassthrough(true)
+        .accounts({ vaultState: vaultStatePda, authority: operator.publicKey })
+        .signers([operator])
+        .rpc();
+      throw new Error("Unexpected config change by operator");
+    } catch (err: any) {
+      console.log("❌ Expected authority error:", err.toString());
+      expect(err.toString()).to.match(/ConstraintHasOne|has_one/i);
+    }
+  });
+});
@@ -31,7 +31,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
 
     // Initialize vault
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
@@ -79,7 +79,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
 
     try {
       await program.methods
-        .deposit(orderId, depositAmount)
+        .deposit(orderId, depositAmount, null)
         .accounts({
           user: authority.publicKey,
           userTokenAccount: userTokenAccount,
@@ -114,7 +114,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
       );
 
       await program.methods
-        .deposit(orderId, depositAmount)
+        .deposit(orderId, depositAmount, null)
         .accounts({
           user: authority.publicKey,
           userTokenAccount: userTokenAccount,
@@ -132,6 +132,39 @@ describe("spl_token_vault_program - Order ID Validation", () => {
     }
   });
 
+  it("Fails to deposit with non-printable order_id", async () => {
+    const orderId = "order\n" + (++testCounter);
+    const depositAmount = new anchor.BN(100_000_000); // 100 tokens
+
+    const [depositRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .deposit(orderId, depositAmount, null)
+        .accounts({
+          user: authority.publicKey,
+          userTokenAccount: userTokenAccount,
+          vaultState: vaultStatePda,
+          vaultTokenAccount: vaultTokenAccount,
+          depositRecord: depositRecordPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      throw new Error("Expected deposit to fail for non-printable order_id");
+    } catch (err: any) {
+      expect(err.toString()).to.include("OrderIdInvalid");
+    }
+  });
+
   it("Successfully deposits with valid order_id (1 byte)", async () => {
     const orderId = "a";
     const depositAmount = new anchor.BN(100_000_000); // 100 tokens
@@ -147,7 +180,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -179,7 +212,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -211,7 +244,7 @@ describe("spl_token_vault_program - Order ID Validation", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
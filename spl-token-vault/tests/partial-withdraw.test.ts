@@ -0,0 +1,154 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("🪙 SPL Token Vault Program - Partial Withdrawals", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+
+  const withdraw = (amount: number) =>
+    program.methods
+      .withdraw(new anchor.BN(amount), null, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      1_000_000_000
+    );
+
+    const orderId = "partial-" + Date.now();
+    const [depositRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+    await program.methods
+      .deposit(orderId, new anchor.BN(1_000_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: depositRecordPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ❌ Cannot withdraw more than the vault holds", async () => {
+    try {
+      await withdraw(2_000_000_000);
+      throw new Error("Unexpected withdrawal above balance");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected balance error:", errMsg);
+      expect(errMsg).to.include("exceeds vault balance");
+    }
+  });
+
+  it("2. ✅ Partial withdrawal leaves a float in the vault", async () => {
+    const tx = await withdraw(300_000_000);
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "withdrawEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event.data.requestedAmount.toNumber()).to.equal(300_000_000);
+    expect(event.data.amount.toNumber()).to.equal(300_000_000);
+    expect(event.data.remainingBalance.toNumber()).to.equal(700_000_000);
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(700_000_000);
+    console.log("✅ Float left in vault:", vault.amount.toString());
+  });
+
+  it("3. ✅ Zero amount drains the remaining balance", async () => {
+    const tx = await withdraw(0);
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "withdrawEvent"
+    );
+    expect(event.data.requestedAmount.toNumber()).to.equal(0);
+    expect(event.data.amount.toNumber()).to.equal(700_000_000);
+    expect(event.data.remainingBalance.toNumber()).to.equal(0);
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+  });
+});
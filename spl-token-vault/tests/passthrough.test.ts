@@ -0,0 +1,140 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("➡️ SPL Token Vault Program - Passthrough Deposits", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const deposit = (orderId: string, destination: anchor.web3.PublicKey | null) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(50_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        passthroughDestination: destination,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      100_000_000
+    );
+  });
+
+  it("1. ❌ Passthrough needs a withdrawal wallet", async () => {
+    try {
+      await program.methods
+        .setPassthrough(true)
+        .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+        .rpc();
+      throw new Error("Unexpected passthrough without wallet");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Withdrawal wallet not set");
+    }
+  });
+
+  it("2. ✅ Deposits are forwarded to the withdrawal wallet", async () => {
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+    await program.methods
+      .setPassthrough(true)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    const orderId = "pass-" + Date.now();
+    await deposit(orderId, withdrawalWalletAta);
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+    const destination = await getAccount(provider.connection, withdrawalWalletAta);
+    expect(Number(destination.amount)).to.equal(50_000_000);
+
+    const record = await program.account.depositRecord.fetch(recordPda(orderId));
+    expect(record.amount.toNumber()).to.equal(50_000_000);
+    console.log("✅ Forwarded deposit recorded:", record.orderId);
+  });
+
+  it("3. ❌ Passthrough deposits must include the destination", async () => {
+    try {
+      await deposit("nodest-" + Date.now(), null);
+      throw new Error("Unexpected passthrough deposit without destination");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected destination error:", errMsg);
+      expect(errMsg).to.include("required in passthrough mode");
+    }
+  });
+});
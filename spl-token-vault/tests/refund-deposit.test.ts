@@ -0,0 +1,212 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("↩️ SPL Token Vault Program - Deposit Refunds", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let depositRecordPda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const orderId = "refund-" + Date.now();
+  const DEPOSIT_AMOUNT = 250_000_000;
+
+  const refund = (signer?: anchor.web3.Keypair) => {
+    const builder = program.methods.refundDeposit(orderId).accounts({
+      vaultState: vaultStatePda,
+      vaultTokenAccount: vaultTokenAccount,
+      depositRecord: depositRecordPda,
+      depositor: user.publicKey,
+      depositorTokenAccount: userTokenAccount,
+      authority: signer ? signer.publicKey : authority.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+    return signer ? builder.signers([signer]).rpc() : builder.rpc();
+  };
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      DEPOSIT_AMOUNT
+    );
+
+    [depositRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        user.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+    await program.methods
+      .deposit(orderId, new anchor.BN(DEPOSIT_AMOUNT), null)
+      .accounts({
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: depositRecordPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("1. ❌ Only the authority can refund", async () => {
+    try {
+      await refund(user);
+      throw new Error("Unexpected refund by non-authority");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/ConstraintHasOne|has_one/i);
+    }
+  });
+
+  it("2. ✅ Authority refunds the recorded amount to the depositor", async () => {
+    const tx = await refund();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getEventsFromTransaction(program, txDetails).find(
+      (e) => e.name === "refundEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event.data.orderId).to.equal(orderId);
+    expect(event.data.user.toString()).to.equal(user.publicKey.toString());
+    expect(event.data.amount.toNumber()).to.equal(DEPOSIT_AMOUNT);
+
+    const userAccount = await getAccount(provider.connection, userTokenAccount);
+    expect(Number(userAccount.amount)).to.equal(DEPOSIT_AMOUNT);
+
+    const record = await program.account.depositRecord.fetch(depositRecordPda);
+    expect(record.status).to.deep.equal({ refunded: {} });
+    console.log("✅ Refunded:", event.data.amount.toString());
+  });
+
+  it("3. ❌ A deposit cannot be refunded twice", async () => {
+    try {
+      await refund();
+      throw new Error("Unexpected second refund");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected refund error:", errMsg);
+      expect(errMsg).to.include("already been refunded");
+    }
+  });
+
+
+  it("4. ❌ A processed deposit cannot be refunded", async () => {
+    const processedOrder = "refund-processed-" + Date.now();
+    const [processedRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        user.publicKey.toBuffer(),
+        Buffer.from(processedOrder),
+      ],
+      program.programId
+    );
+
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      DEPOSIT_AMOUNT
+    );
+    await program.methods
+      .deposit(processedOrder, new anchor.BN(DEPOSIT_AMOUNT), null)
+      .accounts({
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: processedRecordPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .markDepositProcessed(processedOrder)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: processedRecordPda,
+        depositor: user.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .refundDeposit(processedOrder)
+        .accounts({
+          vaultState: vaultStatePda,
+          vaultTokenAccount: vaultTokenAccount,
+          depositRecord: processedRecordPda,
+          depositor: user.publicKey,
+          depositorTokenAccount: userTokenAccount,
+          authority: authority.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      throw new Error("Unexpected refund of a processed deposit");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("already been processed");
+    }
+
+    const record = await program.account.depositRecord.fetch(processedRecordPda);
+    expect(record.status).to.deep.equal({ processed: {} });
+  });
+});
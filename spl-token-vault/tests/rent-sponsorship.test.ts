@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🏠 SPL Token Vault Program - Rent Sponsorship", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let rentEscrowPda: anchor.web3.PublicKey;
+  const depositor = anchor.web3.Keypair.generate();
+  let depositorTokenAccount: anchor.web3.PublicKey;
+  const orderId = "rent-" + Date.now();
+
+  const recordPda = () =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        depositor.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const closeRecord = (rentRecipient: anchor.web3.PublicKey) =>
+    program.methods
+      .closeDepositRecord(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(),
+        depositor: depositor.publicKey,
+        rentRecipient,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+    [rentEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rent_escrow"), vaultStatePda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const sig = await provider.connection.requestAirdrop(
+      depositor.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    depositorTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        depositor.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      depositorTokenAccount,
+      authority.payer,
+      10_000_000
+    );
+
+    await program.methods
+      .fundRentEscrow(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+    await program.methods
+      .setRentSponsorship(true)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("1. ✅ Escrow pays the deposit record rent", async () => {
+    const escrowBefore = await provider.connection.getBalance(rentEscrowPda);
+    const depositorBefore = await provider.connection.getBalance(depositor.publicKey);
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(1_000_000), null)
+      .accounts({
+        user: depositor.publicKey,
+        userTokenAccount: depositorTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(),
+        rentEscrow: rentEscrowPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([depositor])
+      .rpc();
+
+    const recordRent = await provider.connection.getBalance(recordPda());
+    const escrowAfter = await provider.connection.getBalance(rentEscrowPda);
+    const depositorAfter = await provider.connection.getBalance(depositor.publicKey);
+    expect(escrowBefore - escrowAfter).to.equal(recordRent);
+    // Only the transaction fee leaves the depositor's wallet
+    expect(depositorBefore - depositorAfter).to.be.lessThan(recordRent);
+
+    const record = await program.account.depositRecord.fetch(recordPda());
+    expect(record.rentPayer.toBase58()).to.equal(rentEscrowPda.toBase58());
+    console.log("✅ Record rent sponsored:", recordRent, "lamports");
+  });
+
+  it("2. ❌ Active records cannot be closed", async () => {
+    try {
+      await closeRecord(rentEscrowPda);
+      throw new Error("Unexpected close of active record");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Only processed or refunded deposit records can be closed");
+    }
+  });
+
+  it("3. ✅ Closing a processed record refunds the escrow", async () => {
+    await program.methods
+      .markDepositProcessed(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(),
+        depositor: depositor.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    try {
+      await closeRecord(depositor.publicKey);
+      throw new Error("Unexpected rent refund to depositor");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Rent recipient must be the account that paid");
+    }
+
+    const recordRent = await provider.connection.getBalance(recordPda());
+    const escrowBefore = await provider.connection.getBalance(rentEscrowPda);
+    await closeRecord(rentEscrowPda);
+
+    const escrowAfter = await provider.connection.getBalance(rentEscrowPda);
+    expect(escrowAfter - escrowBefore).to.equal(recordRent);
+    expect(await provider.connection.getAccountInfo(recordPda())).to.be.null;
+    console.log("✅ Escrow refunded:", recordRent, "lamports");
+  });
+
+  it("4. ❌ A closed order ID cannot be deposited again", async () => {
+    try {
+      await program.methods
+        .deposit(orderId, new anchor.BN(1_000_000), null)
+        .accounts({
+          user: depositor.publicKey,
+          userTokenAccount: depositorTokenAccount,
+          vaultState: vaultStatePda,
+          vaultTokenAccount: vaultTokenAccount,
+          depositRecord: recordPda(),
+          rentEscrow: rentEscrowPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([depositor])
+        .rpc();
+      throw new Error("Unexpected reuse of a closed order ID");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Order ID was already used by a closed deposit record");
+    }
+  });
+});
@@ -0,0 +1,194 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("⏳ SPL Token Vault Program - Settlement Delay", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let withdrawalWalletAta: anchor.web3.PublicKey;
+  const settledOrder = "settled-" + Date.now();
+  const freshOrder = "fresh-" + Date.now();
+
+  const recordPda = (orderId: string) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const deposit = (orderId: string, amount: number) =>
+    program.methods
+      .deposit(orderId, new anchor.BN(amount), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(orderId),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  const release = (orderId: string) =>
+    program.methods
+      .releaseDeposit(orderId)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(orderId),
+        depositor: authority.publicKey,
+        caller: authority.publicKey,
+      })
+      .rpc();
+
+  const withdraw = (amount: number) =>
+    program.methods
+      .withdraw(new anchor.BN(amount), null, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: withdrawalWalletAta,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const withdrawalWallet = anchor.web3.Keypair.generate();
+    withdrawalWalletAta = await anchor.utils.token.associatedAddress({
+      mint: tokenMint,
+      owner: withdrawalWallet.publicKey,
+    });
+    await program.methods
+      .setWithdrawalAccount()
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        newWallet: withdrawalWallet.publicKey,
+        associatedToken: withdrawalWalletAta,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        authority.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount,
+      authority.payer,
+      100_000_000
+    );
+
+    await program.methods
+      .setSettlementDelay(new anchor.BN(2))
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+  });
+
+  it("1. ✅ Deposits are held as pending", async () => {
+    await deposit(settledOrder, 30_000_000);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.pendingBalance.toNumber()).to.equal(30_000_000);
+    const record = await program.account.depositRecord.fetch(recordPda(settledOrder));
+    expect(record.pending).to.be.true;
+  });
+
+  it("2. ❌ Pending funds cannot be withdrawn or released early", async () => {
+    try {
+      await withdraw(0);
+      throw new Error("Unexpected withdrawal of pending funds");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("No funds");
+    }
+
+    try {
+      await release(settledOrder);
+      throw new Error("Unexpected early release");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Settlement delay has not elapsed");
+    }
+  });
+
+  it("3. ✅ Released deposits become withdrawable, fresh ones stay held", async () => {
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await release(settledOrder);
+    await deposit(freshOrder, 20_000_000);
+
+    await withdraw(0);
+
+    const destination = await getAccount(provider.connection, withdrawalWalletAta);
+    expect(Number(destination.amount)).to.equal(30_000_000);
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(20_000_000);
+
+    const vaultState = await program.account.vaultState.fetch(vaultStatePda);
+    expect(vaultState.pendingBalance.toNumber()).to.equal(20_000_000);
+    console.log("✅ Pending balance left in vault:", vaultState.pendingBalance.toString());
+  });
+
+  it("4. ✅ Processing an order settles it and check reports both balances", async () => {
+    await program.methods
+      .markDepositProcessed(freshOrder)
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(freshOrder),
+        depositor: authority.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const status = await program.methods
+      .check()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+      })
+      .view();
+    expect(status.pendingBalance.toNumber()).to.equal(0);
+    expect(status.settledBalance.toNumber()).to.equal(20_000_000);
+    console.log("✅ Settled balance:", status.settledBalance.toString());
+  });
+});
@@ -53,7 +53,7 @@ describe("🧩 spl_token_vault_program end-to-end", () => {
 
   it("✅ Initializes the vault", async () => {
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState,
         vaultTokenAccount,
@@ -110,7 +110,7 @@ describe("🧩 spl_token_vault_program end-to-end", () => {
 
     // NOW: Initialize the vault - should succeed despite ATA existing (init_if_needed)
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: newVaultState,
         vaultTokenAccount: newVaultTokenAccount,
@@ -172,7 +172,7 @@ describe("🧩 spl_token_vault_program end-to-end", () => {
 
     try {
       await program.methods
-        .withdraw()
+        .withdraw(new anchor.BN(0), null, null)
         .accounts({
           vaultState,
           vaultTokenAccount,
@@ -246,7 +246,7 @@ console.log("✅ Correctly failed when no ATA exists:", err.toString());
 
   // ✅ Perform deposit
   await program.methods
-    .deposit(orderId, new anchor.BN(10_000_000)) // deposit 10 tokens
+    .deposit(orderId, new anchor.BN(10_000_000), null) // deposit 10 tokens
     .accounts({
       user: user.publicKey,
       userTokenAccount,
@@ -295,7 +295,7 @@ it("🚨 Prevents reentrancy during withdraw", async () => {
   // Attempt first withdrawal
   // Using a try/catch to detect reentrancy issues
   try {
-    await program.methods.withdraw()
+    await program.methods.withdraw(new anchor.BN(0), null, null)
       .accounts({
         vaultState,
         vaultTokenAccount,
@@ -345,7 +345,7 @@ it("fails when order_id is longer than MAX_ORDER_ID_LEN", async () => {
         );
 
         await program.methods
-            .deposit(longOrderId, new anchor.BN(1_000_000))
+            .deposit(longOrderId, new anchor.BN(1_000_000), null)
             .accounts({
                 user: user.publicKey,
                 userTokenAccount: userAta,
@@ -401,7 +401,7 @@ it("fails when order_id is empty", async () => {
 
     try {
         await program.methods
-            .deposit(emptyOrderId, new anchor.BN(1_000_000))
+            .deposit(emptyOrderId, new anchor.BN(1_000_000), null)
             .accounts({
                 user: user.publicKey,
                 userTokenAccount: userAta,
@@ -485,7 +485,7 @@ it("✅ PDA uniqueness: Different users can use same order_id without collision"
 
     // Both deposits should succeed
     await program.methods
-        .deposit(sameOrderId, new anchor.BN(1_000_000))
+        .deposit(sameOrderId, new anchor.BN(1_000_000), null)
         .accounts({
             user: user1.publicKey,
             userTokenAccount: user1Ata,
@@ -500,7 +500,7 @@ it("✅ PDA uniqueness: Different users can use same order_id without collision"
         .rpc();
 
     await program.methods
-        .deposit(sameOrderId, new anchor.BN(1_000_000))
+        .deposit(sameOrderId, new anchor.BN(1_000_000), null)
         .accounts({
             user: user2.publicKey,
             userTokenAccount: user2Ata,
@@ -564,7 +564,7 @@ it("🚫 PDA collision attack prevented: User cannot create deposit for another
         // Attacker tries to deposit but uses victim's PDA
         // This should fail because the PDA seeds won't match the signer (attacker)
         await program.methods
-            .deposit(orderId, new anchor.BN(1_000_000))
+            .deposit(orderId, new anchor.BN(1_000_000), null)
             .accounts({
                 user: attacker.publicKey,  // Attacker is the signer
                 userTokenAccount: attackerAta,
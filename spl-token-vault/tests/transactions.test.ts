@@ -30,7 +30,7 @@ describe("🎉 SPL Token Vault Program - Transactions", () => {
 
     // Initialize vault
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
@@ -78,7 +78,7 @@ describe("🎉 SPL Token Vault Program - Transactions", () => {
     );
 
     const tx = await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -122,7 +122,7 @@ describe("🎉 SPL Token Vault Program - Transactions", () => {
     );
 
     await program.methods
-      .deposit(orderId, depositAmount)
+      .deposit(orderId, depositAmount, null)
       .accounts({
         user: authority.publicKey,
         userTokenAccount: userTokenAccount,
@@ -157,4 +157,21 @@ describe("🎉 SPL Token Vault Program - Transactions", () => {
 
     console.log("✅ Vault check ran successfully");
   });
+
+  it("✅ Check returns typed vault status", async () => {
+    const status = await program.methods
+      .check()
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+      })
+      .view();
+
+    expect(status.tokenMint.toString()).to.equal(tokenMint.toString());
+    expect(status.authority.toString()).to.equal(authority.publicKey.toString());
+    expect(status.balance.toNumber()).to.be.greaterThan(0);
+    expect(status.isRentExempt).to.equal(true);
+
+    console.log("✅ Vault status balance:", status.balance.toString());
+  });
 });
@@ -0,0 +1,59 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+} from "./helpers/setup-utils";
+
+describe("📚 SPL Token Vault Program - Vault Registry", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_registry")],
+    program.programId
+  );
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+  });
+
+  it("1. ✅ Initialize appends the vault to the registry", async () => {
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    const registry = await program.account.vaultRegistry.fetch(registryPda);
+    const entry = registry.vaults.find(
+      (v) => v.tokenMint.toString() === tokenMint.toString()
+    );
+    expect(entry).to.not.be.undefined;
+    expect(entry!.vaultState.toString()).to.equal(vaultStatePda.toString());
+    console.log("📚 Registered vaults:", registry.vaults.length);
+  });
+
+  it("2. ❌ A vault cannot be registered twice", async () => {
+    try {
+      await program.methods
+        .registerVault()
+        .accounts({
+          vaultState: vaultStatePda,
+          payer: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected duplicate registration");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected duplicate error:", errMsg);
+      expect(errMsg).to.include("already in the registry");
+    }
+  });
+});
@@ -0,0 +1,119 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+import { getEventsFromTransaction } from "./helpers/utils";
+
+describe("🧾 SPL Token Vault Program - Deposit Verification", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  const orderId = "inv-" + Date.now();
+
+  const recordPda = () =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    )[0];
+
+  const verify = async (expected: number) => {
+    const tx = await program.methods
+      .verifyDeposit(orderId, new anchor.BN(expected))
+      .accounts({
+        vaultState: vaultStatePda,
+        depositRecord: recordPda(),
+        depositor: authority.publicKey,
+        authority: authority.publicKey,
+      })
+      .rpc();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return getEventsFromTransaction(program, txDetails);
+  };
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      10_000_000
+    );
+
+    await program.methods
+      .deposit(orderId, new anchor.BN(995_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: recordPda(),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ❌ Short payment emits a mismatch and stays unverified", async () => {
+    const events = await verify(1_000_000);
+    const mismatch = events.find((e) => e.name === "depositAmountMismatchEvent");
+    expect(mismatch.data.amount.toNumber()).to.equal(995_000);
+    expect(mismatch.data.expectedAmount.toNumber()).to.equal(1_000_000);
+
+    const record = await program.account.depositRecord.fetch(recordPda());
+    expect(record.verified).to.be.false;
+  });
+
+  it("2. ✅ Payment within tolerance is verified", async () => {
+    await program.methods
+      .setVerifyTolerance(50)
+      .accounts({ vaultState: vaultStatePda, authority: authority.publicKey })
+      .rpc();
+
+    const events = await verify(1_000_000);
+    expect(events.find((e) => e.name === "depositVerifiedEvent")).to.not.be.undefined;
+
+    const record = await program.account.depositRecord.fetch(recordPda());
+    expect(record.verified).to.be.true;
+    console.log("✅ Deposit verified:", record.orderId);
+  });
+});
@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("📋 SPL Token Vault Program - Withdrawal Whitelist", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let whitelistPda: anchor.web3.PublicKey;
+  const hotWallet = anchor.web3.Keypair.generate();
+  const coldWallet = anchor.web3.Keypair.generate();
+
+  const ataOf = (owner: anchor.web3.PublicKey) =>
+    anchor.utils.token.associatedAddress({ mint: tokenMint, owner });
+
+  const addWallet = (wallet: anchor.web3.PublicKey) =>
+    program.methods
+      .addWhitelistedWallet()
+      .accounts({
+        vaultState: vaultStatePda,
+        walletWhitelist: whitelistPda,
+        authority: authority.publicKey,
+        wallet,
+        associatedToken: ataOf(wallet),
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+  const withdrawTo = (index: number, destination: anchor.web3.PublicKey) =>
+    program.methods
+      .withdraw(new anchor.BN(100_000_000), index, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        destinationTokenAccount: destination,
+        walletWhitelist: whitelistPda,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+    [whitelistPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("wallet_whitelist"), vaultStatePda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      500_000_000
+    );
+
+    const orderId = "wl-" + Date.now();
+    await program.methods
+      .deposit(orderId, new anchor.BN(500_000_000), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("deposit_record"),
+            tokenMint.toBuffer(),
+            authority.publicKey.toBuffer(),
+            Buffer.from(orderId),
+          ],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ✅ Authority whitelists a hot and a cold wallet", async () => {
+    await addWallet(hotWallet.publicKey);
+    await addWallet(coldWallet.publicKey);
+
+    const whitelist = await program.account.walletWhitelist.fetch(whitelistPda);
+    expect(whitelist.wallets.map((w) => w.toBase58())).to.deep.equal([
+      hotWallet.publicKey.toBase58(),
+      coldWallet.publicKey.toBase58(),
+    ]);
+  });
+
+  it("2. ❌ Duplicate wallets are rejected", async () => {
+    try {
+      await addWallet(hotWallet.publicKey);
+      throw new Error("Unexpected duplicate whitelist entry");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("already in the withdrawal whitelist");
+    }
+  });
+
+  it("3. ✅ Withdraw targets a whitelisted wallet by index", async () => {
+    await withdrawTo(1, await ataOf(coldWallet.publicKey));
+
+    const cold = await getAccount(provider.connection, await ataOf(coldWallet.publicKey));
+    expect(Number(cold.amount)).to.equal(100_000_000);
+    console.log("✅ Withdrawn to cold wallet:", cold.amount.toString());
+  });
+
+  it("4. ❌ Destination must match the selected wallet", async () => {
+    try {
+      await withdrawTo(0, await ataOf(coldWallet.publicKey));
+      throw new Error("Unexpected withdrawal to mismatched destination");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Invalid withdrawal wallet");
+    }
+  });
+
+  it("5. ❌ Removed wallets can no longer be selected", async () => {
+    await program.methods
+      .removeWhitelistedWallet(coldWallet.publicKey)
+      .accounts({
+        vaultState: vaultStatePda,
+        walletWhitelist: whitelistPda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    try {
+      await withdrawTo(1, await ataOf(coldWallet.publicKey));
+      throw new Error("Unexpected withdrawal to removed wallet");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected whitelist error:", errMsg);
+      expect(errMsg).to.include("not in the withdrawal whitelist");
+    }
+  });
+});
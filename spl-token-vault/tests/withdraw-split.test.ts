@@ -0,0 +1,176 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  initializeTestEnvironment,
+  createTestTokenMint,
+  deriveVaultStatePda,
+  getVaultTokenAccount,
+} from "./helpers/setup-utils";
+
+describe("🔀 SPL Token Vault Program - Split Withdrawals", () => {
+  const { provider, program, authority } = initializeTestEnvironment();
+
+  let tokenMint: anchor.web3.PublicKey;
+  let vaultStatePda: anchor.web3.PublicKey;
+  let vaultTokenAccount: anchor.web3.PublicKey;
+  let payoutTablePda: anchor.web3.PublicKey;
+  const wallets = [
+    anchor.web3.Keypair.generate().publicKey,
+    anchor.web3.Keypair.generate().publicKey,
+    anchor.web3.Keypair.generate().publicKey,
+  ];
+  const shares = [6000, 3000, 1000];
+  const atas: anchor.web3.PublicKey[] = [];
+
+  const withdrawSplit = (splits: { destination: anchor.web3.PublicKey; bps: number }[]) =>
+    program.methods
+      .withdrawSplit(new anchor.BN(0), splits, null)
+      .accounts({
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        payoutTable: payoutTablePda,
+        authority: authority.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        splits.map((s) => ({ pubkey: s.destination, isSigner: false, isWritable: true }))
+      )
+      .rpc();
+
+  before(async () => {
+    tokenMint = await createTestTokenMint(provider, authority);
+    [vaultStatePda] = deriveVaultStatePda(tokenMint, program.programId);
+    [payoutTablePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_table"), vaultStatePda.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initialize(null)
+      .accounts({
+        vaultState: vaultStatePda,
+        authority: authority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    vaultTokenAccount = await getVaultTokenAccount(tokenMint, vaultStatePda);
+
+    for (const wallet of wallets) {
+      const ata = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        authority.payer,
+        tokenMint,
+        wallet
+      );
+      atas.push(ata.address);
+    }
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      authority.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      authority.payer,
+      tokenMint,
+      userTokenAccount.address,
+      authority.payer,
+      1_000_000_001
+    );
+
+    const orderId = "split-" + Date.now();
+    const [depositRecordPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("deposit_record"),
+        tokenMint.toBuffer(),
+        authority.publicKey.toBuffer(),
+        Buffer.from(orderId),
+      ],
+      program.programId
+    );
+    await program.methods
+      .deposit(orderId, new anchor.BN(1_000_000_001), null)
+      .accounts({
+        user: authority.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        vaultState: vaultStatePda,
+        vaultTokenAccount: vaultTokenAccount,
+        depositRecord: depositRecordPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  });
+
+  it("1. ❌ Shares must sum to 10000 bps", async () => {
+    try {
+      await program.methods
+        .setPayoutTable([
+          { wallet: wallets[0], bps: 6000 },
+          { wallet: wallets[1], bps: 3000 },
+        ])
+        .accounts({
+          vaultState: vaultStatePda,
+          payoutTable: payoutTablePda,
+          authority: authority.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected success with incomplete table");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected payout table error:", errMsg);
+      expect(errMsg).to.include("sum to 10000 bps");
+    }
+  });
+
+  it("2. ✅ Authority configures the payout table", async () => {
+    await program.methods
+      .setPayoutTable(wallets.map((wallet, i) => ({ wallet, bps: shares[i] })))
+      .accounts({
+        vaultState: vaultStatePda,
+        payoutTable: payoutTablePda,
+        authority: authority.publicKey,
+      })
+      .rpc();
+
+    const table = await program.account.payoutTable.fetch(payoutTablePda);
+    expect(table.entries.length).to.equal(3);
+    expect(table.entries.map((e) => e.bps)).to.deep.equal(shares);
+  });
+
+  it("3. ❌ Splits that deviate from the table are rejected", async () => {
+    try {
+      await withdrawSplit([
+        { destination: atas[0], bps: 5000 },
+        { destination: atas[1], bps: 4000 },
+        { destination: atas[2], bps: 1000 },
+      ]);
+      throw new Error("Unexpected success with mismatched shares");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected mismatch error:", errMsg);
+      expect(errMsg).to.include("do not match the payout table");
+    }
+  });
+
+  it("4. ✅ Balance is distributed by share with dust to the last destination", async () => {
+    await withdrawSplit(atas.map((destination, i) => ({ destination, bps: shares[i] })));
+
+    const balances = await Promise.all(
+      atas.map(async (ata) => Number((await getAccount(provider.connection, ata)).amount))
+    );
+    expect(balances).to.deep.equal([600_000_000, 300_000_000, 100_000_001]);
+
+    const vault = await getAccount(provider.connection, vaultTokenAccount);
+    expect(Number(vault.amount)).to.equal(0);
+    console.log("✅ Split balances:", balances);
+  });
+});
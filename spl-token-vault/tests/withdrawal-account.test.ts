@@ -27,7 +27,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Account", () => {
 
     // Initialize vault
     await program.methods
-      .initialize()
+      .initialize(null)
       .accounts({
         vaultState: vaultStatePda,
         authority: authority.publicKey,
@@ -96,7 +96,7 @@ describe("🎉 SPL Token Vault Program - Withdrawal Account", () => {
 
     try {
       await program.methods
-        .withdraw()
+        .withdraw(new anchor.BN(0), null, null)
         .accounts({
           vaultState: vaultStatePda,
           vaultTokenAccount: vaultTokenAccount,
@@ -60,7 +60,7 @@ describe("spl_token_vault_program - Enhanced Withdrawal Wallet Validation", () =
       await provider.sendAndConfirm(tx, [mintKeypair]);
 
       await program.methods
-        .initialize()
+        .initialize(null)
         .accounts({
           vaultState: vaultStatePda,
           vaultTokenAccount: vaultTokenAccount,
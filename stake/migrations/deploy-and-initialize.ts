@@ -132,7 +132,8 @@ async function deployAndInitialize() {
       .createPool(
         null, // maybe_owner - null means admin will be the owner
         rewardPercentage,
-        poolId // pool_id parameter
+        poolId, // pool_id parameter
+        null // end_slot - null means the pool is open-ended
       )
       .accounts({
         poolIdCounter: poolIdCounterPda,
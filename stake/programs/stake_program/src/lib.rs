@@ -8,8 +8,13 @@
 // =============================================================================
 
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::get_associated_token_address;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{self, get_associated_token_address, AssociatedToken, Create};
+use anchor_lang::system_program;
+use anchor_spl::token::{self, spl_token, CloseAccount, Mint, SyncNative, Token, TokenAccount, Transfer};
+use anchor_spl::metadata::{
+    create_master_edition_v3, create_metadata_accounts_v3, mpl_token_metadata::types::DataV2,
+    CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+};
 
 declare_id!("GtgbhnDFLdbh1kBu4htmBbZrB3c5C8MP8px8Yq5jbstX");
 
@@ -19,11 +24,190 @@ declare_id!("GtgbhnDFLdbh1kBu4htmBbZrB3c5C8MP8px8Yq5jbstX");
 /// = 78,894,000 slots/year (rounded to 78,840,000 for conservative estimates)
 const SLOTS_PER_YEAR: u64 = 78_840_000;
 
+/// Seconds per year (365.25 days) for pools accruing on unix timestamps
+const SECONDS_PER_YEAR: u64 = 31_557_600;
+
+/// Maximum governance lock duration (4 years of slots).
+/// Voting weight is amount × remaining lock slots, so this also bounds the maximum weight.
+const MAX_GOVERNANCE_LOCK_SLOTS: u64 = 4 * SLOTS_PER_YEAR;
+
+/// Maximum per-deposit lock duration a pool can configure, in years of the pool's time basis
+const MAX_DEPOSIT_LOCK_YEARS: u64 = 4;
+
+/// Current Pool account layout version. Bump together with the layout when
+/// fields are carved out of `Pool::_reserved` or the account grows; growth is
+/// applied to existing pools by migrate_pool.
+/// Version 0 pools predate the version field (see POOL_V0_SPACE).
+/// Version 2 appended end_slot and enlarged _reserved (POOL_V2_EXTRA_SPACE bytes).
+const POOL_VERSION: u8 = 2;
+
+/// Bytes pool_space grew by in Pool layout version 2
+const POOL_V2_EXTRA_SPACE: usize = 68;
+
+/// Offset of the reward_epochs length prefix, the same in every Pool layout:
+/// 8 (discriminator) + 32 (token_mint) + 32 (reward_mint) + 32 (reward_vault) +
+/// 32 (owner) + 8 (total_staked) + 8 (reward_percentage) + 1 (bump) + 1 (is_active)
+const POOL_EPOCHS_OFFSET: usize = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1;
+
+/// Size of pools created before layout versioning: the fields up to pool_id with room
+/// for 10 epochs. No later layout is this small, so migrate_pool recognizes them by size.
+const POOL_V0_SPACE: usize = POOL_EPOCHS_OFFSET + 4 + (10 * 16) + 8 + 8;
+
+/// Current UserStake account layout version. Bump together with the layout and
+/// handle the upgrade in migrate_user_stake.
+const USER_STAKE_VERSION: u8 = 2;
+
+/// UserStake account size:
+/// 8 (discriminator) + 32 (owner) + 32 (pool) + 8 (amount) + 8 (last_staked_slot) +
+/// 8 (total_earned) + 8 (unclaimed) + 1 (bump) + 8 (governance_locked_amount) +
+/// 8 (governance_lock_end_slot) + 1 (reward_capped) + 8 (position_index) +
+/// 4 + MAX_STAKE_TRANCHES * 32 (tranches) + 1 (frozen) + 1 (auto_claim) + 8 (keeper_fee) +
+/// 1 (version) + 8 (first_deposit_slot) + 16 (reward_index_snapshot) + 8 (reward_remainder) +
+/// 16 (partner_tag) + 2 (boost_bps) + 8 (active_slots_snapshot) + 6 (_reserved)
+const USER_STAKE_SPACE: usize =
+    8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 1 + 8 + 4 + (MAX_STAKE_TRANCHES * 32) + 1 + 1 + 8 + 1 + 8 + 16 + 8 + 16 + 2 + 8 + 6;
+
+/// Number of reward epochs a pool keeps unless expanded with expand_pool_epochs
+/// (also the capacity of pools created before the field existed)
+const DEFAULT_EPOCH_CAPACITY: u16 = 10;
+
+/// Upper bound for a pool's reward epoch capacity
+const MAX_EPOCH_CAPACITY: u16 = 128;
+
+/// Pool account size for a given reward epoch capacity:
+/// 8 (discriminator) + 32 (token_mint) + 32 (reward_mint) + 32 (reward_vault) +
+/// 32 (owner) + 8 (total_staked) + 8 (reward_percentage) + 1 (bump) + 1 (is_active) +
+/// 4 (vec length) + epoch_capacity * (8 + 8) (reward_percentage + start_slot) +
+/// 8 (last_reward_update_slot) + 8 (pool_id) + 8 (max_reward_per_user) +
+/// 1 (halt_on_depletion) + 1 (accrual_halted) + 8 (accrual_halted_slot) +
+/// 1 (accrual_frozen) + 8 (lock_duration_slots) + 32 (governance_program) +
+/// 32 (governance_realm) + 1 (version) + 2 (epoch_capacity) + 1 (time_basis) +
+/// 8 (periods_per_year) + 32 (withdraw_guardian) + 8 (guardian_threshold) +
+/// 8 (min_slots_between_rate_changes) + 8 (reward_cliff_slots) + 8 (min_claim_amount) +
+/// 16 (archived_reward_index) + 1 (rounding_mode) + 32 (attestation_issuer) + 8 (end_slot) +
+/// 32 (booster_program) + 1 (lock_rate_at_deposit) + 8 (archived_active_slots) + 8 (reward_scale_periods) +
+/// 8 (unguarded_withdrawn) + 7 (_reserved)
+const fn pool_space(epoch_capacity: u16) -> usize {
+    8 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 4 + (epoch_capacity as usize * 16) + 8 + 8 + 8 + 1 + 1 + 8 + 1 + 8 + 32 + 32 + 1 + 2 + 1 + 8 + 32 + 8 + 8 + 8 + 8 + 16 + 1 + 32 + 8 + 32 + 1 + 8 + 8 + 8 + 7
+}
+
+/// Maximum number of future reward epochs that can be queued at once
+const MAX_SCHEDULED_EPOCHS: usize = 3;
+
+/// Largest page get_reward_epochs serves; keeps the page within the return data limit
+const MAX_EPOCH_PAGE_SIZE: u32 = 32;
+
+/// Seed prefix spl-governance uses for governance account PDAs:
+/// [b"account-governance", realm, governance_seed]
+const SPL_GOVERNANCE_ACCOUNT_SEED: &[u8] = b"account-governance";
+
+/// Maximum number of deposit tranches tracked per stake position.
+/// Unlocked tranches are merged when the list is full.
+const MAX_STAKE_TRANCHES: usize = 8;
+
+/// StakePositionNft account size:
+/// 8 (discriminator) + 32 (pool) + 32 (nft_mint) + 8 (amount) + 8 (deposit_slot) +
+/// 8 (lock_end_slot) + 8 (unclaimed) + 1 (bump)
+const STAKE_POSITION_NFT_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+/// PoolTemplate account size:
+/// 8 (discriminator) + 8 (template_id) + 8 (reward_percentage) + 8 (max_reward_per_user) +
+/// 8 (lock_duration_slots) + 1 (halt_on_depletion) + 1 (time_basis) +
+/// 8 (min_slots_between_rate_changes) + 1 (bump)
+const POOL_TEMPLATE_SPACE: usize = 8 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 1;
+
+/// Number of positions kept on a pool leaderboard
+const MAX_LEADERBOARD_ENTRIES: usize = 10;
+
+/// Leaderboard account size:
+/// 8 (discriminator) + 32 (pool) + 4 + MAX_LEADERBOARD_ENTRIES * 72 (entries) + 1 (bump)
+const LEADERBOARD_SPACE: usize = 8 + 32 + 4 + (MAX_LEADERBOARD_ENTRIES * 72) + 1;
+
+/// Largest share of a crank_claim the keeper fee may take, in basis points (10%).
+/// Cranks are rejected until the claim is big enough, so a keeper cannot crank as soon
+/// as rewards pass the flat fee and keep most of them.
+const MAX_KEEPER_FEE_SHARE_BPS: u64 = 1_000;
+
+/// Largest reward multiplier a booster program can grant, in basis points (3x)
+const MAX_BOOST_BPS: u16 = 30_000;
+
+/// Number of operators a pool owner can delegate day-to-day operations to
+const MAX_POOL_ADMINS: usize = 5;
+
+/// PoolAdmins account size:
+/// 8 (discriminator) + 32 (pool) + 4 + MAX_POOL_ADMINS * 32 (admins) + 1 (bump)
+const POOL_ADMINS_SPACE: usize = 8 + 32 + 4 + (MAX_POOL_ADMINS * 32) + 1;
+
+/// PDA derivation helpers for programs and clients that consume this crate
+/// (build with the `cpi` feature to get `stake_program::cpi` and `cpi::accounts`).
+/// Seeds must stay in sync with the account constraints below.
+pub mod pda {
+    use super::*;
+
+    pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+    pub const POOL_SEED: &[u8] = b"staking_pool";
+    pub const POOL_VAULT_SEED: &[u8] = b"vault";
+    pub const REWARD_VAULT_SEED: &[u8] = b"reward_vault";
+    pub const USER_STAKE_SEED: &[u8] = b"user_stake";
+    pub const USER_POSITIONS_SEED: &[u8] = b"user_positions";
+    pub const POSITION_NFT_SEED: &[u8] = b"position_nft";
+    pub const POOL_TEMPLATE_SEED: &[u8] = b"pool_template";
+    pub const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+    pub const POOL_ADMINS_SEED: &[u8] = b"pool_admins";
+
+    pub fn find_global_config_address() -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &crate::ID)
+    }
+
+    pub fn find_pool_address(token_mint: &Pubkey, pool_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[POOL_SEED, token_mint.as_ref(), &pool_id.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+
+    pub fn find_pool_vault_address(pool: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[POOL_VAULT_SEED, pool.as_ref(), token_mint.as_ref()], &crate::ID)
+    }
+
+    pub fn find_reward_vault_address(pool: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[REWARD_VAULT_SEED, pool.as_ref(), reward_mint.as_ref()], &crate::ID)
+    }
+
+    pub fn find_user_stake_address(pool: &Pubkey, user: &Pubkey, position_index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[USER_STAKE_SEED, pool.as_ref(), user.as_ref(), &position_index.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+
+    pub fn find_user_positions_address(pool: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[USER_POSITIONS_SEED, pool.as_ref(), user.as_ref()], &crate::ID)
+    }
+
+    pub fn find_position_nft_address(nft_mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[POSITION_NFT_SEED, nft_mint.as_ref()], &crate::ID)
+    }
+
+    pub fn find_pool_template_address(template_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[POOL_TEMPLATE_SEED, &template_id.to_le_bytes()], &crate::ID)
+    }
+
+    pub fn find_leaderboard_address(pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[LEADERBOARD_SEED, pool.as_ref()], &crate::ID)
+    }
+
+    pub fn find_pool_admins_address(pool: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[POOL_ADMINS_SEED, pool.as_ref()], &crate::ID)
+    }
+}
+
 /// Validates that a token account address is safe to use as a withdrawal destination
 /// Ensures the address is not:
 /// - Default/zero address
 /// - The pool PDA or any vault PDA
 /// - A token mint address
+///
 /// Additionally validates the token account owner (wallet) is not a program-owned account
 fn validate_withdrawal_address(
     token_account_address: &Pubkey,
@@ -82,6 +266,46 @@ fn validate_withdrawal_address(
     Ok(())
 }
 
+/// Creates the user's reward-token ATA (payer = user) when the provided reward account
+/// doesn't exist yet, so first-time claimers don't need a separate setup transaction.
+/// An existing account must be a token account of the reward mint; the caller applies
+/// any further ownership checks to the returned account.
+fn ensure_reward_token_account<'info>(
+    reward_account: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+    reward_mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<TokenAccount> {
+    if reward_account.owner != &token::ID {
+        // Only the canonical ATA can be created on the user's behalf
+        require_keys_eq!(
+            reward_account.key(),
+            get_associated_token_address(&user.key(), &reward_mint.key()),
+            CustomError::InvalidRewardAccount
+        );
+
+        associated_token::create(CpiContext::new(
+            associated_token_program.clone(),
+            Create {
+                payer: user.clone(),
+                associated_token: reward_account.clone(),
+                authority: user.clone(),
+                mint: reward_mint.clone(),
+                system_program: system_program.clone(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+        msg!("Created reward token account {}", reward_account.key());
+    }
+
+    let token_account = TokenAccount::try_deserialize(&mut &reward_account.data.borrow()[..])?;
+    require_keys_eq!(token_account.mint, reward_mint.key(), CustomError::InvalidRewardAccount);
+
+    Ok(token_account)
+}
+
 /// Validates that an address is safe to use as a new authority
 /// Ensures the address is not:
 /// - Default/zero address
@@ -110,6 +334,248 @@ fn validate_authority_address(
     Ok(())
 }
 
+/// Self-CPI event emitter for shared helpers that run outside an instruction handler.
+/// Mirrors `emit_cpi!`, which can only be used where `ctx` is in scope, so events
+/// from helpers land in the same self-CPI instruction data as the handlers' events.
+struct EventCpi<'a, 'info> {
+    event_authority: &'a AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'a, 'info> EventCpi<'a, 'info> {
+    fn new(event_authority: &'a AccountInfo<'info>, bump: u8) -> Self {
+        Self { event_authority, bump }
+    }
+
+    fn emit<T: anchor_lang::Event>(&self, event: T) -> Result<()> {
+        let ix_data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .iter()
+            .copied()
+            .chain(event.data())
+            .collect();
+        let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+            crate::ID,
+            &ix_data,
+            vec![anchor_lang::solana_program::instruction::AccountMeta::new_readonly(
+                *self.event_authority.key,
+                true,
+            )],
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            std::slice::from_ref(self.event_authority),
+            &[&[b"__event_authority", &[self.bump]]],
+        )
+        .map_err(Into::into)
+    }
+}
+
+/// Credits a stake deposit to a user's stake account.
+/// The account must already exist (see init_user_stake); pending rewards are settled
+/// into `unclaimed` before the stake amount changes.
+/// Shared by all deposit paths so relayed and direct deposits stay identical.
+fn record_stake_deposit(
+    pool: &mut Pool,
+    pool_key: Pubkey,
+    user_stake: &mut UserStake,
+    amount: u64,
+    partner_tag: Option<[u8; 16]>,
+    clock: &Clock,
+    events: &EventCpi,
+) -> Result<()> {
+    let now = pool.now(clock);
+
+    // Validate pool association (defense in depth; seeds already bind the pool)
+    require!(
+        user_stake.pool == pool_key,
+        CustomError::InvalidPoolAssociation
+    );
+    require!(!user_stake.frozen, CustomError::UserStakeFrozen);
+    require!(!pool.has_ended(now), CustomError::PoolEnded);
+
+    let pending = user_stake.settle_pending_reward(pool, now);
+    user_stake.unclaimed = user_stake.unclaimed.checked_add(pending).unwrap();
+
+    // The reward cliff runs from the position's first deposit, which is also the one
+    // attributed to an integration partner
+    if user_stake.first_deposit_slot == 0 && user_stake.amount == 0 {
+        user_stake.first_deposit_slot = now;
+        if let Some(tag) = partner_tag {
+            user_stake.partner_tag = tag;
+        }
+    }
+
+    // Each deposit keeps its own lock, so topping up never resets earlier tranches
+    user_stake.add_tranche(StakeTranche {
+        amount,
+        deposit_slot: now,
+        lock_end_slot: now.checked_add(pool.lock_duration_slots).unwrap(),
+        reward_percentage: pool.current_reward_percentage(now),
+    }, now)?;
+
+    // Update user stake
+    user_stake.amount = user_stake.amount.checked_add(amount).unwrap();
+
+    if user_stake.update_reward_cap_status(pool) {
+        events.emit(RewardCapReachedEvent {
+            user: user_stake.owner,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            total_rewards: pool.max_reward_per_user,
+            slot: clock.slot,
+        })?;
+    }
+
+    // Update pool info
+    pool.total_staked = pool.total_staked.checked_add(amount).unwrap();
+
+    Ok(())
+}
+
+/// Checks that a depositor into an attestation-gated pool holds the issuer's credential:
+/// a token account of the `attestation_issuer` mint, owned by the staker, with a balance.
+/// Ungated pools (default issuer) accept any depositor.
+fn verify_attestation(pool: &Pool, attestation: Option<&TokenAccount>, staker: &Pubkey) -> Result<()> {
+    if pool.attestation_issuer == Pubkey::default() {
+        return Ok(());
+    }
+
+    let attestation = attestation.ok_or(CustomError::AttestationRequired)?;
+    require!(
+        attestation.mint == pool.attestation_issuer
+            && attestation.owner == *staker
+            && attestation.amount > 0,
+        CustomError::InvalidAttestation
+    );
+
+    Ok(())
+}
+
+/// Debits a stake withdrawal from a user's stake account and settles rewards.
+/// Rewards (pending + unclaimed) are paid only if the reward vault can cover them in full,
+/// otherwise they are preserved as unclaimed. Returns (reward_to_send, total_rewards).
+fn record_stake_withdrawal(
+    pool: &mut Pool,
+    pool_key: Pubkey,
+    user_stake: &mut UserStake,
+    amount: u64,
+    reward_vault_balance: u64,
+    clock: &Clock,
+    events: &EventCpi,
+) -> Result<(u64, u64)> {
+    let now = pool.now(clock);
+    require!(!user_stake.frozen, CustomError::UserStakeFrozen);
+
+    // Ensure user has enough staked
+    require!(user_stake.amount >= amount, CustomError::Unauthorized);
+
+    // Only tranches whose deposit lock has expired can be withdrawn
+    require!(
+        amount <= user_stake.unlocked_amount(now),
+        CustomError::StakeStillLocked
+    );
+
+    // Tokens under an active governance lock cannot be withdrawn
+    if clock.slot < user_stake.governance_lock_end_slot {
+        require!(
+            user_stake.amount - amount >= user_stake.governance_locked_amount,
+            CustomError::StakeLockedForGovernance
+        );
+    }
+
+    let pending = user_stake.settle_pending_reward(pool, now);
+    let total_rewards = pending.checked_add(user_stake.unclaimed).unwrap();
+
+    // Check if reward vault has sufficient balance to pay rewards
+    let reward_to_send = if reward_vault_balance >= total_rewards
+        && user_stake.rewards_unlocked(pool, now)
+    {
+        // Vault has enough - pay rewards now
+        total_rewards
+    } else {
+        // Vault insufficient or reward cliff not reached - keep rewards as unclaimed for later
+        0
+    };
+
+    // Update user state
+    if reward_to_send > 0 {
+        // Rewards paid out - clear unclaimed and update total earned
+        user_stake.total_earned = user_stake.total_earned.checked_add(reward_to_send).unwrap();
+        user_stake.unclaimed = 0;
+    } else {
+        // Rewards not paid - preserve all rewards (old unclaimed + new pending) for later withdrawal
+        // Note: total_rewards already includes user_stake.unclaimed
+        user_stake.unclaimed = total_rewards;
+    }
+
+    user_stake.remove_unlocked(amount, now);
+    user_stake.amount = user_stake.amount.checked_sub(amount).unwrap();
+    pool.total_staked = pool.total_staked.checked_sub(amount).unwrap();
+
+    if user_stake.update_reward_cap_status(pool) {
+        events.emit(RewardCapReachedEvent {
+            user: user_stake.owner,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            total_rewards: pool.max_reward_per_user,
+            slot: clock.slot,
+        })?;
+    }
+
+    Ok((reward_to_send, total_rewards))
+}
+
+/// Re-evaluates the depletion halt after the reward vault balance changed,
+/// emitting an event when accrual stops or resumes.
+fn sync_depletion_halt(
+    pool: &mut Pool,
+    pool_key: Pubkey,
+    reward_vault_balance: u64,
+    clock: &Clock,
+    events: &EventCpi,
+) -> Result<()> {
+    let slot = clock.slot;
+    match pool.update_depletion_state(reward_vault_balance, pool.now(clock)) {
+        Some((true, archived)) => {
+            emit_epoch_archived(pool_key, archived, clock, events)?;
+            events.emit(RewardAccrualHaltedEvent { pool: pool_key, slot })?;
+            msg!("Reward vault depleted: accrual halted at slot {}", slot);
+        }
+        Some((false, archived)) => {
+            emit_epoch_archived(pool_key, archived, clock, events)?;
+            events.emit(RewardAccrualResumedEvent {
+                pool: pool_key,
+                halted_since_slot: pool.accrual_halted_slot,
+                reward_vault_balance,
+                slot,
+            })?;
+            msg!("Reward accrual resumed at slot {}", slot);
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Emits EpochArchivedEvent for an epoch pruned from a pool's reward history
+fn emit_epoch_archived(
+    pool_key: Pubkey,
+    archived: Option<ArchivedEpoch>,
+    clock: &Clock,
+    events: &EventCpi,
+) -> Result<()> {
+    if let Some(archived) = archived {
+        events.emit(EpochArchivedEvent {
+            pool: pool_key,
+            reward_percentage: archived.reward_percentage,
+            start_slot: archived.start_slot,
+            end_slot: archived.end_slot,
+            archived_reward_index: archived.archived_reward_index,
+            slot: clock.slot,
+        })?;
+    }
+    Ok(())
+}
+
 #[program]
 pub mod stake_program {
     use super::*;
@@ -149,6 +615,7 @@ pub mod stake_program {
         maybe_owner: Option<Pubkey>,
         reward_percentage: u64,
         pool_id: u64,
+        end_slot: Option<u64>,
     ) -> Result<()> {
         // Enforce same-token staking: reward mint must be the same as the staking token mint.
         // This eliminates decimal mismatch issues and simplifies reward calculations.
@@ -164,60 +631,40 @@ pub mod stake_program {
             CustomError::UnauthorizedPoolCreator
         );
 
-        // Validate reward percentage to prevent accidental extreme values
-        // Format: Basis points (bps) - 10000 bps = 100% APY
-        // Examples: 550 bps = 5.50%, 1000 bps = 10.00%, 2500 bps = 25.00%
-        // - Allow 0 for no-reward staking
-        // - Cap at 100_000_000 bps (1,000,000% APY) to prevent typos and excess rewards
-        require!(
-            reward_percentage <= 100_000_000,
-            CustomError::InvalidRewardPercentage
-        );
+        // Ad hoc pools start from the default configuration with only the rate set
+        let params = PoolTemplateParams {
+            reward_percentage,
+            ..Default::default()
+        };
+        params.validate()?;
 
         let pool_key = ctx.accounts.pool.key(); // immutable borrow first
-        
-        // Initialize or update pool_id_counter
-        let pool_id_counter = &mut ctx.accounts.pool_id_counter;
-        if pool_id_counter.next_pool_id == 0 && pool_id_counter.token_mint == Pubkey::default() {
-            // First time initialization
-            pool_id_counter.token_mint = ctx.accounts.token_mint.key();
-            pool_id_counter.bump = ctx.bumps.pool_id_counter;
-        }
-        
-        // Validate pool_id matches expected next_pool_id for auto-increment
-        // This ensures pools are created in sequential order
-        require!(
-            pool_id == pool_id_counter.next_pool_id,
-            CustomError::InvalidPoolId
-        );
-        
-        // Increment counter for next pool (check for overflow)
-        pool_id_counter.next_pool_id = pool_id_counter.next_pool_id
-            .checked_add(1)
-            .ok_or(CustomError::PoolCounterOverflow)?;
+
+        ctx.accounts.pool_id_counter.claim_pool_id(
+            ctx.accounts.token_mint.key(),
+            ctx.bumps.pool_id_counter,
+            pool_id,
+        )?;
 
         let pool = &mut ctx.accounts.pool; // mutable borrow starts here
 
         // Set owner: user provided or fallback to admin (signer)
-        pool.owner = maybe_owner.unwrap_or(ctx.accounts.admin.key());
-        pool.token_mint = ctx.accounts.token_mint.key();
-        pool.reward_mint = ctx.accounts.reward_mint.key();
-        pool.reward_percentage = reward_percentage;
-        pool.total_staked = 0;
-        pool.bump = ctx.bumps.pool;
-        pool.reward_vault = ctx.accounts.reward_vault.key();
-        pool.is_active = true;
-        pool.pool_id = pool_id;
-        
-        // Initialize first reward epoch with current slot
+        pool.initialize(
+            pool_id,
+            ctx.bumps.pool,
+            maybe_owner.unwrap_or(ctx.accounts.admin.key()),
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.reward_vault.key(),
+            &params,
+        )?;
+
         let clock = Clock::get()?;
-        pool.reward_epochs = vec![RewardEpoch {
-            reward_percentage,
-            start_slot: clock.slot,
-        }];
-        pool.last_reward_update_slot = clock.slot;
-        
-        emit!(PoolCreatedEvent {
+        // Campaign pools may be created with a fixed end
+        if let Some(end_slot) = end_slot {
+            let now = pool.now(&clock);
+            pool.extend_end_slot(end_slot, now)?;
+        }
+        emit_cpi!(PoolCreatedEvent {
             pool: pool_key,
             token_mint: pool.token_mint,
             reward_mint: pool.reward_mint,
@@ -233,37 +680,195 @@ pub mod stake_program {
         Ok(())
     }
 
+    /// Create or update a pool template (global config admin only).
+    /// Pools created from the template inherit its parameters; pools that already
+    /// exist are not affected by later updates.
+    pub fn set_pool_template(
+        ctx: Context<SetPoolTemplate>,
+        template_id: u64,
+        params: PoolTemplateParams,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        params.validate()?;
+
+        let template = &mut ctx.accounts.template;
+        template.template_id = template_id;
+        template.params = params;
+        template.bump = ctx.bumps.template;
+
+        let clock = Clock::get()?;
+
+        emit_cpi!(PoolTemplateUpdatedEvent {
+            template: template.key(),
+            template_id,
+            params,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Pool template {} updated", template_id);
+
+        Ok(())
+    }
+
+    /// Create a pool whose reward rate, caps, lock and cooldown settings come from
+    /// an admin-managed template instead of being configured one by one
+    pub fn create_pool_from_template(
+        ctx: Context<CreatePoolFromTemplate>,
+        template_id: u64,
+        maybe_owner: Option<Pubkey>,
+        pool_id: u64,
+        end_slot: Option<u64>,
+    ) -> Result<()> {
+        // Enforce same-token staking, as in create_pool
+        require!(
+            ctx.accounts.token_mint.key() == ctx.accounts.reward_mint.key(),
+            CustomError::RewardMintMustMatchStakeMint
+        );
+
+        // Verify admin is the global config admin
+        require!(
+            ctx.accounts.config.admin == ctx.accounts.admin.key(),
+            CustomError::UnauthorizedPoolCreator
+        );
+
+        let params = ctx.accounts.template.params;
+        params.validate()?;
+
+        let pool_key = ctx.accounts.pool.key();
+
+        ctx.accounts.pool_id_counter.claim_pool_id(
+            ctx.accounts.token_mint.key(),
+            ctx.bumps.pool_id_counter,
+            pool_id,
+        )?;
+
+        let pool = &mut ctx.accounts.pool;
+        pool.initialize(
+            pool_id,
+            ctx.bumps.pool,
+            maybe_owner.unwrap_or(ctx.accounts.admin.key()),
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.reward_vault.key(),
+            &params,
+        )?;
+
+        let clock = Clock::get()?;
+        // Campaign pools may be created with a fixed end
+        if let Some(end_slot) = end_slot {
+            let now = pool.now(&clock);
+            pool.extend_end_slot(end_slot, now)?;
+        }
+        emit_cpi!(PoolCreatedEvent {
+            pool: pool_key,
+            token_mint: pool.token_mint,
+            reward_mint: pool.reward_mint,
+            owner: pool.owner,
+            reward_percentage: pool.reward_percentage,
+            slot: clock.slot,
+        });
+
+        msg!("Staking pool created from template {}", template_id);
+        msg!("Pool PDA: {}", pool_key);
+        msg!("Pool ID: {}", pool_id);
+
+        Ok(())
+    }
+
     pub fn get_pool_info(ctx: Context<GetPoolInfo>, _pool_id: u64) -> Result<PoolData> {
         let pool = &ctx.accounts.pool;
+        let clock = Clock::get()?;
         Ok(PoolData {
             token_mint: pool.token_mint,
             reward_vault: pool.reward_vault,
             reward_mint: pool.reward_mint,
             owner: pool.owner,
             total_staked: pool.total_staked,
-            reward_percentage: pool.reward_percentage,
+            reward_percentage: pool.current_reward_percentage(pool.now(&clock)),
             bump: pool.bump,
             is_active: pool.is_active,
             reward_epochs: pool.reward_epochs.clone(),
             last_reward_update_slot: pool.last_reward_update_slot,
             pool_id: pool.pool_id,
+            max_reward_per_user: pool.max_reward_per_user,
+            halt_on_depletion: pool.halt_on_depletion,
+            accrual_halted: pool.accrual_halted,
+            accrual_halted_slot: pool.accrual_halted_slot,
+            accrual_frozen: pool.accrual_frozen,
+            lock_duration_slots: pool.lock_duration_slots,
+            governance_program: pool.governance_program,
+            governance_realm: pool.governance_realm,
+            version: pool.version,
+            epoch_capacity: pool.epoch_capacity(),
+            time_basis: pool.time_basis,
+            periods_per_year: pool.periods_per_year(),
+            withdraw_guardian: pool.withdraw_guardian,
+            guardian_threshold: pool.guardian_threshold,
+            min_slots_between_rate_changes: pool.min_slots_between_rate_changes,
+            reward_cliff_slots: pool.reward_cliff_slots,
+            min_claim_amount: pool.min_claim_amount,
+            archived_reward_index: pool.archived_reward_index,
+            rounding_mode: pool.rounding_mode,
+            attestation_issuer: pool.attestation_issuer,
+            end_slot: pool.end_slot(),
+            booster_program: pool.booster_program,
+            lock_rate_at_deposit: pool.lock_rate_at_deposit,
+        })
+    }
+
+    /// Read one page of the pool's reward epoch history (oldest first).
+    /// Like get_pool_info, the RewardEpochPage is exposed as return data, so clients
+    /// can fetch long histories without deserializing the whole pool.
+    pub fn get_reward_epochs(
+        ctx: Context<GetPoolInfo>,
+        _pool_id: u64,
+        page: u32,
+        page_size: u32,
+    ) -> Result<RewardEpochPage> {
+        require!(
+            page_size > 0 && page_size <= MAX_EPOCH_PAGE_SIZE,
+            CustomError::InvalidPageSize
+        );
+
+        let epochs = &ctx.accounts.pool.reward_epochs;
+        let start = (page as usize).saturating_mul(page_size as usize).min(epochs.len());
+        let end = start.saturating_add(page_size as usize).min(epochs.len());
+
+        Ok(RewardEpochPage {
+            total: epochs.len() as u32,
+            page,
+            epochs: epochs[start..end].to_vec(),
         })
     }
 
     /// Toggle staking active status.
-    /// When paused (is_active = false), all user operations are blocked.
-    /// Only the pool owner can pause/unpause.
+    /// When paused (is_active = false), all user operations are blocked and no rewards
+    /// accrue: the pause is recorded as a zero-rate epoch and the configured rate
+    /// resumes with a new epoch on reactivation.
+    /// The pool owner or one of its pool admins can pause/unpause.
     pub fn set_staking_active(ctx: Context<SetStakingActive>, _pool_id: u64, active: bool) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
         require!(
-            pool.owner == ctx.accounts.admin.key(),
+            pool.is_operator(&ctx.accounts.admin.key(), ctx.accounts.pool_admins.as_deref()),
             CustomError::Unauthorized
         );
-        pool.is_active = active;
-        
+
         let clock = Clock::get()?;
+        let now = pool.now(&clock);
+        pool.apply_reward_schedule(now);
+        pool.is_active = active;
+        let archived = pool.sync_reward_epoch(now);
+        emit_epoch_archived(
+            pool.key(),
+            archived,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
         
-        emit!(PoolStakingActiveChangedEvent {
+        emit_cpi!(PoolStakingActiveChangedEvent {
             pool: pool.key(),
             is_active: active,
             admin: ctx.accounts.admin.key(),
@@ -299,7 +904,7 @@ pub mod stake_program {
 
         let clock = Clock::get()?;
         
-        emit!(PoolRewardMintUpdatedEvent {
+        emit_cpi!(PoolRewardMintUpdatedEvent {
             pool: pool.key(),
             new_reward_mint: pool.reward_mint,
             new_reward_vault: pool.reward_vault,
@@ -335,25 +940,37 @@ pub mod stake_program {
             CustomError::InvalidRewardPercentage
         );
 
-        let old_percentage = pool.reward_percentage;
         let clock = Clock::get()?;
-        
-        // Maintain a sliding window of reward epochs (max 10) for accurate reward calculations.
-        // When capacity is reached, the oldest epoch is removed before adding the new one.
-        if pool.reward_epochs.len() >= 10 {
-            pool.reward_epochs.remove(0);
+        let now = pool.now(&clock);
+        require!(
+            now.saturating_sub(pool.last_reward_update_slot) >= pool.min_slots_between_rate_changes,
+            CustomError::RateChangeTooSoon
+        );
+        pool.apply_reward_schedule(now);
+        let old_percentage = pool.reward_percentage;
+
+        // An immediate change supersedes any queued rate changes
+        let cancelled = pool.cancel_scheduled_epochs(now);
+        if cancelled > 0 {
+            msg!("Cancelled {} scheduled reward epoch(s)", cancelled);
         }
-        
-        pool.reward_epochs.push(RewardEpoch {
-            reward_percentage: new_percentage,
-            start_slot: clock.slot,
-        });
-        
+
         // Update current reward percentage and last update slot
         pool.reward_percentage = new_percentage;
-        pool.last_reward_update_slot = clock.slot;
+        pool.last_reward_update_slot = now;
+
+        // Start a new epoch at the effective rate (stays zero while accrual is halted;
+        // the configured rate takes effect when accrual resumes)
+        let effective_percentage = pool.effective_reward_percentage();
+        let archived = pool.push_reward_epoch(effective_percentage, now);
+        emit_epoch_archived(
+            pool.key(),
+            archived,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
         
-        emit!(PoolRewardPercentageUpdatedEvent {
+        emit_cpi!(PoolRewardPercentageUpdatedEvent {
             pool: pool.key(),
             old_percentage,
             new_percentage,
@@ -366,600 +983,4412 @@ pub mod stake_program {
         Ok(())
     }
 
-    /// Updates the pool authority (owner) - enables authority rotation and recovery
-    /// Only the current authority can call this function
-    pub fn update_pool_authority(
-        ctx: Context<UpdatePoolAuthority>,
-        new_authority: Pubkey,
+    /// Queue a reward rate change that takes effect at `effective_slot` (a slot or unix
+    /// timestamp, following the pool's time basis).
+    /// The change is appended as a reward epoch with a future start slot; reward
+    /// calculation ignores it until it starts, so the new APY can be announced and
+    /// committed in advance. At most MAX_SCHEDULED_EPOCHS changes can be queued.
+    /// Queued changes are cancelled if accrual is paused, halted or frozen before they
+    /// start, or if update_reward_percentage sets a new rate immediately.
+    pub fn schedule_reward_percentage(
+        ctx: Context<UpdateRewardPercentage>,
+        _pool_id: u64,
+        new_percentage: u64,
+        effective_slot: u64,
     ) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
 
-        // Only current pool owner can update authority
+        // Only pool owner can update
         require!(
-            pool.owner == ctx.accounts.current_authority.key(),
+            pool.owner == ctx.accounts.admin.key(),
             CustomError::Unauthorized
         );
 
-        // Validate new authority address
-        validate_authority_address(&new_authority, &pool.key())?;
-
-        let old_authority = pool.owner;
-        pool.owner = new_authority;
-
-        msg!("Pool authority updated");
-        msg!("Old authority: {}", old_authority);
-        msg!("New authority: {}", new_authority);
-
-        Ok(())
-    }
+        // Same bounds as update_reward_percentage
+        require!(
+            new_percentage <= 100_000_000,
+            CustomError::InvalidRewardPercentage
+        );
 
-    pub fn deposit_reward(ctx: Context<DepositReward>, _pool_id: u64, amount: u64) -> Result<()> {
-        let pool = &ctx.accounts.pool;
+        let clock = Clock::get()?;
+        let now = pool.now(&clock);
+        require!(!pool.is_accrual_gated(), CustomError::RewardAccrualGated);
 
-        // Only pool owner can deposit
+        // Epochs must stay chronological: after the current time and any queued change
+        let last_start_slot = pool.reward_epochs.last().map(|e| e.start_slot).unwrap_or(0);
         require!(
-            pool.owner == ctx.accounts.admin.key(),
-            CustomError::Unauthorized
+            effective_slot > now && effective_slot > last_start_slot,
+            CustomError::InvalidScheduledEpoch
+        );
+        // Queued changes must respect the same spacing as immediate ones
+        require!(
+            effective_slot.saturating_sub(last_start_slot.max(pool.last_reward_update_slot))
+                >= pool.min_slots_between_rate_changes,
+            CustomError::RateChangeTooSoon
+        );
+        require!(
+            pool.scheduled_epoch_count(now) < MAX_SCHEDULED_EPOCHS,
+            CustomError::TooManyScheduledEpochs
         );
 
-        // Transfer tokens from admin → reward_vault (PDA)
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.admin_reward_account.to_account_info(),
-            to: ctx.accounts.reward_vault.to_account_info(),
-            authority: ctx.accounts.admin.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        let archived = pool.push_reward_epoch(new_percentage, effective_slot);
+        emit_epoch_archived(
+            pool.key(),
+            archived,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
 
-        let clock = Clock::get()?;
-        
-        emit!(RewardDepositedEvent {
+        emit_cpi!(RewardPercentageScheduledEvent {
             pool: pool.key(),
-            amount,
+            new_percentage,
+            effective_slot,
             admin: ctx.accounts.admin.key(),
             slot: clock.slot,
         });
 
-        msg!("Reward deposited: {} tokens", amount);
+        msg!("Reward percentage {} scheduled for slot {}", new_percentage, effective_slot);
 
         Ok(())
     }
 
-    pub fn withdraw_reward(ctx: Context<WithdrawReward>, _pool_id: u64, amount: u64) -> Result<()> {
-        let pool = &ctx.accounts.pool;
+    /// Set the per-user lifetime reward cap (0 disables the cap).
+    /// Users whose earned + unclaimed rewards reach the cap stop accruing.
+    pub fn set_max_reward_per_user(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        max_reward_per_user: u64,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
 
-        // Only pool owner can withdraw
+        // Only pool owner can update
         require!(
             pool.owner == ctx.accounts.admin.key(),
             CustomError::Unauthorized
         );
 
-        // Validate withdrawal address for safety
-        validate_withdrawal_address(
-            &ctx.accounts.admin_reward_account.key(),
-            &ctx.accounts.admin_reward_account.owner,
-            &ctx.accounts.pool.key(),
-            Some(&ctx.accounts.reward_vault.key()),
-            &pool.token_mint,
-            Some(&pool.reward_mint),
-        )?;
-
-        let seeds = &[
-            b"staking_pool",
-            ctx.accounts.pool.token_mint.as_ref(),
-            &ctx.accounts.pool.pool_id.to_le_bytes(),
-            &[ctx.accounts.pool.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        // Transfer tokens from reward vault → admin
-        let cpi_accounts = token::Transfer {
-            from: ctx.accounts.reward_vault.to_account_info(),
-            to: ctx.accounts.admin_reward_account.to_account_info(),
-            authority: ctx.accounts.pool.to_account_info(), // vault authority is pool PDA
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer,
-        );
-        token::transfer(cpi_ctx, amount)?;
+        pool.max_reward_per_user = max_reward_per_user;
 
         let clock = Clock::get()?;
-        
-        emit!(RewardWithdrawnEvent {
+
+        emit_cpi!(PoolMaxRewardPerUserUpdatedEvent {
             pool: pool.key(),
-            amount,
+            max_reward_per_user,
             admin: ctx.accounts.admin.key(),
             slot: clock.slot,
         });
 
-        msg!("Admin withdrew {} reward tokens", amount);
+        msg!("Max reward per user set to {}", max_reward_per_user);
 
         Ok(())
     }
 
-    pub fn deposit_stake(ctx: Context<DepositStake>, _pool_id: u64, amount: u64) -> Result<()> {
+    /// Freeze or unfreeze reward accrual without pausing the pool.
+    /// Unlike set_staking_active, deposits, withdrawals and claims of already
+    /// accrued rewards keep working; only new accumulation stops (zero-rate epoch).
+    pub fn freeze_accrual(ctx: Context<UpdatePoolConfig>, _pool_id: u64, frozen: bool) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
         let pool = &mut ctx.accounts.pool;
-        let user_stake = &mut ctx.accounts.user_stake;
-        let user = &ctx.accounts.user;
-        let clock = Clock::get()?; // get current timestamp
-
-        // Check if pool is active
-        require!(pool.is_active, CustomError::StakingDisabled);
-
-        // Transfer tokens from user -> pool vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.pool_vault.to_account_info(),
-            authority: user.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
-
-        // Initialize UserStake if first time
-        if user_stake.amount == 0 {
-            // EDGE CASE: Account exists but has zero stake (after full withdrawal or reinitialization)
-            // SECURITY: Validate pool association to prevent resurrection attacks where an attacker
-            // attempts to reuse a zeroed account (after full withdrawal) for a different pool.
-            // Since the user controls their own account via PDA seeds (derived from user.key()),
-            // this check ensures the account can only be reused for the same pool it was created for.
-            if user_stake.owner != Pubkey::default() {
-                // Account was previously used - validate pool association to prevent reuse for different pool
-                require!(
-                    user_stake.pool == pool.key(),
-                    CustomError::InvalidPoolAssociation
-                );
-                // Account already exists (after full withdrawal) - preserve unclaimed rewards
-                // but add any new pending rewards since last action
-                let pending = user_stake.calculate_pending_reward(pool);
-                user_stake.unclaimed = user_stake.unclaimed.checked_add(pending).unwrap();
-            } else {
-                // First time initialization - set up account
-                user_stake.owner = user.key();
-                user_stake.pool = pool.key();
-                user_stake.total_earned = 0;
-                user_stake.unclaimed = 0;
-                user_stake.bump = ctx.bumps.user_stake;
-            }
-        } else {
-            // Existing stake with non-zero amount - validate pool association
-            require!(
-                user_stake.pool == pool.key(),
-                CustomError::InvalidPoolAssociation
-            );
-            let pending = user_stake.calculate_pending_reward(pool);
-            user_stake.unclaimed = user_stake.unclaimed.checked_add(pending).unwrap();
-        }
 
-        // Update user stake
-        user_stake.amount = user_stake.amount.checked_add(amount).unwrap();
-        user_stake.last_staked_slot = clock.slot;
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
 
-        // Update pool info
-        pool.total_staked = pool.total_staked.checked_add(amount).unwrap();
+        let clock = Clock::get()?;
+        let now = pool.now(&clock);
+        pool.apply_reward_schedule(now);
+        pool.accrual_frozen = frozen;
+        let archived = pool.sync_reward_epoch(now);
+        emit_epoch_archived(
+            pool_key,
+            archived,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
 
-        emit!(StakeDepositedEvent {
-            user: user.key(),
-            pool: pool.key(),
-            amount,
-            total_user_stake: user_stake.amount,
-            total_pool_stake: pool.total_staked,
+        emit_cpi!(PoolAccrualFrozenEvent {
+            pool: pool_key,
+            frozen,
+            admin: ctx.accounts.admin.key(),
             slot: clock.slot,
         });
 
-        msg!("{} tokens staked by {}", amount, user.key());
-        msg!("Total staked in pool: {}", pool.total_staked);
+        msg!("Reward accrual {}", if frozen { "frozen" } else { "unfrozen" });
 
         Ok(())
     }
 
-    pub fn get_user_stake_info(ctx: Context<GetUserStakeInfo>, _pool_id: u64) -> Result<UserStakeData> {
-        let user_stake = &ctx.accounts.user_stake;
-        Ok(UserStakeData {
-            owner: user_stake.owner,
-            pool: user_stake.pool,
-            amount: user_stake.amount,
-            total_earned: user_stake.total_earned,
-            last_staked_slot: user_stake.last_staked_slot,
-            unclaimed: user_stake.unclaimed,
-            bump: user_stake.bump,
-        })
-    }
-
-    pub fn get_user_stake_with_reward(
-        ctx: Context<GetUserStakeInfo>,
+    /// Set the lock applied to each new deposit, in units of the pool's time basis.
+    /// Existing tranches keep the lock they were deposited with.
+    pub fn set_lock_duration(
+        ctx: Context<UpdatePoolConfig>,
         _pool_id: u64,
-    ) -> Result<UserStakeInfoWithReward> {
-        let user_stake = &ctx.accounts.user_stake;
-        let pool = &ctx.accounts.pool;
+        lock_duration_slots: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
 
-        let pending_reward = user_stake.calculate_pending_reward(pool);
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        require!(
+            lock_duration_slots <= MAX_DEPOSIT_LOCK_YEARS * pool.periods_per_year(),
+            CustomError::InvalidLockDuration
+        );
 
-        Ok(UserStakeInfoWithReward {
-            owner: user_stake.owner,
-            pool: user_stake.pool,
-            amount: user_stake.amount,
-            total_earned: user_stake.total_earned,
-            last_staked_slot: user_stake.last_staked_slot,
-            unclaimed: user_stake.unclaimed,
-            bump: user_stake.bump,
-            pending_reward,
-        })
-    }
+        let clock = Clock::get()?;
+        let old_lock_duration_slots = pool.lock_duration_slots;
+        pool.lock_duration_slots = lock_duration_slots;
 
-    pub fn withdraw_stake(ctx: Context<WithdrawStake>, _pool_id: u64, amount: u64) -> Result<()> {
-        let pool = &mut ctx.accounts.pool;
-        let user_stake = &mut ctx.accounts.user_stake;
+        emit_cpi!(PoolLockDurationUpdatedEvent {
+            pool: pool_key,
+            old_lock_duration_slots,
+            new_lock_duration_slots: lock_duration_slots,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
 
-        let clock = Clock::get()?; // get current timestamp
+        msg!("Deposit lock duration set to {} slots", lock_duration_slots);
 
-        // Check if pool is active
-        require!(pool.is_active, CustomError::StakingDisabled);
+        Ok(())
+    }
 
-        // Ensure user has enough staked
-        require!(user_stake.amount >= amount, CustomError::Unauthorized);
+    /// Set the minimum spacing between reward rate changes (update_reward_percentage
+    /// and scheduled changes), so the epoch history cannot be cycled in quick succession.
+    pub fn set_rate_change_cooldown(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        min_slots_between_rate_changes: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
 
-        let pending = user_stake.calculate_pending_reward(pool);
-        let total_rewards = pending.checked_add(user_stake.unclaimed).unwrap();
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
 
-        // Check if reward vault has sufficient balance to pay rewards
-        let reward_to_send = if ctx.accounts.reward_vault.amount >= total_rewards {
-            // Vault has enough - pay rewards now
-            total_rewards
-        } else {
-            // Vault insufficient - keep rewards as unclaimed for later
-            0
-        };
+        let clock = Clock::get()?;
+        let old_min_slots = pool.min_slots_between_rate_changes;
+        pool.min_slots_between_rate_changes = min_slots_between_rate_changes;
 
-        // Update user state
-        if reward_to_send > 0 {
-            // Rewards paid out - clear unclaimed and update total earned
-            user_stake.total_earned = user_stake.total_earned.checked_add(reward_to_send).unwrap();
-            user_stake.unclaimed = 0;
-        } else {
-            // Rewards not paid - preserve all rewards (old unclaimed + new pending) for later withdrawal
-            // Note: total_rewards already includes user_stake.unclaimed from line 288
-            user_stake.unclaimed = total_rewards;
-        }
+        emit_cpi!(PoolRateChangeCooldownUpdatedEvent {
+            pool: pool_key,
+            old_min_slots,
+            new_min_slots: min_slots_between_rate_changes,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
 
-        user_stake.amount = user_stake.amount.checked_sub(amount).unwrap();
-        user_stake.last_staked_slot = clock.slot;
-        pool.total_staked = pool.total_staked.checked_sub(amount).unwrap();
+        msg!("Minimum spacing between rate changes set to {}", min_slots_between_rate_changes);
 
-        // Transfer staked tokens from pool vault -> user
-        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
-        let signer = &[&seeds[..]];
+        Ok(())
+    }
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.pool_vault.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
-            amount,
-        )?;
+    /// Choose how settled rewards are rounded to whole token units
+    pub fn set_rounding_mode(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        rounding_mode: RoundingMode,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
 
-        // Transfer rewards if vault has sufficient balance
-        if reward_to_send > 0 {
-            token::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.reward_vault.to_account_info(),
-                        to: ctx.accounts.user_reward_account.to_account_info(),
-                        authority: pool.to_account_info(),
-                    },
-                    signer,
-                ),
-                reward_to_send,
-            )?;
-        }
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
 
-        emit!(StakeWithdrawnEvent {
-            user: ctx.accounts.user.key(),
-            pool: pool.key(),
-            amount,
-            rewards_sent: reward_to_send,
-            rewards_unclaimed: user_stake.unclaimed,
-            remaining_user_stake: user_stake.amount,
-            total_pool_stake: pool.total_staked,
+        let clock = Clock::get()?;
+        let old_rounding_mode = pool.rounding_mode;
+        pool.rounding_mode = rounding_mode;
+
+        emit_cpi!(PoolRoundingModeUpdatedEvent {
+            pool: pool_key,
+            old_rounding_mode,
+            new_rounding_mode: rounding_mode,
+            admin: ctx.accounts.admin.key(),
             slot: clock.slot,
         });
 
-        if reward_to_send > 0 {
-            msg!("Withdrawn stake: {}", amount);
-            msg!("Rewards sent: {}", reward_to_send);
-        } else {
-            msg!("Withdrawn stake: {}", amount);
-            msg!("Rewards unavailable (vault empty). {} tokens saved as unclaimed.", total_rewards);
-        }
+        msg!("Rounding mode set to {:?}", rounding_mode);
 
         Ok(())
     }
 
-    pub fn claim_reward(ctx: Context<ClaimReward>, _pool_id: u64) -> Result<()> {
-        let pool = &ctx.accounts.pool;
-        let user_stake = &mut ctx.accounts.user_stake;
-        let clock = Clock::get()?;
-
-        // Check if pool is active
-        require!(pool.is_active, CustomError::StakingDisabled);
+    /// Set or extend the pool's end (in the pool's time basis). An open-ended pool can
+    /// be given any future end; an existing end can only be pushed later, and only
+    /// before it has passed, so stakers never lose rewards they were promised.
+    /// Withdrawals and claims stay open after the end.
+    pub fn set_end_slot(ctx: Context<UpdatePoolConfig>, _pool_id: u64, end_slot: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
 
-        // Ensure user has some stake or unclaimed rewards
+        // Only pool owner can update
         require!(
-            user_stake.amount > 0 || user_stake.unclaimed > 0,
-            CustomError::NoRewardsAvailable
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
         );
 
-        // Calculate pending rewards
-        let pending = user_stake.calculate_pending_reward(pool);
-        let total_reward = pending.checked_add(user_stake.unclaimed).unwrap();
-
-        require!(total_reward > 0, CustomError::NoRewardsAvailable);
+        let clock = Clock::get()?;
+        let old_end_slot = pool.end_slot();
+        let now = pool.now(&clock);
+        pool.extend_end_slot(end_slot, now)?;
 
-        // Check reward vault has sufficient balance
+        emit_cpi!(PoolEndSlotUpdatedEvent {
+            pool: pool_key,
+            old_end_slot,
+            new_end_slot: end_slot,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Pool end set to {}", end_slot);
+
+        Ok(())
+    }
+
+    /// Gate deposits on a credential: stakers must present a token account of the
+    /// `attestation_issuer` mint (e.g. a non-transferable KYC token) holding a balance.
+    /// Pubkey::default() removes the gate. Existing positions can always withdraw.
+    pub fn set_attestation_issuer(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        attestation_issuer: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        let old_attestation_issuer = pool.attestation_issuer;
+        pool.attestation_issuer = attestation_issuer;
+
+        emit_cpi!(PoolAttestationIssuerUpdatedEvent {
+            pool: pool_key,
+            old_attestation_issuer,
+            new_attestation_issuer: attestation_issuer,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Attestation issuer set to {}", attestation_issuer);
+
+        Ok(())
+    }
+
+    /// Set the smallest reward claim_reward will pay out, to avoid dust claims.
+    /// withdraw_stake still settles rewards of any size.
+    pub fn set_min_claim_amount(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        min_claim_amount: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        let old_min_claim_amount = pool.min_claim_amount;
+        pool.min_claim_amount = min_claim_amount;
+
+        emit_cpi!(PoolMinClaimAmountUpdatedEvent {
+            pool: pool_key,
+            old_min_claim_amount,
+            new_min_claim_amount: min_claim_amount,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Minimum claim amount set to {}", min_claim_amount);
+
+        Ok(())
+    }
+
+    /// Set the reward cliff: rewards of a position cannot be claimed until its first
+    /// deposit is `reward_cliff_slots` old (pool time basis), then unlock all at once.
+    /// Accrual is unaffected; withdrawals before the cliff keep rewards as unclaimed.
+    pub fn set_reward_cliff(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        reward_cliff_slots: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        require!(
+            reward_cliff_slots <= MAX_DEPOSIT_LOCK_YEARS * pool.periods_per_year(),
+            CustomError::InvalidRewardCliff
+        );
+
+        let clock = Clock::get()?;
+        let old_cliff_slots = pool.reward_cliff_slots;
+        pool.reward_cliff_slots = reward_cliff_slots;
+
+        emit_cpi!(PoolRewardCliffUpdatedEvent {
+            pool: pool_key,
+            old_cliff_slots,
+            new_cliff_slots: reward_cliff_slots,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Reward cliff set to {}", reward_cliff_slots);
+
+        Ok(())
+    }
+
+    /// Tune the number of time-basis units per year used to turn the APY into a
+    /// per-unit rate, e.g. to track actual slot times. Only applies from now on: the
+    /// epoch history is archived under the old divisor (as when epochs are pruned) and
+    /// a new epoch starts at the current rate. Scaled rewards stay in the divisor pinned
+    /// by the first change, so carried rounding remainders keep their value. Subject to
+    /// the same cooldown as rate changes.
+    pub fn set_periods_per_year(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        periods_per_year: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        require!(pool.version >= 2, CustomError::PoolNotMigrated);
+
+        // Bound the divisor around the nominal value so a typo cannot multiply the APY
+        let nominal = pool.time_basis.periods_per_year();
+        require!(
+            periods_per_year >= nominal / 2 && periods_per_year <= nominal * 2,
+            CustomError::InvalidPeriodsPerYear
+        );
+
+        let clock = Clock::get()?;
+        let now = pool.now(&clock);
+        require!(
+            now.saturating_sub(pool.last_reward_update_slot) >= pool.min_slots_between_rate_changes,
+            CustomError::RateChangeTooSoon
+        );
+
+        // Pin the unit scaled rewards are kept in, then fix accrual so far under the
+        // old divisor before switching
+        let old_periods_per_year = pool.periods_per_year();
+        pool.reward_scale_periods = pool.reward_scale_periods();
+        pool.archive_reward_epochs(now);
+        pool.periods_per_year = periods_per_year;
+        pool.last_reward_update_slot = now;
+
+        emit_cpi!(PoolPeriodsPerYearUpdatedEvent {
+            pool: pool_key,
+            old_periods_per_year,
+            new_periods_per_year: periods_per_year,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Periods per year set to {}", periods_per_year);
+
+        Ok(())
+    }
+
+    /// Lock each deposit's reward rate at the rate in effect when it is made (fixed-term
+    /// products). Later rate changes then only affect new deposits, while pauses, halts
+    /// and freezes still stop accrual. Only allowed while nothing is staked.
+    pub fn set_lock_rate_at_deposit(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        require!(pool.total_staked == 0, CustomError::PoolHasActiveStakers);
+
+        let clock = Clock::get()?;
+        pool.lock_rate_at_deposit = enabled;
+
+        emit_cpi!(PoolLockRateAtDepositUpdatedEvent {
+            pool: pool_key,
+            enabled,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Lock rate at deposit {}", if enabled { "enabled" } else { "disabled" });
+
+        Ok(())
+    }
+
+    /// Switch the clock the pool accrues on. Only allowed while nothing is staked:
+    /// the epoch history is reset to a single epoch at the current rate starting now
+    /// (queued rate changes are dropped) and the APY divisor is reset to the basis
+    /// default. The deposit lock duration is kept as-is and read in the new unit; the
+    /// pool end is cleared since it cannot be translated between clocks.
+    pub fn set_time_basis(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        time_basis: TimeBasis,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        require!(pool.total_staked == 0, CustomError::PoolHasActiveStakers);
+
+        let clock = Clock::get()?;
+        let old_now = pool.now(&clock);
+        pool.apply_reward_schedule(old_now);
+
+        let old_time_basis = pool.time_basis;
+        pool.time_basis = time_basis;
+        pool.periods_per_year = time_basis.periods_per_year();
+
+        let now = pool.now(&clock);
+        pool.reward_epochs = vec![RewardEpoch {
+            reward_percentage: pool.effective_reward_percentage(),
+            start_slot: now,
+        }];
+        pool.last_reward_update_slot = now;
+        if pool.accrual_halted {
+            pool.accrual_halted_slot = now;
+        }
+        pool.end_slot = 0;
+
+        emit_cpi!(PoolTimeBasisUpdatedEvent {
+            pool: pool_key,
+            old_time_basis,
+            new_time_basis: time_basis,
+            periods_per_year: pool.periods_per_year,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Pool time basis set to {:?}", time_basis);
+
+        Ok(())
+    }
+
+    /// Configure the guardian that must co-sign reward withdrawals once the owner has
+    /// withdrawn more than `threshold` on their own. Pass Pubkey::default() to remove it. While a guardian is set, changing or
+    /// removing it (or the threshold) also needs the current guardian's signature,
+    /// so a single compromised owner key cannot switch the protection off.
+    pub fn set_withdraw_guardian(
+        ctx: Context<SetWithdrawGuardian>,
+        _pool_id: u64,
+        guardian: Pubkey,
+        threshold: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let old_guardian = pool.withdraw_guardian;
+        if old_guardian != Pubkey::default() {
+            require!(
+                ctx.accounts
+                    .current_guardian
+                    .as_ref()
+                    .is_some_and(|g| g.key() == old_guardian),
+                CustomError::GuardianSignatureRequired
+            );
+        }
+
+        pool.withdraw_guardian = guardian;
+        pool.guardian_threshold = threshold;
+        pool.unguarded_withdrawn = 0;
+
+        let clock = Clock::get()?;
+        emit_cpi!(WithdrawGuardianUpdatedEvent {
+            pool: pool_key,
+            old_guardian,
+            new_guardian: guardian,
+            threshold,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Withdraw guardian set to {} (threshold {})", guardian, threshold);
+
+        Ok(())
+    }
+
+    /// Grow the pool account so it can keep more reward epochs before the oldest
+    /// are dropped. The owner pays rent for the extra space; capacity can only grow.
+    pub fn expand_pool_epochs(
+        ctx: Context<ExpandPoolEpochs>,
+        _pool_id: u64,
+        new_capacity: u16,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let old_capacity = pool.epoch_capacity();
+        require!(
+            new_capacity > old_capacity && new_capacity <= MAX_EPOCH_CAPACITY,
+            CustomError::InvalidEpochCapacity
+        );
+
+        let clock = Clock::get()?;
+        pool.epoch_capacity = new_capacity;
+
+        emit_cpi!(PoolEpochCapacityExpandedEvent {
+            pool: pool_key,
+            old_capacity,
+            new_capacity,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Reward epoch capacity expanded from {} to {}", old_capacity, new_capacity);
+
+        Ok(())
+    }
+
+    /// Enable or disable halting accrual while the reward vault is empty.
+    /// When enabled, accrual stops (a zero-rate epoch is recorded) as soon as the vault
+    /// balance hits zero and resumes at the configured rate after a refill.
+    pub fn set_halt_on_depletion(
+        ctx: Context<SetHaltOnDepletion>,
+        _pool_id: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        pool.halt_on_depletion = enabled;
+
+        // Apply immediately against the current vault balance
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+
+        emit_cpi!(PoolHaltOnDepletionUpdatedEvent {
+            pool: pool_key,
+            enabled,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Halt on depletion {}", if enabled { "enabled" } else { "disabled" });
+
+        Ok(())
+    }
+
+    /// Updates the pool authority (owner) - enables authority rotation and recovery
+    /// Only the current authority can call this function
+    pub fn update_pool_authority(
+        ctx: Context<UpdatePoolAuthority>,
+        _pool_id: u64,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only current pool owner can update authority
+        require!(
+            pool.owner == ctx.accounts.current_authority.key(),
+            CustomError::Unauthorized
+        );
+
+        // Validate new authority address
+        validate_authority_address(&new_authority, &pool_key)?;
+
+        let old_authority = pool.owner;
+        pool.owner = new_authority;
+
+        // Ownership leaving a governance drops the realm binding
+        pool.governance_program = Pubkey::default();
+        pool.governance_realm = Pubkey::default();
+
+        let clock = Clock::get()?;
+        emit_cpi!(PoolAuthorityUpdatedEvent {
+            pool: pool_key,
+            old_authority,
+            new_authority,
+            slot: clock.slot,
+        });
+
+        msg!("Pool authority updated");
+        msg!("Old authority: {}", old_authority);
+        msg!("New authority: {}", new_authority);
+
+        Ok(())
+    }
+
+    /// Grant `pool_admin` day-to-day operator rights on the pool: pausing/unpausing
+    /// staking and depositing or scheduling rewards. Withdrawing rewards, config changes
+    /// and authority rotation stay with the owner. Only the pool owner can do this;
+    /// the admin list is created on first use (owner funds rent).
+    pub fn add_pool_admin(
+        ctx: Context<ManagePoolAdmins>,
+        _pool_id: u64,
+        pool_admin: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+        validate_authority_address(&pool_admin, &pool_key)?;
+        require!(pool_admin != pool.owner, CustomError::DuplicatePoolAdmin);
+
+        let pool_admins = &mut ctx.accounts.pool_admins;
+        pool_admins.pool = pool_key;
+        pool_admins.bump = ctx.bumps.pool_admins;
+        require!(
+            !pool_admins.admins.contains(&pool_admin),
+            CustomError::DuplicatePoolAdmin
+        );
+        require!(
+            pool_admins.admins.len() < MAX_POOL_ADMINS,
+            CustomError::PoolAdminLimitReached
+        );
+        pool_admins.admins.push(pool_admin);
+
+        let clock = Clock::get()?;
+        emit_cpi!(PoolAdminsUpdatedEvent {
+            pool: pool_key,
+            pool_admin,
+            added: true,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Pool admin added: {}", pool_admin);
+
+        Ok(())
+    }
+
+    /// Revoke a pool admin's operator rights. Only the pool owner can do this.
+    pub fn remove_pool_admin(
+        ctx: Context<ManagePoolAdmins>,
+        _pool_id: u64,
+        pool_admin: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+
+        // Only pool owner can update
+        require!(
+            ctx.accounts.pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let pool_admins = &mut ctx.accounts.pool_admins;
+        let index = pool_admins
+            .admins
+            .iter()
+            .position(|a| *a == pool_admin)
+            .ok_or(CustomError::PoolAdminNotFound)?;
+        pool_admins.admins.remove(index);
+
+        let clock = Clock::get()?;
+        emit_cpi!(PoolAdminsUpdatedEvent {
+            pool: pool_key,
+            pool_admin,
+            added: false,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Pool admin removed: {}", pool_admin);
+
+        Ok(())
+    }
+
+    /// Hand pool ownership to an spl-governance governance PDA.
+    /// The governance account must be the PDA [b"account-governance", realm, governance_seed]
+    /// of `governance_program` and, like the realm, must already exist under that program.
+    /// Afterwards owner-only instructions (update_reward_percentage, withdraw_reward,
+    /// set_staking_active, ...) are executed through governance proposals: spl-governance
+    /// signs for the governance PDA when it CPIs into this program, so the PDA is
+    /// accepted as the `admin` signer like any other owner.
+    /// Only the current pool owner can do this.
+    pub fn set_pool_governance(
+        ctx: Context<SetPoolGovernance>,
+        _pool_id: u64,
+        governance_seed: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let governance_program = ctx.accounts.governance_program.key();
+        let realm = ctx.accounts.realm.key();
+        let (expected_governance, _) = Pubkey::find_program_address(
+            &[SPL_GOVERNANCE_ACCOUNT_SEED, realm.as_ref(), governance_seed.as_ref()],
+            &governance_program,
+        );
+        require_keys_eq!(
+            ctx.accounts.governance.key(),
+            expected_governance,
+            CustomError::InvalidGovernanceAccount
+        );
+        require!(
+            *ctx.accounts.realm.owner == governance_program
+                && *ctx.accounts.governance.owner == governance_program,
+            CustomError::InvalidGovernanceAccount
+        );
+
+        let clock = Clock::get()?;
+        let previous_owner = pool.owner;
+        pool.owner = expected_governance;
+        pool.governance_program = governance_program;
+        pool.governance_realm = realm;
+
+        emit_cpi!(PoolGovernanceSetEvent {
+            pool: pool_key,
+            governance_program,
+            realm,
+            governance: expected_governance,
+            previous_owner,
+            slot: clock.slot,
+        });
+
+        msg!("Pool owner set to governance {}", expected_governance);
+        msg!("Realm: {}", realm);
+
+        Ok(())
+    }
+
+    pub fn deposit_reward(ctx: Context<DepositReward>, _pool_id: u64, amount: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Pool owner or a pool admin can deposit
+        require!(
+            pool.is_operator(&ctx.accounts.admin.key(), ctx.accounts.pool_admins.as_deref()),
+            CustomError::Unauthorized
+        );
+
+        // Transfer tokens from admin → reward_vault (PDA)
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.admin_reward_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.admin.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let clock = Clock::get()?;
+
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        
+        emit_cpi!(RewardDepositedEvent {
+            pool: pool_key,
+            amount,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Reward deposited: {} tokens", amount);
+
+        Ok(())
+    }
+
+    /// Withdraw reward tokens from the vault (pool owner only). With a withdraw guardian
+    /// set, the owner alone may withdraw at most guardian_threshold in total; beyond that
+    /// the guardian must co-sign, which also resets the owner's allowance.
+    pub fn withdraw_reward(ctx: Context<WithdrawReward>, _pool_id: u64, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can withdraw
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        // Withdrawals are summed until the guardian co-signs, so splitting a large
+        // withdrawal into small ones does not get around the threshold
+        if pool.withdraw_guardian != Pubkey::default() {
+            let guardian_signed = ctx
+                .accounts
+                .guardian
+                .as_ref()
+                .is_some_and(|g| g.key() == pool.withdraw_guardian);
+            if guardian_signed {
+                pool.unguarded_withdrawn = 0;
+            } else {
+                let unguarded_withdrawn = pool.unguarded_withdrawn.saturating_add(amount);
+                require!(
+                    unguarded_withdrawn <= pool.guardian_threshold,
+                    CustomError::GuardianSignatureRequired
+                );
+                pool.unguarded_withdrawn = unguarded_withdrawn;
+            }
+        }
+        let pool = &ctx.accounts.pool;
+
+        // Validate withdrawal address for safety
+        validate_withdrawal_address(
+            &ctx.accounts.admin_reward_account.key(),
+            &ctx.accounts.admin_reward_account.owner,
+            &ctx.accounts.pool.key(),
+            Some(&ctx.accounts.reward_vault.key()),
+            &pool.token_mint,
+            Some(&pool.reward_mint),
+        )?;
+
+        let seeds = &[
+            b"staking_pool",
+            ctx.accounts.pool.token_mint.as_ref(),
+            &ctx.accounts.pool.pool_id.to_le_bytes(),
+            &[ctx.accounts.pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        // Transfer tokens from reward vault → admin
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.admin_reward_account.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(), // vault authority is pool PDA
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let clock = Clock::get()?;
+
+        let pool_key = ctx.accounts.pool.key();
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(&mut ctx.accounts.pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        
+        emit_cpi!(RewardWithdrawnEvent {
+            pool: pool_key,
+            amount,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Admin withdrew {} reward tokens", amount);
+
+        Ok(())
+    }
+
+    /// Escrow rewards to be streamed into the reward vault linearly between
+    /// start_slot and end_slot, so funding tracks accrual instead of landing upfront.
+    /// Only one stream per pool can be active; a new one may be scheduled once the
+    /// previous stream has been fully released.
+    pub fn schedule_reward_deposit(
+        ctx: Context<ScheduleRewardDeposit>,
+        _pool_id: u64,
+        total_amount: u64,
+        start_slot: u64,
+        end_slot: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let stream = &mut ctx.accounts.reward_stream;
+        let clock = Clock::get()?;
+
+        // Pool owner or a pool admin can schedule deposits
+        require!(
+            pool.is_operator(&ctx.accounts.admin.key(), ctx.accounts.pool_admins.as_deref()),
+            CustomError::Unauthorized
+        );
+        require!(
+            total_amount > 0 && end_slot > start_slot && end_slot > clock.slot,
+            CustomError::InvalidRewardStream
+        );
+        require!(!stream.is_active(), CustomError::RewardStreamActive);
+
+        // Escrow the full amount up front
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin_reward_account.to_account_info(),
+                    to: ctx.accounts.stream_escrow.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                },
+            ),
+            total_amount,
+        )?;
+
+        stream.pool = pool.key();
+        stream.total_amount = total_amount;
+        stream.released_amount = 0;
+        stream.start_slot = start_slot;
+        stream.end_slot = end_slot;
+        stream.bump = ctx.bumps.reward_stream;
+
+        emit_cpi!(RewardStreamScheduledEvent {
+            pool: pool.key(),
+            total_amount,
+            start_slot,
+            end_slot,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!(
+            "Scheduled {} reward tokens streaming from slot {} to {}",
+            total_amount,
+            start_slot,
+            end_slot
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless crank: release the vested portion of the reward stream
+    /// from escrow into the reward vault.
+    pub fn release_streamed_rewards(ctx: Context<ReleaseStreamedRewards>, _pool_id: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let stream = &mut ctx.accounts.reward_stream;
+        let clock = Clock::get()?;
+
+        let releasable = stream
+            .vested_amount(clock.slot)
+            .saturating_sub(stream.released_amount);
+        require!(releasable > 0, CustomError::NothingToRelease);
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.stream_escrow.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            releasable,
+        )?;
+
+        stream.released_amount = stream.released_amount.checked_add(releasable).unwrap();
+
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+
+        emit_cpi!(RewardStreamReleasedEvent {
+            pool: pool_key,
+            amount: releasable,
+            total_released: stream.released_amount,
+            remaining: stream.total_amount - stream.released_amount,
+            slot: clock.slot,
+        });
+
+        msg!(
+            "Released {} streamed reward tokens ({} / {})",
+            releasable,
+            stream.released_amount,
+            stream.total_amount
+        );
+
+        Ok(())
+    }
+
+    /// Create a user's stake position for a pool ahead of the first deposit.
+    /// The payer may differ from the user, so integrators can pre-create accounts
+    /// from a separate fee-payer transaction (e.g. before a relayed deposit).
+    /// A wallet may hold several independent positions per pool; position_index
+    /// must equal the user's next index (0 for the first position).
+    pub fn init_user_stake(ctx: Context<InitUserStake>, _pool_id: u64, position_index: u64) -> Result<()> {
+        // Initialize or update the user's position counter
+        let user_positions = &mut ctx.accounts.user_positions;
+        if user_positions.next_position_index == 0 && user_positions.owner == Pubkey::default() {
+            // First time initialization
+            user_positions.owner = ctx.accounts.user.key();
+            user_positions.pool = ctx.accounts.pool.key();
+            user_positions.bump = ctx.bumps.user_positions;
+        }
+
+        // Positions are created in sequential order
+        require!(
+            position_index == user_positions.next_position_index,
+            CustomError::InvalidPositionIndex
+        );
+        user_positions.next_position_index = user_positions.next_position_index
+            .checked_add(1)
+            .ok_or(CustomError::InvalidPositionIndex)?;
+
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        user_stake.owner = ctx.accounts.user.key();
+        user_stake.pool = ctx.accounts.pool.key();
+        user_stake.position_index = position_index;
+        user_stake.tranches = Vec::new();
+        user_stake.frozen = false;
+        user_stake.auto_claim = false;
+        user_stake.keeper_fee = 0;
+        user_stake.version = USER_STAKE_VERSION;
+        user_stake.first_deposit_slot = 0;
+        user_stake.reward_remainder = 0;
+        user_stake.partner_tag = [0; 16];
+        user_stake.boost_bps = 0;
+        user_stake.amount = 0;
+        user_stake.total_earned = 0;
+        user_stake.unclaimed = 0;
+        user_stake.checkpoint(&ctx.accounts.pool, ctx.accounts.pool.now(&clock));
+        user_stake.bump = ctx.bumps.user_stake;
+
+        emit_cpi!(UserStakeInitializedEvent {
+            user: user_stake.owner,
+            pool: user_stake.pool,
+            position_index: user_stake.position_index,
+            payer: ctx.accounts.payer.key(),
+            slot: clock.slot,
+        });
+
+        msg!("User stake account created for {}", user_stake.owner);
+
+        Ok(())
+    }
+
+    /// Upgrade a user stake account to the current layout (USER_STAKE_VERSION).
+    /// The account is grown to USER_STAKE_SPACE if needed (the payer covers the extra
+    /// rent; new bytes are zeroed) and then re-serialized with the current version.
+    /// Layout upgrades that need more than zero defaults are applied here, keyed on the
+    /// stored version. Permissionless: it never changes balances or rewards.
+    pub fn migrate_user_stake(ctx: Context<MigrateUserStake>, _pool_id: u64) -> Result<()> {
+        let info = ctx.accounts.user_stake.to_account_info();
+
+        if info.data_len() < USER_STAKE_SPACE {
+            let rent_due = Rent::get()?
+                .minimum_balance(USER_STAKE_SPACE)
+                .saturating_sub(info.lamports());
+            if rent_due > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: ctx.accounts.payer.to_account_info(),
+                            to: info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            info.resize(USER_STAKE_SPACE)?;
+        }
+
+        let mut user_stake = UserStake::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            user_stake.pool == ctx.accounts.pool.key(),
+            CustomError::InvalidPoolAssociation
+        );
+
+        let from_version = user_stake.version;
+        require!(
+            from_version < USER_STAKE_VERSION,
+            CustomError::AlreadyMigrated
+        );
+
+        // Version 0 -> 1: fields were appended; the stake predates deposit tranches, so
+        // it becomes a single unlocked tranche (without one nothing is withdrawable)
+        if from_version == 0 && user_stake.tranches.is_empty() && user_stake.amount > 0 {
+            user_stake.tranches.push(StakeTranche {
+                amount: user_stake.amount,
+                deposit_slot: user_stake.last_staked_slot,
+                lock_end_slot: 0,
+                reward_percentage: ctx.accounts.pool.reward_percentage,
+            });
+        }
+        // Version 1 -> 2: snapshot the reward index at the checkpoint; time before the
+        // oldest retained epoch was never archived for this position, so it is skipped
+        let pool = &ctx.accounts.pool;
+        let oldest_start = pool.reward_epochs.first().map(|e| e.start_slot).unwrap_or(0);
+        user_stake.reward_index_snapshot =
+            pool.reward_index_at(user_stake.last_staked_slot.max(oldest_start));
+        user_stake.active_slots_snapshot =
+            pool.active_slots_at(user_stake.last_staked_slot.max(oldest_start));
+        user_stake.version = USER_STAKE_VERSION;
+        user_stake.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(UserStakeMigratedEvent {
+            user: user_stake.owner,
+            pool: user_stake.pool,
+            position_index: user_stake.position_index,
+            from_version,
+            to_version: USER_STAKE_VERSION,
+            slot: clock.slot,
+        });
+
+        msg!("User stake migrated from version {} to {}", from_version, USER_STAKE_VERSION);
+
+        Ok(())
+    }
+
+    /// Upgrade a pool account to the current layout (POOL_VERSION). The account is
+    /// grown by the space later layouts added (the payer covers the extra rent) and the
+    /// new fields are reset to their defaults, since bytes past the old layout's data
+    /// may be stale. Permissionless: it never changes balances or rewards.
+    /// Version 0 pools, and version 1 pools whose epoch history fills the account, must
+    /// be migrated before any other instruction can load them.
+    pub fn migrate_pool(ctx: Context<MigratePool>, _pool_id: u64) -> Result<()> {
+        let info = ctx.accounts.pool.to_account_info();
+        let old_len = info.data_len();
+
+        // Version 0 -> 2: every field past pool_id is new, so only the data up to it
+        // is kept and the account is sized for the default epoch capacity.
+        // Version 1 -> 2: grow by POOL_V2_EXTRA_SPACE. An up-to-date pool is rejected
+        // below, which rolls the resize back
+        let (new_len, kept_len) = if old_len == POOL_V0_SPACE {
+            let data = info.try_borrow_data()?;
+            let epoch_count = u32::from_le_bytes(
+                data[POOL_EPOCHS_OFFSET..POOL_EPOCHS_OFFSET + 4]
+                    .try_into()
+                    .unwrap(),
+            ) as usize;
+            let kept_len = (POOL_EPOCHS_OFFSET + 4)
+                .saturating_add(epoch_count.saturating_mul(16))
+                .saturating_add(8 + 8)
+                .min(old_len);
+            (pool_space(DEFAULT_EPOCH_CAPACITY), kept_len)
+        } else {
+            (old_len + POOL_V2_EXTRA_SPACE, old_len)
+        };
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(info.lamports());
+        if rent_due > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        info.resize(new_len)?;
+        info.try_borrow_mut_data()?[kept_len..].fill(0);
+
+        let mut pool = Pool::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let from_version = pool.version;
+        require!(from_version < POOL_VERSION, CustomError::AlreadyMigrated);
+
+        if from_version == 0 {
+            // Version 0 pools accrued per slot with the default epoch window
+            pool.epoch_capacity = DEFAULT_EPOCH_CAPACITY;
+            pool.time_basis = TimeBasis::Slot;
+            pool.periods_per_year = SLOTS_PER_YEAR;
+        }
+        pool.end_slot = 0;
+        pool.booster_program = Pubkey::default();
+        pool.lock_rate_at_deposit = false;
+        pool.archived_active_slots = 0;
+        pool.reward_scale_periods = 0;
+        pool.unguarded_withdrawn = 0;
+        pool._reserved = [0; 7];
+        pool.version = POOL_VERSION;
+        pool.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let clock = Clock::get()?;
+        emit_cpi!(PoolMigratedEvent {
+            pool: info.key(),
+            from_version,
+            to_version: POOL_VERSION,
+            slot: clock.slot,
+        });
+
+        msg!("Pool migrated from version {} to {}", from_version, POOL_VERSION);
+
+        Ok(())
+    }
+
+    /// Stake tokens into the pool. `partner_tag` attributes the position to an
+    /// integration partner; it is recorded on the position's first deposit only.
+    pub fn deposit_stake(
+        ctx: Context<DepositStake>,
+        _pool_id: u64,
+        amount: u64,
+        partner_tag: Option<[u8; 16]>,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let user = &ctx.accounts.user;
+        let clock = Clock::get()?; // get current timestamp
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        verify_attestation(pool, ctx.accounts.attestation.as_deref(), &user.key())?;
+
+        // Transfer tokens from user -> pool vault
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.pool_vault.to_account_info(),
+            authority: user.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        record_stake_deposit(
+            pool,
+            pool_key,
+            user_stake,
+            amount,
+            partner_tag,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        emit_cpi!(StakeDepositedEvent {
+            user: user.key(),
+            pool: pool.key(),
+            position_index: user_stake.position_index,
+            amount,
+            total_user_stake: user_stake.amount,
+            total_pool_stake: pool.total_staked,
+            partner_tag: user_stake.partner_tag,
+            slot: clock.slot,
+        });
+
+        msg!("{} tokens staked by {}", amount, user.key());
+        msg!("Total staked in pool: {}", pool.total_staked);
+
+        Ok(())
+    }
+
+    /// Relayed deposit: the user pre-approves the pool vault as SPL delegate on their
+    /// token account and a relayer submits (and pays for) the transaction.
+    /// Tokens are pulled via the delegate allowance and credited to the token account
+    /// owner, never to the relayer. The owner co-signs, so the allowance only goes into
+    /// the position they chose.
+    pub fn deposit_stake_delegated(
+        ctx: Context<DepositStakeDelegated>,
+        _pool_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool_vault_key = ctx.accounts.pool_vault.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let user_token_account = &ctx.accounts.user_token_account;
+        let clock = Clock::get()?;
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(amount > 0, CustomError::InvalidDelegatedAmount);
+        verify_attestation(pool, ctx.accounts.attestation.as_deref(), &user_token_account.owner)?;
+
+        // The pool vault must be the approved delegate with enough remaining allowance
+        require!(
+            user_token_account.delegate == Some(pool_vault_key).into(),
+            CustomError::VaultNotDelegate
+        );
+        require!(
+            user_token_account.delegated_amount >= amount,
+            CustomError::InvalidDelegatedAmount
+        );
+
+        // Pool vault signs as delegate
+        let vault_seeds = &[
+            b"vault",
+            pool_key.as_ref(),
+            pool.token_mint.as_ref(),
+            &[ctx.bumps.pool_vault],
+        ];
+        let signer = &[&vault_seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: user_token_account.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                    authority: ctx.accounts.pool_vault.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        let user_key = ctx.accounts.user.key();
+        record_stake_deposit(
+            pool,
+            pool_key,
+            user_stake,
+            amount,
+            None,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        emit_cpi!(StakeDepositedEvent {
+            user: user_key,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            amount,
+            total_user_stake: user_stake.amount,
+            total_pool_stake: pool.total_staked,
+            partner_tag: user_stake.partner_tag,
+            slot: clock.slot,
+        });
+
+        msg!("{} tokens staked for {} by relayer {}", amount, user_key, ctx.accounts.relayer.key());
+        msg!("Total staked in pool: {}", pool.total_staked);
+
+        Ok(())
+    }
+
+    /// Create the top-stakers leaderboard of a pool (permissionless; payer funds rent).
+    /// Deposits and withdrawals that pass the leaderboard account keep it current.
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>, _pool_id: u64) -> Result<()> {
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.pool = ctx.accounts.pool.key();
+        leaderboard.entries = Vec::new();
+        leaderboard.bump = ctx.bumps.leaderboard;
+
+        msg!("Leaderboard initialized for pool {}", leaderboard.pool);
+
+        Ok(())
+    }
+
+    /// Stake native SOL into a wSOL pool (token_mint == native mint).
+    /// Lamports are sent straight into the pool vault and wrapped with sync_native,
+    /// so users never need to create or fund a wSOL account themselves.
+    pub fn deposit_stake_sol(ctx: Context<DepositStakeSol>, _pool_id: u64, lamports: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let user = &ctx.accounts.user;
+        let clock = Clock::get()?;
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(pool.is_native_sol(), CustomError::NotNativeSolPool);
+        require!(lamports > 0, CustomError::InvalidStakeAmount);
+        verify_attestation(pool, ctx.accounts.attestation.as_deref(), &user.key())?;
+
+        // Transfer lamports user -> pool vault, then wrap them into wSOL
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: user.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.pool_vault.to_account_info(),
+            },
+        ))?;
+
+        record_stake_deposit(
+            pool,
+            pool_key,
+            user_stake,
+            lamports,
+            None,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        emit_cpi!(StakeDepositedEvent {
+            user: user.key(),
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            amount: lamports,
+            total_user_stake: user_stake.amount,
+            total_pool_stake: pool.total_staked,
+            partner_tag: user_stake.partner_tag,
+            slot: clock.slot,
+        });
+
+        msg!("{} lamports wrapped and staked by {}", lamports, user.key());
+        msg!("Total staked in pool: {}", pool.total_staked);
+
+        Ok(())
+    }
+
+    /// Read a user's stake position.
+    /// The serialized UserStakeData is written with set_return_data (Anchor does this for
+    /// every `Result<T>` handler), so it is readable from simulateTransaction's
+    /// `returnData` and by CPI callers via get_return_data.
+    pub fn get_user_stake_info(ctx: Context<GetUserStakeInfo>, _pool_id: u64) -> Result<UserStakeData> {
+        let user_stake = &ctx.accounts.user_stake;
+        Ok(UserStakeData {
+            owner: user_stake.owner,
+            pool: user_stake.pool,
+            amount: user_stake.amount,
+            total_earned: user_stake.total_earned,
+            last_staked_slot: user_stake.last_staked_slot,
+            unclaimed: user_stake.unclaimed,
+            bump: user_stake.bump,
+            governance_locked_amount: user_stake.governance_locked_amount,
+            governance_lock_end_slot: user_stake.governance_lock_end_slot,
+            position_index: user_stake.position_index,
+            tranches: user_stake.tranches.clone(),
+            frozen: user_stake.frozen,
+            auto_claim: user_stake.auto_claim,
+            keeper_fee: user_stake.keeper_fee,
+        })
+    }
+
+    /// Read a user's stake position together with the reward pending at the current slot.
+    /// Like get_user_stake_info, the serialized UserStakeInfoWithReward is exposed as
+    /// return data for simulations and CPI callers.
+    pub fn get_user_stake_with_reward(
+        ctx: Context<GetUserStakeInfo>,
+        _pool_id: u64,
+    ) -> Result<UserStakeInfoWithReward> {
+        let user_stake = &ctx.accounts.user_stake;
+        let pool = &ctx.accounts.pool;
+
+        let pending_reward = user_stake.calculate_pending_reward(pool);
+
+        Ok(UserStakeInfoWithReward {
+            owner: user_stake.owner,
+            pool: user_stake.pool,
+            amount: user_stake.amount,
+            total_earned: user_stake.total_earned,
+            last_staked_slot: user_stake.last_staked_slot,
+            unclaimed: user_stake.unclaimed,
+            bump: user_stake.bump,
+            governance_locked_amount: user_stake.governance_locked_amount,
+            governance_lock_end_slot: user_stake.governance_lock_end_slot,
+            position_index: user_stake.position_index,
+            tranches: user_stake.tranches.clone(),
+            frozen: user_stake.frozen,
+            auto_claim: user_stake.auto_claim,
+            keeper_fee: user_stake.keeper_fee,
+            pending_reward,
+        })
+    }
+
+    /// Simulate the pending reward a user would have at `as_of_slot` (past or future, in
+    /// the pool's time basis), using the pool's current epochs. Returned via return_data so frontends can render
+    /// projections without reimplementing the epoch math. Like get_user_stake_with_reward,
+    /// the result excludes already-settled `unclaimed` rewards.
+    pub fn simulate_reward(
+        ctx: Context<GetUserStakeInfo>,
+        _pool_id: u64,
+        as_of_slot: u64,
+    ) -> Result<u64> {
+        let user_stake = &ctx.accounts.user_stake;
+        let pool = &ctx.accounts.pool;
+
+        Ok(user_stake.calculate_pending_reward_at(pool, as_of_slot))
+    }
+
+    /// Project the reward a new position of `amount` tokens would earn over the next
+    /// `duration_slots` (in the pool's time basis). Uses the settlement math: current and
+    /// queued rates, the current pause/halt state, the pool end, fixed-rate mode, rounding
+    /// and the per-user cap; boosts are not included. Returned via return_data.
+    pub fn estimate_reward(
+        ctx: Context<GetPoolInfo>,
+        _pool_id: u64,
+        amount: u64,
+        duration_slots: u64,
+    ) -> Result<u64> {
+        let pool = &ctx.accounts.pool;
+        let now = pool.now(&Clock::get()?);
+        let end = now.saturating_add(duration_slots);
+
+        let scaled = if pool.lock_rate_at_deposit {
+            // A deposit now would lock the current rate for its tranche
+            let active_slots = pool.active_slots_at(end).saturating_sub(pool.active_slots_at(now));
+            (amount as u128)
+                .saturating_mul(pool.current_reward_percentage(now) as u128)
+                .saturating_mul(active_slots as u128)
+        } else {
+            pool.accrued_reward_scaled(amount, now, end)
+        };
+        let (reward, _) = pool.round_reward(i128::try_from(scaled).unwrap_or(i128::MAX));
+
+        if pool.max_reward_per_user == 0 {
+            Ok(reward)
+        } else {
+            Ok(reward.min(pool.max_reward_per_user))
+        }
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, _pool_id: u64, amount: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+
+        let clock = Clock::get()?; // get current timestamp
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+
+        let user_reward_account = ensure_reward_token_account(
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        require_keys_eq!(
+            user_reward_account.owner,
+            ctx.accounts.user.key(),
+            CustomError::InvalidRewardAccount
+        );
+
+        let (reward_to_send, total_rewards) = record_stake_withdrawal(
+            pool,
+            pool_key,
+            user_stake,
+            amount,
+            ctx.accounts.reward_vault.amount,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        // Transfer staked tokens from pool vault -> user
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        // Transfer rewards if vault has sufficient balance
+        if reward_to_send > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.user_reward_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                reward_to_send,
+            )?;
+
+            ctx.accounts.reward_vault.reload()?;
+            sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        }
+
+        emit_cpi!(StakeWithdrawnEvent {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            amount,
+            rewards_sent: reward_to_send,
+            rewards_unclaimed: user_stake.unclaimed,
+            remaining_user_stake: user_stake.amount,
+            total_pool_stake: pool.total_staked,
+            slot: clock.slot,
+        });
+
+        if reward_to_send > 0 {
+            msg!("Withdrawn stake: {}", amount);
+            msg!("Rewards sent: {}", reward_to_send);
+        } else {
+            msg!("Withdrawn stake: {}", amount);
+            msg!("Rewards unavailable (vault empty). {} tokens saved as unclaimed.", total_rewards);
+        }
+
+        Ok(())
+    }
+
+    /// Withdraw stake from a wSOL pool as native SOL.
+    /// Principal is moved into a temporary wSOL account owned by the pool, which is then
+    /// closed to the user to unwrap. Rewards (when the vault can cover them) are paid in
+    /// the pool's reward mint to the user's reward account, as in withdraw_stake.
+    pub fn withdraw_stake_sol(ctx: Context<WithdrawStakeSol>, _pool_id: u64, amount: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(pool.is_native_sol(), CustomError::NotNativeSolPool);
+
+        let user_reward_account = ensure_reward_token_account(
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        require_keys_eq!(
+            user_reward_account.owner,
+            ctx.accounts.user.key(),
+            CustomError::InvalidRewardAccount
+        );
+
+        let (reward_to_send, total_rewards) = record_stake_withdrawal(
+            pool,
+            pool_key,
+            user_stake,
+            amount,
+            ctx.accounts.reward_vault.amount,
+            &clock,
+            &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority),
+        )?;
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        // Move principal into the temporary unwrap account
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.unwrap_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+        if reward_to_send > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.user_reward_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                reward_to_send,
+            )?;
+        }
+
+        // Closing the wSOL account releases all its lamports (balance + rent) to the user
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.unwrap_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        if reward_to_send > 0 {
+            ctx.accounts.reward_vault.reload()?;
+            sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        }
+
+        emit_cpi!(StakeWithdrawnEvent {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            amount,
+            rewards_sent: reward_to_send,
+            rewards_unclaimed: user_stake.unclaimed,
+            remaining_user_stake: user_stake.amount,
+            total_pool_stake: pool.total_staked,
+            slot: clock.slot,
+        });
+
+        msg!("Withdrawn and unwrapped stake: {} lamports", amount);
+        if reward_to_send > 0 {
+            msg!("Rewards sent: {}", reward_to_send);
+        } else {
+            msg!("Rewards unavailable (vault empty). {} tokens saved as unclaimed.", total_rewards);
+        }
+
+        Ok(())
+    }
+
+    /// Unwind a user's stake position on behalf of the pool owner (compliance takedown).
+    /// The full principal is returned to the user's token account regardless of deposit
+    /// or governance locks, and the position is zeroed. When `include_rewards` is set and
+    /// the reward vault can cover them, settled rewards are paid out too; otherwise they
+    /// stay on the position as unclaimed for the user to claim later.
+    /// Works while the pool is paused. Only the pool owner can force-unstake.
+    pub fn force_unstake(ctx: Context<ForceUnstake>, _pool_id: u64, include_rewards: bool) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        // Only pool owner can force-unstake
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let amount = user_stake.amount;
+        require!(amount > 0, CustomError::InvalidStakeAmount);
+
+        // Settle everything accrued so far into unclaimed
+        let pending = user_stake.settle_pending_reward(pool, pool.now(&clock));
+        user_stake.unclaimed = user_stake.unclaimed.checked_add(pending).unwrap();
+
+        let reward_to_send = if include_rewards
+            && ctx.accounts.reward_vault.amount >= user_stake.unclaimed
+            && user_stake.rewards_unlocked(pool, pool.now(&clock))
+        {
+            user_stake.unclaimed
+        } else {
+            0
+        };
+        if reward_to_send > 0 {
+            user_stake.total_earned = user_stake.total_earned.checked_add(reward_to_send).unwrap();
+            user_stake.unclaimed = 0;
+        }
+
+        // Zero the position, dropping all deposit and governance locks
+        user_stake.amount = 0;
+        user_stake.tranches.clear();
+        user_stake.governance_locked_amount = 0;
+        user_stake.governance_lock_end_slot = 0;
+        pool.total_staked = pool.total_staked.checked_sub(amount).unwrap();
+
+        if let Some(leaderboard) = ctx.accounts.leaderboard.as_mut() {
+            leaderboard.record(user_stake.key(), user_stake.owner, user_stake.amount);
+        }
+
+        // Voting weight must not outlive the stake it was derived from
+        let escrow_info = ctx.accounts.vote_escrow.to_account_info();
+        if escrow_info.owner == ctx.program_id && !escrow_info.data_is_empty() {
+            let mut vote_escrow = VoteEscrow::try_deserialize(&mut &escrow_info.try_borrow_data()?[..])?;
+            vote_escrow.locked_amount = 0;
+            vote_escrow.lock_end_slot = clock.slot;
+            vote_escrow.try_serialize(&mut &mut escrow_info.try_borrow_mut_data()?[..])?;
+        }
+
+        if user_stake.update_reward_cap_status(pool) {
+            emit_cpi!(RewardCapReachedEvent {
+                user: user_stake.owner,
+                pool: pool_key,
+                position_index: user_stake.position_index,
+                total_rewards: pool.max_reward_per_user,
+                slot: clock.slot,
+            });
+        }
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        if reward_to_send > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.user_reward_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                reward_to_send,
+            )?;
+
+            ctx.accounts.reward_vault.reload()?;
+            sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        }
+
+        emit_cpi!(StakeForceUnstakedEvent {
+            user: user_stake.owner,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            admin: ctx.accounts.admin.key(),
+            amount,
+            rewards_sent: reward_to_send,
+            rewards_unclaimed: user_stake.unclaimed,
+            total_pool_stake: pool.total_staked,
+            slot: clock.slot,
+        });
+
+        msg!("Force-unstaked {} tokens for {}", amount, user_stake.owner);
+        msg!("Rewards sent: {}, left unclaimed: {}", reward_to_send, user_stake.unclaimed);
+
+        Ok(())
+    }
+
+    /// Freeze or unfreeze a single user's stake position (e.g. during a fraud investigation).
+    /// While frozen, deposits, withdrawals and claims on the position are rejected.
+    /// Rewards keep accruing at the pool rate as usual, so unfreezing restores the user
+    /// to exactly the position they would have had; force_unstake still applies.
+    /// Only the pool owner can freeze.
+    pub fn set_user_stake_frozen(
+        ctx: Context<SetUserStakeFrozen>,
+        _pool_id: u64,
+        frozen: bool,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        user_stake.frozen = frozen;
+
+        emit_cpi!(UserStakeFrozenEvent {
+            user: user_stake.owner,
+            pool: pool.key(),
+            position_index: user_stake.position_index,
+            frozen,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!(
+            "User stake {} {}",
+            user_stake.owner,
+            if frozen { "frozen" } else { "unfrozen" }
+        );
+
+        Ok(())
+    }
+
+    pub fn claim_reward(ctx: Context<ClaimReward>, _pool_id: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(!user_stake.frozen, CustomError::UserStakeFrozen);
+        require!(
+            user_stake.rewards_unlocked(pool, pool.now(&clock)),
+            CustomError::RewardCliffNotReached
+        );
+
+        // Ensure user has some stake or unclaimed rewards
+        require!(
+            user_stake.amount > 0 || user_stake.unclaimed > 0,
+            CustomError::NoRewardsAvailable
+        );
+
+        // Calculate pending rewards
+        let pending = user_stake.settle_pending_reward(pool, pool.now(&clock));
+        let total_reward = pending.checked_add(user_stake.unclaimed).unwrap();
+
+        require!(total_reward > 0, CustomError::NoRewardsAvailable);
+        // Withdrawals still settle any amount; only standalone claims are gated
+        require!(
+            total_reward >= pool.min_claim_amount,
+            CustomError::ClaimBelowMinimum
+        );
+
+        // Check reward vault has sufficient balance
+        require!(
+            ctx.accounts.reward_vault.amount >= total_reward,
+            CustomError::InsufficientRewardVault
+        );
+
+        let user_reward_account = ensure_reward_token_account(
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // The destination may belong to any wallet (e.g. a multisig or exchange deposit
+        // address), so validate it against the pool's own accounts instead
+        validate_withdrawal_address(
+            &ctx.accounts.user_reward_account.key(),
+            &user_reward_account.owner,
+            &pool.key(),
+            Some(&ctx.accounts.reward_vault.key()),
+            &pool.token_mint,
+            Some(&pool.reward_mint),
+        )?;
+
+        // Update user state
+        user_stake.total_earned = user_stake.total_earned.checked_add(total_reward).unwrap();
+        user_stake.unclaimed = 0;
+
+        if user_stake.update_reward_cap_status(pool) {
+            emit_cpi!(RewardCapReachedEvent {
+                user: ctx.accounts.user.key(),
+                pool: pool.key(),
+                position_index: user_stake.position_index,
+                total_rewards: pool.max_reward_per_user,
+                slot: clock.slot,
+            });
+        }
+
+        // Transfer rewards to user
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.user_reward_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            total_reward,
+        )?;
+
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+
+        emit_cpi!(RewardClaimedEvent {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            destination: ctx.accounts.user_reward_account.key(),
+            amount: total_reward,
+            total_earned: user_stake.total_earned,
+            user_stake: user_stake.amount,
+            slot: clock.slot,
+        });
+
+        msg!("Claimed {} reward tokens", total_reward);
+        msg!("User stake remains: {}", user_stake.amount);
+
+        Ok(())
+    }
+
+    /// Claim only the stored `unclaimed` balance, even while the pool is disabled.
+    /// Recovery path for users who exited (amount == 0) while the reward vault was
+    /// short and whose pool has since been deactivated. No stake is required and
+    /// pending rewards are not settled, so last_staked_slot is left untouched.
+    pub fn claim_unclaimed(ctx: Context<ClaimReward>, _pool_id: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        require!(!user_stake.frozen, CustomError::UserStakeFrozen);
+        require!(
+            user_stake.rewards_unlocked(pool, pool.now(&clock)),
+            CustomError::RewardCliffNotReached
+        );
+
+        let amount = user_stake.unclaimed;
+        require!(amount > 0, CustomError::NoRewardsAvailable);
+        require!(
+            ctx.accounts.reward_vault.amount >= amount,
+            CustomError::InsufficientRewardVault
+        );
+
+        let user_reward_account = ensure_reward_token_account(
+            &ctx.accounts.user_reward_account.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.reward_mint.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.associated_token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        validate_withdrawal_address(
+            &ctx.accounts.user_reward_account.key(),
+            &user_reward_account.owner,
+            &pool.key(),
+            Some(&ctx.accounts.reward_vault.key()),
+            &pool.token_mint,
+            Some(&pool.reward_mint),
+        )?;
+
+        user_stake.total_earned = user_stake.total_earned.checked_add(amount).unwrap();
+        user_stake.unclaimed = 0;
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.user_reward_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+
+        emit_cpi!(RewardClaimedEvent {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            destination: ctx.accounts.user_reward_account.key(),
+            amount,
+            total_earned: user_stake.total_earned,
+            user_stake: user_stake.amount,
+            slot: clock.slot,
+        });
+
+        msg!("Claimed {} unclaimed reward tokens", amount);
+
+        Ok(())
+    }
+
+    /// Register the external program whose BoostRecord accounts grant reward boosts.
+    /// Pubkey::default() disables boosts; multipliers already cached on positions are
+    /// ignored while no booster is registered.
+    pub fn set_booster_program(
+        ctx: Context<UpdatePoolConfig>,
+        _pool_id: u64,
+        booster_program: Pubkey,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+
+        // Only pool owner can update
+        require!(
+            pool.owner == ctx.accounts.admin.key(),
+            CustomError::Unauthorized
+        );
+
+        let clock = Clock::get()?;
+        let old_booster_program = pool.booster_program;
+        pool.booster_program = booster_program;
+
+        emit_cpi!(PoolBoosterProgramUpdatedEvent {
+            pool: pool_key,
+            old_booster_program,
+            new_booster_program: booster_program,
+            admin: ctx.accounts.admin.key(),
+            slot: clock.slot,
+        });
+
+        msg!("Booster program set to {}", booster_program);
+
+        Ok(())
+    }
+
+    /// Re-read a position's boost from the pool booster's BoostRecord (permissionless).
+    /// Rewards accrued so far are settled into unclaimed at the previous multiplier,
+    /// then the new multiplier applies from now on. Booster programs call this by CPI
+    /// whenever they change a record.
+    pub fn refresh_boost(ctx: Context<RefreshBoost>, _pool_id: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let pool_key = pool.key();
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+        let now = pool.now(&clock);
+
+        require!(
+            pool.booster_program != Pubkey::default(),
+            CustomError::BoosterNotRegistered
+        );
+        let record_info = ctx.accounts.boost_record.to_account_info();
+        require_keys_eq!(
+            *record_info.owner,
+            pool.booster_program,
+            CustomError::InvalidBoostRecord
+        );
+        let record = BoostRecord::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+        require!(
+            record.pool == pool_key && record.user == user_stake.owner,
+            CustomError::InvalidBoostRecord
+        );
+
+        let pending = user_stake.settle_pending_reward(pool, now);
+        user_stake.unclaimed = user_stake.unclaimed.checked_add(pending).unwrap();
+        if user_stake.update_reward_cap_status(pool) {
+            emit_cpi!(RewardCapReachedEvent {
+                user: user_stake.owner,
+                pool: pool_key,
+                position_index: user_stake.position_index,
+                total_rewards: pool.max_reward_per_user,
+                slot: clock.slot,
+            });
+        }
+
+        let old_boost_bps = user_stake.effective_boost_bps(pool);
+        let new_boost_bps = record.multiplier_bps.clamp(10_000, MAX_BOOST_BPS);
+        user_stake.boost_bps = new_boost_bps;
+
+        emit_cpi!(BoostRefreshedEvent {
+            user: user_stake.owner,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            old_boost_bps,
+            new_boost_bps,
+            slot: clock.slot,
+        });
+
+        msg!("Boost refreshed: {} -> {} bps", old_boost_bps, new_boost_bps);
+
+        Ok(())
+    }
+
+    /// Opt in or out of keeper-driven claims for a stake position.
+    /// `keeper_fee` is the flat amount of reward tokens a keeper takes from each
+    /// crank_claim as payment; the rest goes to the user's reward ATA. Keepers can only
+    /// crank once the fee is at most MAX_KEEPER_FEE_SHARE_BPS of the claim.
+    pub fn set_auto_claim(
+        ctx: Context<SetAutoClaim>,
+        _pool_id: u64,
+        enabled: bool,
+        keeper_fee: u64,
+    ) -> Result<()> {
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        user_stake.auto_claim = enabled;
+        user_stake.keeper_fee = keeper_fee;
+
+        emit_cpi!(AutoClaimUpdatedEvent {
+            user: user_stake.owner,
+            pool: ctx.accounts.pool.key(),
+            position_index: user_stake.position_index,
+            enabled,
+            keeper_fee,
+            slot: clock.slot,
+        });
+
+        msg!("Auto-claim {} (keeper fee {})", if enabled { "enabled" } else { "disabled" }, keeper_fee);
+
+        Ok(())
+    }
+
+    /// Permissionless claim on behalf of a user who opted in via set_auto_claim.
+    /// All settled rewards are claimed: the position's keeper fee goes to the keeper and
+    /// the remainder to the owner's reward ATA, so keepers never need the user's keys.
+    /// Rejected below the pool's min_claim_amount, and while the keeper fee would be more
+    /// than MAX_KEEPER_FEE_SHARE_BPS of the claim.
+    pub fn crank_claim(ctx: Context<CrankClaim>, _pool_id: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let pool = &mut ctx.accounts.pool;
+        let user_stake = &mut ctx.accounts.user_stake;
+        let clock = Clock::get()?;
+
+        // Check if pool is active
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(!user_stake.frozen, CustomError::UserStakeFrozen);
+        require!(user_stake.auto_claim, CustomError::AutoClaimDisabled);
+        require!(
+            user_stake.rewards_unlocked(pool, pool.now(&clock)),
+            CustomError::RewardCliffNotReached
+        );
+
+        let pending = user_stake.settle_pending_reward(pool, pool.now(&clock));
+        let total_reward = pending.checked_add(user_stake.unclaimed).unwrap();
+
+        require!(total_reward > 0, CustomError::NoRewardsAvailable);
+        require!(
+            total_reward >= pool.min_claim_amount,
+            CustomError::ClaimBelowMinimum
+        );
+        require!(
+            (user_stake.keeper_fee as u128) * 10_000
+                <= (total_reward as u128) * (MAX_KEEPER_FEE_SHARE_BPS as u128),
+            CustomError::RewardBelowKeeperFee
+        );
+        require!(
+            ctx.accounts.reward_vault.amount >= total_reward,
+            CustomError::InsufficientRewardVault
+        );
+
+        let keeper_fee = user_stake.keeper_fee;
+        let user_amount = total_reward - keeper_fee;
+
+        // Update user state
+        user_stake.total_earned = user_stake.total_earned.checked_add(total_reward).unwrap();
+        user_stake.unclaimed = 0;
+
+        if user_stake.update_reward_cap_status(pool) {
+            emit_cpi!(RewardCapReachedEvent {
+                user: user_stake.owner,
+                pool: pool_key,
+                position_index: user_stake.position_index,
+                total_rewards: pool.max_reward_per_user,
+                slot: clock.slot,
+            });
+        }
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.user_reward_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            user_amount,
+        )?;
+
+        if keeper_fee > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.keeper_reward_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                keeper_fee,
+            )?;
+        }
+
+        ctx.accounts.reward_vault.reload()?;
+        sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+
+        emit_cpi!(RewardCrankClaimedEvent {
+            user: user_stake.owner,
+            pool: pool_key,
+            position_index: user_stake.position_index,
+            keeper: ctx.accounts.keeper.key(),
+            amount: user_amount,
+            keeper_fee,
+            total_earned: user_stake.total_earned,
+            slot: clock.slot,
+        });
+
+        msg!("Crank claimed {} reward tokens for {}", user_amount, user_stake.owner);
+        msg!("Keeper fee: {}", keeper_fee);
+
+        Ok(())
+    }
+
+    /// Deposit into the pool as a transferable locked position represented by an NFT.
+    /// The position (amount, lock end) lives in a StakePositionNft account keyed by the
+    /// NFT mint, so whoever holds the NFT can redeem it at maturity. Uses the pool's
+    /// lock_duration_slots, which must be non-zero. When `metadata` is given (and the
+    /// Metaplex accounts are passed) a metadata account and master edition are created;
+    /// otherwise the mint authority is simply revoked so the supply stays at one.
+    pub fn mint_stake_position_nft(
+        ctx: Context<MintStakePositionNft>,
+        _pool_id: u64,
+        amount: u64,
+        metadata: Option<PositionNftMetadata>,
+    ) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let nft_mint_key = ctx.accounts.nft_mint.key();
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let clock = Clock::get()?;
+        let now = pool.now(&clock);
+
+        require!(pool.is_active, CustomError::StakingDisabled);
+        require!(!pool.has_ended(now), CustomError::PoolEnded);
+        verify_attestation(pool, ctx.accounts.attestation.as_deref(), &ctx.accounts.user.key())?;
+        require!(amount > 0, CustomError::InvalidStakeAmount);
+        require!(pool.lock_duration_slots > 0, CustomError::PositionNftRequiresLock);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.pool_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        pool.total_staked = pool.total_staked.checked_add(amount).unwrap();
+
+        position.pool = pool_key;
+        position.nft_mint = nft_mint_key;
+        position.amount = amount;
+        position.deposit_slot = now;
+        position.lock_end_slot = now.checked_add(pool.lock_duration_slots).unwrap();
+        position.unclaimed = 0;
+        position.bump = ctx.bumps.position;
+
+        // The pool PDA is the NFT's mint and update authority
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.nft_mint.to_account_info(),
+                    to: ctx.accounts.user_nft_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+
+        if let Some(metadata) = metadata {
+            let (Some(metadata_account), Some(master_edition), Some(token_metadata_program)) = (
+                ctx.accounts.metadata.as_ref(),
+                ctx.accounts.master_edition.as_ref(),
+                ctx.accounts.token_metadata_program.as_ref(),
+            ) else {
+                return err!(CustomError::MissingMetadataAccounts);
+            };
+
+            create_metadata_accounts_v3(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    CreateMetadataAccountsV3 {
+                        metadata: metadata_account.to_account_info(),
+                        mint: ctx.accounts.nft_mint.to_account_info(),
+                        mint_authority: pool.to_account_info(),
+                        payer: ctx.accounts.user.to_account_info(),
+                        update_authority: pool.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                    signer,
+                ),
+                DataV2 {
+                    name: metadata.name,
+                    symbol: metadata.symbol,
+                    uri: metadata.uri,
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+                false,
+                true,
+                None,
+            )?;
+
+            // Max supply 0: the edition takes over the mint authority
+            create_master_edition_v3(
+                CpiContext::new_with_signer(
+                    token_metadata_program.to_account_info(),
+                    CreateMasterEditionV3 {
+                        edition: master_edition.to_account_info(),
+                        mint: ctx.accounts.nft_mint.to_account_info(),
+                        update_authority: pool.to_account_info(),
+                        mint_authority: pool.to_account_info(),
+                        payer: ctx.accounts.user.to_account_info(),
+                        metadata: metadata_account.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        rent: ctx.accounts.rent.to_account_info(),
+                    },
+                    signer,
+                ),
+                Some(0),
+            )?;
+        } else {
+            token::set_authority(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::SetAuthority {
+                        current_authority: pool.to_account_info(),
+                        account_or_mint: ctx.accounts.nft_mint.to_account_info(),
+                    },
+                    signer,
+                ),
+                spl_token::instruction::AuthorityType::MintTokens,
+                None,
+            )?;
+        }
+
+        emit_cpi!(StakePositionNftMintedEvent {
+            pool: pool_key,
+            nft_mint: nft_mint_key,
+            owner: ctx.accounts.user.key(),
+            amount,
+            lock_end_slot: position.lock_end_slot,
+            total_pool_stake: pool.total_staked,
+            slot: clock.slot,
+        });
+
+        msg!("Minted position NFT {} for {} tokens locked until {}", nft_mint_key, amount, position.lock_end_slot);
+
+        Ok(())
+    }
+
+    /// Redeem a matured NFT position: the current holder gets the principal plus the
+    /// rewards accrued between deposit and lock end. If the reward vault cannot cover
+    /// the rewards they stay on the position as unclaimed and the holder redeems again
+    /// after a refill. Once everything is paid the NFT is burned and the position closed.
+    pub fn redeem_stake_position_nft(ctx: Context<RedeemStakePositionNft>, _pool_id: u64) -> Result<()> {
+        let pool_key = ctx.accounts.pool.key();
+        let nft_mint_key = ctx.accounts.nft_mint.key();
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+        let clock = Clock::get()?;
+
+        require!(pool.is_active, CustomError::StakingDisabled);
+
+        let amount = position.amount;
+        let mut total_rewards = position.unclaimed;
+        if amount > 0 {
+            require!(pool.now(&clock) >= position.lock_end_slot, CustomError::StakeStillLocked);
+
+            // Accrual stops at maturity
+            let accrued = pool.accrued_reward(amount, position.deposit_slot, position.lock_end_slot);
+            total_rewards = total_rewards.checked_add(accrued).unwrap();
+        } else {
+            require!(total_rewards > 0, CustomError::NoRewardsAvailable);
+        }
+
+        let reward_to_send = if ctx.accounts.reward_vault.amount >= total_rewards {
+            total_rewards
+        } else {
+            0
+        };
+
+        position.amount = 0;
+        position.unclaimed = total_rewards - reward_to_send;
+        pool.total_staked = pool.total_staked.checked_sub(amount).unwrap();
+
+        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
+        let signer = &[&seeds[..]];
+
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.pool_vault.to_account_info(),
+                        to: ctx.accounts.holder_token_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        if reward_to_send > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reward_vault.to_account_info(),
+                        to: ctx.accounts.holder_reward_account.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                reward_to_send,
+            )?;
+
+            ctx.accounts.reward_vault.reload()?;
+            sync_depletion_halt(pool, pool_key, ctx.accounts.reward_vault.amount, &clock, &EventCpi::new(&ctx.accounts.event_authority, ctx.bumps.event_authority))?;
+        }
+
+        let rewards_unclaimed = position.unclaimed;
+
+        // Fully settled: burn the NFT and close the position
+        if rewards_unclaimed == 0 {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    token::Burn {
+                        mint: ctx.accounts.nft_mint.to_account_info(),
+                        from: ctx.accounts.holder_nft_account.to_account_info(),
+                        authority: ctx.accounts.holder.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+            token::close_account(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.holder_nft_account.to_account_info(),
+                    destination: ctx.accounts.holder.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ))?;
+            position.close(ctx.accounts.holder.to_account_info())?;
+        }
+
+        emit_cpi!(StakePositionNftRedeemedEvent {
+            pool: pool_key,
+            nft_mint: nft_mint_key,
+            holder: ctx.accounts.holder.key(),
+            amount,
+            rewards_sent: reward_to_send,
+            rewards_unclaimed,
+            total_pool_stake: pool.total_staked,
+            slot: clock.slot,
+        });
+
+        msg!("Redeemed position NFT {}: {} tokens, {} rewards", nft_mint_key, amount, reward_to_send);
+
+        Ok(())
+    }
+
+    /// Lock a portion of the user's stake for governance voting weight.
+    /// Locked tokens cannot be withdrawn until the lock expires. Voting weight is
+    /// locked amount × remaining lock slots and decays linearly to zero at lock end.
+    /// An active lock can only be increased or extended, never reduced.
+    pub fn lock_for_governance(
+        ctx: Context<LockForGovernance>,
+        _pool_id: u64,
+        amount: u64,
+        duration: u64,
+    ) -> Result<()> {
+        let user_stake = &mut ctx.accounts.user_stake;
+        let vote_escrow = &mut ctx.accounts.vote_escrow;
+        let clock = Clock::get()?;
+
+        require!(amount > 0, CustomError::InvalidGovernanceLock);
+        require!(
+            duration > 0 && duration <= MAX_GOVERNANCE_LOCK_SLOTS,
+            CustomError::InvalidGovernanceLock
+        );
+        require!(
+            amount <= user_stake.amount,
+            CustomError::InsufficientStakeForLock
+        );
+
+        let lock_end_slot = clock
+            .slot
+            .checked_add(duration)
+            .ok_or(CustomError::InvalidGovernanceLock)?;
+
+        // An active lock may only grow: same or larger amount, same or later end
+        if clock.slot < user_stake.governance_lock_end_slot {
+            require!(
+                amount >= user_stake.governance_locked_amount
+                    && lock_end_slot >= user_stake.governance_lock_end_slot,
+                CustomError::GovernanceLockReduction
+            );
+        }
+
+        user_stake.governance_locked_amount = amount;
+        user_stake.governance_lock_end_slot = lock_end_slot;
+
+        // Mirror the lock into the escrow PDA read by governance tooling
+        vote_escrow.owner = user_stake.owner;
+        vote_escrow.pool = user_stake.pool;
+        vote_escrow.user_stake = user_stake.key();
+        vote_escrow.locked_amount = amount;
+        vote_escrow.lock_start_slot = clock.slot;
+        vote_escrow.lock_end_slot = lock_end_slot;
+        vote_escrow.bump = ctx.bumps.vote_escrow;
+
+        let voting_weight = vote_escrow.voting_weight(clock.slot);
+
+        emit_cpi!(GovernanceLockedEvent {
+            user: ctx.accounts.user.key(),
+            pool: user_stake.pool,
+            position_index: user_stake.position_index,
+            vote_escrow: vote_escrow.key(),
+            locked_amount: amount,
+            lock_end_slot,
+            voting_weight,
+            slot: clock.slot,
+        });
+
+        msg!("Locked {} tokens for governance until slot {}", amount, lock_end_slot);
+        msg!("Voting weight: {}", voting_weight);
+
+        Ok(())
+    }
+
+    /// Returns the current (decayed) voting weight of a vote escrow.
+    pub fn get_voting_weight(ctx: Context<GetVotingWeight>, _pool_id: u64) -> Result<u128> {
+        let clock = Clock::get()?;
+        Ok(ctx.accounts.vote_escrow.voting_weight(clock.slot))
+    }
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(maybe_owner: Option<Pubkey>, reward_percentage: u64, pool_id: u64)]
+pub struct CreatePool<'info> {
+    /// Pool ID counter for tracking pool IDs per token mint
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"pool_id_counter", token_mint.key().as_ref()],
+        bump,
+        space = 8 + 32 + 8 + 1
+    )]
+    pub pool_id_counter: Account<'info, PoolIdCounter>,
+
+    /// Pool account PDA, must not exist prior to creation to prevent reinitialization attacks
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump,
+        // Space calculation: see pool_space
+        space = pool_space(DEFAULT_EPOCH_CAPACITY)
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for which the pool is created
+    pub token_mint: Account<'info, Mint>,
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"reward_vault", pool.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Pool vault PDA for user stakes (new)
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault", pool.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Admin of the program, used as payer and default owner
+    /// Must match global config admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Global config PDA containing authorized admin
+    #[account(seeds = [b"global_config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ManagePoolAdmins<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"pool_admins", pool.key().as_ref()],
+        bump,
+        space = POOL_ADMINS_SPACE
+    )]
+    pub pool_admins: Account<'info, PoolAdmins>,
+
+    /// Admin signs (must be pool.owner)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct InitLeaderboard<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump,
+        space = LEADERBOARD_SPACE
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct SetPoolTemplate<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"pool_template".as_ref(), &template_id.to_le_bytes()],
+        bump,
+        space = POOL_TEMPLATE_SPACE
+    )]
+    pub template: Account<'info, PoolTemplate>,
+
+    /// Must match global config admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"global_config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(template_id: u64, maybe_owner: Option<Pubkey>, pool_id: u64)]
+pub struct CreatePoolFromTemplate<'info> {
+    #[account(
+        seeds = [b"pool_template".as_ref(), &template_id.to_le_bytes()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, PoolTemplate>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"pool_id_counter", token_mint.key().as_ref()],
+        bump,
+        space = 8 + 32 + 8 + 1
+    )]
+    pub pool_id_counter: Account<'info, PoolIdCounter>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump,
+        space = pool_space(DEFAULT_EPOCH_CAPACITY)
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub token_mint: Account<'info, Mint>,
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"reward_vault", pool.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"vault", pool.key().as_ref(), token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Must match global config admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"global_config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Initialize global config
+/// 
+/// SECURITY: The `init` constraint ensures this can only be called once.
+/// Deployment scripts must call this immediately after program deployment
+/// with the upgrade authority as the admin signer to prevent front-running.
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"global_config"],
+        bump,
+        space = 8 + 32 + 1
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfer admin rights
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(mut, seeds = [b"global_config"], bump = config.bump)]
+    pub config: Account<'info, GlobalConfig>,
+
+    #[account(constraint = admin.key() == config.admin @ CustomError::UnauthorizedPoolCreator)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct GetPoolInfo<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct UpdateRewardMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// The new reward mint account
+    pub new_reward_mint: Account<'info, Mint>,
+
+    /// SECURITY NOTE: init_if_needed is acceptable here because:
+    /// 1. The function has owner authorization check
+    /// 2. The vault is deterministically derived from pool and new_reward_mint
+    /// 3. This allows updating to an existing vault or creating a new one
+    /// 4. Token account reinitialization is safe as authority is set to pool PDA
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"reward_vault", pool.key().as_ref(), new_reward_mint.key().as_ref()],
+        bump,
+        token::mint = new_reward_mint,
+        token::authority = pool,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct DepositReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Admin signs (pool.owner or a pool admin)
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Enforce canonical ATA for admin to prevent token loss to arbitrary accounts
+    #[account(
+        mut,
+        constraint = admin_reward_account.mint == pool.reward_mint,
+        constraint = admin_reward_account.key() == get_associated_token_address(&admin.key(), &pool.reward_mint) @ CustomError::NonCanonicalAta
+    )]
+    pub admin_reward_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Pool admin list, required when the signer is a pool admin rather than the owner
+    #[account(
+        seeds = [b"pool_admins", pool.key().as_ref()],
+        bump = pool_admins.bump
+    )]
+    pub pool_admins: Option<Account<'info, PoolAdmins>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct GetUserStakeInfo<'info> {
+    #[account(
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserStakeData {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub total_earned: u64,
+    pub last_staked_slot: u64,
+    pub unclaimed: u64,
+    pub bump: u8,
+    pub governance_locked_amount: u64,
+    pub governance_lock_end_slot: u64,
+    pub position_index: u64,
+    pub tranches: Vec<StakeTranche>,
+    pub frozen: bool,
+    pub auto_claim: bool,
+    pub keeper_fee: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump,
+        has_one = reward_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Security: Enforce that only the owner of the user_stake account can withdraw.
+    /// This prevents privilege escalation where a malicious user attempts to withdraw
+    /// from another user's stake account by providing a different user_stake PDA.
+    /// Also validates that the user_stake belongs to the correct pool.
+    #[account(
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.token_mint,
+        constraint = user_token_account.owner == user.key(),
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: User's reward token account; created as the user's ATA if missing,
+    /// then validated (mint + owner) in instruction
+    #[account(mut)]
+    pub user_reward_account: UncheckedAccount<'info>,
+
+    /// Reward mint for the pool (needed to create the user's reward ATA)
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Pool leaderboard, if one exists; updated with the position's new amount
+    #[account(
+        mut,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ScheduleRewardDeposit<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump,
+        has_one = reward_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Stream schedule for this pool; reused once the previous stream is fully released
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"reward_stream", pool.key().as_ref()],
+        bump,
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 1
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+
+    /// Escrow holding not-yet-released stream tokens (authority = pool PDA)
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [b"reward_stream_escrow", pool.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool
+    )]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    /// Admin signs (pool.owner or a pool admin)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Pool admin list, required when the signer is a pool admin rather than the owner
+    #[account(
+        seeds = [b"pool_admins", pool.key().as_ref()],
+        bump = pool_admins.bump
+    )]
+    pub pool_admins: Option<Account<'info, PoolAdmins>>,
+
+    #[account(
+        mut,
+        constraint = admin_reward_account.mint == pool.reward_mint,
+        constraint = admin_reward_account.owner == admin.key(),
+    )]
+    pub admin_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ReleaseStreamedRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_stream", pool.key().as_ref()],
+        bump = reward_stream.bump
+    )]
+    pub reward_stream: Account<'info, RewardStream>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_stream_escrow", pool.key().as_ref()],
+        bump
+    )]
+    pub stream_escrow: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct WithdrawReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Admin signer (must be pool owner)
+    pub admin: Signer<'info>,
+
+    /// Pool's withdraw guardian; required once the owner's withdrawals exceed guardian_threshold
+    pub guardian: Option<Signer<'info>>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Admin's token account to receive rewards
+    /// Enforce canonical ATA for admin to prevent token loss to arbitrary accounts
+    #[account(
+        mut,
+        constraint = admin_reward_account.mint == pool.reward_mint,
+        constraint = admin_reward_account.key() == get_associated_token_address(&admin.key(), &pool.reward_mint) @ CustomError::NonCanonicalAta
+    )]
+    pub admin_reward_account: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct ClaimReward<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump,
+        has_one = reward_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Security: Enforce that only the owner of the user_stake account can claim rewards.
+    /// This prevents privilege escalation where a malicious user attempts to claim rewards
+    /// from another user's stake account by providing a different user_stake PDA.
+    /// Also validates that the user_stake belongs to the correct pool.
+    #[account(
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Token account to receive rewards. Does not need to be owned by the user;
+    /// if missing it is created as the user's ATA, then checked with
+    /// validate_withdrawal_address in the handler.
+    #[account(mut)]
+    pub user_reward_account: UncheckedAccount<'info>,
+
+    /// Reward mint for the pool (needed to create the user's reward ATA)
+    pub reward_mint: Account<'info, Mint>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct RefreshBoost<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// CHECK: BoostRecord owned by pool.booster_program; owner, discriminator and
+    /// pool/user binding are checked in the handler
+    pub boost_record: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SetAutoClaim<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Only the owner of the user_stake account can opt in to keeper claims
+    #[account(
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub user: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CrankClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Any stake in the pool; opt-in is checked in the handler
+    #[account(
+        mut,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// Rewards always go to the stake owner's canonical reward ATA
+    #[account(
+        mut,
+        constraint = user_reward_account.key() == get_associated_token_address(&user_stake.owner, &pool.reward_mint) @ CustomError::InvalidRewardAccount
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
+
+    /// Permissionless caller; receives the keeper fee
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = keeper_reward_account.mint == pool.reward_mint @ CustomError::InvalidRewardAccount
+    )]
+    pub keeper_reward_account: Account<'info, TokenAccount>,
+
+    /// Pool's reward vault
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct MintStakePositionNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Fresh NFT mint (keypair signer); the pool PDA is its mint authority
+    #[account(
+        init,
+        payer = user,
+        mint::decimals = 0,
+        mint::authority = pool,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = user,
+        space = STAKE_POSITION_NFT_SPACE,
+        seeds = [b"position_nft", nft_mint.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, StakePositionNft>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = nft_mint,
+        associated_token::authority = user,
+    )]
+    pub user_nft_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == pool.token_mint,
+        constraint = user_token_account.owner == user.key(),
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Metaplex metadata PDA for nft_mint; validated by the token metadata program
+    #[account(mut)]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex master edition PDA for nft_mint; validated by the token metadata program
+    #[account(mut)]
+    pub master_edition: Option<UncheckedAccount<'info>>,
+
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
+
+    /// Staker's credential token account; required when the pool has an attestation_issuer
+    pub attestation: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct RedeemStakePositionNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"position_nft", nft_mint.key().as_ref()],
+        bump = position.bump,
+        has_one = pool @ CustomError::InvalidPoolAssociation,
+        has_one = nft_mint
+    )]
+    pub position: Account<'info, StakePositionNft>,
+
+    /// Current NFT holder; owning the NFT is what authorizes the redemption
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = holder_nft_account.mint == nft_mint.key() @ CustomError::NotPositionHolder,
+        constraint = holder_nft_account.owner == holder.key() @ CustomError::NotPositionHolder,
+        constraint = holder_nft_account.amount == 1 @ CustomError::NotPositionHolder,
+    )]
+    pub holder_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = holder_token_account.mint == pool.token_mint,
+        constraint = holder_token_account.owner == holder.key(),
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = holder_reward_account.mint == pool.reward_mint @ CustomError::InvalidRewardAccount,
+        constraint = holder_reward_account.owner == holder.key() @ CustomError::InvalidRewardAccount,
+    )]
+    pub holder_reward_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct LockForGovernance<'info> {
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Only the owner of the user_stake account can lock its tokens
+    #[account(
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// SECURITY NOTE: init_if_needed is acceptable here because:
+    /// 1. The escrow is derived from the pool and the signing user
+    /// 2. Every field is rewritten from the validated user_stake on each lock
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [b"vote_escrow", pool.key().as_ref(), user.key().as_ref(), &user_stake.position_index.to_le_bytes()],
+        bump,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct GetVotingWeight<'info> {
+    #[account(
+        constraint = vote_escrow.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub vote_escrow: Account<'info, VoteEscrow>,
+    #[account(
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct UpdateRewardPercentage<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+}
+
+/// Shared context for owner-only pool configuration updates
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct UpdatePoolConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SetWithdrawGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub admin: Signer<'info>,
+
+    /// Currently configured guardian; must sign while one is set
+    pub current_guardian: Option<Signer<'info>>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+}
+
+/// Context for expand_pool_epochs; the pool is reallocated to fit new_capacity epochs
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64, new_capacity: u16)]
+pub struct ExpandPoolEpochs<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump,
+        realloc = pool_space(new_capacity),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Pool owner; pays rent for the grown account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SetHaltOnDepletion<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// Pool's reward vault (balance decides whether accrual halts immediately)
+    #[account(
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct UpdatePoolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Current authority must sign to authorize the change
+    pub current_authority: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct SetPoolGovernance<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Current pool owner (validated in instruction)
+    pub admin: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: spl-governance program instance hosting the realm
+    #[account(executable)]
+    pub governance_program: UncheckedAccount<'info>,
+
+    /// CHECK: Realm account; ownership by governance_program is validated in instruction
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: Governance PDA that becomes the pool owner; address and ownership are
+    /// validated in instruction
+    pub governance: UncheckedAccount<'info>,
+}
+
+/// Represents a reward epoch - a period with a specific reward rate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RewardEpoch {
+    /// The reward percentage for this epoch in basis points (bps)
+    pub reward_percentage: u64,
+    /// The slot when this epoch starts
+    pub start_slot: u64,
+}
+
+/// An epoch pruned from a pool's reward history, as folded into archived_reward_index
+pub struct ArchivedEpoch {
+    pub reward_percentage: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    /// archived_reward_index after folding this epoch in
+    pub archived_reward_index: u128,
+}
+
+/// One page of a pool's reward epoch history, returned by get_reward_epochs
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RewardEpochPage {
+    /// Number of epochs in the full history
+    pub total: u32,
+    /// Requested page index
+    pub page: u32,
+    /// Epochs on this page (empty past the end of the history)
+    pub epochs: Vec<RewardEpoch>,
+}
+
+/// Global configuration for pool creation authorization
+#[account]
+pub struct GlobalConfig {
+    /// Authorized admin who can create pools
+    pub admin: Pubkey,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+#[account]
+pub struct Pool {
+    pub token_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub owner: Pubkey,
+    pub total_staked: u64,
+    /// Current annual reward percentage in basis points (bps)
+    /// 10000 bps = 100% APY
+    /// Examples: 550 = 5.50%, 1000 = 10.00%, 2500 = 25.00%
+    pub reward_percentage: u64,
+    pub bump: u8,
+    pub is_active: bool,
+    /// Historical reward epochs (at most epoch_capacity entries to limit account size)
+    /// Most recent epoch is at the end of the vector
+    pub reward_epochs: Vec<RewardEpoch>,
+    /// Slot of the last reward percentage update
+    pub last_reward_update_slot: u64,
+    /// Unique pool identifier for this token mint
+    /// Allows multiple pools per token mint
+    pub pool_id: u64,
+    /// Lifetime reward cap per user (earned + unclaimed), 0 = uncapped
+    pub max_reward_per_user: u64,
+    /// Halt accrual while the reward vault is empty
+    pub halt_on_depletion: bool,
+    /// True while accrual is halted because the reward vault is empty
+    pub accrual_halted: bool,
+    /// Slot at which accrual was last halted
+    pub accrual_halted_slot: u64,
+    /// Admin switch that stops accrual while keeping deposits/withdrawals open
+    pub accrual_frozen: bool,
+    /// Lock applied to each new deposit tranche, in slots (0 = no lock)
+    pub lock_duration_slots: u64,
+    /// spl-governance program whose governance PDA owns the pool (default = not governed)
+    pub governance_program: Pubkey,
+    /// Realm of the owning governance (default = not governed)
+    pub governance_realm: Pubkey,
+    /// Account layout version (see POOL_VERSION)
+    pub version: u8,
+    /// Maximum number of reward epochs kept (0 = DEFAULT_EPOCH_CAPACITY)
+    pub epoch_capacity: u16,
+    /// Clock that accrual and deposit locks are measured in
+    pub time_basis: TimeBasis,
+    /// APY divisor: time-basis units per year (0 = SLOTS_PER_YEAR)
+    pub periods_per_year: u64,
+    /// Co-signer required for reward withdrawals above guardian_threshold (default = none)
+    pub withdraw_guardian: Pubkey,
+    /// Largest reward withdrawal the owner can make without the guardian
+    pub guardian_threshold: u64,
+    /// Minimum spacing between reward rate changes, in the pool's time basis (0 = none)
+    pub min_slots_between_rate_changes: u64,
+    /// Rewards stay locked until a position's first deposit is this old (0 = no cliff)
+    pub reward_cliff_slots: u64,
+    /// Smallest reward claim_reward pays out (0 = no minimum)
+    pub min_claim_amount: u64,
+    /// Rate × time (bps × time-basis units) of epochs pruned from reward_epochs
+    pub archived_reward_index: u128,
+    /// How settled rewards are rounded to whole token units
+    pub rounding_mode: RoundingMode,
+    /// Mint of the credential token stakers must hold to deposit (default = ungated)
+    pub attestation_issuer: Pubkey,
+    /// Accrual stops and deposits close at this point, in the pool's time basis
+    /// (0 = open-ended; layout version >= 2, read through end_slot())
+    pub end_slot: u64,
+    /// External program whose BoostRecord accounts set stakers' reward multipliers
+    /// (default = none)
+    pub booster_program: Pubkey,
+    /// Each deposit tranche accrues at the rate in effect when it was deposited
+    pub lock_rate_at_deposit: bool,
+    /// Time-basis units with a non-zero rate in epochs pruned from reward_epochs
+    pub archived_active_slots: u64,
+    /// Periods per year that scaled rewards (reward_scale, the archived indexes and
+    /// carried remainders) are expressed in; pinned by the first set_periods_per_year
+    /// (0 = periods_per_year, read through reward_scale_periods())
+    pub reward_scale_periods: u64,
+    /// Reward withdrawn by the owner alone since the guardian last co-signed (or was
+    /// set); withdrawals without the guardian may not take it past guardian_threshold
+    pub unguarded_withdrawn: u64,
+    /// Zeroed padding; new fields are carved out of it so the account size stays fixed
+    pub _reserved: [u8; 7],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolData {
+    pub token_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub owner: Pubkey,
+    pub total_staked: u64,
+    /// Current annual reward percentage in basis points (bps)
+    /// 10000 bps = 100% APY
+    /// Examples: 550 = 5.50%, 1000 = 10.00%, 2500 = 25.00%
+    pub reward_percentage: u64,
+    pub bump: u8,
+    pub is_active: bool,
+    /// Historical reward epochs
+    pub reward_epochs: Vec<RewardEpoch>,
+    /// Slot of the last reward percentage update
+    pub last_reward_update_slot: u64,
+    /// Unique pool identifier for this token mint
+    pub pool_id: u64,
+    /// Lifetime reward cap per user, 0 = uncapped
+    pub max_reward_per_user: u64,
+    /// Halt accrual while the reward vault is empty
+    pub halt_on_depletion: bool,
+    /// True while accrual is halted because the reward vault is empty
+    pub accrual_halted: bool,
+    /// Slot at which accrual was last halted
+    pub accrual_halted_slot: u64,
+    /// Admin switch that stops accrual while keeping deposits/withdrawals open
+    pub accrual_frozen: bool,
+    /// Lock applied to each new deposit tranche, in slots (0 = no lock)
+    pub lock_duration_slots: u64,
+    /// spl-governance program whose governance PDA owns the pool (default = not governed)
+    pub governance_program: Pubkey,
+    /// Realm of the owning governance (default = not governed)
+    pub governance_realm: Pubkey,
+    /// Account layout version
+    pub version: u8,
+    /// Maximum number of reward epochs kept
+    pub epoch_capacity: u16,
+    /// Clock that accrual and deposit locks are measured in
+    pub time_basis: TimeBasis,
+    /// APY divisor: time-basis units per year
+    pub periods_per_year: u64,
+    /// Co-signer required for reward withdrawals above guardian_threshold (default = none)
+    pub withdraw_guardian: Pubkey,
+    /// Largest reward withdrawal the owner can make without the guardian
+    pub guardian_threshold: u64,
+    /// Minimum spacing between reward rate changes, in the pool's time basis (0 = none)
+    pub min_slots_between_rate_changes: u64,
+    /// Rewards stay locked until a position's first deposit is this old (0 = no cliff)
+    pub reward_cliff_slots: u64,
+    /// Smallest reward claim_reward pays out (0 = no minimum)
+    pub min_claim_amount: u64,
+    /// Rate × time (bps × time-basis units) of epochs pruned from reward_epochs
+    pub archived_reward_index: u128,
+    /// How settled rewards are rounded to whole token units
+    pub rounding_mode: RoundingMode,
+    /// Mint of the credential token stakers must hold to deposit (default = ungated)
+    pub attestation_issuer: Pubkey,
+    /// End of accrual and deposits, in the pool's time basis (0 = open-ended)
+    pub end_slot: u64,
+    /// External program granting reward boosts (default = none)
+    pub booster_program: Pubkey,
+    /// Deposits accrue at the rate in effect when they were made
+    pub lock_rate_at_deposit: bool,
+}
+
+/// Clock a pool accrues rewards on. Epoch starts, stake checkpoints and deposit locks
+/// of the pool are all expressed in this unit; governance locks and reward streams
+/// always use slots.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum TimeBasis {
+    /// Accrue per slot (default; slot timing drifts from wall-clock)
+    #[default]
+    Slot,
+    /// Accrue per second of `Clock::unix_timestamp`
+    UnixTimestamp,
+}
+
+/// Rounding applied when a position's pending reward is settled into whole token
+/// units. The remainder is carried on the position, so neither mode loses value over
+/// repeated settlements.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum RoundingMode {
+    /// Round down (default)
+    #[default]
+    Floor,
+    /// Round half up; the overshoot is deducted from the next settlement
+    Nearest,
+}
+
+impl TimeBasis {
+    /// Number of clock units in a year for this basis
+    pub fn periods_per_year(self) -> u64 {
+        match self {
+            TimeBasis::Slot => SLOTS_PER_YEAR,
+            TimeBasis::UnixTimestamp => SECONDS_PER_YEAR,
+        }
+    }
+}
+
+/// Pool parameters carried by a pool template
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct PoolTemplateParams {
+    /// Annual reward percentage in basis points (10000 bps = 100% APY)
+    pub reward_percentage: u64,
+    /// Lifetime reward cap per user (0 = unlimited)
+    pub max_reward_per_user: u64,
+    /// Lock applied to each deposit, in units of the time basis (0 = no lock)
+    pub lock_duration_slots: u64,
+    /// Halt accrual automatically when the reward vault is depleted
+    pub halt_on_depletion: bool,
+    /// Clock the pool accrues rewards on
+    pub time_basis: TimeBasis,
+    /// Minimum spacing between reward rate changes (0 = no cooldown)
+    pub min_slots_between_rate_changes: u64,
+}
+
+impl PoolTemplateParams {
+    pub fn validate(&self) -> Result<()> {
+        // Validate reward percentage to prevent accidental extreme values
+        // Format: Basis points (bps) - 10000 bps = 100% APY
+        // Examples: 550 bps = 5.50%, 1000 bps = 10.00%, 2500 bps = 25.00%
+        // - Allow 0 for no-reward staking
+        // - Cap at 100_000_000 bps (1,000,000% APY) to prevent typos and excess rewards
         require!(
-            ctx.accounts.reward_vault.amount >= total_reward,
-            CustomError::InsufficientRewardVault
+            self.reward_percentage <= 100_000_000,
+            CustomError::InvalidRewardPercentage
         );
+        require!(
+            self.lock_duration_slots <= MAX_DEPOSIT_LOCK_YEARS * self.time_basis.periods_per_year(),
+            CustomError::InvalidLockDuration
+        );
+        Ok(())
+    }
+}
 
-        // Update user state
-        user_stake.total_earned = user_stake.total_earned.checked_add(total_reward).unwrap();
-        user_stake.unclaimed = 0;
-        user_stake.last_staked_slot = clock.slot;
+/// Vetted pool configuration managed by the global config admin
+#[account]
+pub struct PoolTemplate {
+    pub template_id: u64,
+    pub params: PoolTemplateParams,
+    pub bump: u8,
+}
 
-        // Transfer rewards to user
-        let seeds = &[b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes(), &[pool.bump]];
-        let signer = &[&seeds[..]];
+/// A stake position on a pool leaderboard
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LeaderboardEntry {
+    pub user_stake: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.reward_vault.to_account_info(),
-                    to: ctx.accounts.user_reward_account.to_account_info(),
-                    authority: pool.to_account_info(),
-                },
-                signer,
-            ),
-            total_reward,
-        )?;
+/// Largest stake positions of a pool, sorted by amount (descending).
+/// Only positions updated since the leaderboard was created are ranked, and a
+/// position that drops off the list reappears on its next deposit or withdrawal.
+#[account]
+pub struct Leaderboard {
+    pub pool: Pubkey,
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
 
-        emit!(RewardClaimedEvent {
-            user: ctx.accounts.user.key(),
-            pool: pool.key(),
-            amount: total_reward,
-            total_earned: user_stake.total_earned,
-            user_stake: user_stake.amount,
-            slot: clock.slot,
-        });
+impl Leaderboard {
+    /// Re-rank a position after its staked amount changed
+    pub fn record(&mut self, user_stake: Pubkey, owner: Pubkey, amount: u64) {
+        self.entries.retain(|e| e.user_stake != user_stake);
+        if amount == 0 {
+            return;
+        }
+        let index = self.entries.partition_point(|e| e.amount >= amount);
+        if index < MAX_LEADERBOARD_ENTRIES {
+            self.entries.insert(index, LeaderboardEntry { user_stake, owner, amount });
+            self.entries.truncate(MAX_LEADERBOARD_ENTRIES);
+        }
+    }
+}
 
-        msg!("Claimed {} reward tokens", total_reward);
-        msg!("User stake remains: {}", user_stake.amount);
+/// Operators the pool owner has delegated day-to-day operations to
+#[account]
+pub struct PoolAdmins {
+    pub pool: Pubkey,
+    pub admins: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Boost a pool's booster program grants a staker; the interface external booster
+/// programs implement. The account must be owned by `pool.booster_program` and carry
+/// the Anchor discriminator of `BoostRecord` (sha256("account:BoostRecord")[..8]), which
+/// an Anchor program gets by declaring an identical `#[account] struct BoostRecord`.
+/// stake_program only reads it, in refresh_boost; boosters invoke refresh_boost by CPI
+/// after changing a record so the new multiplier takes effect immediately.
+#[account]
+pub struct BoostRecord {
+    /// Pool the boost applies to
+    pub pool: Pubkey,
+    /// Staker wallet the boost applies to (all of their positions in the pool)
+    pub user: Pubkey,
+    /// Reward multiplier in basis points; clamped to 10_000..=MAX_BOOST_BPS
+    pub multiplier_bps: u16,
+}
+
+/// Tracks the next position index for a user's stake positions in a pool
+#[account]
+pub struct UserPositionCounter {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub next_position_index: u64,
+    pub bump: u8,
+}
+
+/// Tracks the next available pool_id for a specific token mint
+/// This enables auto-incrementing pool IDs for multiple pools per token
+#[account]
+pub struct PoolIdCounter {
+    pub token_mint: Pubkey,
+    pub next_pool_id: u64,
+    pub bump: u8,
+}
+
+impl PoolIdCounter {
+    /// Reserve `pool_id` for a new pool of `token_mint`
+    pub fn claim_pool_id(&mut self, token_mint: Pubkey, bump: u8, pool_id: u64) -> Result<()> {
+        if self.next_pool_id == 0 && self.token_mint == Pubkey::default() {
+            // First time initialization
+            self.token_mint = token_mint;
+            self.bump = bump;
+        }
+
+        // Validate pool_id matches expected next_pool_id for auto-increment
+        // This ensures pools are created in sequential order
+        require!(
+            pool_id == self.next_pool_id,
+            CustomError::InvalidPoolId
+        );
 
+        // Increment counter for next pool (check for overflow)
+        self.next_pool_id = self.next_pool_id
+            .checked_add(1)
+            .ok_or(CustomError::PoolCounterOverflow)?;
         Ok(())
     }
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(maybe_owner: Option<Pubkey>, reward_percentage: u64, pool_id: u64)]
-pub struct CreatePool<'info> {
-    /// Pool ID counter for tracking pool IDs per token mint
-    #[account(
-        init_if_needed,
-        payer = admin,
-        seeds = [b"pool_id_counter", token_mint.key().as_ref()],
-        bump,
-        space = 8 + 32 + 8 + 1
-    )]
-    pub pool_id_counter: Account<'info, PoolIdCounter>,
-
-    /// Pool account PDA, must not exist prior to creation to prevent reinitialization attacks
+#[instruction(pool_id: u64)]
+pub struct SetStakingActive<'info> {
     #[account(
-        init,
-        payer = admin,
+        mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
-        bump,
-        // Space calculation:
-        // 8 (discriminator) + 32 (token_mint) + 32 (reward_mint) + 32 (reward_vault) +
-        // 32 (owner) + 8 (total_staked) + 8 (reward_percentage) + 1 (bump) + 1 (is_active) +
-        // 4 (vec length) + 10 * (8 + 8) (max 10 epochs: reward_percentage + start_time) +
-        // 8 (last_reward_update_time) + 8 (pool_id)
-        space = 8 + 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1 + 4 + (10 * 16) + 8 + 8
+        bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
-
-    /// Token mint for which the pool is created
+    pub admin: Signer<'info>,
+    /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
-    pub reward_mint: Account<'info, Mint>,
-
-    #[account(
-        init,
-        payer = admin,
-        seeds = [b"reward_vault", pool.key().as_ref(), reward_mint.key().as_ref()],
-        bump,
-        token::mint = reward_mint,
-        token::authority = pool
-    )]
-    pub reward_vault: Account<'info, TokenAccount>,
 
-    /// Pool vault PDA for user stakes (new)
+    /// Pool admin list, required when the signer is a pool admin rather than the owner
     #[account(
-        init,
-        payer = admin,
-        seeds = [b"vault", pool.key().as_ref(), token_mint.key().as_ref()],
-        bump,
-        token::mint = token_mint,
-        token::authority = pool
+        seeds = [b"pool_admins", pool.key().as_ref()],
+        bump = pool_admins.bump
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
-
-    /// Admin of the program, used as payer and default owner
-    /// Must match global config admin
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    pub pool_admins: Option<Account<'info, PoolAdmins>>,
+}
 
-    /// Global config PDA containing authorized admin
-    #[account(seeds = [b"global_config"], bump = config.bump)]
-    pub config: Account<'info, GlobalConfig>,
+#[account]
+pub struct UserStake {
+    pub owner: Pubkey,         // staker wallet
+    pub pool: Pubkey,          // reference to pool
+    pub amount: u64,           // currently staked tokens
+    pub last_staked_slot: u64, // last deposit/withdraw slot
+    pub total_earned: u64,     // total rewards earned including claimed
+    pub unclaimed: u64,        // pending rewards not yet claimed
+    pub bump: u8,
+    pub governance_locked_amount: u64, // stake locked for governance voting
+    pub governance_lock_end_slot: u64, // slot at which the governance lock expires
+    pub reward_capped: bool,           // lifetime reward cap reached
+    pub position_index: u64,           // index of this position among the user's positions in the pool
+    pub tranches: Vec<StakeTranche>,   // per-deposit locks (max MAX_STAKE_TRANCHES), sum == amount
+    pub frozen: bool,                  // set by the pool owner; blocks deposit/withdraw/claim
+    pub auto_claim: bool,              // opt-in for permissionless crank_claim by keepers
+    pub keeper_fee: u64,               // reward tokens paid to the keeper per crank_claim
+    pub version: u8,                   // layout version (0 = created before versioning)
+    pub first_deposit_slot: u64,       // start of the reward cliff (0 = never deposited)
+    pub reward_index_snapshot: u128,   // pool reward index at last_staked_slot (version >= 2)
+    pub reward_remainder: i64,         // scaled rounding remainder carried to the next settlement
+    pub partner_tag: [u8; 16],         // integration partner credited with the first deposit (zero = none)
+    pub boost_bps: u16,                // reward multiplier cached from the pool's booster (0 = none)
+    pub active_slots_snapshot: u64,    // pool active-slot count at last_staked_slot (fixed-rate pools)
+    pub _reserved: [u8; 6],            // zeroed padding for future fields
+}
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+/// A single deposit within a stake position, with its own lock and rate context
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StakeTranche {
+    /// Tokens deposited in this tranche (reduced by withdrawals)
+    pub amount: u64,
+    /// Slot of the deposit
+    pub deposit_slot: u64,
+    /// Slot at which the tranche becomes withdrawable
+    pub lock_end_slot: u64,
+    /// Pool reward rate (bps) when the tranche was deposited
+    pub reward_percentage: u64,
 }
 
-/// Initialize global config
-/// 
-/// SECURITY: The `init` constraint ensures this can only be called once.
-/// Deployment scripts must call this immediately after program deployment
-/// with the upgrade authority as the admin signer to prevent front-running.
-#[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(
-        init,
-        payer = admin,
-        seeds = [b"global_config"],
-        bump,
-        space = 8 + 32 + 1
-    )]
-    pub config: Account<'info, GlobalConfig>,
+/// Locked stake position represented by an NFT. Keyed by the NFT mint, so the
+/// position belongs to whoever currently holds the NFT.
+#[account]
+pub struct StakePositionNft {
+    pub pool: Pubkey,
+    pub nft_mint: Pubkey,
+    /// Principal locked in the pool vault (0 once redeemed)
+    pub amount: u64,
+    pub deposit_slot: u64,
+    /// Slot at which the position can be redeemed; accrual stops here
+    pub lock_end_slot: u64,
+    /// Rewards owed after redemption while the reward vault was short
+    pub unclaimed: u64,
+    pub bump: u8,
+}
 
-    #[account(mut)]
-    pub admin: Signer<'info>,
+/// Optional Metaplex metadata for a position NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PositionNftMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
 
-    pub system_program: Program<'info, System>,
+/// Vote-escrow record mirroring a user's governance lock.
+/// Read by governance tooling to derive voting weight.
+#[account]
+pub struct VoteEscrow {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub user_stake: Pubkey,
+    pub locked_amount: u64,
+    pub lock_start_slot: u64,
+    pub lock_end_slot: u64,
+    pub bump: u8,
 }
 
-/// Transfer admin rights
-#[derive(Accounts)]
-pub struct TransferAdmin<'info> {
-    #[account(mut, seeds = [b"global_config"], bump = config.bump)]
-    pub config: Account<'info, GlobalConfig>,
+impl VoteEscrow {
+    /// Voting weight = locked amount × remaining lock slots (zero once expired)
+    pub fn voting_weight(&self, current_slot: u64) -> u128 {
+        let remaining = self.lock_end_slot.saturating_sub(current_slot);
+        (self.locked_amount as u128) * (remaining as u128)
+    }
+}
 
-    #[account(constraint = admin.key() == config.admin @ CustomError::UnauthorizedPoolCreator)]
-    pub admin: Signer<'info>,
+/// Linear reward funding schedule for a pool.
+/// Escrowed tokens vest between start_slot and end_slot and are moved into the
+/// reward vault by the permissionless release_streamed_rewards crank.
+#[account]
+pub struct RewardStream {
+    pub pool: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub start_slot: u64,
+    pub end_slot: u64,
+    pub bump: u8,
 }
 
-#[derive(Accounts)]
-#[instruction(pool_id: u64)]
-pub struct GetPoolInfo<'info> {
-    #[account(
-        seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
-        bump = pool.bump
-    )]
-    pub pool: Account<'info, Pool>,
-    pub token_mint: Account<'info, Mint>,
+impl RewardStream {
+    /// Amount vested by `slot` (linear between start and end)
+    pub fn vested_amount(&self, slot: u64) -> u64 {
+        if slot <= self.start_slot {
+            return 0;
+        }
+        if slot >= self.end_slot {
+            return self.total_amount;
+        }
+        let elapsed = (slot - self.start_slot) as u128;
+        let duration = (self.end_slot - self.start_slot) as u128;
+        ((self.total_amount as u128) * elapsed / duration) as u64
+    }
+
+    /// A stream is active until everything has been released
+    pub fn is_active(&self) -> bool {
+        self.released_amount < self.total_amount
+    }
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct UpdateRewardMint<'info> {
+pub struct MigratePool<'info> {
+    /// CHECK: Pool in any layout version; deserialized (discriminator checked) after
+    /// being resized in the handler
     #[account(
         mut,
+        owner = crate::ID,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
-        bump = pool.bump
+        bump
     )]
-    pub pool: Account<'info, Pool>,
-
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    pub pool: UncheckedAccount<'info>,
 
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 
-    /// The new reward mint account
-    pub new_reward_mint: Account<'info, Mint>,
-
-    /// SECURITY NOTE: init_if_needed is acceptable here because:
-    /// 1. The function has owner authorization check
-    /// 2. The vault is deterministically derived from pool and new_reward_mint
-    /// 3. This allows updating to an existing vault or creating a new one
-    /// 4. Token account reinitialization is safe as authority is set to pool PDA
-    #[account(
-        init_if_needed,
-        payer = admin,
-        seeds = [b"reward_vault", pool.key().as_ref(), new_reward_mint.key().as_ref()],
-        bump,
-        token::mint = new_reward_mint,
-        token::authority = pool,
-    )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    /// Pays rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct DepositReward<'info> {
+pub struct MigrateUserStake<'info> {
     #[account(
-        mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
         bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
 
-    /// Admin signs (must be pool.owner)
-    pub admin: Signer<'info>,
-
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 
-    /// Enforce canonical ATA for admin to prevent token loss to arbitrary accounts
-    #[account(
-        mut,
-        constraint = admin_reward_account.mint == pool.reward_mint,
-        constraint = admin_reward_account.key() == get_associated_token_address(&admin.key(), &pool.reward_mint) @ CustomError::NonCanonicalAta
-    )]
-    pub admin_reward_account: Account<'info, TokenAccount>,
+    /// CHECK: User stake in any layout version; deserialized (discriminator checked)
+    /// after being resized in the handler
+    #[account(mut, owner = crate::ID)]
+    pub user_stake: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
-        bump
-    )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    /// Pays rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(pool_id: u64)]
-pub struct GetUserStakeInfo<'info> {
-    #[account(
-        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
-    )]
-    pub user_stake: Account<'info, UserStake>,
+#[instruction(pool_id: u64, position_index: u64)]
+pub struct InitUserStake<'info> {
     #[account(
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
         bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
+
+    /// Per-user position counter for this pool
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [b"user_positions", pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = 8 + 32 + 32 + 8 + 1
+    )]
+    pub user_positions: Account<'info, UserPositionCounter>,
+
+    /// `init` (not init_if_needed) so an existing position can never be reset
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"user_stake", pool.key().as_ref(), user.key().as_ref(), &position_index.to_le_bytes()],
+        bump,
+        space = USER_STAKE_SPACE
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// CHECK: Wallet that will own the stake position; only used as PDA seed and owner
+    pub user: UncheckedAccount<'info>,
+
+    /// Pays rent for the new account (may be the user or a separate fee payer)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct UserStakeData {
-    pub owner: Pubkey,
-    pub pool: Pubkey,
-    pub amount: u64,
-    pub total_earned: u64,
-    pub last_staked_slot: u64,
-    pub unclaimed: u64,
-    pub bump: u8,
+    pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct WithdrawStake<'info> {
+pub struct DepositStake<'info> {
+    /// The staking pool
     #[account(
         mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
@@ -967,23 +5396,22 @@ pub struct WithdrawStake<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
-    /// Security: Enforce that only the owner of the user_stake account can withdraw.
-    /// This prevents privilege escalation where a malicious user attempts to withdraw
-    /// from another user's stake account by providing a different user_stake PDA.
-    /// Also validates that the user_stake belongs to the correct pool.
+    /// PDA tracking this user's stake in the pool (created by init_user_stake)
     #[account(
         mut,
-        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
-        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+        seeds = [b"user_stake", pool.key().as_ref(), user.key().as_ref(), &user_stake.position_index.to_le_bytes()],
+        bump = user_stake.bump
     )]
     pub user_stake: Account<'info, UserStake>,
 
+    /// The user who is staking
     #[account(mut)]
     pub user: Signer<'info>,
 
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 
+    /// User's token account to transfer tokens from
     #[account(
         mut,
         constraint = user_token_account.mint == pool.token_mint,
@@ -991,13 +5419,7 @@ pub struct WithdrawStake<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
-     #[account(
-        mut,
-        constraint = user_reward_account.mint == pool.reward_mint,
-        constraint = user_reward_account.owner == user.key(),
-    )]
-    pub user_reward_account: Account<'info, TokenAccount>, 
-
+    /// The pool's vault (single vault for all users)
     #[account(
         mut,
         seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
@@ -1005,19 +5427,27 @@ pub struct WithdrawStake<'info> {
     )]
     pub pool_vault: Account<'info, TokenAccount>,
 
+    /// Pool leaderboard, if one exists; updated with the position's new amount
     #[account(
         mut,
-        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
-        bump,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Staker's credential token account; required when the pool has an attestation_issuer
+    pub attestation: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct WithdrawReward<'info> {
+pub struct DepositStakeDelegated<'info> {
+    /// The staking pool
     #[account(
         mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
@@ -1025,80 +5455,113 @@ pub struct WithdrawReward<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
-    /// Admin signer (must be pool owner)
-    pub admin: Signer<'info>,
+    /// PDA tracking the staker's (not the relayer's) position.
+    /// The seeds are bound to the token account owner, so a relayer can only credit that owner.
+    #[account(
+        mut,
+        seeds = [b"user_stake", pool.key().as_ref(), user.key().as_ref(), &user_stake.position_index.to_le_bytes()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// Staker wallet being credited; bound to user_token_account.owner below and must
+    /// sign so a relayer cannot pick which of the owner's positions receives the tokens
+    pub user: Signer<'info>,
+
+    /// Relayer submitting the transaction and paying fees/rent
+    #[account(mut)]
+    pub relayer: Signer<'info>,
 
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 
-    /// Admin's token account to receive rewards
-    /// Enforce canonical ATA for admin to prevent token loss to arbitrary accounts
+    /// Staker's token account with the pool vault approved as delegate
     #[account(
         mut,
-        constraint = admin_reward_account.mint == pool.reward_mint,
-        constraint = admin_reward_account.key() == get_associated_token_address(&admin.key(), &pool.reward_mint) @ CustomError::NonCanonicalAta
+        constraint = user_token_account.mint == pool.token_mint,
+        constraint = user_token_account.owner == user.key(),
     )]
-    pub admin_reward_account: Account<'info, TokenAccount>,
+    pub user_token_account: Account<'info, TokenAccount>,
 
-    /// Pool's reward vault
+    /// The pool's vault, acting as the approved delegate
     #[account(
         mut,
-        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
-        bump
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub pool_vault: Account<'info, TokenAccount>,
+
+    /// Pool leaderboard, if one exists; updated with the position's new amount
+    #[account(
+        mut,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Staker's credential token account; required when the pool has an attestation_issuer
+    pub attestation: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct ClaimReward<'info> {
+pub struct DepositStakeSol<'info> {
+    /// The staking pool (must be a wSOL pool)
     #[account(
+        mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
         bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
 
-    /// Token mint for the pool (used for PDA validation)
-    pub token_mint: Account<'info, Mint>,
-
-    /// Security: Enforce that only the owner of the user_stake account can claim rewards.
-    /// This prevents privilege escalation where a malicious user attempts to claim rewards
-    /// from another user's stake account by providing a different user_stake PDA.
-    /// Also validates that the user_stake belongs to the correct pool.
+    /// PDA tracking this user's stake in the pool (created by init_user_stake)
     #[account(
         mut,
-        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
-        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+        seeds = [b"user_stake", pool.key().as_ref(), user.key().as_ref(), &user_stake.position_index.to_le_bytes()],
+        bump = user_stake.bump
     )]
     pub user_stake: Account<'info, UserStake>,
 
+    /// The user who is staking SOL
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// User's reward token account to receive rewards
+    /// Native mint (used for PDA validation)
+    pub token_mint: Account<'info, Mint>,
+
+    /// The pool's wSOL vault
     #[account(
         mut,
-        constraint = user_reward_account.mint == pool.reward_mint,
-        constraint = user_reward_account.owner == user.key(),
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub pool_vault: Account<'info, TokenAccount>,
 
-    /// Pool's reward vault
+    /// Pool leaderboard, if one exists; updated with the position's new amount
     #[account(
         mut,
-        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
-        bump
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    /// Staker's credential token account; required when the pool has an attestation_issuer
+    pub attestation: Option<Account<'info, TokenAccount>>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct UpdateRewardPercentage<'info> {
+pub struct ForceUnstake<'info> {
     #[account(
         mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
@@ -1106,172 +5569,167 @@ pub struct UpdateRewardPercentage<'info> {
     )]
     pub pool: Account<'info, Pool>,
 
+    #[account(
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// CHECK: Owner of the position being unwound; only used to validate destination accounts
+    pub user: UncheckedAccount<'info>,
+
+    /// Pool owner (validated in instruction)
     pub admin: Signer<'info>,
 
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
-}
 
-#[derive(Accounts)]
-pub struct UpdatePoolAuthority<'info> {
     #[account(
         mut,
-        seeds = [b"staking_pool", pool.token_mint.as_ref(), &pool.pool_id.to_le_bytes()],
-        bump = pool.bump
+        constraint = user_token_account.mint == pool.token_mint,
+        constraint = user_token_account.owner == user.key(),
     )]
-    pub pool: Account<'info, Pool>,
-
-    /// Current authority must sign to authorize the change
-    pub current_authority: Signer<'info>,
-}
+    pub user_token_account: Account<'info, TokenAccount>,
 
-/// Represents a reward epoch - a period with a specific reward rate
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RewardEpoch {
-    /// The reward percentage for this epoch in basis points (bps)
-    pub reward_percentage: u64,
-    /// The slot when this epoch starts
-    pub start_slot: u64,
-}
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == pool.reward_mint @ CustomError::InvalidRewardAccount,
+        constraint = user_reward_account.owner == user.key() @ CustomError::InvalidRewardAccount,
+    )]
+    pub user_reward_account: Account<'info, TokenAccount>,
 
-/// Global configuration for pool creation authorization
-#[account]
-pub struct GlobalConfig {
-    /// Authorized admin who can create pools
-    pub admin: Pubkey,
-    /// PDA bump seed
-    pub bump: u8,
-}
+    /// CHECK: Vote escrow PDA of the position; its lock is cleared with the stake when
+    /// it has been created (checked in the handler)
+    #[account(
+        mut,
+        seeds = [b"vote_escrow", pool.key().as_ref(), user.key().as_ref(), &user_stake.position_index.to_le_bytes()],
+        bump
+    )]
+    pub vote_escrow: UncheckedAccount<'info>,
 
-#[account]
-pub struct Pool {
-    pub token_mint: Pubkey,
-    pub reward_mint: Pubkey,
-    pub reward_vault: Pubkey,
-    pub owner: Pubkey,
-    pub total_staked: u64,
-    /// Current annual reward percentage in basis points (bps)
-    /// 10000 bps = 100% APY
-    /// Examples: 550 = 5.50%, 1000 = 10.00%, 2500 = 25.00%
-    pub reward_percentage: u64,
-    pub bump: u8,
-    pub is_active: bool,
-    /// Historical reward epochs (max 10 epochs to limit account size)
-    /// Most recent epoch is at the end of the vector
-    pub reward_epochs: Vec<RewardEpoch>,
-    /// Slot of the last reward percentage update
-    pub last_reward_update_slot: u64,
-    /// Unique pool identifier for this token mint
-    /// Allows multiple pools per token mint
-    pub pool_id: u64,
-}
+    #[account(
+        mut,
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
+    )]
+    pub pool_vault: Account<'info, TokenAccount>,
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct PoolData {
-    pub token_mint: Pubkey,
-    pub reward_mint: Pubkey,
-    pub reward_vault: Pubkey,
-    pub owner: Pubkey,
-    pub total_staked: u64,
-    /// Current annual reward percentage in basis points (bps)
-    /// 10000 bps = 100% APY
-    /// Examples: 550 = 5.50%, 1000 = 10.00%, 2500 = 25.00%
-    pub reward_percentage: u64,
-    pub bump: u8,
-    pub is_active: bool,
-    /// Historical reward epochs
-    pub reward_epochs: Vec<RewardEpoch>,
-    /// Slot of the last reward percentage update
-    pub last_reward_update_slot: u64,
-    /// Unique pool identifier for this token mint
-    pub pool_id: u64,
-}
+    #[account(
+        mut,
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
+        bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
 
-/// Tracks the next available pool_id for a specific token mint
-/// This enables auto-incrementing pool IDs for multiple pools per token
-#[account]
-pub struct PoolIdCounter {
-    pub token_mint: Pubkey,
-    pub next_pool_id: u64,
-    pub bump: u8,
+    /// Pool leaderboard, if one exists; updated with the position's new amount
+    #[account(
+        mut,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
+
+    pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct SetStakingActive<'info> {
+pub struct SetUserStakeFrozen<'info> {
     #[account(
-        mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
         bump = pool.bump
     )]
     pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    /// Pool owner (validated in instruction)
     pub admin: Signer<'info>,
+
     /// Token mint for the pool (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 }
 
-#[account]
-pub struct UserStake {
-    pub owner: Pubkey,         // staker wallet
-    pub pool: Pubkey,          // reference to pool
-    pub amount: u64,           // currently staked tokens
-    pub last_staked_slot: u64, // last deposit/withdraw slot
-    pub total_earned: u64,     // total rewards earned including claimed
-    pub unclaimed: u64,        // pending rewards not yet claimed
-    pub bump: u8,
-}
-
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(pool_id: u64)]
-pub struct DepositStake<'info> {
-    /// The staking pool
+pub struct WithdrawStakeSol<'info> {
     #[account(
         mut,
         seeds = [b"staking_pool", token_mint.key().as_ref(), &pool_id.to_le_bytes()],
-        bump = pool.bump
+        bump = pool.bump,
+        has_one = reward_mint
     )]
     pub pool: Account<'info, Pool>,
 
-    /// PDA to track this user's stake in the pool
-    /// SECURITY NOTE: init_if_needed is acceptable here because:
-    /// 1. The account is derived from user's pubkey (signer) and pool
-    /// 2. deposit_stake function has logic to handle both new and existing stakes
-    /// 3. Reinitialization by the same user would only affect their own account
-    /// 4. However, the logic checks amount == 0 to detect first initialization
-    /// 5. Pool association is validated in the instruction logic
+    /// Security: only the owner of the user_stake account can withdraw
     #[account(
-        init_if_needed,
-        payer = user,
-        seeds = [b"user_stake", pool.key().as_ref(), user.key().as_ref()],
-        bump,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1
+        mut,
+        constraint = user_stake.owner == user.key() @ CustomError::Unauthorized,
+        constraint = user_stake.pool == pool.key() @ CustomError::InvalidPoolAssociation
     )]
     pub user_stake: Account<'info, UserStake>,
 
-    /// The user who is staking
+    /// Receives the unwrapped SOL
     #[account(mut)]
     pub user: Signer<'info>,
 
-    /// Token mint for the pool (used for PDA validation)
+    /// Native mint (used for PDA validation)
     pub token_mint: Account<'info, Mint>,
 
-    /// User's token account to transfer tokens from
+    /// CHECK: User's reward token account; created as the user's ATA if missing,
+    /// then validated (mint + owner) in instruction
+    #[account(mut)]
+    pub user_reward_account: UncheckedAccount<'info>,
+
+    /// Reward mint for the pool (needed to create the user's reward ATA)
+    pub reward_mint: Account<'info, Mint>,
+
     #[account(
         mut,
-        constraint = user_token_account.mint == pool.token_mint,
-        constraint = user_token_account.owner == user.key(),
+        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        bump,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub pool_vault: Account<'info, TokenAccount>,
 
-    /// The pool's vault (single vault for all users)
     #[account(
         mut,
-        seeds = [b"vault", pool.key().as_ref(), pool.token_mint.as_ref()],
+        seeds = [b"reward_vault", pool.key().as_ref(), pool.reward_mint.as_ref()],
         bump,
+        constraint = reward_vault.key() == pool.reward_vault @ CustomError::InvalidRewardVault,
+        constraint = reward_vault.mint == pool.reward_mint @ CustomError::InvalidRewardVault
     )]
-    pub pool_vault: Account<'info, TokenAccount>,
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Temporary wSOL account, created and closed within this instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"unwrap", pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool
+    )]
+    pub unwrap_account: Account<'info, TokenAccount>,
+
+    /// Pool leaderboard, if one exists; updated with the position's new amount
+    #[account(
+        mut,
+        seeds = [b"leaderboard", pool.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Account<'info, Leaderboard>>,
 
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -1285,28 +5743,362 @@ pub struct UserStakeInfoWithReward {
     pub last_staked_slot: u64,
     pub unclaimed: u64,
     pub bump: u8,
+    pub governance_locked_amount: u64,
+    pub governance_lock_end_slot: u64,
+    pub position_index: u64,
+    pub tranches: Vec<StakeTranche>,
+    pub frozen: bool,
+    pub auto_claim: bool,
+    pub keeper_fee: u64,
     pub pending_reward: u64,
 }
 
-impl UserStake {
-    pub fn calculate_pending_reward(&self, pool: &Pool) -> u64 {
-        let clock = Clock::get().unwrap();
-        let current_slot = clock.slot;
+impl Pool {
+    /// Populate a freshly created pool. Callers enforce same-token staking, so the
+    /// reward mint is the staking mint.
+    pub fn initialize(
+        &mut self,
+        pool_id: u64,
+        bump: u8,
+        owner: Pubkey,
+        token_mint: Pubkey,
+        reward_vault: Pubkey,
+        params: &PoolTemplateParams,
+    ) -> Result<()> {
+        self.owner = owner;
+        self.token_mint = token_mint;
+        self.reward_mint = token_mint;
+        self.reward_percentage = params.reward_percentage;
+        self.total_staked = 0;
+        self.bump = bump;
+        self.reward_vault = reward_vault;
+        self.is_active = true;
+        self.pool_id = pool_id;
+        self.max_reward_per_user = params.max_reward_per_user;
+        self.halt_on_depletion = params.halt_on_depletion;
+        self.accrual_halted = false;
+        self.accrual_halted_slot = 0;
+        self.accrual_frozen = false;
+        self.lock_duration_slots = params.lock_duration_slots;
+        self.governance_program = Pubkey::default();
+        self.governance_realm = Pubkey::default();
+        self.version = POOL_VERSION;
+        self.epoch_capacity = DEFAULT_EPOCH_CAPACITY;
+        self.time_basis = params.time_basis;
+        self.periods_per_year = params.time_basis.periods_per_year();
+        self.withdraw_guardian = Pubkey::default();
+        self.guardian_threshold = 0;
+        self.min_slots_between_rate_changes = params.min_slots_between_rate_changes;
+        self.reward_cliff_slots = 0;
+        self.min_claim_amount = 0;
+        self.archived_reward_index = 0;
+        self.rounding_mode = RoundingMode::Floor;
+        self.attestation_issuer = Pubkey::default();
+        self.end_slot = 0;
+        self.booster_program = Pubkey::default();
+        self.lock_rate_at_deposit = false;
+        self.archived_active_slots = 0;
+        self.reward_scale_periods = 0;
+        self.unguarded_withdrawn = 0;
+        self._reserved = [0; 7];
+
+        // Initialize first reward epoch at the current time
+        let now = self.now(&Clock::get()?);
+        self.reward_epochs = vec![RewardEpoch {
+            reward_percentage: params.reward_percentage,
+            start_slot: now,
+        }];
+        self.last_reward_update_slot = now;
+        Ok(())
+    }
+
+    /// Whether `signer` may run day-to-day operations: the owner or a listed pool admin
+    pub fn is_operator(&self, signer: &Pubkey, pool_admins: Option<&PoolAdmins>) -> bool {
+        self.owner == *signer || pool_admins.is_some_and(|a| a.admins.contains(signer))
+    }
+
+    /// True for pools staking wrapped SOL, which support native SOL deposits/withdrawals
+    pub fn is_native_sol(&self) -> bool {
+        self.token_mint == spl_token::native_mint::ID
+    }
+
+    /// True while accrual is stopped by a pause, depletion halt or freeze
+    pub fn is_accrual_gated(&self) -> bool {
+        !self.is_active || self.accrual_halted || self.accrual_frozen
+    }
+
+    /// Rate actually accruing right now: zero while the pool is paused or accrual is
+    /// halted or frozen, otherwise the configured reward_percentage
+    pub fn effective_reward_percentage(&self) -> u64 {
+        if self.is_accrual_gated() {
+            0
+        } else {
+            self.reward_percentage
+        }
+    }
+
+    /// Latest epoch that has started at `slot`
+    pub fn active_epoch(&self, slot: u64) -> Option<&RewardEpoch> {
+        self.reward_epochs.iter().rev().find(|e| e.start_slot <= slot)
+    }
+
+    /// Configured rate at `slot`, including scheduled changes that have started.
+    /// Scheduled epochs only exist while accrual is ungated, so in that state the
+    /// active epoch's rate is the configured rate.
+    pub fn current_reward_percentage(&self, slot: u64) -> u64 {
+        if self.is_accrual_gated() {
+            return self.reward_percentage;
+        }
+        self.active_epoch(slot)
+            .map(|e| e.reward_percentage)
+            .unwrap_or(self.reward_percentage)
+    }
+
+    /// Folds scheduled changes that have started into reward_percentage.
+    /// Must run before a gate flag changes, while the schedule is still authoritative.
+    pub fn apply_reward_schedule(&mut self, slot: u64) {
+        self.reward_percentage = self.current_reward_percentage(slot);
+    }
+
+    /// Number of queued epochs that have not started yet
+    pub fn scheduled_epoch_count(&self, slot: u64) -> usize {
+        self.reward_epochs.iter().filter(|e| e.start_slot > slot).count()
+    }
+
+    /// Drops queued epochs that have not started yet, returning how many were removed
+    pub fn cancel_scheduled_epochs(&mut self, slot: u64) -> usize {
+        let before = self.reward_epochs.len();
+        self.reward_epochs.retain(|e| e.start_slot <= slot);
+        before - self.reward_epochs.len()
+    }
+
+    /// Current time in the pool's time basis
+    pub fn now(&self, clock: &Clock) -> u64 {
+        match self.time_basis {
+            TimeBasis::Slot => clock.slot,
+            TimeBasis::UnixTimestamp => clock.unix_timestamp.max(0) as u64,
+        }
+    }
+
+    /// APY divisor; pools created before the field existed use SLOTS_PER_YEAR
+    pub fn periods_per_year(&self) -> u64 {
+        if self.periods_per_year == 0 {
+            SLOTS_PER_YEAR
+        } else {
+            self.periods_per_year
+        }
+    }
+
+    /// Divisor scaled rewards are expressed in. Until the APY divisor is first changed
+    /// this is periods_per_year; pools not yet migrated to layout version 2 may hold
+    /// stale bytes in the field.
+    pub fn reward_scale_periods(&self) -> u64 {
+        if self.version >= 2 && self.reward_scale_periods != 0 {
+            self.reward_scale_periods
+        } else {
+            self.periods_per_year()
+        }
+    }
+
+    /// Converts rate × time accrued under the current periods_per_year into the units of
+    /// reward_scale_periods (rounded down)
+    pub fn to_scale_units(&self, value: u128) -> u128 {
+        let reference = self.reward_scale_periods() as u128;
+        let current = self.periods_per_year() as u128;
+        if reference == current {
+            return value;
+        }
+        (value / current)
+            .saturating_mul(reference)
+            .saturating_add((value % current) * reference / current)
+    }
+
+    /// Folds every epoch that has started into the archived indexes and restarts the
+    /// history at `slot` with the active rate, so accrual so far is fixed under the
+    /// current periods_per_year. Queued epochs are kept.
+    pub fn archive_reward_epochs(&mut self, slot: u64) {
+        let rate = self
+            .active_epoch(slot)
+            .map(|e| e.reward_percentage)
+            .unwrap_or_else(|| self.effective_reward_percentage());
+        self.archived_reward_index = self.reward_index_at(slot);
+        self.archived_active_slots = self.active_slots_at(slot);
+        self.reward_epochs.retain(|e| e.start_slot > slot);
+        self.reward_epochs.insert(0, RewardEpoch {
+            reward_percentage: rate,
+            start_slot: slot,
+        });
+    }
+
+    /// Effective reward epoch capacity; pools created before the field existed keep 10
+    pub fn epoch_capacity(&self) -> u16 {
+        if self.epoch_capacity == 0 {
+            DEFAULT_EPOCH_CAPACITY
+        } else {
+            self.epoch_capacity
+        }
+    }
+
+    /// Configured end of the pool (0 = open-ended). Pools not yet migrated to layout
+    /// version 2 have no end; their end_slot bytes may hold stale data.
+    pub fn end_slot(&self) -> u64 {
+        if self.version >= 2 {
+            self.end_slot
+        } else {
+            0
+        }
+    }
+
+    /// Whether the pool has reached its end_slot at `now`
+    pub fn has_ended(&self, now: u64) -> bool {
+        let end_slot = self.end_slot();
+        end_slot != 0 && now >= end_slot
+    }
+
+    /// Sets the pool's end: any future point for an open-ended pool, otherwise only a
+    /// later one, and only before the current end has passed
+    pub fn extend_end_slot(&mut self, end_slot: u64, now: u64) -> Result<()> {
+        require!(self.version >= 2, CustomError::PoolNotMigrated);
+        let old_end_slot = self.end_slot();
+        require!(end_slot > now, CustomError::InvalidEndSlot);
+        require!(
+            old_end_slot == 0 || (!self.has_ended(now) && end_slot > old_end_slot),
+            CustomError::InvalidEndSlot
+        );
+        self.end_slot = end_slot;
+        Ok(())
+    }
+
+    /// `slot` clamped to the pool's end, the last point at which rewards accrue
+    pub fn accrual_cutoff(&self, slot: u64) -> u64 {
+        match self.end_slot() {
+            0 => slot,
+            end_slot => slot.min(end_slot),
+        }
+    }
+
+    /// Appends a reward epoch, keeping a sliding window of at most epoch_capacity epochs.
+    /// Returns the epoch that was pruned to make room, if any.
+    pub fn push_reward_epoch(&mut self, reward_percentage: u64, slot: u64) -> Option<ArchivedEpoch> {
+        // When capacity is reached, the oldest epoch is folded into archived_reward_index
+        // and removed before adding the new one
+        let archived = if self.reward_epochs.len() >= self.epoch_capacity() as usize
+            && self.reward_epochs.len() >= 2
+        {
+            let oldest = self.reward_epochs.remove(0);
+            let end_slot = self.reward_epochs[0].start_slot;
+            // Time past the pool's end never accrues; the end can no longer move once
+            // passed, so clamping here is final
+            let accrued_slots = self
+                .accrual_cutoff(end_slot)
+                .saturating_sub(self.accrual_cutoff(oldest.start_slot));
+            self.archived_reward_index = self.archived_reward_index.saturating_add(
+                self.to_scale_units((oldest.reward_percentage as u128) * (accrued_slots as u128)),
+            );
+            if oldest.reward_percentage > 0 {
+                let active_slots = self.to_scale_units(accrued_slots as u128) as u64;
+                self.archived_active_slots = self.archived_active_slots.saturating_add(active_slots);
+            }
+            Some(ArchivedEpoch {
+                reward_percentage: oldest.reward_percentage,
+                start_slot: oldest.start_slot,
+                end_slot,
+                archived_reward_index: self.archived_reward_index,
+            })
+        } else {
+            None
+        };
+
+        self.reward_epochs.push(RewardEpoch {
+            reward_percentage,
+            start_slot: slot,
+        });
+        archived
+    }
+
+    /// Cumulative rate × time (bps × time-basis units, in reward_scale_periods units)
+    /// up to `slot`, counting pruned epochs through archived_reward_index. Differences
+    /// between two points give the reward per token between them.
+    pub fn reward_index_at(&self, slot: u64) -> u128 {
+        let slot = self.accrual_cutoff(slot);
+        let mut index: u128 = 0;
+        for (i, epoch) in self.reward_epochs.iter().enumerate() {
+            if epoch.start_slot >= slot {
+                break;
+            }
+            let end = self.reward_epochs
+                .get(i + 1)
+                .map(|next| next.start_slot.min(slot))
+                .unwrap_or(slot);
+            index = index.saturating_add(
+                (epoch.reward_percentage as u128)
+                    * (end.saturating_sub(epoch.start_slot) as u128),
+            );
+        }
+        self.archived_reward_index.saturating_add(self.to_scale_units(index))
+    }
 
-        let elapsed = current_slot.saturating_sub(self.last_staked_slot);
-        if elapsed == 0 || self.amount == 0 {
+    /// Cumulative time-basis units up to `slot` during which the pool paid a non-zero
+    /// rate (in reward_scale_periods units), counting pruned epochs through
+    /// archived_active_slots. Fixed-rate positions accrue only over these units, so
+    /// pauses and halts still stop them.
+    pub fn active_slots_at(&self, slot: u64) -> u64 {
+        let slot = self.accrual_cutoff(slot);
+        let mut active: u64 = 0;
+        for (i, epoch) in self.reward_epochs.iter().enumerate() {
+            if epoch.start_slot >= slot {
+                break;
+            }
+            if epoch.reward_percentage == 0 {
+                continue;
+            }
+            let end = self.reward_epochs
+                .get(i + 1)
+                .map(|next| next.start_slot.min(slot))
+                .unwrap_or(slot);
+            active = active.saturating_add(end.saturating_sub(epoch.start_slot));
+        }
+        self.archived_active_slots
+            .saturating_add(self.to_scale_units(active as u128) as u64)
+    }
+
+    /// Divisor turning scaled rewards (tokens × bps × time-basis units) into tokens
+    pub fn reward_scale(&self) -> u128 {
+        (self.reward_scale_periods() as u128) * 10_000
+    }
+
+    /// Reward earned by `amount` tokens staked from `start_slot` to `current_slot`,
+    /// rounded down to whole token units (no per-user cap applied)
+    pub fn accrued_reward(&self, amount: u64, start_slot: u64, current_slot: u64) -> u64 {
+        let total_reward = self.accrued_reward_scaled(amount, start_slot, current_slot) / self.reward_scale();
+
+        // AUDIT NOTE (L-07): Reward amount is capped at u64::MAX (~18.44 tokens for 18-decimal tokens).
+        // This is acceptable because the protocol restricts stake and reward to the same token mint,
+        // and rewards (a percentage of staked amount) will not approach u64::MAX under normal conditions.
+        total_reward.min(u64::MAX as u128) as u64
+    }
+
+    /// Scaled reward (tokens × bps × time-basis units) earned by `amount` tokens staked
+    /// from `start_slot` to `current_slot`, walking the reward epochs. Divide by
+    /// reward_scale for token units. Nothing accrues past the pool's end_slot.
+    /// Retained epochs all accrue under the current periods_per_year, since changing
+    /// it archives the history.
+    pub fn accrued_reward_scaled(&self, amount: u64, start_slot: u64, current_slot: u64) -> u128 {
+        let current_slot = self.accrual_cutoff(current_slot);
+        let elapsed = current_slot.saturating_sub(start_slot);
+        if elapsed == 0 || amount == 0 {
             return 0;
         }
 
 
         // Calculate rewards across all relevant epochs
         let mut total_reward: u128 = 0;
-        let mut period_start = self.last_staked_slot;
+        let mut period_start = start_slot;
         
         // Process all relevant epochs in chronological order
         // Epochs are stored chronologically, so we iterate from the beginning
-        for i in 0..pool.reward_epochs.len() {
-            let epoch = &pool.reward_epochs[i];
+        for i in 0..self.reward_epochs.len() {
+            let epoch = &self.reward_epochs[i];
             
             // Skip epochs that started after the current slot
             if epoch.start_slot > current_slot {
@@ -1314,9 +6106,9 @@ impl UserStake {
             }
             
             // Determine the end slot for this epoch
-            let period_end = if i + 1 < pool.reward_epochs.len() {
+            let period_end = if i + 1 < self.reward_epochs.len() {
                 // Next epoch exists, use its start slot as this epoch's end
-                let next_epoch_start = pool.reward_epochs[i + 1].start_slot;
+                let next_epoch_start = self.reward_epochs[i + 1].start_slot;
                 // Only consider this epoch if it overlaps with our staking period
                 if next_epoch_start <= period_start {
                     continue; // This epoch ended before our staking period
@@ -1334,18 +6126,13 @@ impl UserStake {
             if effective_end > effective_start {
                 let epoch_duration = effective_end - effective_start;
                 
-                // Calculate reward for this epoch
-                let epoch_reward = (self.amount as u128)
-                    .checked_mul(epoch.reward_percentage as u128)
-                    .unwrap()
-                    .checked_mul(epoch_duration as u128)
-                    .unwrap()
-                    .checked_div(SLOTS_PER_YEAR as u128)
-                    .unwrap()
-                    .checked_div(10_000)
-                    .unwrap_or(0);
+                // Scaled reward for this epoch; dividing once at the end avoids
+                // flooring every epoch separately
+                let epoch_reward = (amount as u128)
+                    .saturating_mul(epoch.reward_percentage as u128)
+                    .saturating_mul(epoch_duration as u128);
                 
-                total_reward = total_reward.checked_add(epoch_reward).unwrap_or(total_reward);
+                total_reward = total_reward.saturating_add(epoch_reward);
             }
             
             // Move to the next period
@@ -1357,10 +6144,246 @@ impl UserStake {
             }
         }
 
-        // AUDIT NOTE (L-07): Reward amount is capped at u64::MAX (~18.44 tokens for 18-decimal tokens).
-        // This is acceptable because the protocol restricts stake and reward to the same token mint,
-        // and rewards (a percentage of staked amount) will not approach u64::MAX under normal conditions.
-        total_reward.min(u64::MAX as u128) as u64
+        self.to_scale_units(total_reward)
+    }
+
+    /// Splits a scaled reward into whole tokens per rounding_mode and the signed
+    /// scaled remainder to carry into the next settlement
+    pub fn round_reward(&self, scaled: i128) -> (u64, i64) {
+        if scaled <= 0 {
+            return (0, scaled as i64);
+        }
+        let scale = self.reward_scale() as i128;
+        let tokens = match self.rounding_mode {
+            RoundingMode::Floor => scaled / scale,
+            RoundingMode::Nearest => (scaled + scale / 2) / scale,
+        }
+        .min(u64::MAX as i128);
+        let remainder = (scaled - tokens * scale).clamp(i64::MIN as i128, i64::MAX as i128);
+        (tokens as u64, remainder as i64)
+    }
+
+    /// Starts a new epoch if the effective rate no longer matches the active epoch.
+    /// Queued rate changes are cancelled while accrual is gated.
+    pub fn sync_reward_epoch(&mut self, slot: u64) -> Option<ArchivedEpoch> {
+        if self.is_accrual_gated() {
+            self.cancel_scheduled_epochs(slot);
+        }
+        let effective = self.effective_reward_percentage();
+        let current = self.active_epoch(slot).map(|e| e.reward_percentage);
+        if current != Some(effective) {
+            self.push_reward_epoch(effective, slot)
+        } else {
+            None
+        }
+    }
+
+    /// Halts accrual when the reward vault is empty (if halt_on_depletion is set)
+    /// and resumes it once refilled. Returns Some(halted) when the state changed, along
+    /// with the epoch pruned by the change, if any.
+    pub fn update_depletion_state(
+        &mut self,
+        reward_vault_balance: u64,
+        slot: u64,
+    ) -> Option<(bool, Option<ArchivedEpoch>)> {
+        self.apply_reward_schedule(slot);
+        let should_halt = self.halt_on_depletion && reward_vault_balance == 0;
+        if should_halt == self.accrual_halted {
+            return None;
+        }
+
+        self.accrual_halted = should_halt;
+        if should_halt {
+            self.accrual_halted_slot = slot;
+        }
+        let archived = self.sync_reward_epoch(slot);
+
+        Some((should_halt, archived))
+    }
+}
+
+impl UserStake {
+    /// Records a deposit tranche. When the list is full, tranches whose lock has
+    /// expired are merged into one (amount-weighted rate) to make room.
+    pub fn add_tranche(&mut self, tranche: StakeTranche, slot: u64) -> Result<()> {
+        if self.tranches.len() >= MAX_STAKE_TRANCHES {
+            self.merge_unlocked_tranches(slot);
+        }
+        require!(
+            self.tranches.len() < MAX_STAKE_TRANCHES,
+            CustomError::TooManyTranches
+        );
+        self.tranches.push(tranche);
+        Ok(())
+    }
+
+    /// Amount held in tranches whose lock has expired at `slot`
+    pub fn unlocked_amount(&self, slot: u64) -> u64 {
+        self.tranches
+            .iter()
+            .filter(|t| t.lock_end_slot <= slot)
+            .map(|t| t.amount)
+            .sum()
+    }
+
+    /// Debits `amount` from unlocked tranches, oldest first, dropping emptied tranches.
+    /// Callers must check `unlocked_amount` first.
+    pub fn remove_unlocked(&mut self, amount: u64, slot: u64) {
+        let mut remaining = amount;
+        for tranche in self.tranches.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            if tranche.lock_end_slot <= slot {
+                let take = tranche.amount.min(remaining);
+                tranche.amount -= take;
+                remaining -= take;
+            }
+        }
+        self.tranches.retain(|t| t.amount > 0);
+    }
+
+    fn merge_unlocked_tranches(&mut self, slot: u64) {
+        let (unlocked, mut locked): (Vec<StakeTranche>, Vec<StakeTranche>) = self
+            .tranches
+            .drain(..)
+            .partition(|t| t.lock_end_slot <= slot);
+
+        if let Some(first) = unlocked.first() {
+            let amount: u64 = unlocked.iter().map(|t| t.amount).sum();
+            let weighted_rate: u128 = unlocked
+                .iter()
+                .map(|t| (t.amount as u128) * (t.reward_percentage as u128))
+                .sum();
+            let merged = StakeTranche {
+                amount,
+                deposit_slot: first.deposit_slot,
+                lock_end_slot: unlocked.iter().map(|t| t.lock_end_slot).max().unwrap_or(slot),
+                reward_percentage: if amount > 0 { (weighted_rate / amount as u128) as u64 } else { 0 },
+            };
+            locked.insert(0, merged);
+        }
+
+        self.tranches = locked;
+    }
+
+    /// True once the pool's reward cliff has passed since the first deposit.
+    /// Positions that predate the field (first_deposit_slot = 0) are never held back.
+    pub fn rewards_unlocked(&self, pool: &Pool, now: u64) -> bool {
+        now.saturating_sub(self.first_deposit_slot) >= pool.reward_cliff_slots
+    }
+
+    pub fn calculate_pending_reward(&self, pool: &Pool) -> u64 {
+        let clock = Clock::get().unwrap();
+        self.calculate_pending_reward_at(pool, pool.now(&clock))
+    }
+
+    /// Reward multiplier in basis points (10_000 = 1x). The cached boost only counts
+    /// while the pool still has a booster program registered.
+    pub fn effective_boost_bps(&self, pool: &Pool) -> u16 {
+        if pool.booster_program == Pubkey::default() || self.boost_bps == 0 {
+            10_000
+        } else {
+            self.boost_bps
+        }
+    }
+
+    /// Moves the accrual checkpoint to `now`, snapshotting the pool's reward index
+    pub fn checkpoint(&mut self, pool: &Pool, now: u64) {
+        self.last_staked_slot = now;
+        self.reward_index_snapshot = pool.reward_index_at(now);
+        self.active_slots_snapshot = pool.active_slots_at(now);
+    }
+
+    /// Reward for the part of the accrual period that falls before the oldest retained
+    /// epoch, taken from the pool's archived_reward_index. Positions still on layout
+    /// version 1 have no snapshot and earn nothing for pruned epochs until migrated.
+    pub fn archived_reward_scaled(&self, pool: &Pool) -> u128 {
+        let oldest_start = match pool.reward_epochs.first() {
+            Some(epoch) => epoch.start_slot,
+            None => return 0,
+        };
+        if self.version < 2 || self.last_staked_slot >= oldest_start {
+            return 0;
+        }
+
+        let index_delta = pool.archived_reward_index.saturating_sub(self.reward_index_snapshot);
+        (self.amount as u128).saturating_mul(index_delta)
+    }
+
+    /// Scaled reward of a fixed-rate position: each tranche at the rate it was deposited
+    /// with, over the pool's active (non-zero rate) units since the checkpoint
+    fn fixed_rate_reward_scaled(&self, pool: &Pool, current_slot: u64) -> u128 {
+        let active_slots = pool
+            .active_slots_at(current_slot)
+            .saturating_sub(self.active_slots_snapshot) as u128;
+        self.tranches.iter().fold(0u128, |total, tranche| {
+            total.saturating_add(
+                (tranche.amount as u128)
+                    .saturating_mul(tranche.reward_percentage as u128)
+                    .saturating_mul(active_slots),
+            )
+        })
+    }
+
+    /// Scaled reward pending as of `current_slot`, including the archived span and the
+    /// remainder carried from the previous settlement
+    fn pending_reward_scaled(&self, pool: &Pool, current_slot: u64) -> i128 {
+        let accrued = if pool.lock_rate_at_deposit {
+            self.fixed_rate_reward_scaled(pool, current_slot)
+        } else {
+            pool.accrued_reward_scaled(self.amount, self.last_staked_slot, current_slot)
+                .saturating_add(self.archived_reward_scaled(pool))
+        };
+        let scaled = accrued.saturating_mul(self.effective_boost_bps(pool) as u128) / 10_000;
+        i128::try_from(scaled)
+            .unwrap_or(i128::MAX)
+            .saturating_add(self.reward_remainder as i128)
+    }
+
+    /// Pending reward as of `current_slot`, walking the pool's reward epochs.
+    /// Slots before last_staked_slot yield zero since earlier accrual is already
+    /// settled into `unclaimed`.
+    pub fn calculate_pending_reward_at(&self, pool: &Pool, current_slot: u64) -> u64 {
+        let (reward, _) = pool.round_reward(self.pending_reward_scaled(pool, current_slot));
+
+        // Stop accrual once the pool's per-user lifetime cap is reached
+        reward.min(self.remaining_reward_cap(pool))
+    }
+
+    /// Settles the reward pending at `now`: returns it rounded per the pool's rounding
+    /// mode and capped, carries the rounding remainder, and moves the checkpoint to `now`.
+    /// Callers must credit the returned amount (pay it or add it to unclaimed).
+    pub fn settle_pending_reward(&mut self, pool: &Pool, now: u64) -> u64 {
+        let (reward, remainder) = pool.round_reward(self.pending_reward_scaled(pool, now));
+        let cap = self.remaining_reward_cap(pool);
+
+        // A capped position has nothing left to carry
+        self.reward_remainder = if reward > cap { 0 } else { remainder };
+        self.checkpoint(pool, now);
+        reward.min(cap)
+    }
+
+    /// Rewards still payable before hitting the pool's per-user cap (u64::MAX if uncapped)
+    pub fn remaining_reward_cap(&self, pool: &Pool) -> u64 {
+        if pool.max_reward_per_user == 0 {
+            return u64::MAX;
+        }
+        let lifetime = self.total_earned.saturating_add(self.unclaimed);
+        pool.max_reward_per_user.saturating_sub(lifetime)
+    }
+
+    /// Flags the stake as capped once lifetime rewards reach the pool cap.
+    /// Returns true only on the transition so callers emit RewardCapReachedEvent once.
+    pub fn update_reward_cap_status(&mut self, pool: &Pool) -> bool {
+        if self.reward_capped || pool.max_reward_per_user == 0 {
+            return false;
+        }
+        if self.remaining_reward_cap(pool) == 0 {
+            self.reward_capped = true;
+            return true;
+        }
+        false
     }
 }
 
@@ -1386,6 +6409,21 @@ pub struct PoolCreatedEvent {
     pub slot: u64,
 }
 
+/// Emitted when a pool template is created or updated
+#[event]
+pub struct PoolTemplateUpdatedEvent {
+    /// The template's PDA address
+    pub template: Pubkey,
+    /// Template identifier
+    pub template_id: u64,
+    /// Parameters new pools will inherit
+    pub params: PoolTemplateParams,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
 /// Emitted when pool staking is enabled or disabled
 #[event]
 pub struct PoolStakingActiveChangedEvent {
@@ -1442,6 +6480,417 @@ pub struct RewardDepositedEvent {
     pub slot: u64,
 }
 
+/// Emitted when the owner schedules a streaming reward deposit
+#[event]
+pub struct RewardStreamScheduledEvent {
+    /// The pool receiving the stream
+    pub pool: Pubkey,
+    /// Total tokens escrowed for the stream
+    pub total_amount: u64,
+    /// Slot at which vesting begins
+    pub start_slot: u64,
+    /// Slot at which the stream is fully vested
+    pub end_slot: u64,
+    /// Admin who scheduled the stream
+    pub admin: Pubkey,
+    /// Slot of scheduling
+    pub slot: u64,
+}
+
+/// Emitted when vested stream tokens are released into the reward vault
+#[event]
+pub struct RewardStreamReleasedEvent {
+    /// The pool receiving rewards
+    pub pool: Pubkey,
+    /// Amount released by this crank
+    pub amount: u64,
+    /// Total released so far
+    pub total_released: u64,
+    /// Amount still held in escrow
+    pub remaining: u64,
+    /// Slot of release
+    pub slot: u64,
+}
+
+/// Emitted when the owner queues a future reward rate change
+#[event]
+pub struct RewardPercentageScheduledEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Reward percentage (bps) that will apply
+    pub new_percentage: u64,
+    /// Slot at which the new rate starts
+    pub effective_slot: u64,
+    /// Admin who scheduled the change
+    pub admin: Pubkey,
+    /// Slot of scheduling
+    pub slot: u64,
+}
+
+/// Emitted when the owner freezes or unfreezes reward accrual
+#[event]
+pub struct PoolAccrualFrozenEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Whether accrual is frozen
+    pub frozen: bool,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes the per-deposit lock duration
+#[event]
+pub struct PoolLockDurationUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous lock duration in slots
+    pub old_lock_duration_slots: u64,
+    /// New lock duration in slots
+    pub new_lock_duration_slots: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when a locked position is minted as an NFT
+#[event]
+pub struct StakePositionNftMintedEvent {
+    /// The pool
+    pub pool: Pubkey,
+    /// Mint of the position NFT
+    pub nft_mint: Pubkey,
+    /// Depositor and initial NFT holder
+    pub owner: Pubkey,
+    /// Tokens locked in the position
+    pub amount: u64,
+    /// Slot at which the position can be redeemed
+    pub lock_end_slot: u64,
+    /// Total staked in the pool after the deposit
+    pub total_pool_stake: u64,
+    /// Slot of the deposit
+    pub slot: u64,
+}
+
+/// Emitted when the holder of a position NFT redeems it
+#[event]
+pub struct StakePositionNftRedeemedEvent {
+    /// The pool
+    pub pool: Pubkey,
+    /// Mint of the position NFT
+    pub nft_mint: Pubkey,
+    /// NFT holder who redeemed
+    pub holder: Pubkey,
+    /// Principal returned (0 when only collecting unclaimed rewards)
+    pub amount: u64,
+    /// Rewards paid out
+    pub rewards_sent: u64,
+    /// Rewards still owed (NFT kept until they are paid)
+    pub rewards_unclaimed: u64,
+    /// Total staked in the pool after the redemption
+    pub total_pool_stake: u64,
+    /// Slot of the redemption
+    pub slot: u64,
+}
+
+/// Emitted when the pool owner rotates the pool authority
+#[event]
+pub struct PoolAuthorityUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous pool owner
+    pub old_authority: Pubkey,
+    /// New pool owner
+    pub new_authority: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the withdraw guardian or its threshold changes
+#[event]
+pub struct WithdrawGuardianUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous guardian (default = none)
+    pub old_guardian: Pubkey,
+    /// New guardian (default = none)
+    pub new_guardian: Pubkey,
+    /// Withdrawals above this amount need the guardian
+    pub threshold: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes the minimum spacing between rate changes
+#[event]
+pub struct PoolRateChangeCooldownUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous minimum spacing
+    pub old_min_slots: u64,
+    /// New minimum spacing
+    pub new_min_slots: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the oldest reward epoch is pruned and folded into the archive checkpoint
+#[event]
+pub struct EpochArchivedEvent {
+    /// The pool whose history was pruned
+    pub pool: Pubkey,
+    /// Rate of the pruned epoch in basis points
+    pub reward_percentage: u64,
+    /// Start of the pruned epoch (pool time basis)
+    pub start_slot: u64,
+    /// End of the pruned epoch (start of the oldest retained epoch)
+    pub end_slot: u64,
+    /// Pool archived_reward_index after folding the epoch in
+    pub archived_reward_index: u128,
+    /// Slot of pruning
+    pub slot: u64,
+}
+
+/// Emitted when the owner sets or extends the end of a pool
+#[event]
+pub struct PoolEndSlotUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous end (0 = open-ended)
+    pub old_end_slot: u64,
+    /// New end
+    pub new_end_slot: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner switches a pool to or from fixed-rate deposits
+#[event]
+pub struct PoolLockRateAtDepositUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Whether deposits lock the rate in effect when they are made
+    pub enabled: bool,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner registers or removes a pool's booster program
+#[event]
+pub struct PoolBoosterProgramUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous booster program (default = none)
+    pub old_booster_program: Pubkey,
+    /// New booster program (default = none)
+    pub new_booster_program: Pubkey,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when a position's boost is re-read from the pool's booster program
+#[event]
+pub struct BoostRefreshedEvent {
+    /// Owner of the position
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Multiplier before the refresh, in basis points
+    pub old_boost_bps: u16,
+    /// Multiplier after the refresh, in basis points
+    pub new_boost_bps: u16,
+    /// Slot of refresh
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes the attestation issuer gating deposits
+#[event]
+pub struct PoolAttestationIssuerUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous issuer mint (default = ungated)
+    pub old_attestation_issuer: Pubkey,
+    /// New issuer mint (default = ungated)
+    pub new_attestation_issuer: Pubkey,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes how a pool rounds settled rewards
+#[event]
+pub struct PoolRoundingModeUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous rounding mode
+    pub old_rounding_mode: RoundingMode,
+    /// New rounding mode
+    pub new_rounding_mode: RoundingMode,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner adds or removes a pool admin
+#[event]
+pub struct PoolAdminsUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Pool admin added or removed
+    pub pool_admin: Pubkey,
+    /// True if added, false if removed
+    pub added: bool,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes the minimum claim amount of a pool
+#[event]
+pub struct PoolMinClaimAmountUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous minimum claim
+    pub old_min_claim_amount: u64,
+    /// New minimum claim
+    pub new_min_claim_amount: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner changes the reward cliff of a pool
+#[event]
+pub struct PoolRewardCliffUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous cliff
+    pub old_cliff_slots: u64,
+    /// New cliff
+    pub new_cliff_slots: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner tunes the APY divisor of a pool
+#[event]
+pub struct PoolPeriodsPerYearUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous periods per year
+    pub old_periods_per_year: u64,
+    /// New periods per year
+    pub new_periods_per_year: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner switches the clock a pool accrues on
+#[event]
+pub struct PoolTimeBasisUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous time basis
+    pub old_time_basis: TimeBasis,
+    /// New time basis
+    pub new_time_basis: TimeBasis,
+    /// APY divisor for the new basis
+    pub periods_per_year: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner grows a pool's reward epoch capacity
+#[event]
+pub struct PoolEpochCapacityExpandedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Previous epoch capacity
+    pub old_capacity: u16,
+    /// New epoch capacity
+    pub new_capacity: u16,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when pool ownership is handed to an spl-governance governance
+#[event]
+pub struct PoolGovernanceSetEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// spl-governance program instance
+    pub governance_program: Pubkey,
+    /// Realm of the governance
+    pub realm: Pubkey,
+    /// Governance PDA that now owns the pool
+    pub governance: Pubkey,
+    /// Owner before the change
+    pub previous_owner: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when the owner toggles halt-on-depletion
+#[event]
+pub struct PoolHaltOnDepletionUpdatedEvent {
+    /// The pool being updated
+    pub pool: Pubkey,
+    /// Whether accrual halts while the reward vault is empty
+    pub enabled: bool,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when accrual halts because the reward vault is empty
+#[event]
+pub struct RewardAccrualHaltedEvent {
+    /// The pool whose accrual stopped
+    pub pool: Pubkey,
+    /// Slot at which accrual stopped
+    pub slot: u64,
+}
+
+/// Emitted when accrual resumes after the reward vault is refilled
+#[event]
+pub struct RewardAccrualResumedEvent {
+    /// The pool whose accrual resumed
+    pub pool: Pubkey,
+    /// Slot at which accrual had been halted
+    pub halted_since_slot: u64,
+    /// Reward vault balance after the refill
+    pub reward_vault_balance: u64,
+    /// Slot at which accrual resumed
+    pub slot: u64,
+}
+
 /// Emitted when admin withdraws rewards from the pool
 #[event]
 pub struct RewardWithdrawnEvent {
@@ -1455,6 +6904,21 @@ pub struct RewardWithdrawnEvent {
     pub slot: u64,
 }
 
+/// Emitted when a user stake account is created
+#[event]
+pub struct UserStakeInitializedEvent {
+    /// Wallet owning the new stake account
+    pub user: Pubkey,
+    /// The pool the account belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Account that paid rent
+    pub payer: Pubkey,
+    /// Slot of creation
+    pub slot: u64,
+}
+
 /// Emitted when a user stakes tokens
 #[event]
 pub struct StakeDepositedEvent {
@@ -1462,16 +6926,50 @@ pub struct StakeDepositedEvent {
     pub user: Pubkey,
     /// The pool where tokens were staked
     pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
     /// Amount of tokens staked
     pub amount: u64,
     /// User's total staked amount after deposit
     pub total_user_stake: u64,
     /// Pool's total staked amount after deposit
     pub total_pool_stake: u64,
+    /// Integration partner credited with the position (zero = none)
+    pub partner_tag: [u8; 16],
     /// Slot of stake
     pub slot: u64,
 }
 
+/// Emitted when a pool account is upgraded to the current layout
+#[event]
+pub struct PoolMigratedEvent {
+    /// The pool being upgraded
+    pub pool: Pubkey,
+    /// Layout version before migration
+    pub from_version: u8,
+    /// Layout version after migration
+    pub to_version: u8,
+    /// Slot of migration
+    pub slot: u64,
+}
+
+/// Emitted when a user stake account is upgraded to the current layout
+#[event]
+pub struct UserStakeMigratedEvent {
+    /// Owner of the position
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Layout version before migration
+    pub from_version: u8,
+    /// Layout version after migration
+    pub to_version: u8,
+    /// Slot of migration
+    pub slot: u64,
+}
+
 /// Emitted when a user withdraws staked tokens
 #[event]
 pub struct StakeWithdrawnEvent {
@@ -1479,6 +6977,8 @@ pub struct StakeWithdrawnEvent {
     pub user: Pubkey,
     /// The pool from which tokens were withdrawn
     pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
     /// Amount of tokens withdrawn
     pub amount: u64,
     /// Amount of rewards sent (0 if vault was empty)
@@ -1493,6 +6993,84 @@ pub struct StakeWithdrawnEvent {
     pub slot: u64,
 }
 
+/// Emitted when the pool owner force-unstakes a user's position
+#[event]
+pub struct StakeForceUnstakedEvent {
+    /// The user whose position was unwound
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Pool owner who performed the takedown
+    pub admin: Pubkey,
+    /// Principal returned to the user
+    pub amount: u64,
+    /// Amount of rewards sent (0 if not requested or vault insufficient)
+    pub rewards_sent: u64,
+    /// Rewards left on the position as unclaimed
+    pub rewards_unclaimed: u64,
+    /// Pool's total staked amount after the takedown
+    pub total_pool_stake: u64,
+    /// Slot of the takedown
+    pub slot: u64,
+}
+
+/// Emitted when the pool owner freezes or unfreezes a user's stake position
+#[event]
+pub struct UserStakeFrozenEvent {
+    /// Owner of the position
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Whether the position is frozen
+    pub frozen: bool,
+    /// Pool owner who made the change
+    pub admin: Pubkey,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when a user opts in or out of keeper-driven claims
+#[event]
+pub struct AutoClaimUpdatedEvent {
+    /// Owner of the position
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Whether keepers may claim for this position
+    pub enabled: bool,
+    /// Reward tokens paid to the keeper per claim
+    pub keeper_fee: u64,
+    /// Slot of update
+    pub slot: u64,
+}
+
+/// Emitted when a keeper claims rewards on a user's behalf
+#[event]
+pub struct RewardCrankClaimedEvent {
+    /// Owner of the position
+    pub user: Pubkey,
+    /// The pool the position belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Keeper who executed the claim
+    pub keeper: Pubkey,
+    /// Rewards sent to the user (after keeper fee)
+    pub amount: u64,
+    /// Rewards paid to the keeper
+    pub keeper_fee: u64,
+    /// User's lifetime earned rewards after the claim
+    pub total_earned: u64,
+    /// Slot of claim
+    pub slot: u64,
+}
+
 /// Emitted when a user claims rewards without withdrawing stake
 #[event]
 pub struct RewardClaimedEvent {
@@ -1500,6 +7078,10 @@ pub struct RewardClaimedEvent {
     pub user: Pubkey,
     /// The pool from which rewards were claimed
     pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Token account that received the rewards
+    pub destination: Pubkey,
     /// Amount of reward tokens claimed
     pub amount: u64,
     /// User's total earned rewards (lifetime)
@@ -1510,6 +7092,55 @@ pub struct RewardClaimedEvent {
     pub slot: u64,
 }
 
+/// Emitted when the per-user lifetime reward cap is changed
+#[event]
+pub struct PoolMaxRewardPerUserUpdatedEvent {
+    /// The pool affected
+    pub pool: Pubkey,
+    /// The new cap (0 = uncapped)
+    pub max_reward_per_user: u64,
+    /// Admin who made the change
+    pub admin: Pubkey,
+    /// Slot of change
+    pub slot: u64,
+}
+
+/// Emitted once when a user's lifetime rewards reach the pool cap
+#[event]
+pub struct RewardCapReachedEvent {
+    /// The user who capped out
+    pub user: Pubkey,
+    /// The pool whose cap was reached
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// Lifetime rewards credited to the user (equal to the cap)
+    pub total_rewards: u64,
+    /// Slot at which the cap was reached
+    pub slot: u64,
+}
+
+/// Emitted when a user locks stake for governance voting weight
+#[event]
+pub struct GovernanceLockedEvent {
+    /// The user who locked
+    pub user: Pubkey,
+    /// The pool the locked stake belongs to
+    pub pool: Pubkey,
+    /// Index of the user's stake position in the pool
+    pub position_index: u64,
+    /// The vote escrow PDA holding the lock
+    pub vote_escrow: Pubkey,
+    /// Amount of stake locked
+    pub locked_amount: u64,
+    /// Slot at which the lock expires
+    pub lock_end_slot: u64,
+    /// Voting weight at the time of locking
+    pub voting_weight: u128,
+    /// Slot of lock
+    pub slot: u64,
+}
+
 #[error_code]
 pub enum CustomError {
     #[msg("Unauthorized: Only pool owner can perform this action")]
@@ -1544,4 +7175,96 @@ pub enum CustomError {
     PoolCounterOverflow,
     #[msg("Reward mint must be the same as the staking token mint")]
     RewardMintMustMatchStakeMint,
+    #[msg("Invalid governance lock: amount and duration must be non-zero and duration within the maximum")]
+    InvalidGovernanceLock,
+    #[msg("Governance lock amount exceeds staked amount")]
+    InsufficientStakeForLock,
+    #[msg("An active governance lock can only be increased or extended")]
+    GovernanceLockReduction,
+    #[msg("Requested amount is locked for governance until the lock expires")]
+    StakeLockedForGovernance,
+    #[msg("Pool vault is not the approved delegate of the user's token account")]
+    VaultNotDelegate,
+    #[msg("Invalid delegated amount: must be non-zero and within the approved allowance")]
+    InvalidDelegatedAmount,
+    #[msg("Native SOL staking is only available on wrapped SOL pools")]
+    NotNativeSolPool,
+    #[msg("Stake amount must be greater than zero")]
+    InvalidStakeAmount,
+    #[msg("Reward account must be a token account of the reward mint, or the user's associated token account")]
+    InvalidRewardAccount,
+    #[msg("Invalid reward stream: amount must be non-zero and end slot must be after start slot and the current slot")]
+    InvalidRewardStream,
+    #[msg("A reward stream is already active for this pool")]
+    RewardStreamActive,
+    #[msg("No streamed rewards available to release yet")]
+    NothingToRelease,
+    #[msg("Invalid position index: positions must be created in sequential order")]
+    InvalidPositionIndex,
+    #[msg("Stake is still locked: only tranches past their lock end can be withdrawn")]
+    StakeStillLocked,
+    #[msg("Too many locked deposit tranches in this position")]
+    TooManyTranches,
+    #[msg("Invalid lock duration: must not exceed 4 years")]
+    InvalidLockDuration,
+    #[msg("This stake position is frozen by the pool owner")]
+    UserStakeFrozen,
+    #[msg("Invalid governance: account must be the realm's governance PDA owned by the governance program")]
+    InvalidGovernanceAccount,
+    #[msg("Auto-claim is not enabled for this stake position")]
+    AutoClaimDisabled,
+    #[msg("Rewards are too small for the keeper fee")]
+    RewardBelowKeeperFee,
+    #[msg("Invalid scheduled epoch: effective slot must be in the future and after all existing epochs")]
+    InvalidScheduledEpoch,
+    #[msg("Too many scheduled reward rate changes")]
+    TooManyScheduledEpochs,
+    #[msg("Reward accrual is paused, halted or frozen")]
+    RewardAccrualGated,
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    #[msg("Invalid epoch capacity: must exceed the current capacity and not exceed 128")]
+    InvalidEpochCapacity,
+    #[msg("Position NFTs require the pool to have a deposit lock duration")]
+    PositionNftRequiresLock,
+    #[msg("Metadata, master edition and token metadata program accounts are required for NFT metadata")]
+    MissingMetadataAccounts,
+    #[msg("Signer does not hold the position NFT")]
+    NotPositionHolder,
+    #[msg("Reward vault must be the pool's configured reward vault")]
+    InvalidRewardVault,
+    #[msg("Withdraw guardian co-signature required")]
+    GuardianSignatureRequired,
+    #[msg("Reward rate was changed too recently")]
+    RateChangeTooSoon,
+    #[msg("Periods per year must be within half to double the time basis default")]
+    InvalidPeriodsPerYear,
+    #[msg("Rewards are locked until the reward cliff has passed")]
+    RewardCliffNotReached,
+    #[msg("Reward cliff exceeds the maximum allowed duration")]
+    InvalidRewardCliff,
+    #[msg("Page size must be between 1 and 32")]
+    InvalidPageSize,
+    #[msg("Pending rewards are below the pool's minimum claim amount")]
+    ClaimBelowMinimum,
+    #[msg("Pool admin list is full")]
+    PoolAdminLimitReached,
+    #[msg("Address is already a pool admin or the pool owner")]
+    DuplicatePoolAdmin,
+    #[msg("Address is not a pool admin")]
+    PoolAdminNotFound,
+    #[msg("This pool requires an attestation account to deposit")]
+    AttestationRequired,
+    #[msg("Attestation account is not a credential from the pool's issuer held by the staker")]
+    InvalidAttestation,
+    #[msg("End slot must be in the future and may only be extended before it passes")]
+    InvalidEndSlot,
+    #[msg("Pool has ended; deposits are closed")]
+    PoolEnded,
+    #[msg("Pool must be migrated to the current layout version first")]
+    PoolNotMigrated,
+    #[msg("Pool has no booster program registered")]
+    BoosterNotRegistered,
+    #[msg("Boost record is not owned by the pool's booster program or is for another pool or user")]
+    InvalidBoostRecord,
 }
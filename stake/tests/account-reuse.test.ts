@@ -8,7 +8,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
   const { provider, program, admin } = getTestEnvironment();
@@ -47,7 +47,7 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     // Create Pool
     const rewardPercentage = 1000; // 10.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -140,7 +140,7 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     );
 
     const [bobStakePda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), pool.toBuffer(), bob.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), pool.toBuffer(), bob.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -154,8 +154,9 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     // STEP 1: Bob stakes tokens
     console.log("🔹 Step 1: Bob stakes 500 tokens");
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
+    await initUserStake(program, poolId, pool, tokenMint, bob.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: pool,
         user: bob.publicKey,
@@ -203,8 +204,9 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     // STEP 3: Bob deposits again (reusing the account)
     console.log("\n🔹 Step 3: Bob stakes again (account reuse with same pool)");
     const STAKE_AMOUNT_2 = new anchor.BN(300_000_000);
+    await initUserStake(program, poolId, pool, tokenMint, bob.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT_2)
+      .depositStake(poolId, STAKE_AMOUNT_2, null)
       .accounts({
         pool: pool,
         user: bob.publicKey,
@@ -267,6 +269,7 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
         Buffer.from("user_stake"),
         pool.toBuffer(),
         charlie.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -281,8 +284,9 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     // Charlie stakes
     console.log("🔹 Step 1: Charlie stakes tokens");
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
+    await initUserStake(program, poolId, pool, tokenMint, charlie.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: pool,
         user: charlie.publicKey,
@@ -404,7 +408,7 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
     );
 
     const [davidStakePda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), pool.toBuffer(), david.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), pool.toBuffer(), david.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -417,8 +421,9 @@ describe("🔒 Stake Program - Account Reuse Prevention Tests", () => {
       "🔹 David stakes for the first time (new account initialization)"
     );
     const STAKE_AMOUNT = new anchor.BN(100_000_000);
+    await initUserStake(program, poolId, pool, tokenMint, david.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: pool,
         user: david.publicKey,
@@ -0,0 +1,293 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  AccountLayout,
+  MintLayout,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { startAnchor, BankrunProvider } from "anchor-bankrun";
+import { expect } from "chai";
+import { StakeProgram } from "../target/types/stake_program";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  initUserStake,
+} from "./test-utils";
+
+/** Size of a pool created before layout versioning (fields up to pool_id, 10 epochs) */
+const POOL_V0_SPACE = 334;
+/** Size of a user stake created before layout versioning */
+const USER_STAKE_V0_SPACE = 105;
+
+function discriminator(program: anchor.Program<StakeProgram>, name: string): Buffer {
+  return Buffer.from(program.idl.accounts.find((a) => a.name === name).discriminator);
+}
+
+function u64(value: number): Buffer {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(value));
+  return buf;
+}
+
+function packMint(authority: anchor.web3.PublicKey, supply: number): Buffer {
+  const data = Buffer.alloc(MintLayout.span);
+  MintLayout.encode(
+    {
+      mintAuthorityOption: 1,
+      mintAuthority: authority,
+      supply: BigInt(supply),
+      decimals: 6,
+      isInitialized: true,
+      freezeAuthorityOption: 0,
+      freezeAuthority: anchor.web3.PublicKey.default,
+    },
+    data
+  );
+  return data;
+}
+
+function packTokenAccount(
+  mint: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey,
+  amount: number
+): Buffer {
+  const data = Buffer.alloc(AccountLayout.span);
+  AccountLayout.encode(
+    {
+      mint,
+      owner,
+      amount: BigInt(amount),
+      delegateOption: 0,
+      delegate: anchor.web3.PublicKey.default,
+      state: 1,
+      isNativeOption: 0,
+      isNative: BigInt(0),
+      delegatedAmount: BigInt(0),
+      closeAuthorityOption: 0,
+      closeAuthority: anchor.web3.PublicKey.default,
+    },
+    data
+  );
+  return data;
+}
+
+describe("🏷️ Stake Program - Account Versioning", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  const user = anchor.web3.Keypair.generate();
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    userStakePda = await initUserStake(
+      program,
+      poolId,
+      poolPda,
+      tokenMint,
+      user.publicKey
+    );
+  });
+
+  it("1. ✅ New accounts are stamped with the current layout version", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    const userStake = await program.account.userStake.fetch(userStakePda);
+
+    expect(pool.version).to.equal(2);
+    expect(userStake.version).to.equal(2);
+    expect(userStake.reserved.every((b: number) => b === 0)).to.be.true;
+    console.log("✅ Pool version:", pool.version, "UserStake version:", userStake.version);
+  });
+
+  it("2. ❌ Migrating an up-to-date user stake is rejected", async () => {
+    try {
+      await program.methods
+        .migrateUserStake(poolId)
+        .accounts({
+          pool: poolPda,
+          tokenMint,
+          userStake: userStakePda,
+          payer: admin.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected migration of current layout");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected migration error:", errMsg);
+      expect(errMsg).to.include("already at the current layout version");
+    }
+  });
+
+  it("3. ❌ Migrating an up-to-date pool is rejected", async () => {
+    try {
+      await program.methods
+        .migratePool(poolId)
+        .accounts({
+          pool: poolPda,
+          tokenMint,
+          payer: admin.publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected migration of current pool layout");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected pool migration error:", errMsg);
+      expect(errMsg).to.include("already at the current layout version");
+    }
+  });
+
+  it("4. ✅ A pool created before versioning migrates and can be withdrawn from", async () => {
+    // Pre-versioning accounts cannot be created by the current program, so they are
+    // written directly into a bankrun bank in their original layout
+    const owner = anchor.web3.Keypair.generate();
+    const staker = anchor.web3.Keypair.generate();
+    const mint = anchor.web3.Keypair.generate().publicKey;
+    const stakeAmount = 5_000_000;
+
+    const [legacyPool, poolBump] = getPoolPDA(program.programId, mint, 0);
+    const [poolVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), legacyPool.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    const [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), legacyPool.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    const [legacyUserStake, userStakeBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stake"), legacyPool.toBuffer(), staker.publicKey.toBuffer()],
+      program.programId
+    );
+    const stakerTokenAccount = getAssociatedTokenAddressSync(mint, staker.publicKey);
+
+    const poolData = Buffer.alloc(POOL_V0_SPACE);
+    Buffer.concat([
+      discriminator(program, "pool"),
+      mint.toBuffer(), // token_mint
+      mint.toBuffer(), // reward_mint
+      rewardVault.toBuffer(),
+      owner.publicKey.toBuffer(),
+      u64(stakeAmount), // total_staked
+      u64(0), // reward_percentage
+      Buffer.from([poolBump, 1]), // bump, is_active
+      Buffer.from([1, 0, 0, 0]), // reward_epochs length
+      u64(0), // epoch reward_percentage
+      u64(0), // epoch start_slot
+      u64(0), // last_reward_update_slot
+      u64(0), // pool_id
+    ]).copy(poolData);
+
+    const userStakeData = Buffer.concat([
+      discriminator(program, "userStake"),
+      staker.publicKey.toBuffer(), // owner
+      legacyPool.toBuffer(), // pool
+      u64(stakeAmount), // amount
+      u64(0), // last_staked_slot
+      u64(0), // total_earned
+      u64(0), // unclaimed
+      Buffer.from([userStakeBump]),
+    ]);
+    expect(userStakeData.length).to.equal(USER_STAKE_V0_SPACE);
+
+    const programAccount = (data: Buffer) => ({
+      lamports: anchor.web3.LAMPORTS_PER_SOL,
+      data,
+      owner: program.programId,
+      executable: false,
+    });
+    const tokenAccount = (data: Buffer) => ({
+      lamports: anchor.web3.LAMPORTS_PER_SOL,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+    const context = await startAnchor("", [], [
+      { address: legacyPool, info: programAccount(poolData) },
+      { address: legacyUserStake, info: programAccount(userStakeData) },
+      { address: mint, info: tokenAccount(packMint(owner.publicKey, stakeAmount)) },
+      { address: poolVault, info: tokenAccount(packTokenAccount(mint, legacyPool, stakeAmount)) },
+      { address: rewardVault, info: tokenAccount(packTokenAccount(mint, legacyPool, 0)) },
+      { address: stakerTokenAccount, info: tokenAccount(packTokenAccount(mint, staker.publicKey, 0)) },
+      {
+        address: staker.publicKey,
+        info: {
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: anchor.web3.SystemProgram.programId,
+          executable: false,
+        },
+      },
+    ]);
+    const bankProvider = new BankrunProvider(context);
+    const bankProgram = new anchor.Program<StakeProgram>(program.idl, bankProvider);
+
+    await bankProgram.methods
+      .migratePool(poolId)
+      .accounts({ pool: legacyPool, tokenMint: mint, payer: bankProvider.wallet.publicKey })
+      .rpc();
+    await bankProgram.methods
+      .migrateUserStake(poolId)
+      .accounts({
+        pool: legacyPool,
+        tokenMint: mint,
+        userStake: legacyUserStake,
+        payer: bankProvider.wallet.publicKey,
+      })
+      .rpc();
+
+    const pool = await bankProgram.account.pool.fetch(legacyPool);
+    expect(pool.version).to.equal(2);
+    expect(pool.epochCapacity).to.equal(10);
+    expect(pool.totalStaked.toNumber()).to.equal(stakeAmount);
+    expect(pool.rewardEpochs.length).to.equal(1);
+    expect(pool.endSlot.toNumber()).to.equal(0);
+
+    await bankProgram.methods
+      .withdrawStake(poolId, new BN(stakeAmount))
+      .accounts({
+        pool: legacyPool,
+        user: staker.publicKey,
+        userStake: legacyUserStake,
+        userTokenAccount: stakerTokenAccount,
+        userRewardAccount: stakerTokenAccount,
+        poolVault,
+        rewardVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: mint,
+      })
+      .signers([staker])
+      .rpc();
+
+    const stakerAccount = await context.banksClient.getAccount(stakerTokenAccount);
+    expect(
+      Number(AccountLayout.decode(Buffer.from(stakerAccount.data)).amount)
+    ).to.equal(stakeAmount);
+    const userStake = await bankProgram.account.userStake.fetch(legacyUserStake);
+    expect(userStake.amount.toNumber()).to.equal(0);
+    console.log("✅ Pre-versioning pool migrated and withdrawn:", stakeAmount);
+  });
+});
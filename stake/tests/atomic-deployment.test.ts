@@ -83,7 +83,7 @@ describe("🔒 Stake Program - Atomic Deployment Security", () => {
     if (!isCreated) {
       // Create pool
       const tx = await program.methods
-        .createPool(null, rewardPercentage, poolId)
+        .createPool(null, rewardPercentage, poolId, null)
         .accounts({
           poolIdCounter: poolIdCounterPda,
           pool: poolPda,
@@ -116,7 +116,7 @@ describe("🔒 Stake Program - Atomic Deployment Security", () => {
     // CRITICAL TEST: Attempt to recreate should fail
     try {
       const tx = await program.methods
-        .createPool(null, new BN(2000), poolId) // Try with different parameters
+        .createPool(null, new BN(2000), poolId, null) // Try with different parameters
         .accounts({
           poolIdCounter: poolIdCounterPda,
           pool: poolPda,
@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  initUserStake,
+} from "./test-utils";
+
+describe("🪪 Stake Program - Attestation-Gated Staking", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let credentialMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let credentialAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  const deposit = (attestation: anchor.web3.PublicKey | null) =>
+    program.methods
+      .depositStake(poolId, new BN(10_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        attestation,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+    credentialMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      0
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    credentialAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        credentialMint,
+        user.publicKey
+      )
+    ).address;
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+  });
+
+  it("1. ❌ Non-owner cannot set the attestation issuer", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setAttestationIssuer(poolId, credentialMint)
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Gated pools reject deposits without an attestation", async () => {
+    await program.methods
+      .setAttestationIssuer(poolId, credentialMint)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    try {
+      await deposit(null);
+      throw new Error("Unexpected deposit without attestation");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected attestation error:", errMsg);
+      expect(errMsg).to.include("requires an attestation");
+    }
+  });
+
+  it("3. ❌ An empty credential account is not an attestation", async () => {
+    try {
+      await deposit(credentialAccount);
+      throw new Error("Unexpected deposit with empty credential");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("not a credential");
+    }
+  });
+
+  it("4. ✅ Credential holders can stake", async () => {
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      credentialMint,
+      credentialAccount,
+      admin.publicKey,
+      1
+    );
+
+    await deposit(credentialAccount);
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.amount.toNumber()).to.equal(10_000_000);
+    console.log("✅ Attested deposit:", userStake.amount.toString());
+  });
+
+  it("5. ❌ Position NFT deposits into gated pools also need an attestation", async () => {
+    const nftMint = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .mintStakePositionNft(poolId, new BN(10_000_000), null)
+        .accounts({
+          pool: poolPda,
+          tokenMint,
+          nftMint: nftMint.publicKey,
+          user: user.publicKey,
+          userTokenAccount,
+          poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+          metadata: null,
+          masterEdition: null,
+          tokenMetadataProgram: null,
+          attestation: null,
+        })
+        .signers([user, nftMint])
+        .rpc();
+      throw new Error("Unexpected position NFT without attestation");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected attestation error:", errMsg);
+      expect(errMsg).to.include("requires an attestation");
+    }
+  });
+});
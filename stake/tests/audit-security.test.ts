@@ -102,7 +102,7 @@ describe("🔒 Audit Security Validations", () => {
       
       try {
         await program.methods
-          .createPool(null, new BN(1000), new BN(0))
+          .createPool(null, new BN(1000), new BN(0), null)
           .accounts({
             poolIdCounter,
             pool,
@@ -130,7 +130,7 @@ describe("🔒 Audit Security Validations", () => {
       const [rewardVault] = deriveRewardVaultPda(pool, rewardMint);
       
       await program.methods
-        .createPool(null, new BN(1000), new BN(0))
+        .createPool(null, new BN(1000), new BN(0), null)
         .accounts({
           poolIdCounter,
           pool,
@@ -187,7 +187,7 @@ describe("🔒 Audit Security Validations", () => {
       const programData = getProgramDataAddress();
       
       await program.methods
-        .createPool(null, new BN(1000), new BN(0))
+        .createPool(null, new BN(1000), new BN(0), null)
         .accounts({
           poolIdCounter,
           pool: poolPda,
@@ -259,7 +259,7 @@ describe("🔒 Audit Security Validations", () => {
       
       try {
         await program.methods
-          .createPool(null, new BN(1000), new BN(0))
+          .createPool(null, new BN(1000), new BN(0), null)
           .accounts({
             poolIdCounter,
             pool,
@@ -413,7 +413,7 @@ describe("🔒 Audit Security Validations", () => {
       const programData = getProgramDataAddress();
       
       await program.methods
-        .createPool(null, new BN(1000), new BN(0))
+        .createPool(null, new BN(1000), new BN(0), null)
         .accounts({
           poolIdCounter,
           pool: testPool,
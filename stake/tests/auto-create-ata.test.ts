@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  warpSlots,
+  initUserStake,
+} from "./test-utils";
+
+describe("🧾 Stake Program - Auto-Create Reward ATA", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let userAta: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(100_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    // Stake from a non-ATA token account so the user has no reward ATA yet
+    userTokenAccount = await createAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      user.publicKey,
+      anchor.web3.Keypair.generate()
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+    userAta = getAssociatedTokenAddressSync(tokenMint, user.publicKey);
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, 10);
+  });
+
+  it("1. ✅ Claim creates the missing reward ATA", async () => {
+    expect(await provider.connection.getAccountInfo(userAta)).to.be.null;
+
+    await program.methods
+      .claimReward(poolId)
+      .accounts({
+        pool: poolPda,
+        tokenMint: tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userRewardAccount: userAta,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const ata = await getAccount(provider.connection, userAta);
+    expect(ata.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(Number(ata.amount)).to.be.greaterThan(0);
+    console.log("✅ Reward ATA created and funded:", ata.amount.toString());
+  });
+
+  it("2. ❌ A missing non-ATA reward account cannot be created", async () => {
+    try {
+      await program.methods
+        .withdrawStake(poolId, new BN(10_000_000))
+        .accounts({
+          pool: poolPda,
+          userStake: userStakePda,
+          user: user.publicKey,
+          userTokenAccount: userTokenAccount,
+          userRewardAccount: anchor.web3.Keypair.generate().publicKey,
+          poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+          rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected success with arbitrary reward account");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected reward account error:", errMsg);
+      expect(errMsg).to.include("Reward account must be");
+    }
+  });
+});
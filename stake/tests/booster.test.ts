@@ -0,0 +1,107 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  initUserStake,
+} from "./test-utils";
+
+describe("🚀 Stake Program - External Booster", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  const user = anchor.web3.Keypair.generate();
+  const boosterProgram = anchor.web3.Keypair.generate().publicKey;
+  const poolId = new BN(0);
+
+  const refresh = (boostRecord: anchor.web3.PublicKey) =>
+    program.methods
+      .refreshBoost(poolId)
+      .accounts({ pool: poolPda, tokenMint, userStake: userStakePda, boostRecord })
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    userStakePda = await initUserStake(
+      program,
+      poolId,
+      poolPda,
+      tokenMint,
+      user.publicKey
+    );
+  });
+
+  it("1. ❌ Boosts cannot be refreshed without a registered booster", async () => {
+    try {
+      await refresh(admin.publicKey);
+      throw new Error("Unexpected refresh without booster");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("no booster program");
+    }
+  });
+
+  it("2. ❌ Non-owner cannot register a booster", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setBoosterProgram(poolId, boosterProgram)
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("3. ✅ Owner registers a booster program", async () => {
+    await program.methods
+      .setBoosterProgram(poolId, boosterProgram)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.boosterProgram.toBase58()).to.equal(boosterProgram.toBase58());
+  });
+
+  it("4. ❌ Records not owned by the booster are rejected", async () => {
+    try {
+      await refresh(admin.publicKey);
+      throw new Error("Unexpected refresh with foreign record");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected boost record error:", errMsg);
+      expect(errMsg).to.include("Boost record is not owned");
+    }
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.boostBps).to.equal(0);
+  });
+});
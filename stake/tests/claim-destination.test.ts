@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  warpSlots,
+  initUserStake,
+} from "./test-utils";
+
+describe("📬 Stake Program - Claim To Designated Account", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let rewardVaultPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(100_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    [rewardVaultPda] = getRewardVaultPDA(program.programId, poolPda, tokenMint);
+
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: rewardVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount.address,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, 10);
+  });
+
+  it("1. ✅ Rewards can be claimed into another wallet's token account", async () => {
+    const treasury = anchor.web3.Keypair.generate();
+    const treasuryAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      treasury.publicKey
+    );
+
+    await program.methods
+      .claimReward(poolId)
+      .accounts({
+        pool: poolPda,
+        tokenMint: tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userRewardAccount: treasuryAccount.address,
+        rewardVault: rewardVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const received = await getAccount(provider.connection, treasuryAccount.address);
+    expect(Number(received.amount)).to.be.greaterThan(0);
+    console.log("✅ Treasury received rewards:", received.amount.toString());
+  });
+
+  it("2. ❌ Claiming into the reward vault itself is rejected", async () => {
+    await warpSlots(provider, 10);
+
+    try {
+      await program.methods
+        .claimReward(poolId)
+        .accounts({
+          pool: poolPda,
+          tokenMint: tokenMint,
+          userStake: userStakePda,
+          user: user.publicKey,
+          userRewardAccount: rewardVaultPda,
+          rewardVault: rewardVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected success claiming into the reward vault");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected destination error:", errMsg);
+      expect(errMsg).to.include("Invalid withdrawal address");
+    }
+  });
+});
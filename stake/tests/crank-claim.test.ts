@@ -0,0 +1,263 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  initUserStake,
+  warpSlots,
+} from "./test-utils";
+
+describe("🤖 Stake Program - Keeper Crank Claims", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let keeper: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let keeperTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const KEEPER_FEE = new BN(10);
+
+  const crankClaim = () =>
+    program.methods
+      .crankClaim(poolId)
+      .accounts({
+        pool: poolPda,
+        tokenMint: tokenMint,
+        userStake: userStakePda,
+        userRewardAccount: userTokenAccount,
+        keeper: keeper.publicKey,
+        keeperRewardAccount: keeperTokenAccount,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([keeper])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    // High APY so rewards exceed the keeper fee within a few slots
+    await program.methods
+      .createPool(null, new BN(10_000_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    // Fund the reward vault
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    keeper = anchor.web3.Keypair.generate();
+    for (const kp of [user, keeper]) {
+      const sig = await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    keeperTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        keeper.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, 10);
+  });
+
+  it("1. ❌ Keeper cannot claim for a user who has not opted in", async () => {
+    try {
+      await crankClaim();
+      throw new Error("Unexpected crank claim without opt-in");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected opt-in error:", errMsg);
+      expect(errMsg).to.include("Auto-claim is not enabled");
+    }
+  });
+
+  it("2. ✅ Keeper claims for an opted-in user and takes the fee", async () => {
+    await program.methods
+      .setAutoClaim(poolId, true, KEEPER_FEE)
+      .accounts({
+        pool: poolPda,
+        tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const userBefore = await getAccount(provider.connection, userTokenAccount);
+    await crankClaim();
+
+    const keeperAccount = await getAccount(provider.connection, keeperTokenAccount);
+    const userAfter = await getAccount(provider.connection, userTokenAccount);
+    const userStake = await program.account.userStake.fetch(userStakePda);
+
+    expect(Number(keeperAccount.amount)).to.equal(KEEPER_FEE.toNumber());
+    const received = Number(userAfter.amount) - Number(userBefore.amount);
+    expect(received + KEEPER_FEE.toNumber()).to.equal(userStake.totalEarned.toNumber());
+    console.log("✅ User received", received, "keeper fee", keeperAccount.amount.toString());
+  });
+
+  it("3. ❌ Rewards go only to the owner's reward ATA", async () => {
+    await warpSlots(provider, 10);
+    try {
+      await program.methods
+        .crankClaim(poolId)
+        .accounts({
+          pool: poolPda,
+          tokenMint: tokenMint,
+          userStake: userStakePda,
+          userRewardAccount: keeperTokenAccount,
+          keeper: keeper.publicKey,
+          keeperRewardAccount: keeperTokenAccount,
+          rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper])
+        .rpc();
+      throw new Error("Unexpected crank claim to keeper account");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Reward account must be");
+    }
+  });
+
+  it("4. ❌ Keepers cannot crank while the fee is a large share of the rewards", async () => {
+    // Roughly 1.3k rewards accrue per slot; a 50k fee needs ~400 slots of rewards
+    await program.methods
+      .setAutoClaim(poolId, true, new BN(50_000))
+      .accounts({
+        pool: poolPda,
+        tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    try {
+      await crankClaim();
+      throw new Error("Unexpected crank claim with an oversized fee");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected keeper fee error:", errMsg);
+      expect(errMsg).to.include("too small for the keeper fee");
+    }
+  });
+
+  it("5. ❌ Cranks respect the pool's minimum claim amount", async () => {
+    await program.methods
+      .setAutoClaim(poolId, true, KEEPER_FEE)
+      .accounts({
+        pool: poolPda,
+        tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+    await program.methods
+      .setMinClaimAmount(poolId, new BN(1_000_000_000))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    try {
+      await crankClaim();
+      throw new Error("Unexpected crank claim below the minimum");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("below the pool's minimum claim amount");
+    }
+  });
+});
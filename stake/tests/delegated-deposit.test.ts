@@ -0,0 +1,216 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  approve,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  initUserStake,
+} from "./test-utils";
+
+describe("🤝 Stake Program - Delegated Deposits", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let poolVaultPda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let relayer: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    [poolVaultPda] = getPoolVaultPDA(program.programId, poolPda, tokenMint);
+
+    // The user never holds SOL; only the relayer pays fees
+    user = anchor.web3.Keypair.generate();
+    relayer = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      relayer.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      500_000_000
+    );
+
+    // Approve is signed by the user but paid by the admin (fee payer)
+    await approve(
+      provider.connection,
+      admin.payer,
+      userTokenAccount,
+      poolVaultPda,
+      user,
+      300_000_000
+    );
+  });
+
+  it("1. ✅ Relayer deposits via delegate allowance and the user is credited", async () => {
+    // Stake account is pre-created by a separate fee payer
+    const userStakePda = await initUserStake(
+      program,
+      poolId,
+      poolPda,
+      tokenMint,
+      user.publicKey
+    );
+
+    await program.methods
+      .depositStakeDelegated(poolId, new BN(200_000_000))
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        relayer: relayer.publicKey,
+        tokenMint: tokenMint,
+        userTokenAccount: userTokenAccount,
+        poolVault: poolVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([relayer, user])
+      .rpc();
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(userStake.amount.toNumber()).to.equal(200_000_000);
+
+    const tokenAccount = await getAccount(provider.connection, userTokenAccount);
+    expect(Number(tokenAccount.delegatedAmount)).to.equal(100_000_000);
+    console.log("✅ User credited, remaining allowance:", tokenAccount.delegatedAmount.toString());
+  });
+
+  it("2. ❌ Deposit above the remaining allowance is rejected", async () => {
+    const [userStakePda] = getUserStakePDA(
+      program.programId,
+      poolPda,
+      user.publicKey
+    );
+
+    try {
+      await program.methods
+        .depositStakeDelegated(poolId, new BN(150_000_000))
+        .accounts({
+          pool: poolPda,
+          userStake: userStakePda,
+          user: user.publicKey,
+          relayer: relayer.publicKey,
+          tokenMint: tokenMint,
+          userTokenAccount: userTokenAccount,
+          poolVault: poolVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer, user])
+        .rpc();
+      throw new Error("Unexpected success above allowance");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected allowance error:", errMsg);
+      expect(errMsg).to.include("Invalid delegated amount");
+    }
+  });
+
+  it("3. ❌ Relayer cannot credit itself with someone else's tokens", async () => {
+    const relayerStakePda = await initUserStake(
+      program,
+      poolId,
+      poolPda,
+      tokenMint,
+      relayer.publicKey
+    );
+
+    try {
+      await program.methods
+        .depositStakeDelegated(poolId, new BN(50_000_000))
+        .accounts({
+          pool: poolPda,
+          userStake: relayerStakePda,
+          user: relayer.publicKey,
+          relayer: relayer.publicKey,
+          tokenMint: tokenMint,
+          userTokenAccount: userTokenAccount,
+          poolVault: poolVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer])
+        .rpc();
+      throw new Error("Unexpected success crediting relayer");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected constraint error:", errMsg);
+      expect(errMsg).to.match(/constraint|Constraint/);
+    }
+  });
+
+  it("4. ❌ Relayer cannot spend the allowance without the owner's signature", async () => {
+    const [userStakePda] = getUserStakePDA(
+      program.programId,
+      poolPda,
+      user.publicKey
+    );
+
+    try {
+      await program.methods
+        .depositStakeDelegated(poolId, new BN(50_000_000))
+        .accounts({
+          pool: poolPda,
+          userStake: userStakePda,
+          user: user.publicKey,
+          relayer: relayer.publicKey,
+          tokenMint: tokenMint,
+          userTokenAccount: userTokenAccount,
+          poolVault: poolVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([relayer])
+        .rpc();
+      throw new Error("Unexpected deposit without the owner's signature");
+    } catch (err: any) {
+      expect(err.toString()).to.match(/Signature verification failed|Missing signature|unknown signer/i);
+    }
+  });
+});
@@ -10,7 +10,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 // Use small slot counts for fast testing - reward logic works the same
 const SLOTS_PER_DAY = TEST_SLOTS_PER_PERIOD;
@@ -49,7 +49,7 @@ describe("🧪 Stake Program - Edge Cases", () => {
     // Create pool for edge case tests
     const rewardPercentage = 2500; // 25.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -151,6 +151,7 @@ describe("🧪 Stake Program - Edge Cases", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         testUser.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -226,8 +227,9 @@ describe("🧪 Stake Program - Edge Cases", () => {
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
     console.log("\n🔹 User staking:", STAKE_AMOUNT.toString());
 
+    await initUserStake(program, poolId, poolPda, tokenMint, testUser.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: testUser.publicKey,
@@ -413,8 +415,9 @@ describe("🧪 Stake Program - Edge Cases", () => {
         STAKE_AMOUNT.toNumber()
       );
 
+      await initUserStake(program, poolId, poolPda, tokenMint, testUser.publicKey);
       await program.methods
-        .depositStake(poolId, STAKE_AMOUNT)
+        .depositStake(poolId, STAKE_AMOUNT, null)
         .accounts({
           pool: poolPda,
           user: testUser.publicKey,
@@ -484,6 +487,7 @@ describe("🧪 Stake Program - Edge Cases", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         testUser.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -540,8 +544,9 @@ describe("🧪 Stake Program - Edge Cases", () => {
       STAKE_AMOUNT.toNumber()
     );
 
+    await initUserStake(program, poolId, poolPda, tokenMint, testUser.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: testUser.publicKey,
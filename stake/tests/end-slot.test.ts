@@ -0,0 +1,187 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getPositionNftPDA,
+  initUserStake,
+  warpSlots,
+} from "./test-utils";
+
+describe("🏁 Stake Program - Pool End Slot", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let endSlot: number;
+  const poolId = new BN(0);
+
+  const deposit = () =>
+    program.methods
+      .depositStake(poolId, new BN(10_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+  const simulate = (slot: number) =>
+    program.methods
+      .simulateReward(poolId, new BN(slot))
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    endSlot = (await provider.connection.getSlot()) + 20;
+    await program.methods
+      .createPool(null, new BN(10_000_000), poolId, new BN(endSlot))
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+  });
+
+  it("1. ✅ Pools store the end set at creation", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.endSlot.toNumber()).to.equal(endSlot);
+  });
+
+  it("2. ✅ Accrual is clamped at the end slot", async () => {
+    await deposit();
+
+    const atEnd = await simulate(endSlot);
+    const afterEnd = await simulate(endSlot + 1_000);
+    console.log("🏁 Reward at end:", atEnd.toString(), "after:", afterEnd.toString());
+    expect(atEnd.toNumber()).to.be.greaterThan(0);
+    expect(afterEnd.toString()).to.equal(atEnd.toString());
+  });
+
+  it("3. ✅ Owner extends the end before it passes", async () => {
+    endSlot += 10;
+    await program.methods
+      .setEndSlot(poolId, new BN(endSlot))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.endSlot.toNumber()).to.equal(endSlot);
+  });
+
+  it("4. ❌ The end cannot be pulled in", async () => {
+    try {
+      await program.methods
+        .setEndSlot(poolId, new BN(endSlot - 5))
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+      throw new Error("Unexpected shortening of the pool");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("may only be extended");
+    }
+  });
+
+  it("5. ❌ Deposits are rejected after the end", async () => {
+    const current = await provider.connection.getSlot();
+    await warpSlots(provider, Math.max(endSlot - current, 0) + 1);
+
+    try {
+      await deposit();
+      throw new Error("Unexpected deposit after pool end");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected pool ended error:", errMsg);
+      expect(errMsg).to.include("Pool has ended");
+    }
+  });
+
+  it("6. ❌ Position NFT deposits are rejected after the end", async () => {
+    const nftMint = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .mintStakePositionNft(poolId, new BN(10_000_000), null)
+        .accounts({
+          pool: poolPda,
+          tokenMint,
+          nftMint: nftMint.publicKey,
+          user: user.publicKey,
+          userTokenAccount,
+          poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+          metadata: null,
+          masterEdition: null,
+          tokenMetadataProgram: null,
+        })
+        .signers([user, nftMint])
+        .rpc();
+      throw new Error("Unexpected position NFT after pool end");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Pool has ended");
+    }
+
+    const position = await provider.connection.getAccountInfo(
+      getPositionNftPDA(program.programId, nftMint.publicKey)[0]
+    );
+    expect(position).to.be.null;
+  });
+});
@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  initUserStake,
+  warpSlots,
+  getCpiEvents,
+} from "./test-utils";
+
+describe("🗄️ Stake Program - Epoch Archival", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+
+  const simulate = (slot: BN) =>
+    program.methods
+      .simulateReward(poolId, slot)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+
+  const updateRate = (rate: number) =>
+    program.methods
+      .updateRewardPercentage(poolId, new BN(rate))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(10_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(1_000_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    // Fill the history up to its capacity of 10 epochs
+    for (let i = 0; i < 9; i++) {
+      await warpSlots(provider, 2);
+      await updateRate(i % 2 === 0 ? 20_000 : 10_000);
+    }
+  });
+
+  it("1. ✅ Pruning folds the oldest epoch into the archive checkpoint", async () => {
+    const poolBefore = await program.account.pool.fetch(poolPda);
+    expect(poolBefore.rewardEpochs.length).to.equal(10);
+    expect(poolBefore.archivedRewardIndex.toString()).to.equal("0");
+
+    const lastStart = poolBefore.rewardEpochs[9].startSlot;
+    const rewardBefore = await simulate(lastStart);
+
+    await warpSlots(provider, 2);
+    const tx = await updateRate(30_000);
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getCpiEvents(program, txDetails).find(
+      (e) => e.name === "epochArchivedEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event!.data.startSlot.toString()).to.equal(
+      poolBefore.rewardEpochs[0].startSlot.toString()
+    );
+
+    const poolAfter = await program.account.pool.fetch(poolPda);
+    expect(poolAfter.rewardEpochs.length).to.equal(10);
+    expect(poolAfter.archivedRewardIndex.toString()).to.equal(
+      event!.data.archivedRewardIndex.toString()
+    );
+
+    // The staker's checkpoint predates the pruned epoch; its reward is preserved
+    const rewardAfter = await simulate(lastStart);
+    console.log("🗄️ Reward before:", rewardBefore.toString(), "after:", rewardAfter.toString());
+    expect(rewardAfter.toString()).to.equal(rewardBefore.toString());
+  });
+});
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program, BN, EventParser } from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
 import {
   createMint,
   getOrCreateAssociatedTokenAccount,
@@ -8,7 +8,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig, initUserStake, getCpiEvents } from "./test-utils";
 
 describe("🎉 Stake Program - Events", () => {
   const { provider, program, admin } = getTestEnvironment();
@@ -20,15 +20,9 @@ describe("🎉 Stake Program - Events", () => {
   let userRewardAccount: anchor.web3.PublicKey;
   const poolId = new BN(0); // Define at module level for reuse across tests
 
-  // Helper function to parse events from transaction
-  const getEventsFromTransaction = (txDetails: any) => {
-    if (!txDetails || !txDetails.meta || !txDetails.meta.logMessages) {
-      return [];
-    }
-    const eventParser = new EventParser(program.programId, program.coder);
-    // parseLogs returns a generator, so we need to convert it to an array
-    return Array.from(eventParser.parseLogs(txDetails.meta.logMessages));
-  };
+  // Helper function to decode events (emitted via emit_cpi) from transaction
+  const getEventsFromTransaction = (txDetails: any) =>
+    getCpiEvents(program, txDetails);
 
   before(async () => {
     // Initialize global config
@@ -71,7 +65,7 @@ describe("🎉 Stake Program - Events", () => {
     const rewardPercentage = 100; // 1.00% APY in basis points
 
     const tx = await program.methods
-      .createPool(null, new BN(rewardPercentage), poolId)
+      .createPool(null, new BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -251,6 +245,7 @@ describe("🎉 Stake Program - Events", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -260,8 +255,9 @@ describe("🎉 Stake Program - Events", () => {
       program.programId
     );
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     const tx = await program.methods
-      .depositStake(poolId, new BN(100_000_000)) // 100 tokens
+      .depositStake(poolId, new BN(100_000_000), null) // 100 tokens
       .accounts({
         pool: poolPda,
         userStake: userStakePda,
@@ -320,6 +316,7 @@ describe("🎉 Stake Program - Events", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -356,6 +353,7 @@ describe("🎉 Stake Program - Events", () => {
     expect(event).to.not.be.undefined;
     expect(event.data.user.toString()).to.equal(user.publicKey.toString());
     expect(event.data.pool.toString()).to.equal(poolPda.toString());
+    expect(event.data.destination.toString()).to.equal(userRewardAccount.toString());
     expect(event.data.amount.toNumber()).to.be.greaterThan(0);
     console.log("✅ RewardClaimedEvent emitted with correct data");
   });
@@ -366,6 +364,7 @@ describe("🎉 Stake Program - Events", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -453,4 +452,44 @@ describe("🎉 Stake Program - Events", () => {
     expect(event.data.amount.toNumber()).to.equal(100_000_000);
     console.log("✅ RewardWithdrawnEvent emitted with correct data");
   });
+
+  it("9. ✅ PoolAuthorityUpdatedEvent emitted on authority rotation", async () => {
+    const newAuthority = anchor.web3.Keypair.generate();
+
+    const tx = await program.methods
+      .updatePoolAuthority(poolId, newAuthority.publicKey)
+      .accounts({
+        pool: poolPda,
+        currentAuthority: admin.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const events = getEventsFromTransaction(txDetails);
+    const event = events.find((e) => e.name === "poolAuthorityUpdatedEvent");
+
+    expect(event).to.not.be.undefined;
+    expect(event.data.pool.toString()).to.equal(poolPda.toString());
+    expect(event.data.oldAuthority.toString()).to.equal(admin.publicKey.toString());
+    expect(event.data.newAuthority.toString()).to.equal(newAuthority.publicKey.toString());
+
+    // Rotate back so the pool stays usable
+    await program.methods
+      .updatePoolAuthority(poolId, admin.publicKey)
+      .accounts({
+        pool: poolPda,
+        currentAuthority: newAuthority.publicKey,
+        tokenMint: tokenMint,
+      })
+      .signers([newAuthority])
+      .rpc();
+    console.log("✅ PoolAuthorityUpdatedEvent emitted with correct data");
+  });
 });
@@ -0,0 +1,100 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+} from "./test-utils";
+
+describe("📚 Stake Program - Expand Pool Epoch Capacity", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+  });
+
+  it("1. ❌ Non-owner cannot expand the pool", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      nonOwner.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .expandPoolEpochs(poolId, 20)
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Capacity cannot shrink", async () => {
+    try {
+      await program.methods
+        .expandPoolEpochs(poolId, 5)
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+      throw new Error("Unexpected shrink");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected capacity error:", errMsg);
+      expect(errMsg).to.include("Invalid epoch capacity");
+    }
+  });
+
+  it("3. ✅ Expanded pool keeps more than 10 epochs", async () => {
+    const sizeBefore = (await provider.connection.getAccountInfo(poolPda))!.data.length;
+
+    await program.methods
+      .expandPoolEpochs(poolId, 20)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const sizeAfter = (await provider.connection.getAccountInfo(poolPda))!.data.length;
+    expect(sizeAfter - sizeBefore).to.equal(10 * 16);
+
+    for (let i = 1; i <= 14; i++) {
+      await program.methods
+        .updateRewardPercentage(poolId, new BN(1000 + i))
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+    }
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.epochCapacity).to.equal(20);
+    expect(pool.rewardEpochs.length).to.equal(15);
+    console.log("✅ Pool now holds", pool.rewardEpochs.length, "epochs");
+  });
+});
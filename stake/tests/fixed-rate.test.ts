@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  initUserStake,
+  warpSlots,
+} from "./test-utils";
+
+describe("📌 Stake Program - Fixed Rate At Deposit", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  const setLockRate = (enabled: boolean) =>
+    program.methods
+      .setLockRateAtDeposit(poolId, enabled)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+  const simulate = (slot: number) =>
+    program.methods
+      .simulateReward(poolId, new BN(slot))
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(10_000_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+  });
+
+  it("1. ✅ Owner enables fixed-rate deposits on an empty pool", async () => {
+    await setLockRate(true);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.lockRateAtDeposit).to.equal(true);
+  });
+
+  it("2. ✅ Later rate changes do not affect existing deposits", async () => {
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    const target = (await provider.connection.getSlot()) + 50;
+    const before = await simulate(target);
+
+    await warpSlots(provider, 2);
+    await program.methods
+      .updateRewardPercentage(poolId, new BN(50_000_000))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const after = await simulate(target);
+    console.log("📌 Projected reward before:", before.toString(), "after:", after.toString());
+    expect(after.toString()).to.equal(before.toString());
+  });
+
+  it("3. ❌ The mode cannot change while stake is active", async () => {
+    try {
+      await setLockRate(false);
+      throw new Error("Unexpected toggle with active stakers");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected active stakers error:", errMsg);
+      expect(errMsg).to.include("active stakers");
+    }
+  });
+});
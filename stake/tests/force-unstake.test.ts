@@ -0,0 +1,233 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  initUserStake,
+  warpSlots,
+  getCpiEvents,
+} from "./test-utils";
+
+describe("🚨 Stake Program - Force Unstake", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let voteEscrowPda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const STAKE_AMOUNT = new BN(200_000_000);
+
+  const forceUnstakeAccounts = (signer: anchor.web3.PublicKey) => ({
+    pool: poolPda,
+    userStake: userStakePda,
+    user: user.publicKey,
+    admin: signer,
+    tokenMint: tokenMint,
+    userTokenAccount: userTokenAccount,
+    userRewardAccount: userTokenAccount,
+    voteEscrow: voteEscrowPda,
+    poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+    rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    // Fund the reward vault
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    // Deposits are locked well past the end of the test
+    await program.methods
+      .setLockDuration(poolId, new BN(1_000_000))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      STAKE_AMOUNT.toNumber()
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    [voteEscrowPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vote_escrow"),
+        poolPda.toBuffer(),
+        user.publicKey.toBuffer(),
+        new BN(0).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, STAKE_AMOUNT, null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, 10);
+  });
+
+  it("1. ❌ Non-owner cannot force-unstake", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .forceUnstake(poolId, true)
+        .accounts(forceUnstakeAccounts(nonOwner.publicKey))
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected unauthorized error:", errMsg);
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ✅ Owner unwinds a locked position and returns principal plus rewards", async () => {
+    await program.methods
+      .lockForGovernance(poolId, new BN(100_000_000), new BN(10_000))
+      .accounts({
+        pool: poolPda,
+        tokenMint: tokenMint,
+        userStake: userStakePda,
+        voteEscrow: voteEscrowPda,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+
+    const tx = await program.methods
+      .forceUnstake(poolId, true)
+      .accounts(forceUnstakeAccounts(admin.publicKey))
+      .rpc();
+    await provider.connection.confirmTransaction(tx, "confirmed");
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.amount.toNumber()).to.equal(0);
+    expect(userStake.tranches.length).to.equal(0);
+    expect(userStake.unclaimed.toNumber()).to.equal(0);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.totalStaked.toNumber()).to.equal(0);
+
+    // The escrow is derived from the position, so its voting weight is always cleared
+    const escrow = await program.account.voteEscrow.fetch(voteEscrowPda);
+    expect(escrow.lockedAmount.toNumber()).to.equal(0);
+
+    const tokenAccount = await getAccount(provider.connection, userTokenAccount);
+    expect(Number(tokenAccount.amount)).to.be.greaterThan(STAKE_AMOUNT.toNumber());
+
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getCpiEvents(program, txDetails).find(
+      (e) => e.name === "stakeForceUnstakedEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(event!.data.admin.toString()).to.equal(admin.publicKey.toString());
+    expect(event!.data.amount.toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(event!.data.rewardsSent.toNumber()).to.be.greaterThan(0);
+    console.log("✅ Position unwound, rewards sent:", event!.data.rewardsSent.toString());
+  });
+
+  it("3. ❌ An empty position cannot be force-unstaked again", async () => {
+    try {
+      await program.methods
+        .forceUnstake(poolId, false)
+        .accounts(forceUnstakeAccounts(admin.publicKey))
+        .rpc();
+      throw new Error("Unexpected success on empty position");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Stake amount must be greater than zero");
+    }
+  });
+});
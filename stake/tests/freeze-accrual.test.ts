@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  warpSlots,
+  initUserStake,
+} from "./test-utils";
+
+describe("🧊 Stake Program - Freeze Accrual", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(100_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("1. ✅ Freezing stops accrual but keeps the pool active", async () => {
+    await program.methods
+      .freezeAccrual(poolId, true)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.accrualFrozen).to.be.true;
+    expect(pool.isActive).to.be.true;
+
+    const before = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    await warpSlots(provider, 20);
+    const after = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+
+    expect(after.pendingReward.toString()).to.equal(before.pendingReward.toString());
+    console.log("✅ Pending reward unchanged while frozen:", after.pendingReward.toString());
+  });
+
+  it("2. ✅ Withdrawals keep working while frozen", async () => {
+    await program.methods
+      .withdrawStake(poolId, new BN(50_000_000))
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        userRewardAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.amount.toNumber()).to.equal(50_000_000);
+  });
+
+  it("3. ❌ Non-owner cannot freeze accrual", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .freezeAccrual(poolId, false)
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("4. ✅ Unfreezing resumes accrual", async () => {
+    await program.methods
+      .freezeAccrual(poolId, false)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    await warpSlots(provider, 20);
+
+    const info = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    expect(info.pendingReward.toNumber()).to.be.greaterThan(0);
+  });
+});
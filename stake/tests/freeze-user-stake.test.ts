@@ -0,0 +1,202 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  initUserStake,
+} from "./test-utils";
+
+describe("🧊 Stake Program - Frozen User Stakes", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  const deposit = async (amount: BN) => {
+    await program.methods
+      .depositStake(poolId, amount, null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+  };
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      300_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await deposit(new BN(100_000_000));
+  });
+
+  it("1. ❌ Non-owner cannot freeze a user stake", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setUserStakeFrozen(poolId, true)
+        .accounts({
+          pool: poolPda,
+          userStake: userStakePda,
+          admin: nonOwner.publicKey,
+          tokenMint,
+        })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Frozen stake blocks deposits, withdrawals and claims", async () => {
+    await program.methods
+      .setUserStakeFrozen(poolId, true)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        admin: admin.publicKey,
+        tokenMint,
+      })
+      .rpc();
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.frozen).to.be.true;
+
+    try {
+      await deposit(new BN(10_000_000));
+      throw new Error("Unexpected deposit into frozen stake");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected frozen error:", errMsg);
+      expect(errMsg).to.include("frozen");
+    }
+
+    try {
+      await program.methods
+        .withdrawStake(poolId, new BN(10_000_000))
+        .accounts({
+          pool: poolPda,
+          userStake: userStakePda,
+          user: user.publicKey,
+          userTokenAccount: userTokenAccount,
+          userRewardAccount: userTokenAccount,
+          poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+          rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected withdrawal from frozen stake");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("frozen");
+    }
+
+    try {
+      await program.methods
+        .claimReward(poolId)
+        .accounts({
+          pool: poolPda,
+          tokenMint: tokenMint,
+          userStake: userStakePda,
+          user: user.publicKey,
+          userRewardAccount: userTokenAccount,
+          rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected claim from frozen stake");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("frozen");
+    }
+  });
+
+  it("3. ✅ Unfrozen stake accepts deposits again", async () => {
+    await program.methods
+      .setUserStakeFrozen(poolId, false)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        admin: admin.publicKey,
+        tokenMint,
+      })
+      .rpc();
+
+    await deposit(new BN(10_000_000));
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.frozen).to.be.false;
+    expect(userStake.amount.toNumber()).to.equal(110_000_000);
+    console.log("✅ Deposit accepted after unfreezing");
+  });
+});
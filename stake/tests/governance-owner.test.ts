@@ -0,0 +1,120 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+} from "./test-utils";
+
+describe("🏛️ Stake Program - SPL Governance Ownership", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  // Any executable stands in for the governance program; no realm exists under it
+  const governanceProgram = TOKEN_PROGRAM_ID;
+  const realm = anchor.web3.Keypair.generate().publicKey;
+  const governanceSeed = anchor.web3.Keypair.generate().publicKey;
+  const [governancePda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("account-governance"), realm.toBuffer(), governanceSeed.toBuffer()],
+    governanceProgram
+  );
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+  });
+
+  it("1. ❌ Non-owner cannot hand the pool to a governance", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setPoolGovernance(poolId, governanceSeed)
+        .accounts({
+          pool: poolPda,
+          admin: nonOwner.publicKey,
+          tokenMint,
+          governanceProgram,
+          realm,
+          governance: governancePda,
+        })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Governance address must match the realm's governance PDA", async () => {
+    try {
+      await program.methods
+        .setPoolGovernance(poolId, governanceSeed)
+        .accounts({
+          pool: poolPda,
+          admin: admin.publicKey,
+          tokenMint,
+          governanceProgram,
+          realm,
+          governance: anchor.web3.Keypair.generate().publicKey,
+        })
+        .rpc();
+      throw new Error("Unexpected success with wrong governance address");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected governance error:", errMsg);
+      expect(errMsg).to.include("Invalid governance");
+    }
+  });
+
+  it("3. ❌ Realm and governance must exist under the governance program", async () => {
+    try {
+      await program.methods
+        .setPoolGovernance(poolId, governanceSeed)
+        .accounts({
+          pool: poolPda,
+          admin: admin.publicKey,
+          tokenMint,
+          governanceProgram,
+          realm,
+          governance: governancePda,
+        })
+        .rpc();
+      throw new Error("Unexpected success with missing realm");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Invalid governance");
+    }
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.owner.toBase58()).to.equal(admin.publicKey.toBase58());
+    expect(pool.governanceRealm.toBase58()).to.equal(
+      anchor.web3.PublicKey.default.toBase58()
+    );
+    console.log("✅ Pool ownership unchanged");
+  });
+});
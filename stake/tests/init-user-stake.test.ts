@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getUserPositionsPDA,
+  getPoolVaultPDA,
+} from "./test-utils";
+
+describe("🆕 Stake Program - Init User Stake", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+
+  const deposit = () =>
+    program.methods
+      .depositStake(poolId, new BN(10_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+  const initUserStake = () =>
+    program.methods
+      .initUserStake(poolId, new BN(0))
+      .accounts({
+        pool: poolPda,
+        userPositions: getUserPositionsPDA(program.programId, poolPda, user.publicKey)[0],
+        userStake: userStakePda,
+        user: user.publicKey,
+        payer: admin.publicKey,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+  });
+
+  it("1. ❌ Deposit fails before the stake account exists", async () => {
+    try {
+      await deposit();
+      throw new Error("Unexpected success without init_user_stake");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected missing account error:", errMsg);
+      expect(errMsg).to.match(/not initialized|AccountNotInitialized/);
+    }
+  });
+
+  it("2. ✅ Separate fee payer pre-creates the account for the user", async () => {
+    await initUserStake();
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.owner.toBase58()).to.equal(user.publicKey.toBase58());
+    expect(userStake.pool.toBase58()).to.equal(poolPda.toBase58());
+    expect(userStake.amount.toNumber()).to.equal(0);
+
+    await deposit();
+    const afterDeposit = await program.account.userStake.fetch(userStakePda);
+    expect(afterDeposit.amount.toNumber()).to.equal(10_000_000);
+  });
+
+  it("3. ❌ An existing stake account cannot be re-initialized", async () => {
+    try {
+      await initUserStake();
+      throw new Error("Unexpected success re-initializing");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected already-in-use error:", errMsg);
+      expect(errMsg).to.match(/already in use|custom program error: 0x0/);
+    }
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.amount.toNumber()).to.equal(10_000_000);
+  });
+});
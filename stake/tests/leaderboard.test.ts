@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  getLeaderboardPDA,
+  initUserStake,
+} from "./test-utils";
+
+describe("🏆 Stake Program - Top Staker Leaderboard", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let leaderboardPda: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const stakers: {
+    kp: anchor.web3.Keypair;
+    tokenAccount: anchor.web3.PublicKey;
+    userStake: anchor.web3.PublicKey;
+  }[] = [];
+
+  const deposit = (i: number, amount: number) =>
+    program.methods
+      .depositStake(poolId, new BN(amount), null)
+      .accounts({
+        pool: poolPda,
+        userStake: stakers[i].userStake,
+        user: stakers[i].kp.publicKey,
+        userTokenAccount: stakers[i].tokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        leaderboard: leaderboardPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([stakers[i].kp])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    [leaderboardPda] = getLeaderboardPDA(program.programId, poolPda);
+
+    for (let i = 0; i < 3; i++) {
+      const kp = anchor.web3.Keypair.generate();
+      const sig = await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+
+      const tokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          admin.payer,
+          tokenMint,
+          kp.publicKey
+        )
+      ).address;
+      await mintTo(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        tokenAccount,
+        admin.publicKey,
+        1_000_000_000
+      );
+
+      const [userStake] = getUserStakePDA(program.programId, poolPda, kp.publicKey);
+      await initUserStake(program, poolId, poolPda, tokenMint, kp.publicKey);
+      stakers.push({ kp, tokenAccount, userStake });
+    }
+  });
+
+  it("1. ✅ Anyone can create the pool leaderboard", async () => {
+    await program.methods
+      .initLeaderboard(poolId)
+      .accounts({ pool: poolPda, tokenMint, payer: admin.publicKey })
+      .rpc();
+
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+    expect(leaderboard.pool.toBase58()).to.equal(poolPda.toBase58());
+    expect(leaderboard.entries.length).to.equal(0);
+  });
+
+  it("2. ✅ Deposits are ranked by staked amount", async () => {
+    await deposit(0, 100_000_000);
+    await deposit(1, 300_000_000);
+    await deposit(2, 200_000_000);
+
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+    const amounts = leaderboard.entries.map((e) => e.amount.toNumber());
+    expect(amounts).to.deep.equal([300_000_000, 200_000_000, 100_000_000]);
+    expect(leaderboard.entries[0].owner.toBase58()).to.equal(
+      stakers[1].kp.publicKey.toBase58()
+    );
+    console.log("🏆 Ranking:", amounts);
+  });
+
+  it("3. ✅ Withdrawals re-rank and full exits drop off", async () => {
+    await program.methods
+      .withdrawStake(poolId, new BN(300_000_000))
+      .accounts({
+        pool: poolPda,
+        userStake: stakers[1].userStake,
+        user: stakers[1].kp.publicKey,
+        userTokenAccount: stakers[1].tokenAccount,
+        userRewardAccount: stakers[1].tokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        leaderboard: leaderboardPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([stakers[1].kp])
+      .rpc();
+
+    const leaderboard = await program.account.leaderboard.fetch(leaderboardPda);
+    const owners = leaderboard.entries.map((e) => e.owner.toBase58());
+    expect(owners).to.deep.equal([
+      stakers[2].kp.publicKey.toBase58(),
+      stakers[0].kp.publicKey.toBase58(),
+    ]);
+    console.log("✅ Exited staker removed from leaderboard");
+  });
+});
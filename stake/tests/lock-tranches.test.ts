@@ -0,0 +1,186 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  initUserStake,
+  warpSlots,
+} from "./test-utils";
+
+describe("🔒 Stake Program - Per-Deposit Lock Tranches", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const LOCK_SLOTS = 20;
+
+  const deposit = async (amount: BN) => {
+    await program.methods
+      .depositStake(poolId, amount, null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+  };
+
+  const withdraw = async (amount: BN) => {
+    await program.methods
+      .withdrawStake(poolId, amount)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        userRewardAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+  };
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      1_000_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+  });
+
+  it("1. ❌ Non-owner cannot set the lock duration", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setLockDuration(poolId, new BN(LOCK_SLOTS))
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ✅ Each deposit records its own tranche and lock end", async () => {
+    await program.methods
+      .setLockDuration(poolId, new BN(LOCK_SLOTS))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    await deposit(new BN(100_000_000));
+    await warpSlots(provider, 5);
+    await deposit(new BN(50_000_000));
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.tranches.length).to.equal(2);
+    const [first, second] = userStake.tranches;
+    expect(first.amount.toNumber()).to.equal(100_000_000);
+    expect(second.amount.toNumber()).to.equal(50_000_000);
+    expect(first.lockEndSlot.sub(first.depositSlot).toNumber()).to.equal(LOCK_SLOTS);
+    // Topping up did not push back the first tranche's lock
+    expect(first.lockEndSlot.lt(second.lockEndSlot)).to.be.true;
+    console.log("✅ Tranche lock ends:", first.lockEndSlot.toString(), second.lockEndSlot.toString());
+  });
+
+  it("3. ❌ Locked stake cannot be withdrawn", async () => {
+    try {
+      await withdraw(new BN(10_000_000));
+      throw new Error("Unexpected success withdrawing locked stake");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected lock error:", errMsg);
+      expect(errMsg).to.include("Stake is still locked");
+    }
+  });
+
+  it("4. ✅ First tranche unlocks on schedule while the top-up stays locked", async () => {
+    const before = await program.account.userStake.fetch(userStakePda);
+    const firstLockEnd = before.tranches[0].lockEndSlot.toNumber();
+    const currentSlot = await provider.connection.getSlot();
+    await warpSlots(provider, Math.max(firstLockEnd - currentSlot + 1, 1));
+
+    try {
+      await withdraw(new BN(150_000_000));
+      throw new Error("Unexpected success withdrawing the top-up tranche");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Stake is still locked");
+    }
+
+    await withdraw(new BN(100_000_000));
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.amount.toNumber()).to.equal(50_000_000);
+    expect(userStake.tranches.length).to.equal(1);
+    expect(userStake.tranches[0].amount.toNumber()).to.equal(50_000_000);
+    console.log("✅ Unlocked tranche withdrawn, top-up still locked");
+  });
+});
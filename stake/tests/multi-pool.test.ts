@@ -54,7 +54,7 @@ describe("🔢 Stake Program - Multiple Pools per Token", () => {
     const [poolPda] = getPoolPDA(program.programId, tokenMint, poolId);
 
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId))
+      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId), null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint1,
@@ -95,7 +95,7 @@ describe("🔢 Stake Program - Multiple Pools per Token", () => {
     );
 
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId))
+      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId), null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint2,
@@ -130,7 +130,7 @@ describe("🔢 Stake Program - Multiple Pools per Token", () => {
     const [poolPda] = getPoolPDA(program.programId, tokenMint, poolId);
 
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId))
+      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId), null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint1, // Reuse first reward mint
@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  initUserStake,
+  warpSlots,
+} from "./test-utils";
+
+describe("🪙 Stake Program - Native SOL Pool", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let poolPda: anchor.web3.PublicKey;
+  let poolVaultPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let unwrapPda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+  const STAKE_LAMPORTS = new BN(anchor.web3.LAMPORTS_PER_SOL);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: NATIVE_MINT,
+        rewardMint: NATIVE_MINT,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, NATIVE_MINT, 0);
+    [poolVaultPda] = getPoolVaultPDA(program.programId, poolPda, NATIVE_MINT);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      3 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userStakePda = await initUserStake(
+      program,
+      poolId,
+      poolPda,
+      NATIVE_MINT,
+      user.publicKey
+    );
+    [unwrapPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), poolPda.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("1. ✅ User stakes native SOL without a wSOL account", async () => {
+    const vaultBefore = await getAccount(provider.connection, poolVaultPda);
+
+    await program.methods
+      .depositStakeSol(poolId, STAKE_LAMPORTS)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        tokenMint: NATIVE_MINT,
+        poolVault: poolVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const vaultAfter = await getAccount(provider.connection, poolVaultPda);
+    const userStake = await program.account.userStake.fetch(userStakePda);
+
+    expect(Number(vaultAfter.amount - vaultBefore.amount)).to.equal(
+      STAKE_LAMPORTS.toNumber()
+    );
+    expect(userStake.amount.toString()).to.equal(STAKE_LAMPORTS.toString());
+    console.log("✅ Staked lamports wrapped in vault:", vaultAfter.amount.toString());
+  });
+
+  it("2. ✅ User withdraws and receives native SOL", async () => {
+    const balanceBefore = await provider.connection.getBalance(user.publicKey);
+
+    await program.methods
+      .withdrawStakeSol(poolId, STAKE_LAMPORTS)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        tokenMint: NATIVE_MINT,
+        userRewardAccount: getAssociatedTokenAddressSync(NATIVE_MINT, user.publicKey),
+        rewardMint: NATIVE_MINT,
+        poolVault: poolVaultPda,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, NATIVE_MINT)[0],
+        unwrapAccount: unwrapPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(user.publicKey);
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    const unwrapInfo = await provider.connection.getAccountInfo(unwrapPda);
+
+    expect(userStake.amount.toNumber()).to.equal(0);
+    expect(unwrapInfo).to.be.null;
+    // Allow for transaction fees
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(
+      STAKE_LAMPORTS.toNumber() - 100_000
+    );
+    console.log("✅ SOL returned:", balanceAfter - balanceBefore);
+  });
+
+  it("3. ❌ SOL deposit into a non-wSOL pool is rejected", async () => {
+    const tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    const [splPoolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    const splUserStakePda = await initUserStake(
+      program,
+      poolId,
+      splPoolPda,
+      tokenMint,
+      user.publicKey
+    );
+
+    try {
+      await program.methods
+        .depositStakeSol(poolId, STAKE_LAMPORTS)
+        .accounts({
+          pool: splPoolPda,
+          userStake: splUserStakePda,
+          user: user.publicKey,
+          tokenMint: tokenMint,
+          poolVault: getPoolVaultPDA(program.programId, splPoolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+      throw new Error("Unexpected success on non-wSOL pool");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected pool type error:", errMsg);
+      expect(errMsg).to.include("wrapped SOL pools");
+    }
+  });
+
+  it("4. ✅ Rewards in a non-wSOL mint are paid to the reward account", async () => {
+    const rewardPoolId = new BN(1);
+    const rewardMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(10_000_000), rewardPoolId, null)
+      .accounts({
+        tokenMint: NATIVE_MINT,
+        rewardMint: NATIVE_MINT,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+    const [rewardPoolPda] = getPoolPDA(program.programId, NATIVE_MINT, 1);
+    const [rewardPoolVault] = getPoolVaultPDA(program.programId, rewardPoolPda, NATIVE_MINT);
+    const [rewardVault] = getRewardVaultPDA(program.programId, rewardPoolPda, rewardMint);
+
+    await program.methods
+      .updateRewardMint(rewardPoolId)
+      .accounts({
+        pool: rewardPoolPda,
+        admin: admin.publicKey,
+        newRewardMint: rewardMint,
+        rewardVault,
+        tokenMint: NATIVE_MINT,
+      })
+      .rpc();
+
+    const adminRewardAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      rewardMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      rewardMint,
+      adminRewardAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(rewardPoolId, new BN(1_000_000_000))
+      .accounts({
+        pool: rewardPoolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminRewardAccount.address,
+        rewardVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: NATIVE_MINT,
+      })
+      .rpc();
+
+    const rewardUserStakePda = await initUserStake(
+      program,
+      rewardPoolId,
+      rewardPoolPda,
+      NATIVE_MINT,
+      user.publicKey
+    );
+    await program.methods
+      .depositStakeSol(rewardPoolId, STAKE_LAMPORTS)
+      .accounts({
+        pool: rewardPoolPda,
+        userStake: rewardUserStakePda,
+        user: user.publicKey,
+        tokenMint: NATIVE_MINT,
+        poolVault: rewardPoolVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, 10);
+
+    const userRewardAccount = getAssociatedTokenAddressSync(rewardMint, user.publicKey);
+    const balanceBefore = await provider.connection.getBalance(user.publicKey);
+    await program.methods
+      .withdrawStakeSol(rewardPoolId, STAKE_LAMPORTS)
+      .accounts({
+        pool: rewardPoolPda,
+        userStake: rewardUserStakePda,
+        user: user.publicKey,
+        tokenMint: NATIVE_MINT,
+        userRewardAccount,
+        rewardMint,
+        poolVault: rewardPoolVault,
+        rewardVault,
+        unwrapAccount: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("unwrap"), rewardPoolPda.toBuffer(), user.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(user.publicKey);
+    const rewards = await getAccount(provider.connection, userRewardAccount);
+    // Principal comes back as SOL; the reward ATA's rent is paid from it
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(
+      STAKE_LAMPORTS.toNumber() - 3_000_000
+    );
+    expect(Number(rewards.amount)).to.be.greaterThan(0);
+    console.log("✅ Rewards paid in reward mint:", rewards.amount.toString());
+  });
+});
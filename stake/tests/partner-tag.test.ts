@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  initUserStake,
+  getCpiEvents,
+} from "./test-utils";
+
+describe("🤝 Stake Program - Partner Attribution", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  const tag = (name: string) => {
+    const bytes = Buffer.alloc(16);
+    bytes.write(name);
+    return Array.from(bytes);
+  };
+
+  const deposit = (partnerTag: number[] | null) =>
+    program.methods
+      .depositStake(poolId, new BN(10_000_000), partnerTag)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+  });
+
+  it("1. ✅ First deposit records the partner tag and emits it", async () => {
+    const tx = await deposit(tag("partner-a"));
+    await provider.connection.confirmTransaction(tx, "confirmed");
+    const txDetails = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const event = getCpiEvents(program, txDetails).find(
+      (e) => e.name === "stakeDepositedEvent"
+    );
+    expect(event).to.not.be.undefined;
+    expect(Array.from(event!.data.partnerTag)).to.deep.equal(tag("partner-a"));
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(Array.from(userStake.partnerTag)).to.deep.equal(tag("partner-a"));
+    console.log("🤝 Attributed to:", Buffer.from(userStake.partnerTag).toString());
+  });
+
+  it("2. ✅ Later deposits keep the original attribution", async () => {
+    await deposit(tag("partner-b"));
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(Array.from(userStake.partnerTag)).to.deep.equal(tag("partner-a"));
+  });
+});
@@ -52,7 +52,7 @@ describe("🔐 Stake Program - PDA Seed Validation", () => {
     // Create pool for correct token mint
     const rewardPercentage = 1000; // 10.00% APY
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -322,7 +322,7 @@ describe("🔐 Stake Program - PDA Seed Validation", () => {
 
       try {
         await program.methods
-          .depositStake(poolId, new anchor.BN(100_000))
+          .depositStake(poolId, new anchor.BN(100_000), null)
           .accounts({
             pool: poolPda,
             user: user.publicKey,
@@ -0,0 +1,120 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+} from "./test-utils";
+
+describe("🗓️ Stake Program - Periods Per Year", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const SLOTS_PER_YEAR = 78_840_000;
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+  });
+
+  it("1. ✅ Pools store the divisor at creation", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.periodsPerYear.toNumber()).to.equal(SLOTS_PER_YEAR);
+  });
+
+  it("2. ❌ Non-owner cannot tune the divisor", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setPeriodsPerYear(poolId, new BN(70_000_000))
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("3. ❌ Divisor must stay near the nominal value", async () => {
+    try {
+      await program.methods
+        .setPeriodsPerYear(poolId, new BN(SLOTS_PER_YEAR / 10))
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+      throw new Error("Unexpected success with extreme divisor");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected divisor error:", errMsg);
+      expect(errMsg).to.include("Periods per year must be within");
+    }
+  });
+
+  it("4. ✅ Owner tunes the divisor", async () => {
+    await program.methods
+      .setPeriodsPerYear(poolId, new BN(70_000_000))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.periodsPerYear.toNumber()).to.equal(70_000_000);
+    console.log("✅ Periods per year:", pool.periodsPerYear.toString());
+  });
+
+  it("5. ✅ The change starts a new epoch and keeps rewards in the original divisor", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.rewardEpochs.length).to.equal(1);
+    expect(pool.rewardEpochs[0].startSlot.toString()).to.equal(
+      pool.lastRewardUpdateSlot.toString()
+    );
+    expect(pool.rewardEpochs[0].rewardPercentage.toNumber()).to.equal(1000);
+    expect(pool.rewardScalePeriods.toNumber()).to.equal(SLOTS_PER_YEAR);
+  });
+
+  it("6. ❌ Divisor changes respect the rate-change cooldown", async () => {
+    await program.methods
+      .setRateChangeCooldown(poolId, new BN(1_000_000))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    try {
+      await program.methods
+        .setPeriodsPerYear(poolId, new BN(72_000_000))
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+      throw new Error("Unexpected divisor change during cooldown");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Reward rate was changed too recently");
+    } finally {
+      await program.methods
+        .setRateChangeCooldown(poolId, new BN(0))
+        .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+        .rpc();
+    }
+  });
+});
@@ -0,0 +1,180 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getRewardVaultPDA,
+  getPoolAdminsPDA,
+} from "./test-utils";
+
+describe("👥 Stake Program - Pool Admins", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let poolAdminsPda: anchor.web3.PublicKey;
+  let operator: anchor.web3.Keypair;
+  let operatorTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+
+  const setActive = (active: boolean) =>
+    program.methods
+      .setStakingActive(poolId, active)
+      .accounts({
+        pool: poolPda,
+        admin: operator.publicKey,
+        tokenMint,
+        poolAdmins: poolAdminsPda,
+      })
+      .signers([operator])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    [poolAdminsPda] = getPoolAdminsPDA(program.programId, poolPda);
+
+    operator = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      operator.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    operatorTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        operator.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      operatorTokenAccount,
+      admin.publicKey,
+      1_000_000_000
+    );
+  });
+
+  it("1. ❌ Non-owner cannot add pool admins", async () => {
+    try {
+      await program.methods
+        .addPoolAdmin(poolId, operator.publicKey)
+        .accounts({ pool: poolPda, admin: operator.publicKey, tokenMint })
+        .signers([operator])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ✅ Owner adds a pool admin", async () => {
+    await program.methods
+      .addPoolAdmin(poolId, operator.publicKey)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const poolAdmins = await program.account.poolAdmins.fetch(poolAdminsPda);
+    expect(poolAdmins.admins.map((a) => a.toBase58())).to.deep.equal([
+      operator.publicKey.toBase58(),
+    ]);
+  });
+
+  it("3. ✅ Pool admin toggles staking and deposits rewards", async () => {
+    await setActive(false);
+    let pool = await program.account.pool.fetch(poolPda);
+    expect(pool.isActive).to.equal(false);
+
+    await setActive(true);
+    pool = await program.account.pool.fetch(poolPda);
+    expect(pool.isActive).to.equal(true);
+
+    const [rewardVault] = getRewardVaultPDA(program.programId, poolPda, tokenMint);
+    await program.methods
+      .depositReward(poolId, new BN(500_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: operator.publicKey,
+        adminRewardAccount: operatorTokenAccount,
+        rewardVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+        poolAdmins: poolAdminsPda,
+      })
+      .signers([operator])
+      .rpc();
+
+    const vault = await provider.connection.getTokenAccountBalance(rewardVault);
+    console.log("✅ Reward vault funded by pool admin:", vault.value.amount);
+    expect(vault.value.amount).to.equal("500000000");
+  });
+
+  it("4. ❌ Pool admin cannot withdraw rewards", async () => {
+    try {
+      await program.methods
+        .withdrawReward(poolId, new BN(1))
+        .accounts({
+          pool: poolPda,
+          admin: operator.publicKey,
+          adminRewardAccount: operatorTokenAccount,
+          rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+          tokenProgram: TOKEN_PROGRAM_ID,
+          tokenMint: tokenMint,
+        })
+        .signers([operator])
+        .rpc();
+      throw new Error("Unexpected withdrawal by pool admin");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected owner-only error:", errMsg);
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("5. ✅ Removed pool admins lose operator rights", async () => {
+    await program.methods
+      .removePoolAdmin(poolId, operator.publicKey)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    try {
+      await setActive(false);
+      throw new Error("Unexpected success by removed pool admin");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+});
@@ -8,7 +8,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 describe("🔒 Stake Program - Pool Association Security Tests", () => {
   const { provider, program, admin } = getTestEnvironment();
@@ -60,7 +60,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     // Create Pool A (for tokenMintA)
     const rewardPercentage = 1000; // 10.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMintA,
         rewardMint: tokenMintA,
@@ -82,7 +82,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
 
     // Create Pool B (for tokenMintB) — same-token: reward = tokenMintB
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMintB,
         rewardMint: tokenMintB,
@@ -205,7 +205,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
 
     // Alice stakes in Pool A
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -215,8 +215,9 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     );
 
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
+    await initUserStake(program, poolId, poolA, tokenMintA, alice.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolA,
         user: alice.publicKey,
@@ -297,7 +298,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     );
 
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -349,7 +350,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     );
 
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -389,7 +390,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     );
 
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -429,7 +430,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
     );
 
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -474,7 +475,7 @@ describe("🔒 Stake Program - Pool Association Security Tests", () => {
 
     // Alice already has a stake in Pool A
     const [aliceStakeAPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer()],
+      [Buffer.from("user_stake"), poolA.toBuffer(), alice.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
@@ -39,7 +39,7 @@ describe("🔧 Stake Program - Pool Configuration", () => {
     // Create pool for configuration tests
     const rewardPercentage = 1000; // 10.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -44,7 +44,7 @@ describe("🪙 Stake Program - Create Pool", () => {
     const poolId = 0; // First pool for this token mint
     
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId))
+      .createPool(null, new anchor.BN(rewardPercentage), new anchor.BN(poolId), null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -92,7 +92,7 @@ describe("🪙 Stake Program - Create Pool", () => {
 
     try {
       await program.methods
-        .createPool(null, new anchor.BN(1000), new anchor.BN(poolId))
+        .createPool(null, new anchor.BN(1000), new anchor.BN(poolId), null)
         .accounts({
           tokenMint: tokenMint,
           rewardMint: rewardMint,
@@ -128,7 +128,7 @@ describe("🪙 Stake Program - Create Pool", () => {
 
     try {
       await program.methods
-        .createPool(null, new anchor.BN(excessivePercentage), new anchor.BN(poolId))
+        .createPool(null, new anchor.BN(excessivePercentage), new anchor.BN(poolId), null)
         .accounts({
           tokenMint: testTokenMint,
           rewardMint: testRewardMint,
@@ -177,7 +177,7 @@ describe("🪙 Stake Program - Create Pool", () => {
 
     const poolId = 0;
     await program.methods
-      .createPool(null, new anchor.BN(0), new anchor.BN(poolId)) // 0 bps = 0% APY
+      .createPool(null, new anchor.BN(0), new anchor.BN(poolId), null) // 0 bps = 0% APY
       .accounts({
         tokenMint: testTokenMint,
         rewardMint: testRewardMint,
@@ -213,7 +213,7 @@ describe("🪙 Stake Program - Create Pool", () => {
     const poolId = 0;
 
     await program.methods
-      .createPool(null, new anchor.BN(highPercentage), new anchor.BN(poolId))
+      .createPool(null, new anchor.BN(highPercentage), new anchor.BN(poolId), null)
       .accounts({
         tokenMint: testTokenMint,
         rewardMint: testRewardMint,
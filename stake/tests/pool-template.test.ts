@@ -0,0 +1,142 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getPoolTemplatePDA,
+} from "./test-utils";
+
+describe("🧩 Stake Program - Pool Templates", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  const templateId = 7;
+  const poolId = new BN(0);
+
+  const params = {
+    rewardPercentage: new BN(1_200),
+    maxRewardPerUser: new BN(5_000_000),
+    lockDurationSlots: new BN(1_000),
+    haltOnDepletion: true,
+    timeBasis: { slot: {} },
+    minSlotsBetweenRateChanges: new BN(500),
+  };
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+  });
+
+  it("1. ❌ Only the global admin can manage templates", async () => {
+    const nonAdmin = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      nonAdmin.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    try {
+      await program.methods
+        .setPoolTemplate(new BN(templateId), params)
+        .accounts({
+          admin: nonAdmin.publicKey,
+          config: getGlobalConfigPDA(program.programId)[0],
+        })
+        .signers([nonAdmin])
+        .rpc();
+      throw new Error("Unexpected template update by non-admin");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected admin error:", errMsg);
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Template parameters are validated", async () => {
+    try {
+      await program.methods
+        .setPoolTemplate(new BN(templateId), {
+          ...params,
+          rewardPercentage: new BN(100_000_001),
+        })
+        .accounts({
+          admin: admin.publicKey,
+          config: getGlobalConfigPDA(program.programId)[0],
+        })
+        .rpc();
+      throw new Error("Unexpected template with extreme rate");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("reward percentage");
+    }
+  });
+
+  it("3. ✅ Admin stores a template", async () => {
+    await program.methods
+      .setPoolTemplate(new BN(templateId), params)
+      .accounts({
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    const [templatePda] = getPoolTemplatePDA(program.programId, templateId);
+    const template = await program.account.poolTemplate.fetch(templatePda);
+    expect(template.templateId.toNumber()).to.equal(templateId);
+    expect(template.params.rewardPercentage.toNumber()).to.equal(1_200);
+    expect(template.params.lockDurationSlots.toNumber()).to.equal(1_000);
+    console.log("✅ Template stored at", templatePda.toBase58());
+  });
+
+  it("4. ✅ Pool created from the template inherits its parameters", async () => {
+    await program.methods
+      .createPoolFromTemplate(new BN(templateId), null, poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    const [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.owner.toBase58()).to.equal(admin.publicKey.toBase58());
+    expect(pool.rewardPercentage.toNumber()).to.equal(1_200);
+    expect(pool.maxRewardPerUser.toNumber()).to.equal(5_000_000);
+    expect(pool.lockDurationSlots.toNumber()).to.equal(1_000);
+    expect(pool.haltOnDepletion).to.be.true;
+    expect(pool.minSlotsBetweenRateChanges.toNumber()).to.equal(500);
+    expect(pool.rewardEpochs.length).to.equal(1);
+    expect(pool.rewardEpochs[0].rewardPercentage.toNumber()).to.equal(1_200);
+    console.log("✅ Pool inherited template parameters");
+  });
+
+  it("5. ❌ Unknown templates cannot be used", async () => {
+    try {
+      await program.methods
+        .createPoolFromTemplate(new BN(templateId + 1), null, new BN(1), null)
+        .accounts({
+          tokenMint: tokenMint,
+          rewardMint: tokenMint,
+          admin: admin.publicKey,
+          config: getGlobalConfigPDA(program.programId)[0],
+        })
+        .rpc();
+      throw new Error("Unexpected pool from missing template");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.not.include("Unexpected");
+    }
+  });
+});
@@ -0,0 +1,247 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  getPositionNftPDA,
+  warpSlots,
+} from "./test-utils";
+
+describe("🖼️ Stake Program - Position NFTs", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let buyer: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  let buyerTokenAccount: anchor.web3.PublicKey;
+  const nftMint = anchor.web3.Keypair.generate();
+  const poolId = new BN(0);
+  const LOCK_SLOTS = 20;
+  const STAKE_AMOUNT = new BN(100_000_000);
+
+  const redeem = (holder: anchor.web3.Keypair, holderTokenAccount: anchor.web3.PublicKey) =>
+    program.methods
+      .redeemStakePositionNft(poolId)
+      .accounts({
+        pool: poolPda,
+        tokenMint,
+        nftMint: nftMint.publicKey,
+        position: getPositionNftPDA(program.programId, nftMint.publicKey)[0],
+        holder: holder.publicKey,
+        holderNftAccount: getAssociatedTokenAddressSync(nftMint.publicKey, holder.publicKey),
+        holderTokenAccount,
+        holderRewardAccount: holderTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([holder])
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    // Fund the reward vault
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    for (const kp of [user, buyer]) {
+      const sig = await provider.connection.requestAirdrop(
+        kp.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    buyerTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        buyer.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      STAKE_AMOUNT.toNumber()
+    );
+  });
+
+  const mintPosition = () =>
+    program.methods
+      .mintStakePositionNft(poolId, STAKE_AMOUNT, null)
+      .accounts({
+        pool: poolPda,
+        tokenMint,
+        nftMint: nftMint.publicKey,
+        user: user.publicKey,
+        userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        metadata: null,
+        masterEdition: null,
+        tokenMetadataProgram: null,
+      })
+      .signers([user, nftMint])
+      .rpc();
+
+  it("1. ❌ Pools without a deposit lock cannot mint position NFTs", async () => {
+    try {
+      await mintPosition();
+      throw new Error("Unexpected mint without lock");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected lock error:", errMsg);
+      expect(errMsg).to.include("require the pool to have a deposit lock");
+    }
+  });
+
+  it("2. ✅ Minting locks the deposit behind a one-of-one NFT", async () => {
+    await program.methods
+      .setLockDuration(poolId, new BN(LOCK_SLOTS))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    await mintPosition();
+
+    const position = await program.account.stakePositionNft.fetch(
+      getPositionNftPDA(program.programId, nftMint.publicKey)[0]
+    );
+    const mint = await getMint(provider.connection, nftMint.publicKey);
+    const pool = await program.account.pool.fetch(poolPda);
+
+    expect(position.amount.toString()).to.equal(STAKE_AMOUNT.toString());
+    expect(position.lockEndSlot.toNumber() - position.depositSlot.toNumber()).to.equal(LOCK_SLOTS);
+    expect(Number(mint.supply)).to.equal(1);
+    expect(mint.decimals).to.equal(0);
+    expect(mint.mintAuthority).to.be.null;
+    expect(pool.totalStaked.toString()).to.equal(STAKE_AMOUNT.toString());
+    console.log("✅ Position NFT minted, lock ends at slot", position.lockEndSlot.toString());
+  });
+
+  it("3. ❌ Position cannot be redeemed before maturity", async () => {
+    try {
+      await redeem(user, userTokenAccount);
+      throw new Error("Unexpected early redemption");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Stake is still locked");
+    }
+  });
+
+  it("4. ✅ Whoever holds the NFT redeems it at maturity", async () => {
+    // Sell the position on the secondary market
+    const buyerNftAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      nftMint.publicKey,
+      buyer.publicKey
+    );
+    await transfer(
+      provider.connection,
+      admin.payer,
+      getAssociatedTokenAddressSync(nftMint.publicKey, user.publicKey),
+      buyerNftAccount.address,
+      user,
+      1
+    );
+
+    await warpSlots(provider, LOCK_SLOTS);
+
+    // The original depositor no longer holds the NFT
+    try {
+      await redeem(user, userTokenAccount);
+      throw new Error("Unexpected redemption by previous holder");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("does not hold the position NFT");
+    }
+
+    const before = await getAccount(provider.connection, buyerTokenAccount);
+    await redeem(buyer, buyerTokenAccount);
+    const after = await getAccount(provider.connection, buyerTokenAccount);
+
+    const received = Number(after.amount) - Number(before.amount);
+    expect(received).to.be.greaterThan(STAKE_AMOUNT.toNumber());
+
+    const position = await provider.connection.getAccountInfo(
+      getPositionNftPDA(program.programId, nftMint.publicKey)[0]
+    );
+    const mint = await getMint(provider.connection, nftMint.publicKey);
+    expect(position).to.be.null;
+    expect(Number(mint.supply)).to.equal(0);
+    console.log("✅ Buyer redeemed", received, "tokens (principal + rewards)");
+  });
+});
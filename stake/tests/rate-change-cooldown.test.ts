@@ -0,0 +1,92 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import { createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  warpSlots,
+} from "./test-utils";
+
+describe("🚦 Stake Program - Rate Change Cooldown", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const COOLDOWN_SLOTS = 20;
+
+  const updateRate = (percentage: number) =>
+    program.methods
+      .updateRewardPercentage(poolId, new BN(percentage))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+  });
+
+  it("1. ❌ Non-owner cannot set the cooldown", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setRateChangeCooldown(poolId, new BN(0))
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ❌ Back-to-back rate changes are rejected", async () => {
+    await program.methods
+      .setRateChangeCooldown(poolId, new BN(COOLDOWN_SLOTS))
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    await warpSlots(provider, COOLDOWN_SLOTS);
+    await updateRate(2000);
+
+    try {
+      await updateRate(1000);
+      throw new Error("Unexpected second rate change");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected cooldown error:", errMsg);
+      expect(errMsg).to.include("changed too recently");
+    }
+  });
+
+  it("3. ✅ Rate can change again after the cooldown", async () => {
+    await warpSlots(provider, COOLDOWN_SLOTS);
+    await updateRate(1000);
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.rewardPercentage.toNumber()).to.equal(1000);
+    console.log("✅ Rate updated after cooldown");
+  });
+});
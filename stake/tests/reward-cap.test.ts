@@ -0,0 +1,188 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  warpSlots,
+  TEST_SLOTS_PER_PERIOD,
+  initUserStake,
+} from "./test-utils";
+
+describe("🏆 Stake Program - Per-User Reward Cap", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  let userTokenAccount: anchor.web3.PublicKey;
+  const poolId = new BN(0);
+  const REWARD_CAP = new BN(1_000);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    // Very high APY so the cap is reached within a few slots
+    await program.methods
+      .createPool(null, new BN(100_000_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    // Fund the reward vault
+    const adminTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      admin.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount.address,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount.address,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    userTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        user.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+  });
+
+  it("1. ❌ Non-owner cannot set the reward cap", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setMaxRewardPerUser(poolId, REWARD_CAP)
+        .accounts({ pool: poolPda, admin: nonOwner.publicKey, tokenMint })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+
+  it("2. ✅ Owner sets the per-user reward cap", async () => {
+    await program.methods
+      .setMaxRewardPerUser(poolId, REWARD_CAP)
+      .accounts({ pool: poolPda, admin: admin.publicKey, tokenMint })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.maxRewardPerUser.toString()).to.equal(REWARD_CAP.toString());
+  });
+
+  it("3. ✅ Rewards stop accruing at the cap", async () => {
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+
+    await warpSlots(provider, TEST_SLOTS_PER_PERIOD);
+
+    const info = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    console.log("📊 Pending reward (capped):", info.pendingReward.toString());
+    expect(info.pendingReward.lte(REWARD_CAP)).to.be.true;
+
+    await program.methods
+      .claimReward(poolId)
+      .accounts({
+        pool: poolPda,
+        tokenMint: tokenMint,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userRewardAccount: userTokenAccount,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const userStake = await program.account.userStake.fetch(userStakePda);
+    expect(userStake.totalEarned.toString()).to.equal(REWARD_CAP.toString());
+    expect(userStake.rewardCapped).to.be.true;
+
+    await warpSlots(provider, 10);
+
+    const after = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    expect(after.pendingReward.toNumber()).to.equal(0);
+    console.log("✅ No further accrual after reaching the cap");
+  });
+});
@@ -0,0 +1,189 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getUserStakePDA,
+  getPoolVaultPDA,
+  getRewardVaultPDA,
+  warpSlots,
+  initUserStake,
+} from "./test-utils";
+
+describe("🪫 Stake Program - Halt Accrual On Depletion", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let rewardVaultPda: anchor.web3.PublicKey;
+  let userStakePda: anchor.web3.PublicKey;
+  let adminTokenAccount: anchor.web3.PublicKey;
+  let user: anchor.web3.Keypair;
+  const poolId = new BN(0);
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(100_000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+    [rewardVaultPda] = getRewardVaultPDA(program.programId, poolPda, tokenMint);
+
+    adminTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        admin.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminTokenAccount,
+      admin.publicKey,
+      1_000_000_000
+    );
+
+    user = anchor.web3.Keypair.generate();
+    const sig = await provider.connection.requestAirdrop(
+      user.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(sig);
+
+    const userTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      user.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      userTokenAccount.address,
+      admin.publicKey,
+      100_000_000
+    );
+
+    [userStakePda] = getUserStakePDA(program.programId, poolPda, user.publicKey);
+
+    // Enable the halt while the reward vault is still empty
+    await program.methods
+      .setHaltOnDepletion(poolId, true)
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        tokenMint: tokenMint,
+        rewardVault: rewardVaultPda,
+      })
+      .rpc();
+
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
+    await program.methods
+      .depositStake(poolId, new BN(100_000_000), null)
+      .accounts({
+        pool: poolPda,
+        userStake: userStakePda,
+        user: user.publicKey,
+        userTokenAccount: userTokenAccount.address,
+        poolVault: getPoolVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .signers([user])
+      .rpc();
+  });
+
+  it("1. ✅ Accrual is halted while the reward vault is empty", async () => {
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.accrualHalted).to.be.true;
+    expect(
+      pool.rewardEpochs[pool.rewardEpochs.length - 1].rewardPercentage.toNumber()
+    ).to.equal(0);
+
+    await warpSlots(provider, 20);
+
+    const info = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    expect(info.pendingReward.toNumber()).to.equal(0);
+    console.log("✅ No liability accrued against an empty vault");
+  });
+
+  it("2. ✅ Refilling the vault resumes accrual at the configured rate", async () => {
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount: adminTokenAccount,
+        rewardVault: rewardVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.accrualHalted).to.be.false;
+    expect(
+      pool.rewardEpochs[pool.rewardEpochs.length - 1].rewardPercentage.toNumber()
+    ).to.equal(100_000);
+
+    await warpSlots(provider, 20);
+
+    const info = await program.methods
+      .getUserStakeWithReward(poolId)
+      .accounts({ pool: poolPda, userStake: userStakePda, tokenMint })
+      .view();
+    expect(info.pendingReward.toNumber()).to.be.greaterThan(0);
+    console.log("✅ Accrual resumed, pending:", info.pendingReward.toString());
+  });
+
+  it("3. ❌ Non-owner cannot toggle the halt", async () => {
+    const nonOwner = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .setHaltOnDepletion(poolId, false)
+        .accounts({
+          pool: poolPda,
+          admin: nonOwner.publicKey,
+          tokenMint: tokenMint,
+          rewardVault: rewardVaultPda,
+        })
+        .signers([nonOwner])
+        .rpc();
+      throw new Error("Unexpected success by non-owner");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("Unauthorized");
+    }
+  });
+});
@@ -7,7 +7,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 // Use small slot counts for fast testing - reward logic works the same
 const SLOTS_PER_DAY = TEST_SLOTS_PER_PERIOD;
@@ -46,7 +46,7 @@ describe("🕐 Stake Program - Reward Epochs", () => {
     // Create pool with initial reward percentage
     const initialRewardPercentage = 1000; // 10% APY
     await program.methods
-      .createPool(null, new anchor.BN(initialRewardPercentage), poolId)
+      .createPool(null, new anchor.BN(initialRewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -135,6 +135,7 @@ describe("🕐 Stake Program - Reward Epochs", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -177,8 +178,9 @@ describe("🕐 Stake Program - Reward Epochs", () => {
       program.programId
     );
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     await program.methods
-      .depositStake(poolId, stakeAmount)
+      .depositStake(poolId, stakeAmount, null)
       .accounts({
         pool: poolPda,
         userStake: userStakePda,
@@ -421,6 +423,7 @@ describe("🕐 Stake Program - Reward Epochs", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         newUser.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -432,8 +435,9 @@ describe("🕐 Stake Program - Reward Epochs", () => {
 
     const stakeAmount = new anchor.BN(100_000_000);
 
+    await initUserStake(program, poolId, poolPda, tokenMint, newUser.publicKey);
     await program.methods
-      .depositStake(poolId, stakeAmount)
+      .depositStake(poolId, stakeAmount, null)
       .accounts({
         pool: poolPda,
         userStake: newUserStakePda,
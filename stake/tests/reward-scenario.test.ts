@@ -8,7 +8,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment, advanceToSlot , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment, advanceToSlot , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 // Test constants for slot-based timing
 // Using the same SLOTS_PER_YEAR constant as defined in the stake program (lib.rs)
@@ -67,7 +67,7 @@ describe("💰 Stake Program - Reward Scenario", () => {
     // Create pool with 9.5% APY (950 basis points)
     const rewardPercentage = 950; // 9.5% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -167,6 +167,7 @@ describe("💰 Stake Program - Reward Scenario", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -188,8 +189,9 @@ describe("💰 Stake Program - Reward Scenario", () => {
   it("1. 💼 User stakes 100,000 tokens", async () => {
     const stakeAmount = new anchor.BN(100_000_000_000); // 100,000 tokens with 6 decimals
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     await program.methods
-      .depositStake(poolId, stakeAmount)
+      .depositStake(poolId, stakeAmount, null)
       .accounts({
         pool: poolPda,
         userStake: userStakePda,
@@ -41,7 +41,7 @@ describe("🔒 Stake Program - Safety Features", () => {
     // Create pool for safety tests
     const rewardPercentage = 1000; // 10.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -121,9 +121,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       // Update pool authority
       await program.methods
-        .updatePoolAuthority(newAuthority.publicKey)
+        .updatePoolAuthority(poolId, newAuthority.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: admin.publicKey,
         })
         .rpc();
@@ -138,9 +139,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       // Rotate back to original admin for other tests
       await program.methods
-        .updatePoolAuthority(admin.publicKey)
+        .updatePoolAuthority(poolId, admin.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: newAuthority.publicKey,
         })
         .signers([newAuthority])
@@ -168,9 +170,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       try {
         await program.methods
-          .updatePoolAuthority(newAuthority.publicKey)
+          .updatePoolAuthority(poolId, newAuthority.publicKey)
           .accounts({
             pool: poolPda,
+            tokenMint: tokenMint,
             currentAuthority: nonAuthority.publicKey,
           })
           .signers([nonAuthority])
@@ -191,9 +194,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       try {
         await program.methods
-          .updatePoolAuthority(anchor.web3.PublicKey.default)
+          .updatePoolAuthority(poolId, anchor.web3.PublicKey.default)
           .accounts({
             pool: poolPda,
+            tokenMint: tokenMint,
             currentAuthority: admin.publicKey,
           })
           .rpc();
@@ -216,9 +220,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       try {
         await program.methods
-          .updatePoolAuthority(poolPda)
+          .updatePoolAuthority(poolId, poolPda)
           .accounts({
             pool: poolPda,
+            tokenMint: tokenMint,
             currentAuthority: admin.publicKey,
           })
           .rpc();
@@ -327,9 +332,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       // Update authority
       await program.methods
-        .updatePoolAuthority(newAuthority.publicKey)
+        .updatePoolAuthority(poolId, newAuthority.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: admin.publicKey,
         })
         .rpc();
@@ -375,9 +381,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       // Restore original authority
       await program.methods
-        .updatePoolAuthority(admin.publicKey)
+        .updatePoolAuthority(poolId, admin.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: newAuthority.publicKey,
         })
         .signers([newAuthority])
@@ -406,9 +413,10 @@ describe("🔒 Stake Program - Safety Features", () => {
       // Rotate authority
       const tempAuthority = anchor.web3.Keypair.generate();
       await program.methods
-        .updatePoolAuthority(tempAuthority.publicKey)
+        .updatePoolAuthority(poolId, tempAuthority.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: admin.publicKey,
         })
         .rpc();
@@ -443,9 +451,10 @@ describe("🔒 Stake Program - Safety Features", () => {
 
       // Restore authority
       await program.methods
-        .updatePoolAuthority(admin.publicKey)
+        .updatePoolAuthority(poolId, admin.publicKey)
         .accounts({
           pool: poolPda,
+          tokenMint: tokenMint,
           currentAuthority: tempAuthority.publicKey,
         })
         .signers([tempAuthority])
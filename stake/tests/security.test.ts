@@ -10,7 +10,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 describe("🔒 Stake Program - Security Tests", () => {
   const { provider, program, admin } = getTestEnvironment();
@@ -47,7 +47,7 @@ describe("🔒 Stake Program - Security Tests", () => {
     // Create pool for security tests
     const rewardPercentage = 2500; // 25.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -141,6 +141,7 @@ describe("🔒 Stake Program - Security Tests", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         alice.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -160,8 +161,9 @@ describe("🔒 Stake Program - Security Tests", () => {
 
     // Alice stakes tokens
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
+    await initUserStake(program, poolId, poolPda, tokenMint, alice.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: alice.publicKey,
@@ -323,6 +325,7 @@ describe("🔒 Stake Program - Security Tests", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         charlie.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -342,8 +345,9 @@ describe("🔒 Stake Program - Security Tests", () => {
 
     // Charlie stakes tokens
     const STAKE_AMOUNT = new anchor.BN(300_000_000);
+    await initUserStake(program, poolId, poolPda, tokenMint, charlie.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: charlie.publicKey,
@@ -449,6 +453,7 @@ describe("🔒 Stake Program - Security Tests", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         david.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -468,8 +473,9 @@ describe("🔒 Stake Program - Security Tests", () => {
 
     // David stakes tokens
     const STAKE_AMOUNT = new anchor.BN(600_000_000);
+    await initUserStake(program, poolId, poolPda, tokenMint, david.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: david.publicKey,
@@ -157,3 +157,188 @@ export async function initializeGlobalConfig(
   return configPda;
 }
 
+
+/**
+ * Derives the user stake PDA for a given pool, user and position index
+ */
+export function getUserStakePDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  positionIndex: number = 0
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("user_stake"),
+      pool.toBuffer(),
+      user.toBuffer(),
+      poolIdToBytes(positionIndex),
+    ],
+    programId
+  );
+}
+
+/**
+ * Derives the per-user position counter PDA for a pool
+ */
+export function getUserPositionsPDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("user_positions"), pool.toBuffer(), user.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derives the pool vault PDA holding staked tokens
+ */
+export function getPoolVaultPDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  tokenMint: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), pool.toBuffer(), tokenMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derives the reward vault PDA for a pool and reward mint
+ */
+export function getRewardVaultPDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey,
+  rewardMint: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("reward_vault"), pool.toBuffer(), rewardMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derives the position record PDA for a stake position NFT mint
+ */
+export function getPositionNftPDA(
+  programId: anchor.web3.PublicKey,
+  nftMint: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("position_nft"), nftMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derives the pool template PDA for a template id
+ */
+export function getPoolTemplatePDA(
+  programId: anchor.web3.PublicKey,
+  templateId: number
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("pool_template"),
+      new anchor.BN(templateId).toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+}
+
+/**
+ * Derives the top-stakers leaderboard PDA for a pool
+ */
+export function getLeaderboardPDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard"), pool.toBuffer()],
+    programId
+  );
+}
+
+export function getPoolAdminsPDA(
+  programId: anchor.web3.PublicKey,
+  pool: anchor.web3.PublicKey
+): [anchor.web3.PublicKey, number] {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("pool_admins"), pool.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Creates the user stake position for a pool if it doesn't exist yet.
+ * Deposits require the account to be created first via init_user_stake.
+ */
+export async function initUserStake(
+  program: Program<StakeProgram>,
+  poolId: anchor.BN,
+  pool: anchor.web3.PublicKey,
+  tokenMint: anchor.web3.PublicKey,
+  user: anchor.web3.PublicKey,
+  positionIndex: number = 0
+): Promise<anchor.web3.PublicKey> {
+  const [userStakePda] = getUserStakePDA(
+    program.programId,
+    pool,
+    user,
+    positionIndex
+  );
+
+  // Check if already initialized
+  const existing = await program.provider.connection.getAccountInfo(userStakePda);
+  if (existing) {
+    return userStakePda;
+  }
+
+  await program.methods
+    .initUserStake(poolId, new anchor.BN(positionIndex))
+    .accounts({
+      pool,
+      userPositions: getUserPositionsPDA(program.programId, pool, user)[0],
+      userStake: userStakePda,
+      user,
+      payer: program.provider.publicKey,
+      tokenMint,
+    })
+    .rpc();
+
+  return userStakePda;
+}
+
+/**
+ * Decodes events from a confirmed transaction.
+ * The program emits events with emit_cpi, so they are recorded as self-CPI
+ * instruction data (8-byte event tag + event) instead of program logs.
+ */
+export function getCpiEvents(
+  program: Program<StakeProgram>,
+  txDetails: any
+): { name: string; data: any }[] {
+  if (!txDetails || !txDetails.meta || !txDetails.meta.innerInstructions) {
+    return [];
+  }
+  const accountKeys = txDetails.transaction.message.staticAccountKeys;
+  const events: { name: string; data: any }[] = [];
+  for (const inner of txDetails.meta.innerInstructions) {
+    for (const ix of inner.instructions) {
+      if (!accountKeys[ix.programIdIndex].equals(program.programId)) {
+        continue;
+      }
+      const ixData = anchor.utils.bytes.bs58.decode(ix.data);
+      const event = program.coder.events.decode(
+        anchor.utils.bytes.base64.encode(Buffer.from(ixData.subarray(8)))
+      );
+      if (event) {
+        events.push(event);
+      }
+    }
+  }
+  return events;
+}
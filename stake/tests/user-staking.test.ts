@@ -9,7 +9,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 describe("🧑‍💼 Stake Program - User Staking", () => {
   const { provider, program, admin } = getTestEnvironment();
@@ -47,7 +47,7 @@ describe("🧑‍💼 Stake Program - User Staking", () => {
     // Create pool for user staking tests
     const rewardPercentage = 2500; // 25.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -99,6 +99,7 @@ describe("🧑‍💼 Stake Program - User Staking", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
@@ -108,8 +109,9 @@ describe("🧑‍💼 Stake Program - User Staking", () => {
       program.programId
     );
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     await program.methods
-      .depositStake(poolId, FIRST_DEPOSIT)
+      .depositStake(poolId, FIRST_DEPOSIT, null)
       .accounts({
         pool: poolPda,
         user: user.publicKey,
@@ -146,8 +148,9 @@ describe("🧑‍💼 Stake Program - User Staking", () => {
       SECOND_DEPOSIT.toNumber()
     );
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     await program.methods
-      .depositStake(poolId, SECOND_DEPOSIT)
+      .depositStake(poolId, SECOND_DEPOSIT, null)
       .accounts({
         pool: poolPda,
         user: user.publicKey,
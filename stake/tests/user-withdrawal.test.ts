@@ -10,7 +10,7 @@ import {
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { StakeProgram } from "../target/types/stake_program";
-import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig } from "./test-utils";
+import { getTestEnvironment, warpSlots, TEST_SLOTS_PER_PERIOD , getGlobalConfigPDA, initializeGlobalConfig, initUserStake } from "./test-utils";
 
 // Use small slot counts for fast testing - reward logic works the same
 const SLOTS_PER_DAY = TEST_SLOTS_PER_PERIOD;
@@ -53,7 +53,7 @@ describe("💸 Stake Program - User Withdrawal", () => {
     // Create pool for user withdrawal tests
     const rewardPercentage = 2500; // 25.00% APY in basis points
     await program.methods
-      .createPool(null, new anchor.BN(rewardPercentage), poolId)
+      .createPool(null, new anchor.BN(rewardPercentage), poolId, null)
       .accounts({
         tokenMint: tokenMint,
         rewardMint: rewardMint,
@@ -138,6 +138,7 @@ describe("💸 Stake Program - User Withdrawal", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         user.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId,
     );
@@ -174,8 +175,9 @@ describe("💸 Stake Program - User Withdrawal", () => {
     const DEPOSIT_AMOUNT = new anchor.BN(800_000_000);
     console.log("\n🔹 Depositing:", DEPOSIT_AMOUNT.toString());
 
+    await initUserStake(program, poolId, poolPda, tokenMint, user.publicKey);
     await program.methods
-      .depositStake(poolId, DEPOSIT_AMOUNT)
+      .depositStake(poolId, DEPOSIT_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: user.publicKey,
@@ -532,6 +534,7 @@ describe("💸 Stake Program - User Withdrawal", () => {
         Buffer.from("user_stake"),
         poolPda.toBuffer(),
         testUser.publicKey.toBuffer(),
+        new anchor.BN(0).toArrayLike(Buffer, "le", 8),
       ],
       program.programId,
     );
@@ -561,8 +564,9 @@ describe("💸 Stake Program - User Withdrawal", () => {
     const STAKE_AMOUNT = new anchor.BN(500_000_000);
     console.log("\n🔹 Staking:", STAKE_AMOUNT.toString());
 
+    await initUserStake(program, poolId, poolPda, tokenMint, testUser.publicKey);
     await program.methods
-      .depositStake(poolId, STAKE_AMOUNT)
+      .depositStake(poolId, STAKE_AMOUNT, null)
       .accounts({
         pool: poolPda,
         user: testUser.publicKey,
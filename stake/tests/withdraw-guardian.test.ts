@@ -0,0 +1,180 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestEnvironment,
+  getGlobalConfigPDA,
+  initializeGlobalConfig,
+  getPoolPDA,
+  getRewardVaultPDA,
+} from "./test-utils";
+
+describe("🛡️ Stake Program - Withdraw Guardian", () => {
+  const { provider, program, admin } = getTestEnvironment();
+  let tokenMint: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let adminRewardAccount: anchor.web3.PublicKey;
+  const guardian = anchor.web3.Keypair.generate();
+  const poolId = new BN(0);
+  const THRESHOLD = new BN(100_000_000);
+
+  const withdrawAccounts = (withGuardian: boolean) => ({
+    pool: poolPda,
+    admin: admin.publicKey,
+    guardian: withGuardian ? guardian.publicKey : null,
+    adminRewardAccount,
+    rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+    tokenProgram: TOKEN_PROGRAM_ID,
+    tokenMint,
+  });
+
+  before(async () => {
+    await initializeGlobalConfig(program, admin);
+
+    tokenMint = await createMint(
+      provider.connection,
+      admin.payer,
+      admin.publicKey,
+      null,
+      6
+    );
+
+    await program.methods
+      .createPool(null, new BN(1000), poolId, null)
+      .accounts({
+        tokenMint: tokenMint,
+        rewardMint: tokenMint,
+        admin: admin.publicKey,
+        config: getGlobalConfigPDA(program.programId)[0],
+      })
+      .rpc();
+
+    [poolPda] = getPoolPDA(program.programId, tokenMint, 0);
+
+    adminRewardAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        tokenMint,
+        admin.publicKey
+      )
+    ).address;
+    await mintTo(
+      provider.connection,
+      admin.payer,
+      tokenMint,
+      adminRewardAccount,
+      admin.publicKey,
+      1_000_000_000
+    );
+    await program.methods
+      .depositReward(poolId, new BN(1_000_000_000))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        adminRewardAccount,
+        rewardVault: getRewardVaultPDA(program.programId, poolPda, tokenMint)[0],
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tokenMint: tokenMint,
+      })
+      .rpc();
+
+    await program.methods
+      .setWithdrawGuardian(poolId, guardian.publicKey, THRESHOLD)
+      .accounts({ pool: poolPda, admin: admin.publicKey, currentGuardian: null, tokenMint })
+      .rpc();
+  });
+
+  it("1. ✅ Withdrawals up to the threshold need only the owner", async () => {
+    await program.methods
+      .withdrawReward(poolId, THRESHOLD)
+      .accounts(withdrawAccounts(false))
+      .rpc();
+    console.log("✅ Owner withdrew", THRESHOLD.toString(), "alone");
+  });
+
+  it("2. ❌ Larger withdrawals without the guardian are rejected", async () => {
+    try {
+      await program.methods
+        .withdrawReward(poolId, THRESHOLD.addn(1))
+        .accounts(withdrawAccounts(false))
+        .rpc();
+      throw new Error("Unexpected withdrawal without guardian");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      console.log("❌ Expected guardian error:", errMsg);
+      expect(errMsg).to.include("guardian co-signature required");
+    }
+  });
+
+  it("3. ❌ Small withdrawals cannot add up past the threshold", async () => {
+    // Test 1 already used the owner's whole allowance
+    try {
+      await program.methods
+        .withdrawReward(poolId, new BN(1))
+        .accounts(withdrawAccounts(false))
+        .rpc();
+      throw new Error("Unexpected withdrawal past the cumulative threshold");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("guardian co-signature required");
+    }
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.unguardedWithdrawn.toString()).to.equal(THRESHOLD.toString());
+  });
+
+  it("4. ✅ Guardian co-signs a large withdrawal", async () => {
+    await program.methods
+      .withdrawReward(poolId, THRESHOLD.muln(2))
+      .accounts(withdrawAccounts(true))
+      .signers([guardian])
+      .rpc();
+    console.log("✅ Large withdrawal co-signed by guardian");
+  });
+
+  it("5. ✅ A guardian co-signature resets the owner's allowance", async () => {
+    const before = await program.account.pool.fetch(poolPda);
+    expect(before.unguardedWithdrawn.toNumber()).to.equal(0);
+
+    await program.methods
+      .withdrawReward(poolId, THRESHOLD)
+      .accounts(withdrawAccounts(false))
+      .rpc();
+  });
+
+  it("6. ❌ Owner alone cannot remove the guardian", async () => {
+    try {
+      await program.methods
+        .setWithdrawGuardian(poolId, anchor.web3.PublicKey.default, new BN(0))
+        .accounts({ pool: poolPda, admin: admin.publicKey, currentGuardian: null, tokenMint })
+        .rpc();
+      throw new Error("Unexpected guardian removal");
+    } catch (err: any) {
+      const errMsg = err.error?.errorMessage || err.message;
+      expect(errMsg).to.include("guardian co-signature required");
+    }
+
+    await program.methods
+      .setWithdrawGuardian(poolId, anchor.web3.PublicKey.default, new BN(0))
+      .accounts({
+        pool: poolPda,
+        admin: admin.publicKey,
+        currentGuardian: guardian.publicKey,
+        tokenMint,
+      })
+      .signers([guardian])
+      .rpc();
+
+    const pool = await program.account.pool.fetch(poolPda);
+    expect(pool.withdrawGuardian.toBase58()).to.equal(
+      anchor.web3.PublicKey.default.toBase58()
+    );
+  });
+});